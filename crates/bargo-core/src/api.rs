@@ -0,0 +1,334 @@
+//! Library entry points for embedding bargo in other Rust tools
+//!
+//! The CLI wraps each workflow in banners, progress spinners and summaries.
+//! The functions here run the same workflows without printing anything and
+//! return what they produced, and the `bargo` commands only format these
+//! results. Every external command goes through the [`Runner`] passed in,
+//! so an embedding tool can execute the commands, record them or answer
+//! them itself.
+//!
+//! Artifact paths are resolved per thread, like `--package-dir` in the CLI,
+//! so concurrent workflows for different projects must run on separate
+//! threads.
+//!
+//! ## Examples
+//!
+//! Building with a runner that records commands instead of running them:
+//!
+//! ```
+//! use bargo_core::api::{self, BuildOptions, ProjectOptions};
+//! use bargo_core::runner::{CapturedOutput, CmdSpec, Runner};
+//! use std::sync::{Arc, Mutex};
+//!
+//! #[derive(Debug, Default)]
+//! struct Recorder(Mutex<Vec<String>>);
+//!
+//! impl Runner for Recorder {
+//!     fn run(&self, spec: &CmdSpec) -> color_eyre::Result<()> {
+//!         self.0.lock().unwrap().push(format!("{} {}", spec.cmd, spec.args.join(" ")));
+//!         Ok(())
+//!     }
+//!
+//!     fn run_capture(&self, spec: &CmdSpec) -> color_eyre::Result<CapturedOutput> {
+//!         self.run(spec).map(|_| CapturedOutput::default())
+//!     }
+//! }
+//!
+//! let project = tempfile::tempdir()?;
+//! std::fs::write(
+//!     project.path().join("Nargo.toml"),
+//!     "[package]\nname = \"embedded\"\ntype = \"bin\"\n",
+//! )?;
+//!
+//! let recorder = Arc::new(Recorder::default());
+//! let options = BuildOptions::new(ProjectOptions::new(project.path()).with_dry_run(true));
+//! let result = api::build(&options, recorder.clone())?;
+//!
+//! assert_eq!(result.package.as_deref(), Some("embedded"));
+//! assert!(result.artifacts[0].ends_with("target/bb/embedded.json"));
+//! assert_eq!(recorder.0.lock().unwrap()[0], "nargo execute");
+//! # Ok::<(), color_eyre::Report>(())
+//! ```
+//!
+//! Verifying a proof with a runner that stands in for `bb`:
+//!
+//! ```
+//! use bargo_core::api::{self, ProjectOptions, VerifyOptions};
+//! use bargo_core::backend::BackendKind;
+//! use bargo_core::runner::{CapturedOutput, CmdSpec, Runner};
+//! use std::sync::Arc;
+//!
+//! #[derive(Debug)]
+//! struct AcceptAll;
+//!
+//! impl Runner for AcceptAll {
+//!     fn run(&self, _spec: &CmdSpec) -> color_eyre::Result<()> {
+//!         Ok(())
+//!     }
+//!
+//!     fn run_capture(&self, _spec: &CmdSpec) -> color_eyre::Result<CapturedOutput> {
+//!         Ok(CapturedOutput::default())
+//!     }
+//! }
+//!
+//! let project = tempfile::tempdir()?;
+//! std::fs::write(
+//!     project.path().join("Nargo.toml"),
+//!     "[package]\nname = \"embedded\"\ntype = \"bin\"\n",
+//! )?;
+//! let evm = project.path().join("target/evm");
+//! std::fs::create_dir_all(&evm)?;
+//! for name in ["proof", "vk", "public_inputs"] {
+//!     std::fs::write(evm.join(name), name)?;
+//! }
+//!
+//! let options = VerifyOptions::new(ProjectOptions::new(project.path()), BackendKind::Evm);
+//! let result = api::verify(&options, Arc::new(AcceptAll))?;
+//! assert!(result.accepted);
+//! assert!(!result.cached);
+//!
+//! // The outcome is cached until the proof, vk or public inputs change
+//! assert!(api::verify(&options, Arc::new(AcceptAll))?.cached);
+//! # Ok::<(), color_eyre::Report>(())
+//! ```
+
+use color_eyre::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::backend::BackendKind;
+use crate::cli::{ArtifactSuffix, ColorChoice};
+use crate::commands;
+use crate::config::{self, BuildConfig, Config, ProveConfig, ToolPaths, VerifyConfig};
+use crate::fs::{DryRunFs, RealFs};
+use crate::runner::Runner;
+
+pub use crate::util::{CircuitStats, CompressedFile};
+
+/// Project and global settings shared by every workflow
+///
+/// These mirror the CLI's global flags; `bargo.toml` in the project directory
+/// is applied as it is for the CLI.
+#[derive(Clone, Debug)]
+pub struct ProjectOptions {
+    /// Directory containing `Nargo.toml` (`--package-dir`)
+    pub project_dir: PathBuf,
+    /// Package to use instead of the one in `Nargo.toml` (`--pkg`)
+    pub pkg: Option<String>,
+    /// Base directory for artifacts instead of `target/` (`--target-dir`)
+    pub target_dir: Option<PathBuf>,
+    /// Abort external tools that run longer than this many seconds (`--timeout`)
+    pub timeout: Option<u64>,
+    /// Skip reading and writing artifacts, as with `--dry-run`
+    ///
+    /// Commands are still handed to the runner, which decides whether they run;
+    /// artifact moves and removals are only recorded.
+    pub dry_run: bool,
+}
+
+impl ProjectOptions {
+    pub fn new(project_dir: impl AsRef<Path>) -> Self {
+        Self {
+            project_dir: project_dir.as_ref().to_path_buf(),
+            pkg: None,
+            target_dir: None,
+            timeout: None,
+            dry_run: false,
+        }
+    }
+
+    /// Use this package instead of the one in `Nargo.toml`
+    pub fn with_pkg(mut self, pkg: impl Into<String>) -> Self {
+        self.pkg = Some(pkg.into());
+        self
+    }
+
+    /// Keep artifacts under `target_dir` instead of `target/`
+    pub fn with_target_dir(mut self, target_dir: impl Into<PathBuf>) -> Self {
+        self.target_dir = Some(target_dir.into());
+        self
+    }
+
+    /// Abort external tools that run longer than `seconds`
+    pub fn with_timeout(mut self, seconds: u64) -> Self {
+        self.timeout = Some(seconds);
+        self
+    }
+
+    /// Skip reading and writing artifacts
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Silent configuration for `runner`, with this thread's paths pointed at the project
+    pub fn config(&self, runner: Arc<dyn Runner>) -> Result<Config> {
+        let mut cfg = Config {
+            verbose: 0,
+            dry_run: self.dry_run,
+            pkg: self.pkg.clone(),
+            quiet: true,
+            timeout: self.timeout,
+            retries: 0,
+            target_dir: self.target_dir.clone(),
+            package_dir: Some(self.project_dir.clone()),
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Never,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner,
+            fs: if self.dry_run {
+                Arc::new(DryRunFs::new())
+            } else {
+                Arc::new(RealFs)
+            },
+        };
+        if let Some(project) = config::load_project_config(&cfg.working_dir()?)? {
+            cfg = cfg.with_project_config(project);
+        }
+        cfg.apply_paths()?;
+        Ok(cfg)
+    }
+}
+
+/// Options for [`build`]
+#[derive(Clone, Debug)]
+pub struct BuildOptions {
+    pub project: ProjectOptions,
+    pub build: BuildConfig,
+}
+
+impl BuildOptions {
+    pub fn new(project: ProjectOptions) -> Self {
+        Self {
+            project,
+            build: BuildConfig::default(),
+        }
+    }
+
+    /// Build with settings such as another prover inputs file
+    pub fn with_build_config(mut self, build: BuildConfig) -> Self {
+        self.build = build;
+        self
+    }
+}
+
+/// Options for [`prove`]
+#[derive(Clone, Debug)]
+pub struct ProveOptions {
+    pub project: ProjectOptions,
+    pub backend: BackendKind,
+    pub prove: ProveConfig,
+}
+
+impl ProveOptions {
+    pub fn new(project: ProjectOptions, backend: BackendKind) -> Self {
+        Self {
+            project,
+            backend,
+            prove: ProveConfig::default(),
+        }
+    }
+
+    /// Prove with settings such as the scheme or oracle hash
+    pub fn with_prove_config(mut self, prove: ProveConfig) -> Self {
+        self.prove = prove;
+        self
+    }
+}
+
+/// Options for [`verify`]
+#[derive(Clone, Debug)]
+pub struct VerifyOptions {
+    pub project: ProjectOptions,
+    pub backend: BackendKind,
+    pub verify: VerifyConfig,
+}
+
+impl VerifyOptions {
+    pub fn new(project: ProjectOptions, backend: BackendKind) -> Self {
+        Self {
+            project,
+            backend,
+            verify: VerifyConfig::default(),
+        }
+    }
+
+    /// Verify other files, or expect the proof to be rejected
+    pub fn with_verify_config(mut self, verify: VerifyConfig) -> Self {
+        self.verify = verify;
+        self
+    }
+}
+
+/// What a build produced
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildResult {
+    /// Package that was built; `None` only in a dry run outside a Nargo project
+    pub package: Option<String>,
+    /// Bytecode and witness paths
+    pub artifacts: Vec<PathBuf>,
+    /// Whether nargo was skipped because the artifacts were already current
+    pub up_to_date: bool,
+    /// Bytecode size and gate count, with `BuildConfig::stats` outside a dry run
+    pub stats: Option<CircuitStats>,
+    pub elapsed: Duration,
+}
+
+/// What proving produced
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProveResult {
+    /// Proof path, ending in `.gz` when compressed
+    pub proof: PathBuf,
+    pub vk: PathBuf,
+    /// Public inputs path, ending in `.gz` when compressed
+    pub public_inputs: PathBuf,
+    /// Whether the existing proof was kept because it was newer than the build
+    pub reused: bool,
+    /// Files replaced by gzip copies (`ProveConfig::compress`)
+    pub compressed: Vec<CompressedFile>,
+    pub elapsed: Duration,
+}
+
+/// Outcome of a local verification
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyResult {
+    /// Whether bb accepted the proof
+    pub accepted: bool,
+    /// Whether the outcome came from the verification cache instead of bb
+    pub cached: bool,
+    pub elapsed: Duration,
+}
+
+/// Compile and execute the circuit, as `bargo build` does
+pub fn build(options: &BuildOptions, runner: Arc<dyn Runner>) -> Result<BuildResult> {
+    let cfg = options.project.config(runner)?;
+    commands::build::build(&cfg, &options.build)
+}
+
+/// Generate the proof and verification key, as `bargo <evm|cairo> prove` does
+pub fn prove(options: &ProveOptions, runner: Arc<dyn Runner>) -> Result<ProveResult> {
+    let cfg = options.project.config(runner)?;
+    match options.backend {
+        BackendKind::Evm => commands::evm::workflow::generate_proof(&cfg, &options.prove),
+        #[cfg(feature = "cairo")]
+        BackendKind::Cairo => commands::cairo::workflow::generate_proof(&cfg, &options.prove),
+    }
+}
+
+/// Verify the proof locally, as `bargo <evm|cairo> verify` does
+///
+/// A proof that should be accepted but is rejected is an error, as is the
+/// reverse with `VerifyConfig::expect_valid` set to `false`.
+pub fn verify(options: &VerifyOptions, runner: Arc<dyn Runner>) -> Result<VerifyResult> {
+    let cfg = options.project.config(runner)?;
+    match options.backend {
+        BackendKind::Evm => commands::evm::workflow::verify_proof(&cfg, &options.verify),
+        #[cfg(feature = "cairo")]
+        BackendKind::Cairo => commands::cairo::workflow::verify_proof(&cfg, &options.verify),
+    }
+}
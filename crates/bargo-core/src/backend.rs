@@ -6,10 +6,13 @@
 
 use color_eyre::Result;
 
-use crate::config::Config;
+use crate::config::{CalldataConfig, Config, FoundryConfig, ProveConfig, VerifyConfig};
 
 #[cfg(feature = "cairo")]
-use crate::config::CairoDeployConfig;
+use crate::config::{CairoDeployConfig, GaragaConfig};
+
+#[cfg(feature = "evm-foundry")]
+use crate::config::EvmDeployConfig;
 
 /// Trait for polymorphic backend implementations (Cairo, EVM, etc.)
 ///
@@ -45,9 +48,23 @@ pub trait Backend {
 /// Backend configuration for backend-specific settings
 #[derive(Debug, Clone)]
 pub enum BackendConfig {
+    /// Prove configuration shared by all backends
+    Prove(ProveConfig),
+    /// Calldata configuration shared by all backends
+    Calldata(CalldataConfig),
+    /// Verify configuration shared by all backends
+    Verify(VerifyConfig),
+    /// Compiler settings for the generated Foundry project
+    Foundry(FoundryConfig),
     /// Cairo/Starknet backend configuration
     #[cfg(feature = "cairo")]
     CairoDeploy(CairoDeployConfig),
+    /// Garaga settings for Cairo verifier and calldata generation
+    #[cfg(feature = "cairo")]
+    Garaga(GaragaConfig),
+    /// EVM deploy configuration
+    #[cfg(feature = "evm-foundry")]
+    EvmDeploy(EvmDeployConfig),
 }
 
 /// Backend type identifier for factory function
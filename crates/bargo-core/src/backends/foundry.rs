@@ -1,43 +1,23 @@
 use color_eyre::Result;
 
-/// Ensure Foundry (forge and cast) is available on the system
-pub fn ensure_available() -> Result<()> {
-    // Check for forge
-    which::which("forge").map_err(|_| {
-        color_eyre::eyre::eyre!(
-            "❌ forge command not found\n\n\
-             EVM features require Foundry to be installed.\n\n\
-             📋 Installation steps:\n\
-             1. Install Foundry: curl -L https://foundry.paradigm.xyz | bash\n\
-             2. Restart your terminal or run: source ~/.bashrc\n\
-             3. Update Foundry: foundryup\n\
-             4. Verify: forge --version && cast --version\n\n\
-             🔧 Alternative installation:\n\
-             • From source: https://github.com/foundry-rs/foundry\n\
-             • Via package manager (brew, etc.)\n\n\
-             💡 You can still use all Cairo/Starknet features without Foundry!\n\
-             Run 'bargo doctor' to check all dependencies."
-        )
-    })?;
+use crate::config::ToolPaths;
+use crate::util::{BargoError, enhance_error_with_suggestions};
 
-    // Check for cast
-    which::which("cast").map_err(|_| {
-        color_eyre::eyre::eyre!(
-            "❌ cast command not found\n\n\
-             EVM features require Foundry (including cast) to be installed.\n\n\
-             📋 Installation steps:\n\
-             1. Install Foundry: curl -L https://foundry.paradigm.xyz | bash\n\
-             2. Restart your terminal or run: source ~/.bashrc\n\
-             3. Update Foundry: foundryup\n\
-             4. Verify: forge --version && cast --version\n\n\
-             🔧 Troubleshooting:\n\
-             • Try: foundryup (to update/reinstall)\n\
-             • Check PATH includes ~/.foundry/bin\n\
-             • Restart terminal after installation\n\n\
-             💡 You can still use all Cairo/Starknet features without Foundry!\n\
-             Run 'bargo doctor' to check all dependencies."
-        )
-    })?;
+/// Ensure Foundry (forge and cast) is available on the system
+///
+/// A forge pinned via `BARGO_FORGE` or `bargo.toml` is checked at its
+/// configured location instead of on `PATH`.
+pub fn ensure_available(tools: &ToolPaths) -> Result<()> {
+    for tool in ["forge", "cast"] {
+        which::which(super::resolve_tool(tool, tools)).map_err(|_| {
+            enhance_error_with_suggestions(
+                BargoError::MissingTool {
+                    tool: tool.to_string(),
+                }
+                .into(),
+            )
+        })?;
+    }
 
     Ok(())
 }
@@ -50,7 +30,7 @@ mod tests {
     fn test_ensure_available() {
         // This test will pass if Foundry is installed, otherwise it will show
         // the helpful error message
-        match ensure_available() {
+        match ensure_available(&ToolPaths::default()) {
             Ok(_) => println!("✓ Foundry (forge and cast) is available"),
             Err(e) => println!("✗ Foundry not available: {}", e),
         }
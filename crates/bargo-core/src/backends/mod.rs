@@ -15,8 +15,86 @@ pub mod foundry;
 pub mod garaga;
 pub mod nargo;
 
+use crate::config::ToolPaths;
+
+/// Tools whose binary can be pinned, with the environment variable for each
+pub const TOOL_OVERRIDES: &[(&str, &str)] = &[
+    ("nargo", "BARGO_NARGO"),
+    ("bb", "BARGO_BB"),
+    ("garaga", "BARGO_GARAGA"),
+    ("forge", "BARGO_FORGE"),
+];
+
+/// Resolve the command to run for an external tool
+///
+/// `BARGO_<TOOL>` wins over the `[tools]` section of `bargo.toml`. Without
+/// either, the bare tool name is returned and looked up on `PATH`.
+pub fn resolve_tool(name: &str, configured: &ToolPaths) -> String {
+    resolve_tool_with(name, configured, |key| std::env::var(key).ok())
+}
+
+/// [`resolve_tool`] with an injectable environment lookup
+fn resolve_tool_with(
+    name: &str,
+    configured: &ToolPaths,
+    env: impl Fn(&str) -> Option<String>,
+) -> String {
+    TOOL_OVERRIDES
+        .iter()
+        .find(|(tool, _)| *tool == name)
+        .and_then(|(_, var)| {
+            env(var)
+                .filter(|value| !value.is_empty())
+                .or_else(|| configured.get(name).map(|path| path.display().to_string()))
+        })
+        .unwrap_or_else(|| name.to_string())
+}
+
 #[cfg(test)]
 mod tests {
-    // Tests for backend functionality should use the runner-based commands
-    // See tests/cli_smoke.rs for examples of testing commands through the CLI
+    use super::*;
+    use std::path::PathBuf;
+
+    fn configured_bb() -> ToolPaths {
+        ToolPaths {
+            bb: Some(PathBuf::from("/opt/bb/0.82.2/bb")),
+            ..ToolPaths::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_tool_defaults_to_bare_name() {
+        assert_eq!(
+            resolve_tool_with("nargo", &ToolPaths::default(), |_| None),
+            "nargo"
+        );
+        assert_eq!(
+            resolve_tool_with("cast", &configured_bb(), |_| None),
+            "cast"
+        );
+    }
+
+    #[test]
+    fn test_resolve_tool_uses_project_config() {
+        assert_eq!(
+            resolve_tool_with("bb", &configured_bb(), |_| None),
+            "/opt/bb/0.82.2/bb"
+        );
+    }
+
+    #[test]
+    fn test_resolve_tool_env_wins_over_project_config() {
+        let env = |key: &str| (key == "BARGO_BB").then(|| "/usr/local/bin/bb".to_string());
+        assert_eq!(
+            resolve_tool_with("bb", &configured_bb(), env),
+            "/usr/local/bin/bb"
+        );
+
+        // An empty variable is treated as unset
+        let empty = |_: &str| Some(String::new());
+        assert_eq!(
+            resolve_tool_with("bb", &configured_bb(), empty),
+            "/opt/bb/0.82.2/bb"
+        );
+    }
 }
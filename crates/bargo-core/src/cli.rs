@@ -1,4 +1,6 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// A developer-friendly CLI wrapper for Noir ZK development
 #[derive(Parser)]
@@ -9,9 +11,9 @@ use clap::{Parser, Subcommand, ValueEnum};
     version
 )]
 pub struct Cli {
-    /// Enable verbose logging (shows underlying commands)
-    #[arg(short, long, global = true)]
-    pub verbose: bool,
+    /// Verbose logging: -v info, -vv debug (command lines), -vvv trace (full command specs)
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
 
     /// Print commands without executing them
     #[arg(long, global = true)]
@@ -25,6 +27,91 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub quiet: bool,
 
+    /// Do not print the banner before each command; summaries are still shown
+    #[arg(long, global = true)]
+    pub no_banner: bool,
+
+    /// Abort external tools that run longer than this many seconds
+    #[arg(long, global = true, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+
+    /// Retry network commands (deploy, verify-onchain) this many times on failure
+    #[arg(long, global = true, value_name = "N", default_value_t = 0)]
+    pub retries: u32,
+
+    /// Base directory for build artifacts instead of `target/`
+    #[arg(long, global = true, value_name = "PATH")]
+    pub target_dir: Option<PathBuf>,
+
+    /// Run as if bargo had been started in this directory, loading its `.env`
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        visible_alias = "working-dir"
+    )]
+    pub package_dir: Option<PathBuf>,
+
+    /// Print a single machine-readable JSON result instead of decorated output
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// When to use colored output
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "WHEN",
+        default_value_t = ColorChoice::Auto
+    )]
+    pub color: ColorChoice,
+
+    /// Suffix added to proof, vk and public_inputs file names
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "SUFFIX",
+        default_value_t = ArtifactSuffix::None
+    )]
+    pub artifact_suffix: ArtifactSuffix,
+
+    /// Keep running the remaining backends or workspace members after one fails
+    #[arg(long, global = true, overrides_with = "fail_fast")]
+    pub keep_going: bool,
+
+    /// Stop at the first failing backend or workspace member (the default)
+    #[arg(long, global = true, overrides_with = "keep_going")]
+    pub fail_fast: bool,
+
+    /// Also write every log event, including executed commands, to this file
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Append to the log file instead of truncating it
+    #[arg(long, global = true, requires = "log_file")]
+    pub log_append: bool,
+
+    /// Save every executed command and its output to this JSON file
+    #[arg(long, global = true, value_name = "FILE", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Answer commands from a `--record` file instead of executing them
+    #[arg(long, global = true, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
+    /// POST a JSON summary of the finished command (success or failure) to this URL
+    #[arg(long, global = true, value_name = "URL")]
+    pub notify_url: Option<String>,
+
+    /// Append a JSON line recording the finished command to this file, for `bargo report`
+    #[arg(long, global = true, value_name = "PATH")]
+    pub summary_file: Option<PathBuf>,
+
+    /// Load environment variables from this dotenv file instead of the project's `.env`
+    #[arg(long, global = true, value_name = "PATH")]
+    pub env_file: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -33,11 +120,42 @@ pub struct Cli {
 pub enum Commands {
     /// Check circuit syntax and dependencies
     #[command(about = "Run nargo check to validate circuit syntax and dependencies")]
-    Check,
+    Check {
+        /// Check every member of a Nargo workspace and report each one's result
+        #[arg(long, visible_alias = "all")]
+        workspace: bool,
+        /// Fail if nargo reports any warnings
+        #[arg(long)]
+        deny_warnings: bool,
+    },
 
     /// Build circuit (compile + execute to generate bytecode and witness)
     #[command(about = "Run nargo execute to generate bytecode and witness files")]
-    Build,
+    Build {
+        /// Build every member of a Nargo workspace
+        #[arg(long)]
+        all: bool,
+
+        /// Only check that artifacts are up to date; fail if a rebuild is needed
+        #[arg(long, conflicts_with = "all")]
+        check: bool,
+
+        /// Prover inputs to execute with instead of Prover.toml
+        #[arg(long, value_name = "PATH", conflicts_with = "all")]
+        input: Option<PathBuf>,
+
+        /// Execute with the prover inputs of this [inputs] profile in bargo.toml
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["all", "input"])]
+        profile: Option<String>,
+
+        /// Report the bytecode size and gate count (runs `bb gates`)
+        #[arg(long, conflicts_with = "check")]
+        stats: bool,
+
+        /// Arguments after `--`, appended verbatim to `nargo execute`
+        #[arg(last = true, value_name = "ARGS", conflicts_with = "check")]
+        extra_args: Vec<String>,
+    },
 
     /// Clean build artifacts
     #[command(about = "Remove target directory and all build artifacts")]
@@ -45,6 +163,15 @@ pub enum Commands {
         /// Backend to clean (defaults to all)
         #[arg(long, value_enum)]
         backend: Option<Backend>,
+        /// Only remove cache metadata (vk fingerprints and manifests)
+        #[arg(long)]
+        cache: bool,
+        /// With --cache, also remove the cached verification keys
+        #[arg(long, requires = "cache")]
+        all: bool,
+        /// Only remove files last modified longer ago than this age (e.g. 30m, 12h, 7d)
+        #[arg(long, value_name = "AGE", value_parser = crate::util::parse_age, conflicts_with = "cache")]
+        older_than: Option<Duration>,
     },
 
     /// Clean and rebuild (equivalent to clean + build)
@@ -55,6 +182,84 @@ pub enum Commands {
         backend: Option<Backend>,
     },
 
+    /// Generate a proof with the chosen backend
+    #[command(about = "Generate a proof and verification key (defaults to the bb/EVM backend)")]
+    Prove {
+        /// Backend to prove with (defaults to bb)
+        #[arg(long, value_enum)]
+        backend: Option<Backend>,
+
+        /// Proving scheme passed to bb (defaults to the backend's own scheme)
+        #[arg(long, value_enum)]
+        scheme: Option<BbScheme>,
+
+        /// Regenerate the proof and verification key even if they are up to date
+        #[arg(long)]
+        force: bool,
+
+        /// Prove with each selected backend on its own thread
+        #[arg(long)]
+        parallel: bool,
+
+        /// Produce a recursion-friendly proof and vk for verification inside another circuit
+        #[arg(long)]
+        recursive: bool,
+
+        /// Like --recursive, also passing bb's honk recursion flag to prove and write_vk
+        #[arg(long)]
+        recursive_friendly: bool,
+
+        /// Transcript hash passed to bb instead of the backend default
+        #[arg(long, value_enum)]
+        oracle_hash: Option<OracleHash>,
+
+        /// Witness to prove instead of target/bb/<package>.gz
+        #[arg(long, value_name = "PATH")]
+        witness: Option<PathBuf>,
+
+        /// Build with the prover inputs of this [inputs] profile in bargo.toml first
+        #[arg(long, value_name = "NAME", conflicts_with = "witness")]
+        profile: Option<String>,
+
+        /// Gzip the proof and public inputs into proof.gz and public_inputs.gz
+        #[arg(long)]
+        compress: bool,
+
+        /// bb performance profile (defaults to bb's own settings)
+        #[arg(long, value_enum, value_name = "PROFILE")]
+        bb_profile: Option<BbProfile>,
+
+        /// Arguments after `--`, appended verbatim to `bb prove`
+        #[arg(last = true, value_name = "ARGS")]
+        extra_args: Vec<String>,
+    },
+
+    /// Verify the proofs of the chosen backend
+    #[command(about = "Verify a proof locally (defaults to the bb/EVM backend)")]
+    Verify {
+        /// Backend whose proof to verify (defaults to bb); `all` verifies every backend with a proof
+        #[arg(long, value_enum)]
+        backend: Option<Backend>,
+
+        /// Run bb even if the same proof, vk and public inputs already verified
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Time repeated proof generation
+    #[command(
+        about = "Prove several times and report min/mean/max/stddev timings and artifact sizes"
+    )]
+    Bench {
+        /// Backend to benchmark (defaults to bb)
+        #[arg(long, value_enum)]
+        backend: Option<Backend>,
+
+        /// Number of times to run the prove step
+        #[arg(long, short = 'n', default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+    },
+
     /// Cairo/Starknet operations
     #[cfg(feature = "cairo")]
     #[command(about = "Generate Cairo verifiers and interact with Starknet")]
@@ -72,7 +277,121 @@ pub enum Commands {
 
     /// Check system dependencies
     #[command(about = "Verify that all required tools are installed and available")]
-    Doctor,
+    Doctor {
+        /// Also check deploy/verify environment variables and dotenv files
+        #[arg(long)]
+        env: bool,
+
+        /// Compare the sha256 of the nargo, bb and garaga binaries against bargo-tools.lock
+        #[arg(long)]
+        verify_binaries: bool,
+
+        /// Write bargo-tools.lock from the currently installed nargo, bb and garaga
+        #[arg(long, conflicts_with_all = ["env", "verify_binaries"])]
+        write_lock: bool,
+
+        /// Offer to run the install command for each missing required tool
+        #[arg(long, conflicts_with = "write_lock")]
+        fix: bool,
+
+        /// Show installed vs recommended nargo/bb versions with pinned upgrade commands
+        #[arg(long, conflicts_with = "write_lock")]
+        versions: bool,
+
+        /// Only these tools must be installed (comma-separated, e.g. nargo,bb); all others are optional
+        #[arg(
+            long,
+            value_name = "TOOLS",
+            value_delimiter = ',',
+            conflicts_with = "write_lock"
+        )]
+        require: Option<Vec<String>>,
+    },
+
+    /// Show resolved project metadata
+    #[command(about = "Show the project root, package, artifacts and tool versions bargo detects")]
+    Info,
+
+    /// Show pipeline progress per backend
+    #[command(
+        about = "Show which pipeline steps (build, prove, gen, deploy, verify-onchain) are done per backend"
+    )]
+    Status,
+
+    /// List recorded contract deployments
+    #[command(about = "List verifier contracts recorded by evm and cairo deploy, newest last")]
+    Addresses {
+        /// Only show deployments on this network
+        #[arg(long)]
+        network: Option<String>,
+    },
+
+    /// Bundle proof artifacts into a tarball
+    #[command(about = "Package the proof, vk, public inputs and manifest into a .tar.gz")]
+    Export {
+        /// Backend whose artifacts to export (defaults to bb)
+        #[arg(long, value_enum)]
+        backend: Option<Backend>,
+
+        /// Path of the archive to write
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+
+    /// Convert a proof between bb's encodings
+    #[command(
+        about = "Convert a proof between binary bytes and JSON field elements without re-proving"
+    )]
+    Convert {
+        /// Proof to read (defaults to target/evm/proof, or target/evm/proof_fields.json with --to bytes)
+        #[arg(long, short)]
+        input: Option<PathBuf>,
+
+        /// Path to write (defaults to proof or proof_fields.json next to the input)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Encoding to convert the proof to
+        #[arg(long, value_enum)]
+        to: ProofEncoding,
+    },
+
+    /// Summarize a session log
+    #[command(about = "Pretty-print the commands recorded in a --summary-file session log")]
+    Report {
+        /// Session log written with --summary-file
+        file: PathBuf,
+    },
+
+    /// Show or change project defaults
+    #[command(about = "Get, set or list the project defaults stored in bargo.toml")]
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Print the value of a setting
+    #[command(about = "Print a bargo.toml setting such as evm.network")]
+    Get {
+        /// Dotted key, e.g. defaults.pkg or tools.bb
+        key: String,
+    },
+
+    /// Change a setting
+    #[command(about = "Validate and store a bargo.toml setting, creating the file if needed")]
+    Set {
+        /// Dotted key, e.g. defaults.pkg or tools.bb
+        key: String,
+        /// New value
+        value: String,
+    },
+
+    /// Show every setting
+    #[command(about = "List every known bargo.toml setting with its current value")]
+    List,
 }
 
 #[cfg(feature = "cairo")]
@@ -80,19 +399,96 @@ pub enum Commands {
 pub enum CairoCommands {
     /// Generate Cairo verifier contract
     #[command(about = "Generate Cairo verifier contract for Starknet deployment")]
-    Gen,
+    Gen {
+        /// Proof system passed to garaga
+        #[arg(long, value_enum, default_value_t = GaragaSystem::default())]
+        system: GaragaSystem,
+
+        /// Transcript hash passed to bb instead of the backend default
+        #[arg(long, value_enum)]
+        oracle_hash: Option<OracleHash>,
+
+        /// Skip proving and generate the contract from the existing target/starknet/vk
+        #[arg(long, conflicts_with = "oracle_hash")]
+        no_prove: bool,
+
+        /// Prove again even if the proof is newer than the build artifacts, and
+        /// replace an --output-dir that bargo did not generate
+        #[arg(long)]
+        force: bool,
+
+        /// Directory the verifier project is written to (default: contracts/cairo)
+        #[arg(long, value_name = "PATH")]
+        output_dir: Option<PathBuf>,
+
+        /// Arguments after `--`, appended verbatim to `garaga gen`
+        #[arg(last = true, value_name = "ARGS")]
+        extra_args: Vec<String>,
+    },
 
     /// Generate Starknet oracle proof
     #[command(about = "Generate proof using bb with Starknet oracle hash")]
-    Prove,
+    Prove {
+        /// Proving scheme passed to bb (defaults to ultra_honk)
+        #[arg(long, value_enum)]
+        scheme: Option<BbScheme>,
+
+        /// Regenerate the proof and verification key even if they are up to date
+        #[arg(long)]
+        force: bool,
+
+        /// Transcript hash passed to bb instead of the backend default
+        #[arg(long, value_enum)]
+        oracle_hash: Option<OracleHash>,
+
+        /// Arguments after `--`, appended verbatim to `bb prove`
+        #[arg(last = true, value_name = "ARGS")]
+        extra_args: Vec<String>,
+    },
 
     /// Verify Starknet oracle proof
     #[command(about = "Verify proof generated with Starknet oracle hash")]
-    Verify,
+    Verify {
+        /// Proof file to verify instead of target/starknet/proof
+        #[arg(long, value_name = "PATH")]
+        proof: Option<PathBuf>,
+
+        /// Verification key to use instead of target/starknet/vk
+        #[arg(long, value_name = "PATH")]
+        vk: Option<PathBuf>,
+
+        /// Public inputs to use instead of target/starknet/public_inputs
+        #[arg(long, value_name = "PATH")]
+        public_inputs: Option<PathBuf>,
+
+        /// Whether the proof should verify; with false, succeed only if bb rejects it
+        #[arg(long, value_name = "BOOL", default_value_t = true, action = ArgAction::Set)]
+        expect: bool,
+
+        /// Run bb even if the same proof, vk and public inputs were already verified
+        #[arg(long)]
+        force: bool,
+
+        /// Re-verify whenever the proof, vk or public inputs change (Ctrl-C to stop)
+        #[arg(long)]
+        watch: bool,
+    },
 
     /// Generate calldata for proof verification
     #[command(about = "Generate calldata JSON for latest proof")]
-    Calldata,
+    Calldata {
+        /// Write calldata here instead of target/starknet/calldata.json
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Proof system passed to garaga
+        #[arg(long, value_enum, default_value_t = GaragaSystem::default())]
+        system: GaragaSystem,
+
+        /// Encoding of the written calldata
+        #[arg(long, value_enum, default_value_t = CalldataFormat::default())]
+        format: CalldataFormat,
+    },
 
     /// Deploy declared verifier contract
     #[command(about = "Deploy declared verifier contract")]
@@ -106,6 +502,25 @@ pub enum CairoCommands {
         /// Skip automatic declaration (fails if contract not declared)
         #[arg(long, conflicts_with = "auto_declare")]
         no_declare: bool,
+        /// Network to deploy to (mainnet or sepolia; defaults to bargo.toml, then sepolia)
+        #[arg(long)]
+        network: Option<String>,
+        /// Constructor calldata felts passed to the deployed contract, decimal or 0x-hex
+        /// (space- or comma-separated)
+        #[arg(
+            long,
+            num_args = 1..,
+            value_delimiter = ',',
+            value_name = "FELT",
+            visible_alias = "constructor-args"
+        )]
+        constructor_calldata: Vec<String>,
+        /// Sign with a starkli keystore (~/.starkli-wallets/<NAME>/keystore.json or a path)
+        #[arg(long, value_name = "NAME")]
+        account: Option<String>,
+        /// Deploy to mainnet without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
 
     /// Verify proof on-chain
@@ -114,6 +529,14 @@ pub enum CairoCommands {
         /// Address of deployed verifier contract
         #[arg(short = 'a', long)]
         address: Option<String>,
+
+        /// Proof system the deployed verifier was generated for
+        #[arg(long, value_enum, default_value_t = GaragaSystem::default())]
+        system: GaragaSystem,
+
+        /// Network to verify on (defaults to bargo.toml, then sepolia)
+        #[arg(long)]
+        network: Option<String>,
     },
 }
 
@@ -121,33 +544,126 @@ pub enum CairoCommands {
 pub enum EvmCommands {
     /// Generate Solidity verifier contract
     #[command(about = "Generate Solidity verifier contract (Foundry project setup when enabled)")]
-    Gen,
+    Gen {
+        /// Transcript hash passed to bb instead of the backend default
+        #[arg(long, value_enum)]
+        oracle_hash: Option<OracleHash>,
+
+        /// Skip proving and generate the contract from the existing target/evm/vk
+        #[arg(long, conflicts_with = "oracle_hash")]
+        no_prove: bool,
+
+        /// Prove again even if the proof is newer than the build artifacts, and
+        /// replace an --output-dir that bargo did not generate
+        #[arg(long)]
+        force: bool,
+
+        /// Directory the Foundry project is created in (default: contracts/evm)
+        #[arg(long, value_name = "PATH")]
+        output_dir: Option<PathBuf>,
+
+        /// solc version written to the generated foundry.toml (e.g. 0.8.27)
+        #[arg(long, value_name = "VERSION")]
+        solc_version: Option<String>,
+
+        /// Optimizer runs written to the generated foundry.toml (enables the optimizer)
+        #[arg(long, value_name = "RUNS")]
+        optimizer_runs: Option<u32>,
+
+        /// Arguments after `--`, appended verbatim to `bb write_solidity_verifier`
+        #[arg(last = true, value_name = "ARGS")]
+        extra_args: Vec<String>,
+    },
 
     /// Generate Keccak oracle proof
     #[command(about = "Generate proof using bb with Keccak oracle hash")]
-    Prove,
+    Prove {
+        /// Proving scheme passed to bb (defaults to bb's own default)
+        #[arg(long, value_enum)]
+        scheme: Option<BbScheme>,
+
+        /// Regenerate the proof and verification key even if they are up to date
+        #[arg(long)]
+        force: bool,
+
+        /// Transcript hash passed to bb instead of the backend default
+        #[arg(long, value_enum)]
+        oracle_hash: Option<OracleHash>,
+
+        /// Proof encoding written by bb (defaults to bb's own)
+        #[arg(long, value_enum, value_name = "FORMAT")]
+        output_format: Option<ProofOutputFormat>,
+
+        /// Arguments after `--`, appended verbatim to `bb prove`
+        #[arg(last = true, value_name = "ARGS")]
+        extra_args: Vec<String>,
+    },
 
     /// Verify Keccak oracle proof
     #[command(about = "Verify proof generated with Keccak oracle hash")]
-    Verify,
+    Verify {
+        /// Proof file to verify instead of target/evm/proof
+        #[arg(long, value_name = "PATH")]
+        proof: Option<PathBuf>,
+
+        /// Verification key to use instead of target/evm/vk
+        #[arg(long, value_name = "PATH")]
+        vk: Option<PathBuf>,
+
+        /// Public inputs to use instead of target/evm/public_inputs
+        #[arg(long, value_name = "PATH")]
+        public_inputs: Option<PathBuf>,
+
+        /// Whether the proof should verify; with false, succeed only if bb rejects it
+        #[arg(long, value_name = "BOOL", default_value_t = true, action = ArgAction::Set)]
+        expect: bool,
+
+        /// Run bb even if the same proof, vk and public inputs were already verified
+        #[arg(long)]
+        force: bool,
+
+        /// Re-verify whenever the proof, vk or public inputs change (Ctrl-C to stop)
+        #[arg(long)]
+        watch: bool,
+    },
 
     /// Deploy verifier contract to EVM network
     #[cfg(feature = "evm-foundry")]
     #[command(about = "Deploy verifier contract using Foundry")]
     Deploy {
-        /// Network to deploy to (mainnet or sepolia)
-        #[arg(long, default_value = "sepolia")]
-        network: String,
+        /// Network to deploy to (mainnet or sepolia; defaults to bargo.toml, then sepolia)
+        #[arg(long)]
+        network: Option<String>,
+        /// Abort if the estimated deployment gas exceeds this limit
+        #[arg(long, value_name = "GAS")]
+        max_gas: Option<u64>,
+        /// Sign with a foundry keystore (~/.foundry/keystores/<NAME> or a path)
+        #[arg(long, value_name = "NAME")]
+        account: Option<String>,
+        /// Arguments for the verifier's constructor, passed to forge create as given
+        #[arg(long, num_args = 1.., value_name = "ARG", allow_hyphen_values = true)]
+        constructor_args: Vec<String>,
+        /// Deploy to mainnet without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
 
     /// Generate calldata for proof verification
     #[command(about = "Generate calldata for proof verification")]
-    Calldata,
+    Calldata {
+        /// Write calldata here instead of target/evm/calldata.json
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
 
     /// Verify proof on-chain
     #[cfg(feature = "evm-foundry")]
     #[command(about = "Verify proof on EVM network using deployed verifier")]
-    VerifyOnchain,
+    VerifyOnchain {
+        /// Network whose latest deployment to verify against (defaults to bargo.toml, then sepolia)
+        #[arg(long)]
+        network: Option<String>,
+    },
 }
 
 #[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
@@ -160,3 +676,219 @@ pub enum Backend {
     /// All backends
     All,
 }
+
+/// Proving schemes accepted by `bb prove --scheme`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BbScheme {
+    /// UltraHonk (default)
+    #[value(name = "ultra_honk")]
+    UltraHonk,
+    /// UltraHonk with Keccak transcript
+    #[value(name = "ultra_keccak_honk")]
+    UltraKeccakHonk,
+    /// UltraHonk with Starknet (Poseidon) transcript
+    #[value(name = "ultra_starknet_honk")]
+    UltraStarknetHonk,
+    /// Client IVC
+    #[value(name = "client_ivc")]
+    ClientIvc,
+}
+
+impl BbScheme {
+    /// Value passed to bb for this scheme
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BbScheme::UltraHonk => "ultra_honk",
+            BbScheme::UltraKeccakHonk => "ultra_keccak_honk",
+            BbScheme::UltraStarknetHonk => "ultra_starknet_honk",
+            BbScheme::ClientIvc => "client_ivc",
+        }
+    }
+
+    /// Whether proofs in this scheme can be verified inside another Noir circuit
+    ///
+    /// The Keccak and Starknet transcripts target on-chain verifiers, and Client
+    /// IVC folds its own recursion, so only plain UltraHonk qualifies.
+    pub fn supports_recursion(&self) -> bool {
+        matches!(self, BbScheme::UltraHonk)
+    }
+}
+
+/// Proof encodings accepted by `bb prove --output_format`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofOutputFormat {
+    /// Binary `proof` and `public_inputs`, as read by calldata and verify
+    Bytes,
+    /// JSON field elements only (`proof_fields.json`, `public_inputs_fields.json`)
+    Fields,
+    /// Both of the above
+    Both,
+}
+
+impl ProofOutputFormat {
+    /// Value passed to bb for this format
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProofOutputFormat::Bytes => "bytes",
+            ProofOutputFormat::Fields => "fields",
+            ProofOutputFormat::Both => "bytes_and_fields",
+        }
+    }
+}
+
+/// Proof encodings `bargo convert` translates between
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofEncoding {
+    /// Binary `proof`, 32 big-endian bytes per field element
+    Bytes,
+    /// `proof_fields.json`, a JSON array of hex field elements
+    Fields,
+}
+
+impl ProofEncoding {
+    /// File name bb gives a proof in this encoding
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ProofEncoding::Bytes => "proof",
+            ProofEncoding::Fields => "proof_fields.json",
+        }
+    }
+
+    /// The encoding converted from when converting to this one
+    pub fn other(&self) -> Self {
+        match self {
+            ProofEncoding::Bytes => ProofEncoding::Fields,
+            ProofEncoding::Fields => ProofEncoding::Bytes,
+        }
+    }
+}
+
+/// Transcript hashes accepted by `bb --oracle_hash`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OracleHash {
+    /// Keccak, as used by Solidity verifiers
+    Keccak,
+    /// Starknet Poseidon, as used by garaga's Cairo verifiers
+    Starknet,
+    /// Poseidon2, the recursion-friendly default of plain UltraHonk
+    Poseidon2,
+}
+
+impl OracleHash {
+    /// Value passed to bb for this hash
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OracleHash::Keccak => "keccak",
+            OracleHash::Starknet => "starknet",
+            OracleHash::Poseidon2 => "poseidon2",
+        }
+    }
+
+    /// Parse a hash name as written by [`OracleHash::as_str`]
+    pub fn from_name(name: &str) -> Option<Self> {
+        <Self as ValueEnum>::from_str(name, false).ok()
+    }
+}
+
+/// bb performance profiles selected with `prove --bb-profile`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BbProfile {
+    /// bb's defaults: fastest, at the cost of peak memory
+    Release,
+    /// bb's defaults plus verbose and debug logging, for troubleshooting
+    Debug,
+    /// bb's low-memory mode: a smaller footprint, often twice as slow or more
+    LowMemory,
+}
+
+impl BbProfile {
+    /// Profile name as recorded in the manifest
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BbProfile::Release => "release",
+            BbProfile::Debug => "debug",
+            BbProfile::LowMemory => "low-memory",
+        }
+    }
+
+    /// Flags appended to `bb prove` for this profile
+    pub fn bb_args(&self) -> &'static [&'static str] {
+        match self {
+            BbProfile::Release => &[],
+            BbProfile::Debug => &["--verbose", "--debug_logging"],
+            BbProfile::LowMemory => &["--slow_low_memory"],
+        }
+    }
+}
+
+/// Encodings `cairo calldata --format` can write
+#[cfg(feature = "cairo")]
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CalldataFormat {
+    /// garaga's felt array, unchanged (default)
+    #[default]
+    Array,
+    /// Space-separated 0x-prefixed felts, ready for `sncast --calldata`
+    Hex,
+}
+
+/// Proof systems accepted by `garaga gen/calldata --system`
+#[cfg(feature = "cairo")]
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GaragaSystem {
+    /// Zero-knowledge UltraHonk with Starknet transcript (default)
+    #[default]
+    #[value(name = "ultra_starknet_zk_honk")]
+    UltraStarknetZkHonk,
+    /// UltraHonk with Starknet transcript
+    #[value(name = "ultra_starknet_honk")]
+    UltraStarknetHonk,
+    /// Zero-knowledge UltraHonk with Keccak transcript
+    #[value(name = "ultra_keccak_zk_honk")]
+    UltraKeccakZkHonk,
+    /// UltraHonk with Keccak transcript
+    #[value(name = "ultra_keccak_honk")]
+    UltraKeccakHonk,
+}
+
+#[cfg(feature = "cairo")]
+impl GaragaSystem {
+    /// Value passed to garaga for this system
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GaragaSystem::UltraStarknetZkHonk => "ultra_starknet_zk_honk",
+            GaragaSystem::UltraStarknetHonk => "ultra_starknet_honk",
+            GaragaSystem::UltraKeccakZkHonk => "ultra_keccak_zk_honk",
+            GaragaSystem::UltraKeccakHonk => "ultra_keccak_honk",
+        }
+    }
+
+    /// Entrypoint of the verifier contract garaga generates for this system
+    pub fn verify_function(&self) -> String {
+        format!("verify_{}_proof", self.as_str())
+    }
+}
+
+/// Suffix for proof artifact file names (`--artifact-suffix`)
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArtifactSuffix {
+    /// Package name, e.g. `proof_my_circuit`
+    Pkg,
+    /// Unix time at which bargo started, e.g. `proof_1760601600`
+    Timestamp,
+    /// Plain `proof`, `vk` and `public_inputs`
+    #[default]
+    None,
+}
+
+/// When bargo's own output is colored (`--color`)
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal, honoring `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE`
+    #[default]
+    Auto,
+    /// Always emit ANSI colors, even when piped
+    Always,
+    /// Never emit ANSI colors
+    Never,
+}
@@ -0,0 +1,80 @@
+//! List the contract deployments recorded in the address books
+//!
+//! `bargo addresses` is a read-only command that prints every deployment
+//! `evm deploy` and `cairo deploy` appended to `target/<flavour>/addresses.json`,
+//! grouped by network with the entry `verify-onchain` uses marked as latest.
+
+use color_eyre::Result;
+use std::collections::BTreeMap;
+
+use crate::{
+    config::Config,
+    util::{self, AddressBook, Flavour},
+};
+
+/// Print the recorded deployments, optionally only those on `network`
+pub fn run(cfg: &Config, network: Option<&str>) -> Result<()> {
+    let books = collect(network)?;
+
+    if cfg.json {
+        println!("{}", serde_json::to_string(&books)?);
+    } else if !cfg.quiet {
+        print_books(&books);
+    }
+
+    Ok(())
+}
+
+/// Address book of each deploy target, keyed by flavour name
+pub fn collect(network: Option<&str>) -> Result<BTreeMap<&'static str, AddressBook>> {
+    let mut books = BTreeMap::new();
+    for (flavour, name) in flavours() {
+        let mut book = util::read_address_book(&util::address_book_path(flavour))?;
+        if let Some(network) = network {
+            book.networks.retain(|key, _| key == network);
+        }
+        books.insert(name, book);
+    }
+    Ok(books)
+}
+
+/// Deploy targets available with the enabled features, with display names
+fn flavours() -> Vec<(Flavour, &'static str)> {
+    #[allow(unused_mut)]
+    let mut flavours = vec![(Flavour::Evm, "evm")];
+    #[cfg(feature = "cairo")]
+    flavours.push((Flavour::Starknet, "starknet"));
+    flavours
+}
+
+/// Print the address books in human-readable form
+fn print_books(books: &BTreeMap<&'static str, AddressBook>) {
+    if books.values().all(AddressBook::is_empty) {
+        println!("No deployments recorded yet");
+        println!("  • Deploy a verifier with 'bargo evm deploy' or 'bargo cairo deploy'");
+        return;
+    }
+
+    for (name, book) in books.iter().filter(|(_, book)| !book.is_empty()) {
+        println!("🎯 {name}:");
+        for (network, deployments) in &book.networks {
+            println!("  🌐 {network}:");
+            for (index, deployment) in deployments.iter().enumerate() {
+                let latest = if index + 1 == deployments.len() {
+                    " (latest)"
+                } else {
+                    ""
+                };
+                let class_hash = deployment
+                    .class_hash
+                    .as_deref()
+                    .map(|hash| format!(" class {hash}"))
+                    .unwrap_or_default();
+                println!(
+                    "    {} {}{class_hash} ({}){latest}",
+                    deployment.timestamp, deployment.address, deployment.package
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Repeatable proving benchmarks (`bargo bench`)
+//!
+//! Runs the selected backends' prove step a fixed number of times and reports
+//! the minimum, maximum, mean and standard deviation of the run times, along
+//! with the size of the proof artifacts from the last run. Every iteration
+//! bypasses the verification key cache so the full prove step is measured.
+//! With `--json` the results are printed as a single JSON array.
+
+use color_eyre::Result;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::{
+    cli::Backend,
+    commands::prove,
+    config::{Config, ProveConfig},
+    util::{self, Timer, format_duration, format_size},
+};
+
+/// Timing statistics over a set of runs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchStats {
+    pub runs: usize,
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub mean_ms: f64,
+    /// Sample standard deviation; zero for a single run
+    pub stddev_ms: f64,
+}
+
+impl BenchStats {
+    /// Summarise `durations`, or `None` if there are none
+    pub fn from_durations(durations: &[Duration]) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+
+        let ms: Vec<f64> = durations
+            .iter()
+            .map(|duration| duration.as_secs_f64() * 1000.0)
+            .collect();
+        let runs = ms.len();
+        let mean_ms = ms.iter().sum::<f64>() / runs as f64;
+        let variance = if runs > 1 {
+            ms.iter().map(|run| (run - mean_ms).powi(2)).sum::<f64>() / (runs - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            runs,
+            min_ms: ms.iter().copied().fold(f64::INFINITY, f64::min),
+            max_ms: ms.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean_ms,
+            stddev_ms: variance.sqrt(),
+        })
+    }
+}
+
+/// Benchmark result for one backend
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    pub backend: &'static str,
+    /// Duration of each run in milliseconds, in order
+    pub runs_ms: Vec<f64>,
+    pub stats: BenchStats,
+    pub artifacts: Vec<ArtifactSize>,
+}
+
+/// Size of a proof artifact written by the last run
+#[derive(Debug, Serialize)]
+pub struct ArtifactSize {
+    pub name: &'static str,
+    pub path: PathBuf,
+    /// Size in bytes, or `null` if the backend did not write the file
+    pub size: Option<u64>,
+}
+
+/// Prove `iterations` times with each backend selected by `backend`
+pub fn run(cfg: &Config, backend: Backend, iterations: u32) -> Result<()> {
+    let prove_cfg = ProveConfig::default().with_force(true);
+    let kinds = prove::backend_kinds(backend);
+
+    if cfg.dry_run {
+        for kind in kinds {
+            println!(
+                "Would prove {iterations} time(s) with the {} backend:",
+                prove::label(kind)
+            );
+            prove::prove_with(cfg, kind, &prove_cfg)?;
+        }
+        return Ok(());
+    }
+
+    // Per-run spinners and success lines would drown the results table
+    let run_cfg = Config {
+        quiet: true,
+        ..cfg.clone()
+    };
+
+    let mut reports = Vec::new();
+    for kind in kinds {
+        let mut durations = Vec::new();
+        for iteration in 1..=iterations {
+            let timer = Timer::start();
+            prove::prove_with(&run_cfg, kind, &prove_cfg)?;
+            durations.push(timer.duration());
+            if !cfg.quiet {
+                println!(
+                    "  {} run {iteration}/{iterations}: {}",
+                    prove::label(kind),
+                    timer.elapsed()
+                );
+            }
+        }
+
+        let Some(stats) = BenchStats::from_durations(&durations) else {
+            continue;
+        };
+        let flavour = prove::flavour(kind);
+        let artifacts = [
+            ("proof", util::get_proof_path(flavour)),
+            ("vk", util::get_vk_path(flavour)),
+            ("public_inputs", util::get_public_inputs_path(flavour)),
+        ]
+        .into_iter()
+        .map(|(name, path)| ArtifactSize {
+            name,
+            size: std::fs::metadata(&path).ok().map(|metadata| metadata.len()),
+            path,
+        })
+        .collect();
+
+        reports.push(BenchReport {
+            backend: prove::label(kind),
+            runs_ms: durations
+                .iter()
+                .map(|duration| duration.as_secs_f64() * 1000.0)
+                .collect(),
+            stats,
+            artifacts,
+        });
+    }
+
+    if cfg.json {
+        println!("{}", serde_json::to_string(&reports)?);
+    } else if !cfg.quiet {
+        print_reports(&reports);
+    }
+
+    Ok(())
+}
+
+/// Print the results as a table followed by artifact sizes
+fn print_reports(reports: &[BenchReport]) {
+    let ms = |value: f64| format_duration(Duration::from_secs_f64(value / 1000.0));
+
+    println!();
+    println!(
+        "{:<10} {:>5} {:>9} {:>9} {:>9} {:>9}",
+        "backend", "runs", "min", "mean", "max", "stddev"
+    );
+    for report in reports {
+        let stats = &report.stats;
+        println!(
+            "{:<10} {:>5} {:>9} {:>9} {:>9} {:>9}",
+            report.backend,
+            stats.runs,
+            ms(stats.min_ms),
+            ms(stats.mean_ms),
+            ms(stats.max_ms),
+            ms(stats.stddev_ms)
+        );
+    }
+
+    for report in reports {
+        println!();
+        println!("📦 {} artifacts:", report.backend);
+        for artifact in &report.artifacts {
+            let size = artifact
+                .size
+                .map(format_size)
+                .unwrap_or_else(|| "missing".to_string());
+            println!(
+                "  {:<14} {} ({size})",
+                artifact.name,
+                artifact.path.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_synthetic_durations() {
+        let durations = [2, 4, 4, 4, 5, 5, 7, 9].map(Duration::from_secs);
+        let stats = BenchStats::from_durations(&durations).unwrap();
+
+        assert_eq!(stats.runs, 8);
+        assert_eq!(stats.min_ms, 2000.0);
+        assert_eq!(stats.max_ms, 9000.0);
+        assert_eq!(stats.mean_ms, 5000.0);
+        // Sum of squared deviations is 32 s², over n - 1 = 7 runs
+        assert!((stats.stddev_ms - (32.0_f64 / 7.0).sqrt() * 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_stats_single_and_empty_runs() {
+        let stats = BenchStats::from_durations(&[Duration::from_millis(340)]).unwrap();
+        assert_eq!(stats.min_ms, 340.0);
+        assert_eq!(stats.max_ms, 340.0);
+        assert_eq!(stats.stddev_ms, 0.0);
+
+        assert!(BenchStats::from_durations(&[]).is_none());
+    }
+}
@@ -1,72 +1,402 @@
 //! Build command implementation
 
+//!
+//! `--input <file>` executes the circuit with another prover inputs file in
+//! place of `Prover.toml`. The witness is still written as
+//! `target/bb/<package>.gz`, so each build overwrites the previous witness
+//! whichever inputs it used.
+//!
+//! `--profile <name>` does the same with an inputs file named in the
+//! `[inputs]` table of `bargo.toml`, so teams can keep several scenarios
+//! (`happy = "inputs/happy.toml"`) and switch between them by name.
+//!
+//! `--stats` reports the bytecode size and the ACIR opcode and gate counts
+//! from `bb gates`, and records them in `target/bb/manifest.json`. bb builds
+//! without `gates` only lose the counts, not the build.
+//!
+//! Arguments after `--` (`bargo build -- --force`) are appended to
+//! `nargo execute` after bargo's own flags. Since bargo cannot tell whether
+//! they change the output, they bypass the up-to-date check.
+
 use color_eyre::Result;
-use std::path::Path;
+use color_eyre::eyre::WrapErr;
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
 use crate::{
-    commands::common::run_nargo_command_in_directory,
-    config::Config,
-    util::{self, Flavour, Timer, format_operation_result, success},
+    api::BuildResult,
+    commands::common::{
+        record_manifest, run_for_each_member, run_nargo_command_with_passthrough, run_tool_capture,
+    },
+    config::{self, BuildConfig, Config},
+    util::{
+        self, BargoError, CircuitStats, ExitCode, Flavour, Timer, create_smart_error,
+        format_operation_duration, format_size, success,
+    },
 };
 
 /// Determine whether a rebuild is needed based on source timestamps
-fn should_rebuild(pkg_name: &str, cfg: &Config) -> Result<bool> {
+fn should_rebuild(pkg_name: &str, cfg: &Config, input: Option<&Path>) -> Result<bool> {
     if cfg.dry_run {
         return Ok(true);
     }
-    util::needs_rebuild(pkg_name)
+    util::needs_rebuild_from_path(pkg_name, &cfg.working_dir()?, input)
+}
+
+/// Absolute path of the `--input` file or `--profile` inputs, checking it is
+/// a `.toml` file
+///
+/// Outside dry-run mode the file must also exist.
+fn resolve_input(cfg: &Config, build_cfg: &BuildConfig) -> Result<Option<PathBuf>> {
+    let input = match &build_cfg.profile {
+        Some(profile) => config::load_project_config(&cfg.working_dir()?)?
+            .unwrap_or_default()
+            .input_profile(profile)?,
+        None => match build_cfg.input_path() {
+            Some(input) => input,
+            None => return Ok(None),
+        },
+    };
+    let input = std::path::absolute(input)?;
+
+    if input.extension().is_none_or(|ext| ext != "toml") {
+        return Err(create_smart_error(
+            &format!("Prover input {} is not a .toml file", input.display()),
+            &["nargo reads prover inputs in the same TOML format as Prover.toml"],
+        ));
+    }
+    if !cfg.dry_run && !input.is_file() {
+        return Err(create_smart_error(
+            &format!("Prover input {} not found", input.display()),
+            &[&match &build_cfg.profile {
+                Some(profile) => format!("Check the path of profile '{profile}' under [inputs]"),
+                None => "Check the path passed to --input".to_string(),
+            }],
+        ));
+    }
+    Ok(Some(input))
+}
+
+/// Arguments for `nargo execute`, pointing it at `input` when given
+///
+/// nargo resolves `--prover-name` relative to the package root and appends
+/// `.toml`; an absolute path replaces the root, so the input can live anywhere.
+fn execute_args(input: Option<&Path>) -> Vec<String> {
+    let mut args = vec!["execute".to_string()];
+    if let Some(input) = input {
+        args.push("--prover-name".to_string());
+        args.push(util::tool_path_string(&input.with_extension("")));
+    }
+    args
+}
+
+/// Execute the build workflow for every member of the current workspace
+pub fn run_all(cfg: &Config, build_cfg: &BuildConfig) -> Result<()> {
+    run_for_each_member(cfg, |cfg| run_with_config(cfg, build_cfg))
+}
+
+/// Report whether the build artifacts are up to date without running nargo
+///
+/// Fails with the list of missing artifacts and newer inputs when a rebuild is
+/// needed. In `--json` mode prints `{"up_to_date": bool, "reasons": [...]}`
+/// and fails with [`ExitCode::ArtifactMissing`] when stale.
+pub fn check(cfg: &Config, build_cfg: &BuildConfig) -> Result<()> {
+    let pkg_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)?;
+    let input = resolve_input(cfg, build_cfg)?;
+    let reasons =
+        util::rebuild_reasons_from_path(&pkg_name, &cfg.working_dir()?, input.as_deref())?;
+
+    if cfg.json {
+        let up_to_date = reasons.is_empty();
+        println!(
+            "{}",
+            serde_json::json!({ "up_to_date": up_to_date, "reasons": reasons })
+        );
+        if !up_to_date {
+            return Err(BargoError::Reported {
+                code: ExitCode::ArtifactMissing,
+            }
+            .into());
+        }
+        return Ok(());
+    }
+
+    if !reasons.is_empty() {
+        return Err(BargoError::StaleArtifacts { reasons }.into());
+    }
+
+    if !cfg.quiet {
+        println!("{}", success("Build is up to date"));
+    }
+    Ok(())
 }
 
 /// Execute the build workflow
 pub fn run(cfg: &Config) -> Result<()> {
-    run_in_directory(cfg, None)
+    run_with_config(cfg, &BuildConfig::default())
 }
 
-/// Execute the build workflow in a specific directory
-pub fn run_in_directory(cfg: &Config, working_dir: Option<&Path>) -> Result<()> {
-    if cfg.dry_run {
-        return run_nargo_command_in_directory(cfg, &["execute"], working_dir);
-    }
+/// Execute the build workflow with build-specific settings
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `build_cfg` - Build settings such as the prover inputs file
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn run_with_config(cfg: &Config, build_cfg: &BuildConfig) -> Result<()> {
+    let result = build(cfg, build_cfg)?;
 
-    let pkg_name = match working_dir {
-        Some(dir) => util::get_package_name_in_directory(cfg.pkg.as_ref(), dir)?,
-        None => util::get_package_name(cfg.pkg.as_ref())?,
-    };
+    if !cfg.quiet && !cfg.dry_run {
+        if result.up_to_date {
+            println!("{}", success("Build is up to date"));
+        } else {
+            println!(
+                "{}",
+                success(&format_operation_duration(
+                    "Build completed",
+                    &result.artifacts[0],
+                    result.elapsed
+                ))
+            );
+        }
+    }
 
-    if !should_rebuild(&pkg_name, cfg)? {
+    if let Some(stats) = &result.stats {
+        util::record_json_detail("bytecode_size", stats.bytecode_size);
+        if let (Some(acir_opcodes), Some(gates)) = (stats.acir_opcodes, stats.gates) {
+            util::record_json_detail("acir_opcodes", acir_opcodes);
+            util::record_json_detail("gates", gates);
+        }
         if !cfg.quiet {
-            println!("{}", success("Build is up to date"));
+            println!("📊 Circuit: {}", describe_stats(stats));
         }
-        return Ok(());
     }
 
+    Ok(())
+}
+
+/// Run nargo unless the artifacts are current, without printing
+///
+/// Outside a dry run the package must resolve; in a dry run the command is
+/// still handed to the runner when it does not.
+pub fn build(cfg: &Config, build_cfg: &BuildConfig) -> Result<BuildResult> {
+    let input = resolve_input(cfg, build_cfg)?;
+    let args = execute_args(input.as_deref());
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let passthrough = &build_cfg.extra_args;
     let timer = Timer::start();
-    run_nargo_command_in_directory(cfg, &["execute"], working_dir)?;
+    let artifacts = |pkg_name: &str| {
+        vec![
+            util::get_bytecode_path(pkg_name, Flavour::Bb),
+            util::get_witness_path(pkg_name, Flavour::Bb),
+        ]
+    };
+
+    if cfg.dry_run {
+        run_nargo_command_with_passthrough(cfg, &args, passthrough)?;
+        let package =
+            util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?).ok();
+        if let Some(pkg_name) = &package {
+            util::organize_build_artifacts(cfg.fs.as_ref(), pkg_name, Flavour::Bb)?;
+        }
+        if build_cfg.stats
+            && let Some(pkg_name) = &package
+        {
+            count_gates(cfg, &util::get_bytecode_path(pkg_name, Flavour::Bb));
+        }
+        return Ok(BuildResult {
+            artifacts: package.as_deref().map(artifacts).unwrap_or_default(),
+            package,
+            up_to_date: false,
+            stats: None,
+            elapsed: timer.duration(),
+        });
+    }
+
+    let pkg_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)?;
+
+    // Passthrough arguments may change nargo's output, so they always rebuild
+    let up_to_date = passthrough.is_empty() && !should_rebuild(&pkg_name, cfg, input.as_deref())?;
+    if !up_to_date {
+        // A Ctrl-C while nargo writes its output must not leave it truncated
+        let partial = util::PartialArtifacts::track(util::nargo_output_paths(&pkg_name));
+        run_nargo_command_with_passthrough(cfg, &args, passthrough)?;
+        partial.finish();
+        util::organize_build_artifacts(cfg.fs.as_ref(), &pkg_name, Flavour::Bb)?;
 
-    match working_dir {
-        Some(dir) => util::organize_build_artifacts_in_directory(&pkg_name, Flavour::Bb, dir)?,
-        None => util::organize_build_artifacts(&pkg_name, Flavour::Bb)?,
+        let manifest = util::manifest_path(Flavour::Bb);
+        record_manifest(cfg, &manifest, "build", &artifacts(&pkg_name), &["nargo"])?;
+        util::set_manifest_prover_input(&manifest, input.as_deref())?;
     }
 
-    if !cfg.quiet {
-        let current_dir;
-        let base_dir = match working_dir {
-            Some(dir) => dir,
-            None => {
-                current_dir = std::env::current_dir()?;
-                &current_dir
+    // Stats from an earlier build no longer describe rebuilt bytecode
+    let stats = if build_cfg.stats {
+        Some(circuit_stats(cfg, &pkg_name)?)
+    } else {
+        None
+    };
+    if stats.is_some() || !up_to_date {
+        util::set_manifest_circuit_stats(&util::manifest_path(Flavour::Bb), stats.as_ref())?;
+    }
+
+    Ok(BuildResult {
+        artifacts: artifacts(&pkg_name),
+        package: Some(pkg_name),
+        up_to_date,
+        stats,
+        elapsed: timer.duration(),
+    })
+}
+
+/// ACIR opcode and gate totals from `bb gates`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GateCount {
+    pub acir_opcodes: u64,
+    pub gates: u64,
+}
+
+/// Parse the JSON report printed by `bb gates`
+///
+/// Recent bb releases list one entry per circuit function under `functions`
+/// and older ones print a single object; either way the `acir_opcodes` and
+/// `circuit_size` fields are summed. Log lines before the JSON are skipped.
+/// Returns `None` when the output holds no such report.
+pub fn parse_gate_count(output: &str) -> Option<GateCount> {
+    let json = &output[output.find('{')?..];
+    let report: serde_json::Value = serde_json::Deserializer::from_str(json)
+        .into_iter()
+        .next()?
+        .ok()?;
+
+    let functions = match report.get("functions") {
+        Some(functions) => functions.as_array()?.iter().collect(),
+        None => vec![&report],
+    };
+    functions.into_iter().try_fold(
+        GateCount {
+            acir_opcodes: 0,
+            gates: 0,
+        },
+        |total, function| {
+            Some(GateCount {
+                acir_opcodes: total.acir_opcodes + function.get("acir_opcodes")?.as_u64()?,
+                gates: total.gates + function.get("circuit_size")?.as_u64()?,
+            })
+        },
+    )
+}
+
+/// Ask `bb gates` to count the gates of `bytecode`
+///
+/// bb releases without the subcommand, or that print something unexpected,
+/// only produce a warning, since the build itself succeeded.
+fn count_gates(cfg: &Config, bytecode: &Path) -> Option<GateCount> {
+    let bytecode = util::tool_path_string(bytecode);
+    match run_tool_capture(cfg, "bb", &["gates", "-b", &bytecode]) {
+        // A dry run captures no output to count
+        Ok(_) if cfg.dry_run => None,
+        Ok(output) => {
+            let count = parse_gate_count(&output.stdout);
+            if count.is_none() {
+                warn!("Could not read a gate count from `bb gates` output");
             }
-        };
-        let bytecode_path = base_dir.join(util::get_bytecode_path(&pkg_name, Flavour::Bb));
-        println!(
-            "{}",
-            success(&format_operation_result(
-                "Build completed",
-                &bytecode_path,
-                &timer
-            ))
+            count
+        }
+        Err(e) => {
+            warn!("Gate count unavailable, this bb may not support `bb gates`: {e:#}");
+            None
+        }
+    }
+}
+
+/// Bytecode size and gate count of the package's compiled circuit
+fn circuit_stats(cfg: &Config, pkg_name: &str) -> Result<CircuitStats> {
+    let bytecode = util::get_bytecode_path(pkg_name, Flavour::Bb);
+    let bytecode_size = std::fs::metadata(&bytecode)
+        .wrap_err_with(|| format!("reading size of {}", bytecode.display()))?
+        .len();
+    let gates = count_gates(cfg, &bytecode);
+
+    Ok(CircuitStats {
+        bytecode_size,
+        acir_opcodes: gates.map(|count| count.acir_opcodes),
+        gates: gates.map(|count| count.gates),
+    })
+}
+
+/// One-line summary of `stats` for the build output
+fn describe_stats(stats: &CircuitStats) -> String {
+    let mut parts = vec![format!("{} bytecode", format_size(stats.bytecode_size))];
+    match (stats.acir_opcodes, stats.gates) {
+        (Some(acir_opcodes), Some(gates)) => {
+            parts.push(format!("{acir_opcodes} ACIR opcodes"));
+            parts.push(format!("{gates} gates"));
+        }
+        _ => parts.push("gate count unavailable".to_string()),
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gate_count_sums_functions() {
+        let output = r#"[bb] scheme is: ultra_honk
+{"functions": [
+  {
+        "acir_opcodes": 12,
+        "circuit_size": 2990,
+        "gates_per_opcode": [1, 2]
+  },
+  {
+        "acir_opcodes": 3,
+        "circuit_size": 10
+  }
+]}"#;
+        assert_eq!(
+            parse_gate_count(output),
+            Some(GateCount {
+                acir_opcodes: 15,
+                gates: 3000
+            })
+        );
+
+        // Older bb prints a single object
+        assert_eq!(
+            parse_gate_count(r#"{"acir_opcodes": 1, "circuit_size": 3}"#),
+            Some(GateCount {
+                acir_opcodes: 1,
+                gates: 3
+            })
+        );
+
+        assert_eq!(parse_gate_count("Unknown subcommand: gates"), None);
+        assert_eq!(
+            parse_gate_count(r#"{"functions": [{"acir_opcodes": 1}]}"#),
+            None
         );
     }
 
-    Ok(())
+    #[test]
+    fn test_describe_stats() {
+        let mut stats = CircuitStats {
+            bytecode_size: 2048,
+            acir_opcodes: Some(15),
+            gates: Some(3000),
+        };
+        assert_eq!(
+            describe_stats(&stats),
+            format!(
+                "{} bytecode, 15 ACIR opcodes, 3000 gates",
+                format_size(2048)
+            )
+        );
+
+        stats.gates = None;
+        assert!(describe_stats(&stats).ends_with("gate count unavailable"));
+    }
 }
@@ -8,15 +8,26 @@ use color_eyre::eyre::WrapErr;
 
 use crate::{
     backend::{Backend, BackendConfig},
-    config::{CairoDeployConfig, Config},
+    commands::{
+        common,
+        keystore::{self, Keystore},
+    },
+    config::{
+        CairoDeployConfig, CalldataConfig, Config, GaragaConfig, Network, ProveConfig, VerifyConfig,
+    },
+    util::{self, Flavour},
 };
 
-use super::workflow;
+use super::{starkli, workflow};
 
 /// Cairo backend implementation for Starknet-based proof systems
 #[derive(Debug)]
 pub struct CairoBackend {
     deploy_config: Option<CairoDeployConfig>,
+    prove_config: ProveConfig,
+    calldata_config: CalldataConfig,
+    verify_config: VerifyConfig,
+    garaga_config: GaragaConfig,
 }
 
 impl CairoBackend {
@@ -24,6 +35,10 @@ impl CairoBackend {
     pub fn new() -> Self {
         Self {
             deploy_config: None,
+            prove_config: ProveConfig::default(),
+            calldata_config: CalldataConfig::default(),
+            verify_config: VerifyConfig::default(),
+            garaga_config: GaragaConfig::default(),
         }
     }
 }
@@ -31,22 +46,22 @@ impl CairoBackend {
 impl Backend for CairoBackend {
     /// Generate Cairo verifier contract and setup project structure
     fn generate(&mut self, cfg: &Config) -> Result<()> {
-        workflow::run_gen(cfg)
+        workflow::run_gen_with_config(cfg, &self.garaga_config, &self.prove_config)
     }
 
     /// Generate proof using Cairo/Starknet proof system
     fn prove(&mut self, cfg: &Config) -> Result<()> {
-        workflow::run_prove(cfg)
+        workflow::run_prove_with_config(cfg, &self.prove_config)
     }
 
     /// Verify a generated Cairo proof
     fn verify(&mut self, cfg: &Config) -> Result<()> {
-        workflow::run_verify(cfg)
+        workflow::run_verify_with_config(cfg, &self.verify_config)
     }
 
     /// Generate calldata for Cairo proof verification
     fn calldata(&mut self, cfg: &Config) -> Result<()> {
-        workflow::run_calldata(cfg)
+        workflow::run_calldata_with_config(cfg, &self.calldata_config, &self.garaga_config)
     }
 
     /// Deploy Cairo verifier contract to Starknet network
@@ -55,8 +70,9 @@ impl Backend for CairoBackend {
     /// 1. Declare the contract on the network to get a class_hash (if auto-declare is enabled)
     /// 2. Deploy an instance of the contract using the class_hash
     fn deploy(&mut self, cfg: &Config, network: Option<&str>) -> Result<()> {
-        // Use provided network or default to "sepolia"
-        let network_str = network.unwrap_or("sepolia");
+        // Use provided network or default to "sepolia", rejecting typos up front
+        let network = Network::parse(network.unwrap_or("sepolia"))?;
+        let network_str = network.to_string();
 
         // Get deploy configuration or use defaults
         let default_config = CairoDeployConfig::new(
@@ -64,19 +80,42 @@ impl Backend for CairoBackend {
             false, // Default to not forcing no-declare
         );
         let deploy_cfg = self.deploy_config.as_ref().unwrap_or(&default_config);
+        common::confirm_mainnet(
+            cfg,
+            &network,
+            deploy_cfg.yes,
+            "declare and deploy the verifier contract",
+        )?;
+        starkli::validate_calldata(&deploy_cfg.constructor_calldata)?;
+
+        // Unlock the keystore once so declare and deploy share the password
+        let keystore = deploy_cfg
+            .account
+            .as_deref()
+            .map(|account| {
+                Keystore::open(keystore::starkli_keystore_path(account), cfg.dry_run)
+                    .map_err(util::enhance_error_with_suggestions)
+            })
+            .transpose()?;
 
         // In dry-run mode, skip all validations and just call the workflow functions
         if cfg.dry_run {
             if deploy_cfg.should_auto_declare() {
-                workflow::internal_declare(cfg, network_str)?;
+                workflow::internal_declare(cfg, &network_str, keystore.as_ref())?;
             }
-            return workflow::run_deploy(cfg, deploy_cfg.class_hash.as_deref());
+            return workflow::run_deploy(
+                cfg,
+                &network_str,
+                deploy_cfg.class_hash.as_deref(),
+                &deploy_cfg.constructor_calldata,
+                keystore.as_ref(),
+            );
         }
 
         // Check if we should auto-declare
         if deploy_cfg.should_auto_declare() {
             // Check if contract is already declared by trying to read saved class hash
-            let class_hash_file = std::path::PathBuf::from("target/starknet/.bargo_class_hash");
+            let class_hash_file = util::target_dir(Flavour::Starknet).join(".bargo_class_hash");
             let class_hash_exists = class_hash_file.exists()
                 && std::fs::read_to_string(&class_hash_file)
                     .wrap_err_with(|| {
@@ -90,11 +129,11 @@ impl Backend for CairoBackend {
 
             if !class_hash_exists {
                 // Step 1: Declare the contract to get class_hash
-                workflow::internal_declare(cfg, network_str)?;
+                workflow::internal_declare(cfg, &network_str, keystore.as_ref())?;
             }
         } else if deploy_cfg.class_hash.is_none() {
             // No auto-declare and no class hash provided - check if saved class hash exists
-            let class_hash_file = std::path::PathBuf::from("target/starknet/.bargo_class_hash");
+            let class_hash_file = util::target_dir(Flavour::Starknet).join(".bargo_class_hash");
             if !class_hash_file.exists() {
                 return Err(color_eyre::eyre::eyre!(
                     "No class hash provided and auto-declare is disabled. Either provide --class-hash or enable auto-declare"
@@ -103,12 +142,18 @@ impl Backend for CairoBackend {
         }
 
         // Step 2: Deploy the contract using the class_hash
-        workflow::run_deploy(cfg, deploy_cfg.class_hash.as_deref())
+        workflow::run_deploy(
+            cfg,
+            &network_str,
+            deploy_cfg.class_hash.as_deref(),
+            &deploy_cfg.constructor_calldata,
+            keystore.as_ref(),
+        )
     }
 
     /// Verify proof on-chain using deployed Cairo verifier on Starknet
     fn verify_onchain(&mut self, cfg: &Config, address: Option<&str>) -> Result<()> {
-        workflow::run_verify_onchain(cfg, address)
+        workflow::run_verify_onchain_with_config(cfg, address, &self.garaga_config)
     }
 
     /// Configure backend with backend-specific settings
@@ -118,6 +163,26 @@ impl Backend for CairoBackend {
                 self.deploy_config = Some(deploy_config);
                 Ok(())
             }
+            BackendConfig::Prove(prove_config) => {
+                self.prove_config = prove_config;
+                Ok(())
+            }
+            BackendConfig::Calldata(calldata_config) => {
+                self.calldata_config = calldata_config;
+                Ok(())
+            }
+            BackendConfig::Verify(verify_config) => {
+                self.verify_config = verify_config;
+                Ok(())
+            }
+            BackendConfig::Garaga(garaga_config) => {
+                self.garaga_config = garaga_config;
+                Ok(())
+            }
+            // EVM deploy and Foundry settings don't apply to the Cairo backend
+            BackendConfig::Foundry(_) => Ok(()),
+            #[cfg(feature = "evm-foundry")]
+            BackendConfig::EvmDeploy(_) => Ok(()),
         }
     }
 }
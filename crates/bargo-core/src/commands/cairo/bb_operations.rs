@@ -4,77 +4,106 @@
 //! specifically for Starknet oracle hash operations.
 
 use color_eyre::Result;
+use std::path::Path;
 
 use crate::{
+    cli::BbScheme,
     commands::common,
-    config::Config,
+    config::{Config, ProveConfig},
     util::{self, Flavour},
 };
 
 /// Generate a Starknet-compatible proof using BB with ultra_honk scheme
 ///
 /// This function generates a proof with the following BB flags:
-/// - `--scheme ultra_honk`
-/// - `--oracle_hash starknet`
+/// - `--scheme ultra_honk` (unless overridden by `prove_cfg.scheme`)
+/// - `--oracle_hash starknet` (unless overridden by `prove_cfg.oracle_hash`)
 /// - `--zk`
 ///
+/// followed by the flags of `prove_cfg.bb_profile` and any passthrough
+/// arguments in `prove_cfg.extra_args`.
+///
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
 /// * `pkg` - Package name for locating bytecode and witness files
+/// * `prove_cfg` - Scheme, oracle hash override, recursion settings, bb
+///   profile and passthrough arguments
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from BB execution
-pub fn generate_starknet_proof(cfg: &Config, pkg: &str) -> Result<()> {
+pub fn generate_starknet_proof(cfg: &Config, pkg: &str, prove_cfg: &ProveConfig) -> Result<()> {
     let bytecode = util::get_bytecode_path(pkg, Flavour::Bb);
-    let witness = util::get_witness_path(pkg, Flavour::Bb);
+    let witness = prove_cfg.witness_path(pkg);
+    let scheme = prove_cfg.scheme.unwrap_or(BbScheme::UltraHonk);
+    let bytecode_str = util::tool_path_string(&bytecode);
+    let witness_str = util::tool_path_string(&witness);
+    let output_dir = util::target_dir(Flavour::Starknet);
+    let output_str = util::tool_path_string(&output_dir);
 
-    common::run_tool(
-        cfg,
-        "bb",
-        &[
-            "prove",
-            "--scheme",
-            "ultra_honk",
-            "--oracle_hash",
-            "starknet",
-            "--zk",
-            "-b",
-            &bytecode.to_string_lossy(),
-            "-w",
-            &witness.to_string_lossy(),
-            "-o",
-            "./target/starknet/",
-        ],
-    )
+    let mut args = vec!["prove", "--scheme", scheme.as_str()];
+    args.extend(common::bb_oracle_args(
+        Flavour::Starknet,
+        prove_cfg.oracle_hash,
+    ));
+    args.extend([
+        "--zk",
+        "-b",
+        &bytecode_str,
+        "-w",
+        &witness_str,
+        "-o",
+        &output_str,
+    ]);
+    args.extend(common::bb_recursion_args(
+        prove_cfg,
+        prove_cfg.bb_output_format(),
+        &prove_cfg.extra_args,
+    ));
+    if let Some(profile) = prove_cfg.bb_profile {
+        args.extend(profile.bb_args());
+    }
+    args.extend(prove_cfg.extra_args.iter().map(String::as_str));
+
+    common::run_tool(cfg, "bb", &args)?;
+    util::apply_artifact_names(cfg.fs.as_ref(), Flavour::Starknet)
 }
 
 /// Generate a Starknet-compatible verification key using BB
 ///
 /// This function generates a VK with the following BB flags:
-/// - `--oracle_hash starknet`
+/// - `--oracle_hash starknet` (unless overridden by `prove_cfg.oracle_hash`)
+///
+/// The existing key is reused when the bytecode has not changed since it was
+/// written, unless `prove_cfg.force` is set.
 ///
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
 /// * `pkg` - Package name for locating bytecode file
+/// * `prove_cfg` - Cache bypass, oracle hash override and recursion settings
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from BB execution
-pub fn generate_starknet_vk(cfg: &Config, pkg: &str) -> Result<()> {
+pub fn generate_starknet_vk(cfg: &Config, pkg: &str, prove_cfg: &ProveConfig) -> Result<()> {
     let bytecode = util::get_bytecode_path(pkg, Flavour::Bb);
+    let bytecode_str = util::tool_path_string(&bytecode);
+    let output_dir = util::target_dir(Flavour::Starknet);
+    let output_str = util::tool_path_string(&output_dir);
 
-    common::run_tool(
-        cfg,
-        "bb",
-        &[
-            "write_vk",
-            "--oracle_hash",
-            "starknet",
-            "-b",
-            &bytecode.to_string_lossy(),
-            "-o",
-            "./target/starknet/",
-        ],
-    )
+    let mut args = vec!["write_vk"];
+    args.extend(common::bb_oracle_args(
+        Flavour::Starknet,
+        prove_cfg.oracle_hash,
+    ));
+    args.extend(["-b", &bytecode_str, "-o", &output_str]);
+    args.extend(common::bb_recursion_args(
+        prove_cfg,
+        prove_cfg
+            .recursive
+            .then_some(common::BB_RECURSIVE_OUTPUT_FORMAT),
+        &[],
+    ));
+
+    common::run_bb_write_vk(cfg, Flavour::Starknet, &bytecode, &args, prove_cfg.force)
 }
 
 /// Verify a Starknet proof using BB
@@ -89,28 +118,76 @@ pub fn generate_starknet_vk(cfg: &Config, pkg: &str) -> Result<()> {
 /// # Returns
 /// * `Result<()>` - Success or error from BB execution
 pub fn verify_starknet_proof(cfg: &Config, _pkg: &str) -> Result<()> {
-    let proof_path = util::get_proof_path(Flavour::Starknet);
-    let vk_path = util::get_vk_path(Flavour::Starknet);
-    let public_inputs_path = util::get_public_inputs_path(Flavour::Starknet);
-
-    common::run_tool(
+    verify_starknet_proof_at(
         cfg,
-        "bb",
-        &[
-            "verify",
-            "--scheme",
-            "ultra_honk",
-            "--zk",
-            "-p",
-            &proof_path.to_string_lossy(),
-            "-k",
-            &vk_path.to_string_lossy(),
-            "-i",
-            &public_inputs_path.to_string_lossy(),
-            "--oracle_hash",
-            "starknet",
-        ],
+        &util::get_proof_path(Flavour::Starknet),
+        &util::get_vk_path(Flavour::Starknet),
+        &util::get_public_inputs_path(Flavour::Starknet),
+        true,
     )
+    .map(|_| ())
+}
+
+/// Verify a Starknet proof using BB with explicit artifact paths
+///
+/// Uses the oracle hash recorded in the manifest next to the proof, if the
+/// proof was generated with `--oracle-hash`. Artifacts written by
+/// `prove --compress` (`.gz`) are decompressed before bb sees them.
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+/// * `proof_path` - Proof file to verify
+/// * `vk_path` - Verification key file
+/// * `public_inputs_path` - Public inputs file; when missing, see
+///   [`common::resolve_public_inputs`]
+/// * `expect_valid` - Whether a rejected proof is an error; with `false` bb's
+///   verdict is captured and returned instead
+///
+/// # Returns
+/// * `Result<bool>` - Whether bb accepted the proof, or error from BB execution
+pub fn verify_starknet_proof_at(
+    cfg: &Config,
+    proof_path: &Path,
+    vk_path: &Path,
+    public_inputs_path: &Path,
+    expect_valid: bool,
+) -> Result<bool> {
+    // Older and newer bb releases lay the public inputs out differently
+    let public_inputs = common::resolve_public_inputs(cfg, proof_path, public_inputs_path)?;
+
+    // bb reads only uncompressed files; expanded copies are removed on return
+    let mut inputs = vec![proof_path, vk_path];
+    inputs.extend(public_inputs.as_deref());
+    let expanded = util::decompress_artifacts(&inputs)?;
+    let paths = expanded.paths();
+    let proof_str = util::tool_path_string(&paths[0]);
+    let vk_str = util::tool_path_string(&paths[1]);
+    let public_inputs_str = paths.get(2).map(|path| util::tool_path_string(path));
+
+    let mut args = vec![
+        "verify",
+        "--scheme",
+        "ultra_honk",
+        "--zk",
+        "-p",
+        &proof_str,
+        "-k",
+        &vk_str,
+    ];
+    if let Some(public_inputs_str) = &public_inputs_str {
+        args.extend(["-i", public_inputs_str]);
+    }
+    args.extend(common::bb_oracle_args(
+        Flavour::Starknet,
+        common::recorded_oracle_hash(proof_path)?,
+    ));
+
+    if expect_valid {
+        common::run_tool(cfg, "bb", &args)?;
+        Ok(true)
+    } else {
+        common::run_bb_verify(cfg, &args)
+    }
 }
 
 /// Generate both Starknet proof and verification key in a single operation
@@ -121,11 +198,16 @@ pub fn verify_starknet_proof(cfg: &Config, _pkg: &str) -> Result<()> {
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
 /// * `pkg` - Package name for locating bytecode and witness files
+/// * `prove_cfg` - Scheme, oracle hash, vk cache bypass and recursion settings
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from either operation
-pub fn generate_starknet_proof_and_vk(cfg: &Config, pkg: &str) -> Result<()> {
-    generate_starknet_proof(cfg, pkg)?;
-    generate_starknet_vk(cfg, pkg)?;
+pub fn generate_starknet_proof_and_vk(
+    cfg: &Config,
+    pkg: &str,
+    prove_cfg: &ProveConfig,
+) -> Result<()> {
+    generate_starknet_proof(cfg, pkg, prove_cfg)?;
+    generate_starknet_vk(cfg, pkg, prove_cfg)?;
     Ok(())
 }
@@ -22,15 +22,16 @@ pub fn ensure_starknet_target_dir() -> Result<()> {
 
 /// Ensure the Cairo contracts directory exists
 ///
-/// This function creates the contracts/cairo directory if it doesn't exist,
-/// which is where generated Cairo verifier contracts are stored.
+/// This function creates the contracts/cairo directory, or `output_dir` and
+/// its parents, if it doesn't exist. That is where generated Cairo verifier
+/// contracts are stored.
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from directory creation
-pub fn ensure_cairo_contracts_dir() -> Result<()> {
-    let cairo_dir = Path::new("./contracts/cairo");
+pub fn ensure_cairo_contracts_dir(output_dir: Option<&Path>) -> Result<()> {
+    let cairo_dir = cairo_contracts_dir(output_dir);
     if !cairo_dir.exists() {
-        std::fs::create_dir_all(cairo_dir).wrap_err_with(|| {
+        std::fs::create_dir_all(&cairo_dir).wrap_err_with(|| {
             format!(
                 "creating Cairo contracts directory at {}",
                 cairo_dir.display()
@@ -43,14 +44,17 @@ pub fn ensure_cairo_contracts_dir() -> Result<()> {
 /// Validate Cairo workflow directory structure
 ///
 /// This function checks that all necessary directories exist for the Cairo workflow
-/// and creates them if they don't exist.
+/// and creates them if they don't exist. With `output_dir` the contracts are
+/// written there, so `contracts/` is left alone.
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from validation/creation
-pub fn validate_cairo_directory_structure() -> Result<()> {
+pub fn validate_cairo_directory_structure(output_dir: Option<&Path>) -> Result<()> {
     ensure_starknet_target_dir()?;
-    util::ensure_contracts_dir()?;
-    ensure_cairo_contracts_dir()?;
+    if output_dir.is_none() {
+        util::ensure_contracts_dir()?;
+    }
+    ensure_cairo_contracts_dir(output_dir)?;
     Ok(())
 }
 
@@ -59,5 +63,34 @@ pub fn validate_cairo_directory_structure() -> Result<()> {
 /// # Returns
 /// * `PathBuf` - Path to the Cairo contracts directory
 pub fn get_cairo_contracts_dir() -> PathBuf {
-    PathBuf::from("./contracts/cairo")
+    cairo_contracts_dir(None)
+}
+
+/// Get the directory the Cairo verifier project is written to
+///
+/// `output_dir` comes from `cairo gen --output-dir` and, like other paths
+/// given on the command line, resolves against `--package-dir`.
+///
+/// # Returns
+/// * `PathBuf` - `output_dir`, or the default Cairo contracts directory
+pub fn cairo_contracts_dir(output_dir: Option<&Path>) -> PathBuf {
+    util::project_path(output_dir.unwrap_or(Path::new("./contracts/cairo")))
+}
+
+/// Check whether `dir` holds a verifier project generated by garaga
+///
+/// bargo names the project it asks garaga for, so a `Scarb.toml` with that
+/// package name marks a directory that is safe to regenerate.
+pub fn is_generated_cairo_project(dir: &Path) -> bool {
+    std::fs::read_to_string(dir.join("Scarb.toml"))
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Table>().ok())
+        .and_then(|scarb| {
+            scarb
+                .get("package")?
+                .get("name")?
+                .as_str()
+                .map(|name| name == super::garaga::VERIFIER_PROJECT_NAME)
+        })
+        .unwrap_or(false)
 }
@@ -6,13 +6,120 @@
 use color_eyre::Result;
 use color_eyre::eyre::WrapErr;
 use std::path::{Path, PathBuf};
+use tracing::warn;
 
 use crate::{
-    commands::common,
+    cli::{CalldataFormat, GaragaSystem},
+    commands::{
+        cairo::sncast,
+        common,
+        doctor::{ToolVersion, VersionCheck},
+    },
     config::Config,
-    util::{self, Flavour, move_generated_project},
+    util::{self, Flavour, create_smart_error, move_generated_project},
 };
 
+/// Oldest garaga release whose CLI output bargo can parse (inclusive)
+pub const GARAGA_MIN: (u64, u64, u64) = (0, 18, 0);
+
+/// First garaga release bargo has not been tested with (exclusive)
+pub const GARAGA_MAX: (u64, u64, u64) = (0, 19, 0);
+
+/// Project name bargo passes to `garaga gen --project-name`
+pub const VERIFIER_PROJECT_NAME: &str = "cairo_verifier";
+
+/// Check that the installed garaga is within the supported version range
+///
+/// Runs `garaga --version` before garaga's output is parsed, so an old or
+/// too-new garaga fails with upgrade instructions instead of a confusing
+/// parse error. A version that cannot be determined only logs a warning; a
+/// missing garaga is reported by the command that needs it.
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+///
+/// # Returns
+/// * `Result<()>` - Error if the installed garaga is outside the supported range
+pub fn check_version(cfg: &Config) -> Result<()> {
+    let Some(output) = common::tool_version(cfg, "garaga") else {
+        return Ok(());
+    };
+    let Some(version) = parse_version(&output) else {
+        warn!("Could not parse garaga version from '{}'", output);
+        return Ok(());
+    };
+
+    match check_supported(&version) {
+        VersionCheck::Mismatch { expected } => Err(create_smart_error(
+            &format!("garaga {version} is not supported; bargo requires garaga {expected}"),
+            &[
+                "Install a supported release: pip install garaga==0.18.1",
+                "If garaga was installed with pipx: pipx install --force garaga==0.18.1",
+                "Run 'bargo doctor' to check the other tool versions",
+            ],
+        )),
+        VersionCheck::Compatible | VersionCheck::Unknown => Ok(()),
+    }
+}
+
+/// Parse the version from `garaga --version` output
+///
+/// garaga is a Python package, so besides `0.18.1` and semver-style
+/// `0.18.1-rc.1` the PEP 440 forms `0.18.1rc1`, `0.18.1.dev2` and
+/// `0.18.1.post1` are accepted. Development and release-candidate suffixes
+/// become the pre-release part; post-releases count as the release itself.
+pub fn parse_version(output: &str) -> Option<ToolVersion> {
+    output
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+        .find_map(parse_version_token)
+}
+
+/// Parse a single version token such as `0.18.1`, `v0.18.1rc1` or `0.18.1.dev2`
+fn parse_version_token(token: &str) -> Option<ToolVersion> {
+    let token = token.strip_prefix('v').unwrap_or(token);
+    let token = token.split('+').next()?;
+    let core_len = token
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(token.len());
+    let (core, suffix) = token.split_at(core_len);
+    let core = core.strip_suffix('.').unwrap_or(core);
+
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let (major, minor, patch) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let suffix = suffix.trim_start_matches(['-', '.', '_']);
+    let pre = (!suffix.is_empty() && !suffix.starts_with("post")).then(|| suffix.to_string());
+    Some(ToolVersion {
+        major,
+        minor,
+        patch,
+        pre,
+    })
+}
+
+/// Compare `version` with the supported garaga range
+///
+/// A pre-release of the minimum version is older than that release and is
+/// therefore rejected; pre-releases inside the range are accepted.
+pub fn check_supported(version: &ToolVersion) -> VersionCheck {
+    let release = (version.major, version.minor, version.patch);
+    let below_min = release < GARAGA_MIN || (release == GARAGA_MIN && version.pre.is_some());
+    if below_min || release >= GARAGA_MAX {
+        let (min_major, min_minor, min_patch) = GARAGA_MIN;
+        let (max_major, max_minor, max_patch) = GARAGA_MAX;
+        VersionCheck::Mismatch {
+            expected: format!(
+                ">={min_major}.{min_minor}.{min_patch}, <{max_major}.{max_minor}.{max_patch}"
+            ),
+        }
+    } else {
+        VersionCheck::Compatible
+    }
+}
+
 /// Generate calldata JSON for Starknet proof verification
 ///
 /// This function uses Garaga to generate properly formatted calldata
@@ -20,28 +127,32 @@ use crate::{
 ///
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
+/// * `system` - Proof system passed to `garaga --system`
 /// * `proof_path` - Path to the proof file
 /// * `vk_path` - Path to the verification key file
 /// * `public_inputs_path` - Path to the public inputs file
 /// * `output_path` - Optional output path for calldata (defaults to target/starknet/calldata.json)
+/// * `format` - Encoding to write, see [`format_calldata`]
 ///
 /// # Returns
 /// * `Result<PathBuf>` - Path to generated calldata file or error
 pub fn generate_calldata(
     cfg: &Config,
+    system: GaragaSystem,
     proof_path: &Path,
     vk_path: &Path,
     public_inputs_path: &Path,
     output_path: Option<&Path>,
+    format: CalldataFormat,
 ) -> Result<PathBuf> {
-    let proof_str = proof_path.to_string_lossy();
-    let vk_str = vk_path.to_string_lossy();
-    let public_inputs_str = public_inputs_path.to_string_lossy();
+    let proof_str = util::tool_path_string(proof_path);
+    let vk_str = util::tool_path_string(vk_path);
+    let public_inputs_str = util::tool_path_string(public_inputs_path);
 
     let garaga_args = vec![
         "calldata",
         "--system",
-        "ultra_starknet_zk_honk",
+        system.as_str(),
         "--proof",
         &proof_str,
         "--vk",
@@ -51,20 +162,97 @@ pub fn generate_calldata(
     ];
 
     // Use runner to capture stdout for calldata generation
-    let stdout = common::run_tool_capture(cfg, "garaga", &garaga_args)?;
+    let output = common::run_tool_capture(cfg, "garaga", &garaga_args)?;
+
+    // garaga can warn on stderr and still emit (possibly partial) calldata
+    let warnings = output.stderr.trim();
+    if !warnings.is_empty() {
+        warn!("garaga calldata reported warnings:\n{}", warnings);
+    }
 
     // Determine output path
     let calldata_path = output_path
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| PathBuf::from("./target/starknet/calldata.json"));
+        .unwrap_or_else(|| util::target_dir(Flavour::Starknet).join("calldata.json"));
+
+    if cfg.dry_run {
+        if !cfg.quiet {
+            println!("Would write calldata to: {}", calldata_path.display());
+        }
+        return Ok(calldata_path);
+    }
 
     // Save calldata to file
-    std::fs::write(&calldata_path, stdout.trim())
+    let calldata = format_calldata(output.stdout.trim(), format)?;
+    util::ensure_parent_dir(&calldata_path)?;
+    std::fs::write(&calldata_path, calldata)
         .wrap_err_with(|| format!("writing calldata to {}", calldata_path.display()))?;
 
     Ok(calldata_path)
 }
 
+/// Re-encode garaga's calldata output as `format`
+///
+/// `array` keeps garaga's output unchanged. `hex` writes every felt as a
+/// `0x`-prefixed hex number on one space-separated line, which can be passed
+/// straight to `sncast call --calldata`.
+pub fn format_calldata(raw: &str, format: CalldataFormat) -> Result<String> {
+    match format {
+        CalldataFormat::Array => Ok(raw.to_string()),
+        CalldataFormat::Hex => {
+            let felts = sncast::parse_calldata(raw)
+                .ok_or_else(|| color_eyre::eyre::eyre!("garaga calldata is not a felt list"))?;
+            let hex = felts
+                .iter()
+                .map(|felt| {
+                    felt_to_hex(felt).ok_or_else(|| {
+                        color_eyre::eyre::eyre!("garaga calldata has invalid felt '{felt}'")
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(hex.join(" "))
+        }
+    }
+}
+
+/// A decimal or `0x` felt as lowercase `0x` hex without leading zeros
+fn felt_to_hex(felt: &str) -> Option<String> {
+    if let Some(digits) = felt.strip_prefix("0x").or_else(|| felt.strip_prefix("0X")) {
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let digits = digits.trim_start_matches('0').to_ascii_lowercase();
+        return Some(format!(
+            "0x{}",
+            if digits.is_empty() { "0" } else { &digits }
+        ));
+    }
+
+    if felt.is_empty() {
+        return None;
+    }
+
+    // Felts exceed u128, so convert base 10 to base 2^32 limb by limb
+    let mut limbs: Vec<u32> = vec![0];
+    for digit in felt.chars() {
+        let mut carry = u64::from(digit.to_digit(10)?);
+        for limb in &mut limbs {
+            let value = u64::from(*limb) * 10 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+    }
+
+    let mut hex = format!("{:x}", limbs.pop().unwrap_or_default());
+    for limb in limbs.iter().rev() {
+        hex.push_str(&format!("{limb:08x}"));
+    }
+    Some(format!("0x{hex}"))
+}
+
 /// Generate calldata using default Starknet artifact paths
 ///
 /// Convenience function that uses the standard Starknet artifact locations
@@ -72,15 +260,27 @@ pub fn generate_calldata(
 ///
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
+/// * `system` - Proof system passed to `garaga --system`
 ///
 /// # Returns
 /// * `Result<PathBuf>` - Path to generated calldata file or error
-pub fn generate_calldata_from_starknet_artifacts(cfg: &Config) -> Result<PathBuf> {
+pub fn generate_calldata_from_starknet_artifacts(
+    cfg: &Config,
+    system: GaragaSystem,
+) -> Result<PathBuf> {
     let proof_path = util::get_proof_path(Flavour::Starknet);
     let vk_path = util::get_vk_path(Flavour::Starknet);
     let public_inputs_path = util::get_public_inputs_path(Flavour::Starknet);
 
-    generate_calldata(cfg, &proof_path, &vk_path, &public_inputs_path, None)
+    generate_calldata(
+        cfg,
+        system,
+        &proof_path,
+        &vk_path,
+        &public_inputs_path,
+        None,
+        CalldataFormat::Array,
+    )
 }
 
 /// Generate Cairo verifier contract using Garaga
@@ -90,38 +290,43 @@ pub fn generate_calldata_from_starknet_artifacts(cfg: &Config) -> Result<PathBuf
 ///
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
+/// * `system` - Proof system passed to `garaga --system`
 /// * `vk_path` - Path to the verification key file
 /// * `output_dir` - Optional output directory (defaults to ./contracts/cairo/)
+/// * `extra_args` - Passthrough arguments appended after bargo's own flags
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from Garaga execution
 pub fn generate_cairo_contract(
     cfg: &Config,
+    system: GaragaSystem,
     vk_path: &Path,
-    output_dir: Option<&str>,
+    output_dir: Option<&Path>,
+    extra_args: &[String],
 ) -> Result<()> {
-    let output = output_dir.unwrap_or("./contracts/cairo/");
+    let output = super::directories::cairo_contracts_dir(output_dir);
 
-    let vk_str = vk_path.to_string_lossy();
+    let vk_str = util::tool_path_string(vk_path);
 
-    let garaga_args = vec![
+    let mut garaga_args = vec![
         "gen",
         "--system",
-        "ultra_starknet_zk_honk",
+        system.as_str(),
         "--vk",
         &vk_str,
         "--project-name",
-        "cairo_verifier",
+        VERIFIER_PROJECT_NAME,
     ];
+    garaga_args.extend(extra_args.iter().map(String::as_str));
 
     common::run_tool(cfg, "garaga", &garaga_args)?;
 
-    // Move the generated project to the correct location (skip in dry-run mode)
-    if cfg.dry_run {
-        Ok(())
-    } else {
-        move_generated_project("cairo_verifier", output)
-    }
+    // Move the generated project to the correct location (only recorded in dry-run mode)
+    move_generated_project(
+        cfg.fs.as_ref(),
+        &util::project_path(VERIFIER_PROJECT_NAME).to_string_lossy(),
+        &output.to_string_lossy(),
+    )
 }
 
 /// Generate Cairo verifier contract using default Starknet VK path
@@ -131,12 +336,20 @@ pub fn generate_cairo_contract(
 ///
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
+/// * `system` - Proof system passed to `garaga --system`
+/// * `output_dir` - Optional output directory (defaults to ./contracts/cairo/)
+/// * `extra_args` - Passthrough arguments appended after bargo's own flags
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from Garaga execution
-pub fn generate_cairo_contract_from_starknet_vk(cfg: &Config) -> Result<()> {
+pub fn generate_cairo_contract_from_starknet_vk(
+    cfg: &Config,
+    system: GaragaSystem,
+    output_dir: Option<&Path>,
+    extra_args: &[String],
+) -> Result<()> {
     let vk_path = util::get_vk_path(Flavour::Starknet);
-    generate_cairo_contract(cfg, &vk_path, None)
+    generate_cairo_contract(cfg, system, &vk_path, output_dir, extra_args)
 }
 
 /// Validate that required Starknet artifacts exist for Garaga operations
@@ -155,3 +368,219 @@ pub fn validate_starknet_artifacts() -> Result<()> {
 
     util::validate_files_exist(&required_files)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{ArtifactSuffix, ColorChoice};
+    use crate::config::ToolPaths;
+    use crate::fs::{DryRunFs, RealFs};
+    use crate::runner::{
+        CapturedOutput, CmdSpec, DryRunRunner, RecordedCommand, Recording, ReplayRunner,
+    };
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generate_calldata_keeps_output_when_garaga_warns() {
+        let temp_dir = TempDir::new().unwrap();
+        let calldata_path = temp_dir.path().join("calldata.json");
+        let runner = DryRunRunner::new().with_fake_stderr("garaga", "warning: vk is deprecated");
+        // Commands go through the dry-run runner but the file is really written
+        let cfg = Config {
+            verbose: 0,
+            dry_run: false,
+            pkg: None,
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: Arc::new(runner),
+            fs: Arc::new(RealFs),
+        };
+
+        let written = generate_calldata(
+            &cfg,
+            GaragaSystem::default(),
+            Path::new("proof"),
+            Path::new("vk"),
+            Path::new("public_inputs"),
+            Some(&calldata_path),
+            CalldataFormat::Array,
+        )
+        .unwrap();
+
+        assert_eq!(written, calldata_path);
+        let calldata = std::fs::read_to_string(&calldata_path).unwrap();
+        assert!(calldata.contains("0x1234567890abcdef"));
+    }
+
+    fn dry_run_config(runner: Arc<DryRunRunner>) -> Config {
+        Config {
+            verbose: 0,
+            dry_run: true,
+            pkg: None,
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner,
+            fs: Arc::new(DryRunFs::new()),
+        }
+    }
+
+    fn system_arg(runner: &DryRunRunner) -> String {
+        let history = runner.history();
+        let args = &history.last().unwrap().0.args;
+        let index = args.iter().position(|arg| arg == "--system").unwrap();
+        args[index + 1].clone()
+    }
+
+    fn version(text: &str) -> ToolVersion {
+        parse_version(text).unwrap()
+    }
+
+    #[test]
+    fn test_parse_version_formats() {
+        assert_eq!(version("garaga 0.18.1").to_string(), "0.18.1");
+        assert_eq!(version("Garaga version: v0.18.0").to_string(), "0.18.0");
+        assert_eq!(version("0.18.2rc1").pre.as_deref(), Some("rc1"));
+        assert_eq!(version("0.18.2-beta.2").pre.as_deref(), Some("beta.2"));
+        assert_eq!(version("0.19.0.dev3").pre.as_deref(), Some("dev3"));
+        assert_eq!(version("0.18.1.post1").pre, None);
+        assert!(parse_version("garaga: command not found").is_none());
+        assert!(parse_version("").is_none());
+    }
+
+    #[test]
+    fn test_check_supported_range() {
+        assert_eq!(
+            check_supported(&version("0.18.0")),
+            VersionCheck::Compatible
+        );
+        assert_eq!(
+            check_supported(&version("0.18.1")),
+            VersionCheck::Compatible
+        );
+        assert_eq!(
+            check_supported(&version("0.18.2a1")),
+            VersionCheck::Compatible
+        );
+        assert_eq!(
+            check_supported(&version("0.18.1.post2")),
+            VersionCheck::Compatible
+        );
+
+        for unsupported in ["0.17.2", "0.18.0rc1", "0.19.0", "0.19.0.dev1", "1.0.0"] {
+            assert_eq!(
+                check_supported(&version(unsupported)),
+                VersionCheck::Mismatch {
+                    expected: ">=0.18.0, <0.19.0".to_string()
+                },
+                "{unsupported} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_version_rejects_old_garaga() {
+        let recording = Recording {
+            commands: vec![RecordedCommand {
+                spec: CmdSpec::new("garaga".to_string(), vec!["--version".to_string()]),
+                output: Some(CapturedOutput {
+                    stdout: "garaga 0.15.3\n".to_string(),
+                    stderr: String::new(),
+                    status: 0,
+                }),
+                error: None,
+            }],
+        };
+        let cfg = Config {
+            runner: Arc::new(ReplayRunner::new(recording)),
+            ..dry_run_config(Arc::new(DryRunRunner::new()))
+        };
+
+        let error = check_version(&cfg).unwrap_err();
+        let message = format!("{error:?}");
+        assert!(message.contains("garaga 0.15.3 is not supported"));
+        assert!(message.contains("pip install garaga==0.18.1"));
+
+        assert!(check_version(&dry_run_config(Arc::new(DryRunRunner::new()))).is_ok());
+    }
+
+    #[test]
+    fn test_generate_cairo_contract_passes_system() {
+        let runner = Arc::new(DryRunRunner::new());
+        let cfg = dry_run_config(runner.clone());
+
+        generate_cairo_contract(&cfg, GaragaSystem::default(), Path::new("vk"), None, &[]).unwrap();
+        assert_eq!(system_arg(&runner), "ultra_starknet_zk_honk");
+
+        generate_cairo_contract(
+            &cfg,
+            GaragaSystem::UltraStarknetHonk,
+            Path::new("vk"),
+            None,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(system_arg(&runner), "ultra_starknet_honk");
+        assert_eq!(runner.history().last().unwrap().0.args[0], "gen");
+    }
+
+    #[test]
+    fn test_generate_calldata_passes_system() {
+        let runner = Arc::new(DryRunRunner::new());
+        let cfg = dry_run_config(runner.clone());
+
+        generate_calldata(
+            &cfg,
+            GaragaSystem::UltraKeccakZkHonk,
+            Path::new("proof"),
+            Path::new("vk"),
+            Path::new("public_inputs"),
+            None,
+            CalldataFormat::Array,
+        )
+        .unwrap();
+        assert_eq!(system_arg(&runner), "ultra_keccak_zk_honk");
+        assert_eq!(runner.history()[0].0.args[0], "calldata");
+    }
+
+    #[test]
+    fn test_format_calldata_array_keeps_garaga_output() {
+        let raw = r#"[3, "0x1", "0x2A", "255"]"#;
+        assert_eq!(format_calldata(raw, CalldataFormat::Array).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_format_calldata_hex_concatenates_felts() {
+        let raw = r#"[3, "0x1", "0x002A", "255", "0", "340282366920938463463374607431768211456"]"#;
+        let hex = format_calldata(raw, CalldataFormat::Hex).unwrap();
+        assert_eq!(
+            hex,
+            "0x3 0x1 0x2a 0xff 0x0 0x100000000000000000000000000000000"
+        );
+
+        // The hex form is still readable by verify-onchain
+        assert_eq!(sncast::parse_calldata(&hex).unwrap().len(), 6);
+        assert!(format_calldata("[\"0xzz\"]", CalldataFormat::Hex).is_err());
+        assert!(format_calldata("[\"-1\"]", CalldataFormat::Hex).is_err());
+    }
+}
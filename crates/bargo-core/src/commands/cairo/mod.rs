@@ -9,10 +9,16 @@ pub mod directories;
 pub mod error;
 pub mod garaga;
 pub mod scarb;
+pub mod sncast;
+pub mod starkli;
 pub mod workflow;
 
 // Re-export main workflow functions for use by main.rs
-pub use workflow::{run_calldata, run_deploy, run_gen, run_prove, run_verify, run_verify_onchain};
+pub use workflow::{
+    run_calldata, run_calldata_with_config, run_deploy, run_gen, run_gen_with_config, run_prove,
+    run_prove_with_config, run_verify, run_verify_onchain, run_verify_onchain_with_config,
+    run_verify_with_config,
+};
 
 // Re-export error types for convenience
 pub use error::{CairoError, Result};
@@ -21,9 +27,10 @@ pub use error::{CairoError, Result};
 // (Currently none are needed externally, but modules are available for import)
 
 // Helper function to load environment variables (shared across workflow)
-pub fn load_env_vars() {
-    dotenv::dotenv().ok();
-    if std::path::Path::new(".secrets").exists() {
-        let _ = dotenv::from_filename(".secrets");
-    }
+//
+// `.env` and `.secrets` are read from the project root rather than the
+// process working directory, so `--package-dir` picks up the project's
+// Starknet accounts. `--env-file` replaces both with the named file.
+pub fn load_env_vars(project_root: &std::path::Path) {
+    crate::util::load_dotenv_files(project_root, &[".env", ".secrets"]);
 }
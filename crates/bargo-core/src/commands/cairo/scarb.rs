@@ -2,3 +2,83 @@
 //!
 //! This module provides focused functions for building Cairo projects using Scarb,
 //! Cairo's native build tool and package manager.
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use std::path::{Path, PathBuf};
+
+use crate::{commands::common, config::Config};
+
+/// Build a Cairo project with `scarb build`
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+/// * `project_dir` - Directory containing the project's Scarb.toml
+///
+/// # Returns
+/// * `Result<()>` - Success or error from Scarb
+pub fn build_project(cfg: &Config, project_dir: &Path) -> Result<()> {
+    common::run_tool_in_directory(cfg, "scarb", &["build"], Some(project_dir))
+}
+
+/// Locate the compiled Sierra contract class produced by `scarb build`
+///
+/// # Arguments
+/// * `project_dir` - Directory containing the project's Scarb.toml
+///
+/// # Returns
+/// * `Result<PathBuf>` - Path to the first `*.contract_class.json` under `target/dev`
+pub fn find_contract_class(project_dir: &Path) -> Result<PathBuf> {
+    let dev_dir = project_dir.join("target/dev");
+    let entries = std::fs::read_dir(&dev_dir)
+        .wrap_err_with(|| format!("reading Scarb output directory {}", dev_dir.display()))?;
+
+    let mut classes: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(".contract_class.json"))
+        })
+        .collect();
+    classes.sort();
+
+    classes.into_iter().next().ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "No compiled contract class (*.contract_class.json) found in {}",
+            dev_dir.display()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_contract_class() {
+        let temp_dir = TempDir::new().unwrap();
+        let dev_dir = temp_dir.path().join("target/dev");
+        std::fs::create_dir_all(&dev_dir).unwrap();
+        std::fs::write(dev_dir.join("verifier.starknet_artifacts.json"), "{}").unwrap();
+        std::fs::write(
+            dev_dir.join("verifier_UltraStarknetZKHonkVerifier.contract_class.json"),
+            "{}",
+        )
+        .unwrap();
+
+        let class = find_contract_class(temp_dir.path()).unwrap();
+        assert!(
+            class
+                .to_string_lossy()
+                .ends_with("verifier_UltraStarknetZKHonkVerifier.contract_class.json")
+        );
+    }
+
+    #[test]
+    fn test_find_contract_class_missing_build() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(find_contract_class(temp_dir.path()).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! sncast operations for calling deployed Cairo verifier contracts
+//!
+//! Garaga verifiers return `Option<Span<u256>>` from their entrypoint: the
+//! public inputs when the proof is valid and `None` otherwise. `sncast call`
+//! prints the Cairo-serialized response, so a valid proof shows up as a
+//! leading `0x0` (the `Some` variant) and an invalid one as `0x1` (`None`).
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use std::path::Path;
+
+use crate::{commands::common, config::Config, util::BargoError};
+
+/// Call `function` on the verifier at `address` with `calldata` using sncast
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+/// * `address` - Address of the deployed verifier contract
+/// * `function` - Verifier entrypoint, e.g. `verify_ultra_starknet_zk_honk_proof`
+/// * `calldata` - Serialized proof calldata felts
+/// * `rpc_url` - Starknet RPC endpoint
+///
+/// # Returns
+/// * `Result<bool>` - Whether the verifier accepted the proof, or error if the
+///   call failed or its response could not be parsed
+pub fn call_verifier(
+    cfg: &Config,
+    address: &str,
+    function: &str,
+    calldata: &[String],
+    rpc_url: &str,
+) -> Result<bool> {
+    let mut args = vec![
+        "call",
+        "--url",
+        rpc_url,
+        "--contract-address",
+        address,
+        "--function",
+        function,
+        "--calldata",
+    ];
+    args.extend(calldata.iter().map(String::as_str));
+
+    let stdout = common::run_tool_capture_with_retry(cfg, "sncast", &args, &[])?.stdout;
+
+    parse_verify_result(&stdout).ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "Could not parse verification result from sncast output: {}",
+            stdout.trim()
+        )
+    })
+}
+
+/// Parse the verifier result from `sncast call` output
+///
+/// Accepts both the `response: [0x0, ...]` and `Response: [0x0, ...]` forms
+/// printed by different sncast versions.
+pub fn parse_verify_result(stdout: &str) -> Option<bool> {
+    let response = stdout.lines().find_map(|line| {
+        let (label, value) = line.split_once(':')?;
+        label
+            .trim()
+            .eq_ignore_ascii_case("response")
+            .then_some(value)
+    })?;
+    let felts = response.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let first = felts.split(',').next()?.trim();
+    let digits = first.strip_prefix("0x")?.trim_start_matches('0');
+    match digits {
+        "" => Some(true),
+        "1" => Some(false),
+        _ => None,
+    }
+}
+
+/// Read the calldata felts written by `bargo cairo calldata`
+///
+/// garaga prints calldata either as whitespace-separated felts or as JSON
+/// (a bare array or an object with a `calldata` array); all three are accepted.
+pub fn read_calldata(path: &Path) -> Result<Vec<String>> {
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("reading calldata from {}", path.display()))?;
+    parse_calldata(&contents).ok_or_else(|| {
+        BargoError::ParseFailure {
+            path: path.to_path_buf(),
+            message: "expected calldata felts".to_string(),
+        }
+        .into()
+    })
+}
+
+/// Parse calldata felts from garaga output
+pub(crate) fn parse_calldata(contents: &str) -> Option<Vec<String>> {
+    let contents = contents.trim();
+    if !contents.starts_with(['[', '{']) {
+        let felts: Vec<String> = contents.split_whitespace().map(str::to_string).collect();
+        return (!felts.is_empty()).then_some(felts);
+    }
+
+    let json: serde_json::Value = serde_json::from_str(contents).ok()?;
+    let felts = match &json {
+        serde_json::Value::Array(felts) => felts,
+        serde_json::Value::Object(object) => object.get("calldata")?.as_array()?,
+        _ => return None,
+    };
+    felts
+        .iter()
+        .map(|felt| match felt {
+            serde_json::Value::String(felt) => Some(felt.clone()),
+            serde_json::Value::Number(felt) => Some(felt.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verify_result_valid_and_invalid() {
+        let valid = "command: call\nresponse: [0x0, 0x1, 0x2a, 0x0]\n";
+        assert_eq!(parse_verify_result(valid), Some(true));
+
+        let invalid = "Success: Call completed\n\nResponse:     [0x1]\n";
+        assert_eq!(parse_verify_result(invalid), Some(false));
+    }
+
+    #[test]
+    fn test_parse_verify_result_unexpected() {
+        assert_eq!(parse_verify_result("error: contract not found"), None);
+        assert_eq!(parse_verify_result("response: [0x2]"), None);
+    }
+
+    #[test]
+    fn test_parse_calldata_formats() {
+        let expected = Some(vec!["3".to_string(), "0x1".to_string(), "0x2".to_string()]);
+        assert_eq!(parse_calldata("3 0x1 0x2\n"), expected);
+        assert_eq!(parse_calldata(r#"["3", "0x1", "0x2"]"#), expected);
+        assert_eq!(
+            parse_calldata(r#"{"calldata": [3, "0x1", "0x2"]}"#),
+            expected
+        );
+        assert_eq!(parse_calldata(""), None);
+    }
+}
@@ -0,0 +1,489 @@
+//! Starkli operations for Starknet contract management
+//!
+//! This module provides focused functions for interacting with starkli
+//! to declare and deploy Cairo verifier contracts on Starknet networks.
+
+use color_eyre::Result;
+
+use crate::{
+    commands::{common, keystore::Keystore},
+    config::{Config, Network},
+    util::{self, create_smart_error},
+};
+
+/// Credentials starkli signs transactions with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StarknetSigner {
+    /// Raw private key, passed through `STARKNET_PRIVATE_KEY`
+    PrivateKey(String),
+    /// Encrypted keystore, passed as `--keystore` with its password in
+    /// `STARKNET_KEYSTORE_PASSWORD`
+    Keystore(Keystore),
+}
+
+/// Starknet account settings for a network
+///
+/// Values are read from `.secrets` / the environment using the network name as
+/// prefix (e.g. `SEPOLIA_RPC_URL`; localhost and custom networks bring their
+/// own RPC URL), plus `STARKNET_ACCOUNT` pointing at the
+/// starkli account descriptor file. When a keystore is given it signs instead
+/// of `<NETWORK>_ACCOUNT_PRIVATE_KEY`, and the account descriptor defaults to
+/// the `account.json` next to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StarknetAccount {
+    pub rpc_url: String,
+    pub account: String,
+    pub signer: StarknetSigner,
+}
+
+impl StarknetAccount {
+    /// Load account settings for `network` from environment variables
+    pub fn from_env(network: &str, keystore: Option<Keystore>) -> Result<Self> {
+        Self::from_lookup(network, keystore, |key| std::env::var(key).ok())
+    }
+
+    /// Load account settings for `network` using a custom variable lookup
+    pub fn from_lookup<F>(network: &str, keystore: Option<Keystore>, lookup: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let network = Network::parse(network)?;
+        let prefix = network_env_prefix(&network);
+        let rpc_key = format!("{prefix}_RPC_URL");
+        let private_key_key = format!("{prefix}_ACCOUNT_PRIVATE_KEY");
+
+        let rpc_url = match network.starknet_rpc_url() {
+            Some(rpc_url) => rpc_url,
+            None => lookup(&rpc_key).ok_or_else(|| missing_var_error(&rpc_key))?,
+        };
+        let signer = match keystore {
+            Some(keystore) => StarknetSigner::Keystore(keystore),
+            None => StarknetSigner::PrivateKey(
+                lookup(&private_key_key).ok_or_else(|| missing_var_error(&private_key_key))?,
+            ),
+        };
+        let account = match (lookup("STARKNET_ACCOUNT"), &signer) {
+            (Some(account), _) => account,
+            (None, StarknetSigner::Keystore(keystore)) => sibling_account_file(keystore),
+            (None, StarknetSigner::PrivateKey(_)) => {
+                return Err(missing_var_error("STARKNET_ACCOUNT"));
+            }
+        };
+
+        Ok(Self {
+            rpc_url,
+            account,
+            signer,
+        })
+    }
+
+    /// Placeholder settings used in dry-run mode when variables are not set
+    ///
+    /// Secrets are never echoed; the private key is always shown as the name
+    /// of the variable it would be read from.
+    pub fn placeholder(network: &str, keystore: Option<Keystore>) -> Result<Self> {
+        let network = Network::parse(network)?;
+        let prefix = network_env_prefix(&network);
+        let lookup = |key: &str| std::env::var(key).ok();
+
+        let account = match (lookup("STARKNET_ACCOUNT"), &keystore) {
+            (Some(account), _) => account,
+            (None, Some(keystore)) => sibling_account_file(keystore),
+            (None, None) => "$STARKNET_ACCOUNT".to_string(),
+        };
+        let signer = match keystore {
+            Some(keystore) => StarknetSigner::Keystore(keystore),
+            None => StarknetSigner::PrivateKey(format!("${prefix}_ACCOUNT_PRIVATE_KEY")),
+        };
+
+        Ok(Self {
+            rpc_url: network
+                .starknet_rpc_url()
+                .or_else(|| lookup(&format!("{prefix}_RPC_URL")))
+                .unwrap_or_else(|| format!("${prefix}_RPC_URL")),
+            account,
+            signer,
+        })
+    }
+
+    /// Extra starkli arguments selecting the signer
+    fn signer_args(&self) -> Vec<String> {
+        match &self.signer {
+            StarknetSigner::PrivateKey(_) => Vec::new(),
+            StarknetSigner::Keystore(keystore) => vec![
+                "--keystore".to_string(),
+                util::tool_path_string(&keystore.path),
+            ],
+        }
+    }
+
+    /// Environment variables carrying the signer's secret
+    fn signer_env(&self) -> Vec<(&str, &str)> {
+        match &self.signer {
+            StarknetSigner::PrivateKey(private_key) => {
+                vec![("STARKNET_PRIVATE_KEY", private_key.as_str())]
+            }
+            StarknetSigner::Keystore(keystore) => keystore
+                .password()
+                .map(|password| ("STARKNET_KEYSTORE_PASSWORD", password))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// The `account.json` descriptor starkli keeps next to a wallet's keystore
+fn sibling_account_file(keystore: &Keystore) -> String {
+    util::tool_path_string(&keystore.path.with_file_name("account.json"))
+}
+
+/// RPC URL for `network`, read from `<NETWORK>_RPC_URL` unless the network
+/// brings its own
+///
+/// In dry-run mode a missing variable is shown by name instead of failing.
+pub fn rpc_url_from_env(network: &Network, dry_run: bool) -> Result<String> {
+    if let Some(rpc_url) = network.starknet_rpc_url() {
+        return Ok(rpc_url);
+    }
+    let key = format!("{}_RPC_URL", network_env_prefix(network));
+    match std::env::var(&key) {
+        Ok(url) => Ok(url),
+        Err(_) if dry_run => Ok(format!("${key}")),
+        Err(_) => Err(missing_var_error(&key)),
+    }
+}
+
+/// Environment variable prefix for a network (`SEPOLIA`, `CUSTOM`, ...)
+fn network_env_prefix(network: &Network) -> String {
+    network.name().to_uppercase()
+}
+
+/// Build the error returned when a required account variable is missing
+fn missing_var_error(key: &str) -> color_eyre::eyre::Error {
+    create_smart_error(
+        &format!("{key} environment variable not found"),
+        &[
+            &format!("Add to your .secrets file: {key}=..."),
+            "STARKNET_ACCOUNT must point to a starkli account file (see 'starkli account fetch')",
+            "⚠️  Keep your private key secure and never commit it to version control",
+        ],
+    )
+}
+
+/// Declare a compiled contract class using starkli
+///
+/// The private key (or keystore password) is passed through the environment
+/// rather than on the command line so it does not show up in verbose logs or
+/// process listings.
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+/// * `contract_class_path` - Path to the compiled `*.contract_class.json`
+/// * `account` - Starknet account settings for the target network
+///
+/// # Returns
+/// * `Result<String>` - Declared class hash or error
+pub fn declare_contract(
+    cfg: &Config,
+    contract_class_path: &str,
+    account: &StarknetAccount,
+) -> Result<String> {
+    let signer_args = account.signer_args();
+    let mut args = vec![
+        "declare",
+        contract_class_path,
+        "--rpc",
+        &account.rpc_url,
+        "--account",
+        &account.account,
+    ];
+    args.extend(signer_args.iter().map(String::as_str));
+    args.push("--watch");
+    let env = account.signer_env();
+
+    let stdout = common::run_tool_capture_with_retry(cfg, "starkli", &args, &env)?.stdout;
+
+    parse_class_hash(&stdout).ok_or_else(|| {
+        color_eyre::eyre::eyre!("Could not parse class hash from starkli output:\n{stdout}")
+    })
+}
+
+/// Deploy an instance of a declared contract class using starkli
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+/// * `class_hash` - Class hash returned by the declare step
+/// * `constructor_calldata` - Constructor calldata felts, if any
+/// * `account` - Starknet account settings for the target network
+///
+/// # Returns
+/// * `Result<String>` - Deployed contract address or error
+pub fn deploy_contract(
+    cfg: &Config,
+    class_hash: &str,
+    constructor_calldata: &[String],
+    account: &StarknetAccount,
+) -> Result<String> {
+    let signer_args = account.signer_args();
+    let mut args = vec!["deploy", class_hash];
+    args.extend(constructor_calldata.iter().map(String::as_str));
+    args.extend(["--rpc", &account.rpc_url, "--account", &account.account]);
+    args.extend(signer_args.iter().map(String::as_str));
+    args.push("--watch");
+    let env = account.signer_env();
+
+    let stdout = common::run_tool_capture_with_retry(cfg, "starkli", &args, &env)?.stdout;
+
+    parse_contract_address(&stdout).ok_or_else(|| {
+        color_eyre::eyre::eyre!("Could not parse contract address from starkli output:\n{stdout}")
+    })
+}
+
+/// Reject constructor calldata that is not a list of Starknet felts
+///
+/// starkli would otherwise only fail after the contract has been declared.
+pub fn validate_calldata(calldata: &[String]) -> Result<()> {
+    match calldata.iter().find(|felt| parse_felt(felt).is_none()) {
+        Some(felt) => Err(create_smart_error(
+            &format!("Invalid constructor calldata '{felt}': not a Starknet felt"),
+            &[
+                "Pass decimal or 0x-prefixed hex values below the Starknet prime",
+                "Separate values with commas or spaces: --constructor-args 1,0x2a",
+            ],
+        )),
+        None => Ok(()),
+    }
+}
+
+/// The Starknet field prime, 2^251 + 17 * 2^192 + 1, as big-endian bytes
+const FELT_PRIME: [u8; 32] = {
+    let mut prime = [0; 32];
+    prime[0] = 0x08;
+    prime[7] = 0x11;
+    prime[31] = 0x01;
+    prime
+};
+
+/// Big-endian bytes of a decimal or `0x`-hex felt, if it is one
+fn parse_felt(value: &str) -> Option<[u8; 32]> {
+    let mut bytes = [0u8; 32];
+    if let Some(digits) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        let significant = digits.trim_start_matches('0');
+        if digits.is_empty()
+            || significant.len() > 64
+            || !digits.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return None;
+        }
+        hex::decode_to_slice(format!("{significant:0>64}"), &mut bytes).ok()?;
+    } else {
+        if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        for digit in value.bytes().map(|b| u32::from(b - b'0')) {
+            let mut carry = digit;
+            for byte in bytes.iter_mut().rev() {
+                let next = u32::from(*byte) * 10 + carry;
+                *byte = next as u8;
+                carry = next >> 8;
+            }
+            if carry != 0 {
+                return None;
+            }
+        }
+    }
+    (bytes < FELT_PRIME).then_some(bytes)
+}
+
+/// Parse the class hash from `starkli declare` output
+///
+/// starkli prints the class hash as the last hex value on its own line, both
+/// for fresh declarations and for classes that are already declared.
+pub fn parse_class_hash(stdout: &str) -> Option<String> {
+    last_felt_line(stdout)
+}
+
+/// Parse the contract address from `starkli deploy` output
+///
+/// starkli prints the deployed address as the last hex value on its own line.
+pub fn parse_contract_address(stdout: &str) -> Option<String> {
+    last_felt_line(stdout)
+}
+
+/// Find the last line of output that consists of a single hex felt
+fn last_felt_line(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| {
+            line.len() > 2
+                && line.starts_with("0x")
+                && line[2..].chars().all(|c| c.is_ascii_hexdigit())
+        })
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_felts_must_be_below_the_starknet_prime() {
+        let prime = "3618502788666131213697322783095070105623107215331596699973092056135872020481";
+        let below = "3618502788666131213697322783095070105623107215331596699973092056135872020480";
+        assert!(parse_felt(below).is_some());
+        assert!(parse_felt(prime).is_none());
+        assert_eq!(
+            parse_felt(below),
+            parse_felt("0x800000000000011000000000000000000000000000000000000000000000000")
+        );
+
+        for valid in [
+            "0",
+            "42",
+            "0x2a",
+            "0X2A",
+            "0x0000000000000000000000000000000000000000000000000000000000000002a",
+        ] {
+            assert!(parse_felt(valid).is_some(), "{valid}");
+        }
+        for invalid in ["", "0x", "-1", "1.5", "0xg", "str:hello", &"1".repeat(80)] {
+            assert!(parse_felt(invalid).is_none(), "{invalid}");
+        }
+
+        assert!(validate_calldata(&["1".to_string(), "0x2".to_string()]).is_ok());
+        let error = validate_calldata(&["1".to_string(), "two".to_string()])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Invalid constructor calldata 'two'"),
+            "{error}"
+        );
+    }
+
+    fn lookup_from(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn test_parse_class_hash_from_declare_output() {
+        let stdout = "Declaring Cairo 1 class: 0x1234\nContract declaration transaction: 0xabc\nClass hash declared:\n0x0123456789abcdef\n";
+        assert_eq!(
+            parse_class_hash(stdout).as_deref(),
+            Some("0x0123456789abcdef")
+        );
+    }
+
+    #[test]
+    fn test_parse_class_hash_already_declared() {
+        let stdout = "Not declaring class as it's already declared. Class hash:\n0x0fedcba\n";
+        assert_eq!(parse_class_hash(stdout).as_deref(), Some("0x0fedcba"));
+    }
+
+    #[test]
+    fn test_parse_class_hash_missing() {
+        assert!(parse_class_hash("Error: account not deployed").is_none());
+    }
+
+    #[test]
+    fn test_parse_contract_address_from_deploy_output() {
+        let stdout = "Deploying class 0x0123 with salt 0x0456...\nThe contract will be deployed at address 0x0789\nContract deployed:\n0x0789abc\n";
+        assert_eq!(parse_contract_address(stdout).as_deref(), Some("0x0789abc"));
+    }
+
+    #[test]
+    fn test_account_from_lookup() {
+        let account = StarknetAccount::from_lookup(
+            "mainnet",
+            None,
+            lookup_from(&[
+                ("MAINNET_RPC_URL", "https://rpc"),
+                ("MAINNET_ACCOUNT_PRIVATE_KEY", "0x3"),
+                ("STARKNET_ACCOUNT", "account.json"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(account.rpc_url, "https://rpc");
+        assert_eq!(
+            account.signer,
+            StarknetSigner::PrivateKey("0x3".to_string())
+        );
+        assert_eq!(account.account, "account.json");
+    }
+
+    #[test]
+    fn test_account_from_lookup_with_keystore() {
+        let keystore = Keystore::new(
+            "/home/dev/.starkli-wallets/deployer/keystore.json".into(),
+            "hunter2".to_string(),
+        );
+        let account = StarknetAccount::from_lookup(
+            "sepolia",
+            Some(keystore.clone()),
+            lookup_from(&[("SEPOLIA_RPC_URL", "https://rpc")]),
+        )
+        .unwrap();
+
+        // No private key is needed and the descriptor defaults to the wallet's
+        assert_eq!(account.signer, StarknetSigner::Keystore(keystore));
+        assert_eq!(
+            account.account,
+            "/home/dev/.starkli-wallets/deployer/account.json"
+        );
+        assert_eq!(
+            account.signer_args(),
+            [
+                "--keystore",
+                "/home/dev/.starkli-wallets/deployer/keystore.json"
+            ]
+        );
+        assert_eq!(
+            account.signer_env(),
+            [("STARKNET_KEYSTORE_PASSWORD", "hunter2")]
+        );
+    }
+
+    #[test]
+    fn test_account_missing_private_key_suggests_secrets() {
+        let error = StarknetAccount::from_lookup(
+            "sepolia",
+            None,
+            lookup_from(&[("SEPOLIA_RPC_URL", "https://rpc")]),
+        )
+        .unwrap_err();
+
+        let message = format!("{error:?}");
+        assert!(message.contains("SEPOLIA_ACCOUNT_PRIVATE_KEY environment variable not found"));
+        assert!(message.contains(".secrets"));
+    }
+
+    #[test]
+    fn test_unsupported_network() {
+        let error = StarknetAccount::from_lookup("goerli", None, lookup_from(&[])).unwrap_err();
+        assert!(error.to_string().contains("Unsupported network: goerli"));
+    }
+
+    #[test]
+    fn test_custom_network_brings_rpc_url() {
+        let account = StarknetAccount::from_lookup(
+            "custom:http://127.0.0.1:5050",
+            None,
+            lookup_from(&[
+                ("CUSTOM_ACCOUNT_PRIVATE_KEY", "0x3"),
+                ("STARKNET_ACCOUNT", "account.json"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(account.rpc_url, "http://127.0.0.1:5050");
+    }
+}
@@ -5,17 +5,20 @@
 
 use color_eyre::Result;
 use color_eyre::eyre::WrapErr;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::{
-    config::Config,
+    api::{ProveResult, VerifyResult},
+    commands::{common, keystore::Keystore},
+    config::{CalldataConfig, Config, GaragaConfig, Network, ProveConfig, VerifyConfig},
     util::{
-        self, Flavour, OperationSummary, Timer, create_smart_error, enhance_error_with_suggestions,
-        format_operation_result, success,
+        self, Deployment, Flavour, OperationSummary, Spinner, Timer, create_smart_error,
+        enhance_error_with_suggestions, format_operation_duration, format_operation_result,
+        success,
     },
 };
 
-use super::{bb_operations, directories, garaga, load_env_vars};
+use super::{bb_operations, directories, garaga, load_env_vars, scarb, sncast, starkli};
 
 /// Run the Cairo gen workflow
 ///
@@ -30,85 +33,170 @@ use super::{bb_operations, directories, garaga, load_env_vars};
 /// # Returns
 /// * `Result<()>` - Success or error from workflow
 pub fn run_gen(cfg: &Config) -> Result<()> {
-    let pkg_name = util::get_package_name(cfg.pkg.as_ref())?;
-    load_env_vars();
+    run_gen_with_config(cfg, &GaragaConfig::default(), &ProveConfig::default())
+}
 
-    if cfg.verbose {
+/// Run the Cairo gen workflow with garaga and prove settings
+///
+/// With `prove_cfg.no_prove` the proof and vk are not regenerated and garaga
+/// runs against the existing `target/starknet/vk`.
+/// The same happens when the existing proof is newer than the build
+/// artifacts, unless `prove_cfg.force` is set.
+///
+/// The verifier project lands in `garaga_cfg.output_dir`, or `contracts/cairo`.
+/// A destination holding files bargo did not generate is only replaced with
+/// `garaga_cfg.overwrite`.
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `garaga_cfg` - Garaga settings such as the proof system
+/// * `prove_cfg` - Settings for the proof and VK, such as an oracle hash override
+///
+/// # Returns
+/// * `Result<()>` - Success or error from workflow
+pub fn run_gen_with_config(
+    cfg: &Config,
+    garaga_cfg: &GaragaConfig,
+    prove_cfg: &ProveConfig,
+) -> Result<()> {
+    let pkg_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)?;
+    load_env_vars(&cfg.project_root()?);
+    let prove_cfg = &common::with_recorded_recursion(cfg, Flavour::Starknet, prove_cfg)?;
+
+    if cfg.is_verbose() {
         info!("Starting Cairo verifier generation workflow");
     }
 
-    // Validate required files exist
-    let required_files = vec![
-        util::get_bytecode_path(&pkg_name, Flavour::Bb),
-        util::get_witness_path(&pkg_name, Flavour::Bb),
-    ];
+    // Validate required files exist; --no-prove only needs the existing vk
+    let required_files = if prove_cfg.no_prove {
+        vec![util::get_vk_path(Flavour::Starknet)]
+    } else {
+        vec![
+            util::get_bytecode_path(&pkg_name, Flavour::Bb),
+            util::get_witness_path(&pkg_name, Flavour::Bb),
+        ]
+    };
+
+    // Checked up front, so a bad destination fails before anything is proved
+    let output_dir = garaga_cfg.output_dir.as_deref();
+    let cairo_dir = directories::cairo_contracts_dir(output_dir);
+    util::ensure_replaceable_dir(
+        &cairo_dir,
+        directories::is_generated_cairo_project,
+        garaga_cfg.overwrite,
+    )?;
 
     if !cfg.dry_run {
         util::validate_files_exist(&required_files).map_err(enhance_error_with_suggestions)?;
-        directories::validate_cairo_directory_structure()
+        directories::validate_cairo_directory_structure(output_dir)
             .map_err(enhance_error_with_suggestions)?;
+        garaga::check_version(cfg)?;
     }
 
-    let mut summary = OperationSummary::new();
-
-    // Step 1: Generate Starknet proof
-    if cfg.verbose {
-        info!("Generating Starknet proof");
-    }
-    let proof_timer = Timer::start();
-    bb_operations::generate_starknet_proof(cfg, &pkg_name)
-        .map_err(enhance_error_with_suggestions)?;
-
-    if !cfg.quiet {
-        let proof_path = util::get_proof_path(Flavour::Starknet);
-        println!(
-            "{}",
-            success(&format_operation_result(
-                "Starknet proof generated",
-                &proof_path,
-                &proof_timer
-            ))
-        );
-        summary.add_operation(&format!(
-            "Starknet proof ({})",
-            util::format_file_size(&proof_path)
-        ));
-    }
+    let reuse_proof = prove_cfg.no_prove
+        || common::proof_is_current(cfg, &pkg_name, Flavour::Starknet, prove_cfg)?;
+    let mut summary = OperationSummary::new().verbose(cfg.is_verbose());
 
-    // Step 2: Generate Starknet VK
-    if cfg.verbose {
-        info!("Generating Starknet verification key");
-    }
-    let vk_timer = Timer::start();
-    bb_operations::generate_starknet_vk(cfg, &pkg_name).map_err(enhance_error_with_suggestions)?;
+    if reuse_proof {
+        if !cfg.quiet {
+            summary.add_operation(&format!(
+                "Reused verification key ({})",
+                util::format_file_size(&util::get_vk_path(Flavour::Starknet))
+            ));
+        }
+    } else {
+        // Step 1: Generate Starknet proof
+        if cfg.is_verbose() {
+            info!("Generating Starknet proof");
+        }
+        let proof_timer = Timer::start();
+        let spinner = Spinner::start("Generating Starknet proof", cfg.show_spinner());
+        bb_operations::generate_starknet_proof(cfg, &pkg_name, prove_cfg)
+            .map_err(enhance_error_with_suggestions)?;
+        spinner.finish();
+        summary.add_timed_operation("Proof generation", proof_timer.duration());
+
+        if !cfg.quiet {
+            let proof_path = util::get_proof_path(Flavour::Starknet);
+            println!(
+                "{}",
+                success(&format_operation_result(
+                    "Starknet proof generated",
+                    &proof_path,
+                    &proof_timer
+                ))
+            );
+            summary.add_operation(&format!(
+                "Starknet proof ({})",
+                util::format_file_size(&proof_path)
+            ));
+        }
 
-    if !cfg.quiet {
-        let vk_path = util::get_vk_path(Flavour::Starknet);
-        println!(
-            "{}",
-            success(&format_operation_result(
-                "Starknet VK generated",
-                &vk_path,
-                &vk_timer
-            ))
-        );
-        summary.add_operation(&format!(
-            "Verification key ({})",
-            util::format_file_size(&vk_path)
-        ));
+        // Step 2: Generate Starknet VK
+        if cfg.is_verbose() {
+            info!("Generating Starknet verification key");
+        }
+        let vk_timer = Timer::start();
+        let spinner = Spinner::start("Generating Starknet verification key", cfg.show_spinner());
+        bb_operations::generate_starknet_vk(cfg, &pkg_name, prove_cfg)
+            .map_err(enhance_error_with_suggestions)?;
+        spinner.finish();
+        summary.add_timed_operation("VK generation", vk_timer.duration());
+
+        if !cfg.quiet {
+            let vk_path = util::get_vk_path(Flavour::Starknet);
+            println!(
+                "{}",
+                success(&format_operation_result(
+                    "Starknet VK generated",
+                    &vk_path,
+                    &vk_timer
+                ))
+            );
+            summary.add_operation(&format!(
+                "Verification key ({})",
+                util::format_file_size(&vk_path)
+            ));
+        }
     }
 
     // Step 3: Generate Cairo verifier contract
-    if cfg.verbose {
+    if cfg.is_verbose() {
         info!("Generating Cairo verifier contract");
     }
     let contract_timer = Timer::start();
 
-    garaga::generate_cairo_contract_from_starknet_vk(cfg)
-        .map_err(enhance_error_with_suggestions)?;
+    let spinner = Spinner::start("Generating Cairo verifier contract", cfg.show_spinner());
+    garaga::generate_cairo_contract_from_starknet_vk(
+        cfg,
+        garaga_cfg.system,
+        output_dir,
+        &garaga_cfg.extra_args,
+    )
+    .map_err(enhance_error_with_suggestions)?;
+    spinner.finish();
+    summary.add_timed_operation("Verifier contract", contract_timer.duration());
+    // Reused artifacts keep the provenance recorded when they were proved
+    if !reuse_proof {
+        common::record_manifest(
+            cfg,
+            &util::manifest_path(Flavour::Starknet),
+            "cairo gen",
+            &[
+                util::get_proof_path(Flavour::Starknet),
+                util::get_vk_path(Flavour::Starknet),
+                util::get_public_inputs_path(Flavour::Starknet),
+            ],
+            &["bb", "garaga"],
+        )?;
+        common::record_oracle_hash(
+            cfg,
+            &util::manifest_path(Flavour::Starknet),
+            prove_cfg.oracle_hash,
+        )?;
+    }
 
     if !cfg.quiet {
-        let cairo_dir = directories::get_cairo_contracts_dir();
         println!(
             "{}",
             success(&format_operation_result(
@@ -136,40 +224,105 @@ pub fn run_gen(cfg: &Config) -> Result<()> {
 /// # Returns
 /// * `Result<()>` - Success or error from workflow
 pub fn run_prove(cfg: &Config) -> Result<()> {
-    let pkg_name =
-        util::get_package_name(cfg.pkg.as_ref()).map_err(enhance_error_with_suggestions)?;
+    run_prove_with_config(cfg, &ProveConfig::default())
+}
+
+/// Run the Cairo prove workflow with prove-specific settings
+///
+/// Proving is skipped when the proof in `target/starknet/` is newer than the
+/// bytecode and witness, unless `prove_cfg.force` is set.
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `prove_cfg` - Prove settings such as the proving scheme
+///
+/// # Returns
+/// * `Result<()>` - Success or error from workflow
+pub fn run_prove_with_config(cfg: &Config, prove_cfg: &ProveConfig) -> Result<()> {
+    let result = generate_proof(cfg, prove_cfg)?;
+
+    if !cfg.quiet {
+        if result.reused {
+            println!(
+                "{}",
+                success(
+                    "Starknet proof is newer than the build artifacts, skipping (use --force to regenerate)"
+                )
+            );
+        } else {
+            println!(
+                "{}",
+                success(&format_operation_duration(
+                    "Starknet proof and VK generated",
+                    &result.proof,
+                    result.elapsed
+                ))
+            );
+            println!("  • Proof: {}", result.proof.display());
+            println!("  • VK: {}", result.vk.display());
+            if let Some(summary) = common::compression_summary(&result.compressed) {
+                println!("  • {summary}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate the Starknet proof and vk without printing a summary
+///
+/// The existing proof is kept when [`common::proof_is_current`] allows it.
+pub fn generate_proof(cfg: &Config, prove_cfg: &ProveConfig) -> Result<ProveResult> {
+    let pkg_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)
+        .map_err(enhance_error_with_suggestions)?;
 
     // Validate that required build files exist
-    let required_files = vec![
-        util::get_bytecode_path(&pkg_name, Flavour::Bb),
-        util::get_witness_path(&pkg_name, Flavour::Bb),
-    ];
+    let bytecode = util::get_bytecode_path(&pkg_name, Flavour::Bb);
+    let witness = prove_cfg.witness_path(&pkg_name);
+    let required_files = vec![bytecode.clone(), witness.clone()];
 
     if !cfg.dry_run {
         util::validate_files_exist(&required_files).map_err(enhance_error_with_suggestions)?;
+        util::validate_artifact_files(&bytecode, &witness)?;
         directories::ensure_starknet_target_dir().map_err(enhance_error_with_suggestions)?;
     }
 
     let timer = Timer::start();
-    bb_operations::generate_starknet_proof_and_vk(cfg, &pkg_name)
-        .map_err(enhance_error_with_suggestions)?;
-
-    if !cfg.quiet {
-        let proof_path = util::get_proof_path(Flavour::Starknet);
-        let vk_path = util::get_vk_path(Flavour::Starknet);
-        println!(
-            "{}",
-            success(&format_operation_result(
-                "Starknet proof and VK generated",
-                &proof_path,
-                &timer
-            ))
+    let reused = common::proof_is_current(cfg, &pkg_name, Flavour::Starknet, prove_cfg)?;
+    let mut compressed = Vec::new();
+    if !reused {
+        let spinner = Spinner::start(
+            "Generating Starknet proof and verification key",
+            cfg.show_spinner(),
         );
-        println!("  • Proof: {}", proof_path.display());
-        println!("  • VK: {}", vk_path.display());
+        bb_operations::generate_starknet_proof_and_vk(cfg, &pkg_name, prove_cfg)
+            .map_err(enhance_error_with_suggestions)?;
+        spinner.finish();
+        compressed = common::compress_proof_artifacts(cfg, Flavour::Starknet, prove_cfg.compress)?;
+        let manifest = util::manifest_path(Flavour::Starknet);
+        common::record_manifest(
+            cfg,
+            &manifest,
+            "cairo prove",
+            &common::proof_artifacts(Flavour::Starknet),
+            &["bb"],
+        )?;
+        common::record_compression(cfg, &manifest, &compressed)?;
+        common::record_oracle_hash(cfg, &manifest, prove_cfg.oracle_hash)?;
+        common::record_bb_profile(cfg, &manifest, prove_cfg.bb_profile)?;
+        common::record_recursive_friendly(cfg, &manifest, prove_cfg.recursive_friendly)?;
+        common::record_proof_meta(cfg, Flavour::Starknet, &pkg_name, &["nargo", "bb"])?;
     }
 
-    Ok(())
+    let [proof, vk, public_inputs] = common::proof_artifacts(Flavour::Starknet);
+    Ok(ProveResult {
+        proof,
+        vk,
+        public_inputs,
+        reused,
+        compressed,
+        elapsed: timer.duration(),
+    })
 }
 
 /// Run the Cairo verify workflow
@@ -180,34 +333,113 @@ pub fn run_prove(cfg: &Config) -> Result<()> {
 /// # Returns
 /// * `Result<()>` - Success or error from workflow
 pub fn run_verify(cfg: &Config) -> Result<()> {
-    let pkg_name =
-        util::get_package_name(cfg.pkg.as_ref()).map_err(enhance_error_with_suggestions)?;
+    run_verify_with_config(cfg, &VerifyConfig::default())
+}
+
+/// Run the Cairo verify workflow with optional artifact path overrides
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `verify_cfg` - Proof, vk and public inputs paths to use instead of `target/starknet/`,
+///   and whether the proof is expected to verify
+///
+/// # Returns
+/// * `Result<()>` - Success or error from workflow
+pub fn run_verify_with_config(cfg: &Config, verify_cfg: &VerifyConfig) -> Result<()> {
+    let result = verify_proof(cfg, verify_cfg)?;
+
+    if !cfg.quiet {
+        let message = if verify_cfg.expect_valid {
+            "Starknet proof verified successfully"
+        } else {
+            "Starknet proof rejected as expected"
+        };
+        let detail = if result.cached {
+            "cached, inputs unchanged; use --force to re-run".to_string()
+        } else {
+            util::format_duration(result.elapsed)
+        };
+        println!("{}", success(&format!("{message} ({detail})")));
+    }
+
+    Ok(())
+}
 
-    // Validate that required Starknet artifacts exist
-    let required_files = vec![
-        util::get_proof_path(Flavour::Starknet),
-        util::get_vk_path(Flavour::Starknet),
-        util::get_public_inputs_path(Flavour::Starknet),
-    ];
+/// Verify the Starknet proof without printing the outcome
+///
+/// The last outcome is reused while the proof, vk and public inputs are
+/// unchanged, unless `verify_cfg.force` is set. Fails when bb's verdict does
+/// not match `verify_cfg.expect_valid`.
+pub fn verify_proof(cfg: &Config, verify_cfg: &VerifyConfig) -> Result<VerifyResult> {
+    util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)
+        .map_err(enhance_error_with_suggestions)?;
+
+    // Validate that required Starknet artifacts exist; default public inputs
+    // may be derived from their field encoding or embedded in the proof
+    let [proof_path, vk_path, public_inputs_path] = &verify_cfg.paths(Flavour::Starknet);
 
     if !cfg.dry_run {
+        let mut required_files = vec![proof_path, vk_path];
+        if verify_cfg.public_inputs.is_some() {
+            required_files.push(public_inputs_path);
+        }
         util::validate_files_exist(&required_files).map_err(enhance_error_with_suggestions)?;
     }
 
     let timer = Timer::start();
-    bb_operations::verify_starknet_proof(cfg, &pkg_name).map_err(enhance_error_with_suggestions)?;
 
-    if !cfg.quiet {
-        println!(
-            "{}",
-            success(&format!(
-                "Starknet proof verified successfully ({})",
-                timer.elapsed()
-            ))
-        );
+    // Reuse the last outcome while the proof, vk and public inputs are unchanged
+    let cache_path = util::verify_cache_path(Flavour::Starknet);
+    let inputs = (!cfg.dry_run)
+        .then(|| util::VerifyInputs::from_files(proof_path, vk_path, public_inputs_path))
+        .transpose()?;
+    if !verify_cfg.force
+        && inputs
+            .as_ref()
+            .and_then(|inputs| util::cached_verification(&cache_path, inputs))
+            == Some(verify_cfg.expect_valid)
+    {
+        return Ok(VerifyResult {
+            accepted: verify_cfg.expect_valid,
+            cached: true,
+            elapsed: timer.duration(),
+        });
     }
 
-    Ok(())
+    let spinner = Spinner::start("Verifying Starknet proof", cfg.show_spinner());
+    let accepted = bb_operations::verify_starknet_proof_at(
+        cfg,
+        proof_path,
+        vk_path,
+        public_inputs_path,
+        verify_cfg.expect_valid,
+    )
+    .map_err(enhance_error_with_suggestions)?;
+    spinner.finish();
+
+    // With --expect false the proof must be rejected; a dry run has no verdict
+    if !verify_cfg.expect_valid && accepted && !cfg.dry_run {
+        return Err(create_smart_error(
+            &format!(
+                "Expected the Starknet proof to fail verification, but bb accepted {}",
+                proof_path.display()
+            ),
+            &[
+                "Check that the proof, vk or public inputs were altered as intended",
+                "Drop '--expect false' to require a valid proof instead",
+            ],
+        ));
+    }
+
+    if let Some(inputs) = inputs {
+        util::store_verification(&cache_path, inputs, accepted)?;
+    }
+
+    Ok(VerifyResult {
+        accepted,
+        cached: false,
+        elapsed: timer.duration(),
+    })
 }
 
 /// Run the Cairo calldata workflow (generate calldata)
@@ -218,32 +450,53 @@ pub fn run_verify(cfg: &Config) -> Result<()> {
 /// # Returns
 /// * `Result<()>` - Success or error
 pub fn run_calldata(cfg: &Config) -> Result<()> {
+    run_calldata_with_config(cfg, &CalldataConfig::default(), &GaragaConfig::default())
+}
+
+/// Run the Cairo calldata workflow with calldata-specific settings
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `calldata_cfg` - Calldata settings such as the output path
+/// * `garaga_cfg` - Garaga settings such as the proof system
+///
+/// # Returns
+/// * `Result<()>` - Success or error
+pub fn run_calldata_with_config(
+    cfg: &Config,
+    calldata_cfg: &CalldataConfig,
+    garaga_cfg: &GaragaConfig,
+) -> Result<()> {
     let mut summary = OperationSummary::new();
 
     if !cfg.dry_run {
         garaga::validate_starknet_artifacts().map_err(enhance_error_with_suggestions)?;
+        garaga::check_version(cfg)?;
     }
 
-    if cfg.dry_run {
-        let proof_path = util::get_proof_path(Flavour::Starknet);
-        let vk_path = util::get_vk_path(Flavour::Starknet);
-        let public_inputs_path = util::get_public_inputs_path(Flavour::Starknet);
-        println!(
-            "Would run: garaga calldata --system ultra_starknet_zk_honk --proof {} --vk {} --public-inputs {}",
-            proof_path.display(),
-            vk_path.display(),
-            public_inputs_path.display()
-        );
-        return Ok(());
-    }
+    let output_path = calldata_cfg.output_path(Flavour::Starknet);
 
-    if cfg.verbose {
+    if cfg.is_verbose() {
         info!("Generating calldata for Starknet proof verification");
     }
 
     let calldata_timer = Timer::start();
-    let calldata_path = garaga::generate_calldata_from_starknet_artifacts(cfg)
-        .map_err(enhance_error_with_suggestions)?;
+    let spinner = Spinner::start("Generating calldata", cfg.show_spinner());
+    let calldata_path = garaga::generate_calldata(
+        cfg,
+        garaga_cfg.system,
+        &util::get_proof_path(Flavour::Starknet),
+        &util::get_vk_path(Flavour::Starknet),
+        &util::get_public_inputs_path(Flavour::Starknet),
+        Some(&output_path),
+        garaga_cfg.calldata_format,
+    )
+    .map_err(enhance_error_with_suggestions)?;
+    spinner.finish();
+
+    if cfg.dry_run {
+        return Ok(());
+    }
 
     if !cfg.quiet {
         println!(
@@ -275,18 +528,29 @@ pub fn run_calldata(cfg: &Config) -> Result<()> {
 /// # Arguments
 /// * `cfg` - CLI configuration
 /// * `network` - Starknet network to declare on
+/// * `keystore` - starkli keystore to sign with instead of the private key
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from workflow
-pub(crate) fn internal_declare(cfg: &Config, network: &str) -> Result<()> {
-    load_env_vars();
+pub(crate) fn internal_declare(
+    cfg: &Config,
+    network: &str,
+    keystore: Option<&Keystore>,
+) -> Result<()> {
+    load_env_vars(&cfg.project_root()?);
+
+    let cairo_dir = directories::get_cairo_contracts_dir();
 
     if cfg.dry_run {
         println!("Would declare contract on network: {network}");
+        let account = starkli::StarknetAccount::placeholder(network, keystore.cloned())?;
+        scarb::build_project(cfg, &cairo_dir)?;
+        let class_path = scarb::find_contract_class(&cairo_dir)
+            .unwrap_or_else(|_| cairo_dir.join("target/dev/<package>.contract_class.json"));
+        starkli::declare_contract(cfg, &util::tool_path_string(&class_path), &account)?;
         return Ok(());
     }
 
-    let cairo_dir = directories::get_cairo_contracts_dir();
     if !cairo_dir.exists() {
         return Err(create_smart_error(
             "Cairo contract directory not found",
@@ -297,15 +561,39 @@ pub(crate) fn internal_declare(cfg: &Config, network: &str) -> Result<()> {
         ));
     }
 
-    if cfg.verbose {
+    let account = starkli::StarknetAccount::from_env(network, keystore.cloned())?;
+
+    if cfg.is_verbose() {
         info!("Declaring Cairo verifier contract on {}", network);
     }
 
-    // Implementation would depend on Starknet CLI integration
-    // This is a placeholder for the actual declare logic
-    println!("🚧 Contract declaration functionality coming soon");
-    println!("Network: {network}");
-    println!("Contract directory: {}", cairo_dir.display());
+    let declare_timer = Timer::start();
+    let spinner = Spinner::start("Declaring Cairo verifier contract", cfg.show_spinner());
+    scarb::build_project(cfg, &cairo_dir).map_err(enhance_error_with_suggestions)?;
+    let class_path = scarb::find_contract_class(&cairo_dir)?;
+    let class_hash = starkli::declare_contract(cfg, &util::tool_path_string(&class_path), &account)
+        .map_err(enhance_error_with_suggestions)?;
+    spinner.finish();
+
+    // Save class hash for the deploy step
+    let class_hash_file = util::target_dir(Flavour::Starknet).join(".bargo_class_hash");
+    if let Some(parent) = class_hash_file.parent() {
+        std::fs::create_dir_all(parent)
+            .wrap_err_with(|| format!("creating directory {}", parent.display()))?;
+    }
+    std::fs::write(&class_hash_file, &class_hash)
+        .wrap_err_with(|| format!("writing class hash to {}", class_hash_file.display()))?;
+
+    if !cfg.quiet {
+        println!(
+            "{}",
+            success(&format!(
+                "Verifier contract declared on {network} ({})",
+                declare_timer.elapsed()
+            ))
+        );
+        println!("Class hash: {class_hash}");
+    }
 
     Ok(())
 }
@@ -313,13 +601,24 @@ pub(crate) fn internal_declare(cfg: &Config, network: &str) -> Result<()> {
 /// Run the Cairo deploy workflow
 ///
 /// # Arguments
-/// * `cli` - CLI configuration
+/// * `cfg` - CLI configuration
+/// * `network` - Starknet network to deploy on
 /// * `class_hash` - Optional class hash of the declared contract
+/// * `constructor_calldata` - Constructor calldata felts, if any
+/// * `keystore` - starkli keystore to sign with instead of the private key
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from workflow
-pub fn run_deploy(cfg: &Config, class_hash: Option<&str>) -> Result<()> {
-    load_env_vars();
+pub fn run_deploy(
+    cfg: &Config,
+    network: &str,
+    class_hash: Option<&str>,
+    constructor_calldata: &[String],
+    keystore: Option<&Keystore>,
+) -> Result<()> {
+    load_env_vars(&cfg.project_root()?);
+
+    let class_hash_file = util::target_dir(Flavour::Starknet).join(".bargo_class_hash");
 
     if cfg.dry_run {
         let hash = match class_hash {
@@ -327,6 +626,8 @@ pub fn run_deploy(cfg: &Config, class_hash: Option<&str>) -> Result<()> {
             None => "<class_hash_from_declare>".to_string(), // Placeholder for dry-run
         };
         println!("Would deploy contract with class hash: {hash}");
+        let account = starkli::StarknetAccount::placeholder(network, keystore.cloned())?;
+        starkli::deploy_contract(cfg, &hash, constructor_calldata, &account)?;
         return Ok(());
     }
 
@@ -334,11 +635,14 @@ pub fn run_deploy(cfg: &Config, class_hash: Option<&str>) -> Result<()> {
         Some(hash) => hash.to_string(),
         None => {
             // Try to read class hash from file saved by declare command
-            match std::fs::read_to_string("target/starknet/.bargo_class_hash")
-                .wrap_err("reading saved class hash from target/starknet/.bargo_class_hash")
-            {
-                Ok(saved_hash) => saved_hash.trim().to_string(),
-                Err(_) => {
+            match std::fs::read_to_string(&class_hash_file).wrap_err_with(|| {
+                format!(
+                    "reading saved class hash from {}",
+                    class_hash_file.display()
+                )
+            }) {
+                Ok(saved_hash) if !saved_hash.trim().is_empty() => saved_hash.trim().to_string(),
+                _ => {
                     return Err(create_smart_error(
                         "No class hash provided and no saved class hash found",
                         &[
@@ -351,14 +655,47 @@ pub fn run_deploy(cfg: &Config, class_hash: Option<&str>) -> Result<()> {
         }
     };
 
-    if cfg.verbose {
-        info!("Deploying Cairo verifier contract");
+    let account = starkli::StarknetAccount::from_env(network, keystore.cloned())?;
+    let pkg_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)?;
+
+    if cfg.is_verbose() {
+        info!("Deploying Cairo verifier contract on {}", network);
     }
 
-    // Implementation would depend on Starknet CLI integration
-    // This is a placeholder for the actual deploy logic
-    println!("🚧 Contract deployment functionality coming soon");
-    println!("Class hash: {hash}");
+    let deploy_timer = Timer::start();
+    let spinner = Spinner::start("Deploying Cairo verifier contract", cfg.show_spinner());
+    let contract_address = starkli::deploy_contract(cfg, &hash, constructor_calldata, &account)
+        .map_err(enhance_error_with_suggestions)?;
+    spinner.finish();
+
+    // Record the address for verify-onchain
+    util::record_deployment(
+        Flavour::Starknet,
+        &Network::parse(network)?.to_string(),
+        Deployment::new(&contract_address, Some(&hash), &pkg_name)
+            .with_vk_hash(util::vk_hash(&util::get_vk_path(Flavour::Starknet))),
+    )?;
+
+    if !cfg.quiet {
+        println!(
+            "{}",
+            success(&format!(
+                "Verifier contract deployed successfully ({})",
+                deploy_timer.elapsed()
+            ))
+        );
+        println!("Contract address: {contract_address}");
+
+        let mut summary = OperationSummary::new();
+        summary.add_operation(&format!(
+            "Verifier contract deployed at: {contract_address}"
+        ));
+        summary.print();
+        println!();
+        println!("🎯 Next steps:");
+        println!("  • Generate calldata: bargo cairo calldata");
+        println!("  • Verify on-chain: bargo cairo verify-onchain");
+    }
 
     Ok(())
 }
@@ -372,22 +709,63 @@ pub fn run_deploy(cfg: &Config, class_hash: Option<&str>) -> Result<()> {
 /// # Returns
 /// * `Result<()>` - Success or error from workflow
 pub fn run_verify_onchain(cfg: &Config, address: Option<&str>) -> Result<()> {
-    load_env_vars();
+    run_verify_onchain_with_config(cfg, address, &GaragaConfig::default())
+}
 
+/// Run the Cairo verify-onchain workflow against a verifier for a given system
+///
+/// Calls the deployed verifier with `sncast call` and succeeds only when it
+/// accepts the proof in `target/starknet/calldata.json`.
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `address` - Optional contract address to verify against
+/// * `garaga_cfg` - Garaga settings selecting the verifier entrypoint
+///
+/// # Returns
+/// * `Result<()>` - Success or error from workflow
+pub fn run_verify_onchain_with_config(
+    cfg: &Config,
+    address: Option<&str>,
+    garaga_cfg: &GaragaConfig,
+) -> Result<()> {
+    load_env_vars(&cfg.project_root()?);
+
+    let network = Network::parse(cfg.cairo_network.as_deref().unwrap_or("sepolia"))?;
     let contract_address = match address {
         Some(addr) => addr.to_string(),
         None => {
-            // Try to read contract address from file saved by deploy command
-            match std::fs::read_to_string("target/starknet/.bargo_contract_address").wrap_err(
-                "reading saved contract address from target/starknet/.bargo_contract_address",
-            ) {
-                Ok(saved_address) => saved_address.trim().to_string(),
-                Err(_) => {
+            // Latest deploy on this network, or the file saved before the address book
+            let address_file = util::target_dir(Flavour::Starknet).join(".bargo_contract_address");
+            let saved_address =
+                match util::latest_deployment(Flavour::Starknet, &network.to_string())? {
+                    Some(deployment) => {
+                        let vk_path = util::get_vk_path(Flavour::Starknet);
+                        if let Some(warning) =
+                            util::stale_vk_warning(&deployment, &vk_path, "cairo")
+                        {
+                            warn!("{warning}");
+                        }
+                        Some(deployment.address)
+                    }
+                    None => std::fs::read_to_string(&address_file)
+                        .ok()
+                        .map(|saved| saved.trim().to_string()),
+                };
+            match saved_address {
+                Some(saved_address) => saved_address,
+                None if cfg.dry_run => "<contract_address>".to_string(),
+                None => {
                     return Err(create_smart_error(
-                        "No contract address provided and no saved address found",
+                        &format!(
+                            "No contract address provided and none recorded for network {network}"
+                        ),
                         &[
                             "Provide contract address with --address option",
-                            "Or run 'bargo cairo deploy' first to save contract address",
+                            &format!(
+                                "Or run 'bargo cairo deploy --network {network}' first to record the contract address"
+                            ),
+                            "List recorded deployments with 'bargo addresses'",
                         ],
                     ));
                 }
@@ -396,31 +774,78 @@ pub fn run_verify_onchain(cfg: &Config, address: Option<&str>) -> Result<()> {
     };
 
     // Validate calldata exists
-    let calldata_path = std::path::PathBuf::from("./target/starknet/calldata.json");
-    if !cfg.dry_run && !calldata_path.exists() {
+    let calldata_path = util::target_dir(Flavour::Starknet).join("calldata.json");
+    let calldata = if calldata_path.exists() {
+        sncast::read_calldata(&calldata_path)?
+    } else if cfg.dry_run {
+        vec!["<calldata>".to_string()]
+    } else {
         return Err(create_smart_error(
             "Calldata file not found",
             &[
                 "Run 'bargo cairo calldata' first to generate calldata",
-                "Ensure the target/starknet/calldata.json file exists",
+                &format!("Ensure the {} file exists", calldata_path.display()),
             ],
         ));
-    }
+    };
+
+    let rpc_url = starkli::rpc_url_from_env(&network, cfg.dry_run)?;
+    let function = garaga_cfg.system.verify_function();
 
     if cfg.dry_run {
         println!("Would verify proof on-chain at address: {contract_address}");
+        println!("Would use calldata from: {}", calldata_path.display());
+    } else if cfg.is_verbose() {
+        info!(
+            "Verifying proof on-chain at address: {} ({})",
+            contract_address, function
+        );
+    }
+
+    let verify_timer = Timer::start();
+    let spinner = Spinner::start("Verifying proof on-chain", cfg.show_spinner());
+    let verified = sncast::call_verifier(cfg, &contract_address, &function, &calldata, &rpc_url)
+        .map_err(|error| match util::find_bargo_error(&error) {
+            // A non-zero exit from `sncast call` means the call reverted
+            Some(util::BargoError::CommandFailed { .. }) => create_smart_error(
+                &format!("Call to verifier contract at {contract_address} reverted: {error}"),
+                &[
+                    &format!(
+                        "Check that {contract_address} is a {} verifier deployed on {network}",
+                        garaga_cfg.system.as_str()
+                    ),
+                    "Regenerate calldata if the proof changed: bargo cairo calldata",
+                ],
+            ),
+            _ => enhance_error_with_suggestions(error),
+        })?;
+    spinner.finish();
+
+    if cfg.dry_run {
         return Ok(());
     }
 
-    if cfg.verbose {
-        info!("Verifying proof on-chain at address: {}", contract_address);
+    if !verified {
+        return Err(create_smart_error(
+            &format!("Verifier contract at {contract_address} rejected the proof"),
+            &[
+                "Regenerate the proof and calldata: bargo cairo prove && bargo cairo calldata",
+                "Ensure the deployed verifier was generated from the same circuit (bargo cairo gen)",
+                &format!("Check that {network} is the network the contract was deployed to"),
+            ],
+        ));
     }
+    util::record_verification(Flavour::Starknet, &network.to_string(), &contract_address)?;
 
-    // Implementation would depend on Starknet CLI integration
-    // This is a placeholder for the actual on-chain verification logic
-    println!("🚧 On-chain verification functionality coming soon");
-    println!("Contract address: {contract_address}");
-    println!("Calldata: {}", calldata_path.display());
+    if !cfg.quiet {
+        println!(
+            "{}",
+            success(&format!(
+                "Proof verified on-chain at {contract_address} ({})",
+                verify_timer.elapsed()
+            ))
+        );
+    }
 
     Ok(())
 }
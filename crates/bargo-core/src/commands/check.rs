@@ -1,7 +1,262 @@
 use color_eyre::Result;
+use std::path::{Path, PathBuf};
 
-use crate::{commands::common::run_nargo_command, config::Config};
+use crate::{
+    commands::common::{run_nargo_command, run_nargo_command_capture},
+    config::Config,
+    util::{self, Cell, NargoTomlKind, Table, colors, create_smart_error},
+};
 
 pub fn run(cfg: &Config) -> Result<()> {
     run_nargo_command(cfg, &["check"])
 }
+
+/// Run `nargo check` and fail if it reports any warnings
+///
+/// nargo exits zero on warnings, so its output is captured and scanned
+/// instead. The offending warning lines are printed before returning the error.
+pub fn run_deny_warnings(cfg: &Config) -> Result<()> {
+    let output = run_nargo_command_capture(cfg, &["check"])?;
+
+    let warnings: Vec<&str> = find_warnings(&output.stdout)
+        .chain(find_warnings(&output.stderr))
+        .collect();
+    if warnings.is_empty() {
+        return Ok(());
+    }
+
+    for warning in &warnings {
+        eprintln!("{warning}");
+    }
+    Err(create_smart_error(
+        &format!("nargo check reported {} warning(s)", warnings.len()),
+        &[
+            "Fix the warnings listed above",
+            "Run without --deny-warnings to allow them",
+        ],
+    ))
+}
+
+/// Result of checking one workspace member with `check --workspace`
+#[derive(Debug)]
+pub enum MemberOutcome {
+    /// nargo check succeeded
+    Passed,
+    /// nargo check failed, or reported warnings with `--deny-warnings`
+    Failed(color_eyre::Report),
+    /// The member's Nargo.toml is missing or has no package name, so it was not checked
+    Invalid(color_eyre::Report),
+}
+
+/// One workspace member and the outcome of checking it
+#[derive(Debug)]
+pub struct MemberCheck {
+    /// Package name, or the directory name when Nargo.toml could not be read
+    pub name: String,
+    /// Member directory, resolved against the workspace root
+    pub path: PathBuf,
+    pub outcome: MemberOutcome,
+}
+
+/// Check every member of the current workspace and report each one
+///
+/// Unlike `build --all`, every member is checked even after a failure, and a
+/// member with a missing or invalid Nargo.toml is reported rather than
+/// aborting the run. The command fails if any member failed or was invalid.
+/// Outside a workspace the single package is checked as usual.
+pub fn run_workspace(cfg: &Config, deny_warnings: bool) -> Result<()> {
+    let root = util::find_project_root(&cfg.working_dir()?)?;
+    let nargo_toml = root.join("Nargo.toml");
+    if util::detect_nargo_toml_kind(&nargo_toml)? != NargoTomlKind::Workspace {
+        return check_package(cfg, deny_warnings);
+    }
+
+    let results = check_members(cfg, &nargo_toml, deny_warnings)?;
+    if !cfg.quiet {
+        println!();
+        workspace_table(&root, &results).print();
+    }
+
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|member| !matches!(member.outcome, MemberOutcome::Passed))
+        .map(|member| member.name.as_str())
+        .collect();
+    if failed.is_empty() {
+        return Ok(());
+    }
+    Err(create_smart_error(
+        &format!(
+            "nargo check failed for {} of {} workspace members: {}",
+            failed.len(),
+            results.len(),
+            failed.join(", ")
+        ),
+        &[
+            "Fix the members marked failed or invalid above",
+            "Check a single member with --pkg <name>",
+        ],
+    ))
+}
+
+/// Check each member of the workspace at `nargo_toml`, without stopping at a failure
+pub fn check_members(
+    cfg: &Config,
+    nargo_toml: &Path,
+    deny_warnings: bool,
+) -> Result<Vec<MemberCheck>> {
+    let results = util::workspace_member_dirs(nargo_toml)?
+        .into_iter()
+        .map(|path| {
+            let (name, outcome) = match util::parse_package_name(&path.join("Nargo.toml")) {
+                Ok(name) => {
+                    if !cfg.quiet {
+                        println!("📦 {name}");
+                    }
+                    let member_cfg = Config {
+                        pkg: Some(name.clone()),
+                        ..cfg.clone()
+                    };
+                    let outcome = match check_package(&member_cfg, deny_warnings) {
+                        Ok(()) => MemberOutcome::Passed,
+                        Err(e) => MemberOutcome::Failed(e),
+                    };
+                    (name, outcome)
+                }
+                Err(e) => {
+                    let name = path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| path.display().to_string());
+                    (name, MemberOutcome::Invalid(e))
+                }
+            };
+            MemberCheck {
+                name,
+                path,
+                outcome,
+            }
+        })
+        .collect();
+    Ok(results)
+}
+
+/// Check one package, honouring `--deny-warnings`
+fn check_package(cfg: &Config, deny_warnings: bool) -> Result<()> {
+    if deny_warnings {
+        run_deny_warnings(cfg)
+    } else {
+        run(cfg)
+    }
+}
+
+/// Per-member pass/fail table, with paths relative to the workspace root
+fn workspace_table(root: &Path, results: &[MemberCheck]) -> Table {
+    let mut table = Table::new(&["MEMBER", "PATH", "STATUS"]);
+    for member in results {
+        let path = member.path.strip_prefix(root).unwrap_or(&member.path);
+        let status = match &member.outcome {
+            MemberOutcome::Passed => Cell::colored("passed", colors::GREEN),
+            MemberOutcome::Failed(e) => Cell::colored(format!("failed: {e:#}"), colors::RED),
+            MemberOutcome::Invalid(e) => Cell::colored(format!("invalid: {e:#}"), colors::RED),
+        };
+        table.row(vec![
+            member.name.clone().into(),
+            path.display().to_string().into(),
+            status,
+        ]);
+    }
+    table
+}
+
+/// Lines of nargo output that start with `warning`, ignoring case and indentation
+fn find_warnings(output: &str) -> impl Iterator<Item = &str> {
+    output.lines().map(str::trim).filter(|line| {
+        line.get(..7)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("warning"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{ArtifactSuffix, ColorChoice};
+    use crate::config::ToolPaths;
+    use crate::fs::RealFs;
+    use crate::runner::{CapturedOutput, CmdSpec, RecordedCommand, Recording, ReplayRunner};
+    use std::sync::Arc;
+
+    const WARNINGS: &str = "warning: unused variable y
+  ┌─ src/main.nr:2:9
+  │
+2 │     let y = x + 1;
+  │         - unused variable
+  │
+
+Warning: unused import std::hash
+";
+
+    /// Configuration whose nargo check prints `stderr`
+    fn config_with_check_output(stderr: &str) -> Config {
+        let recording = Recording {
+            commands: vec![RecordedCommand {
+                spec: CmdSpec::new("nargo".to_string(), vec!["check".to_string()]),
+                output: Some(CapturedOutput {
+                    stdout: String::new(),
+                    stderr: stderr.to_string(),
+                    status: 0,
+                }),
+                error: None,
+            }],
+        };
+        Config {
+            verbose: 0,
+            dry_run: false,
+            pkg: None,
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: Arc::new(ReplayRunner::new(recording)),
+            fs: Arc::new(RealFs),
+        }
+    }
+
+    #[test]
+    fn test_find_warnings() {
+        assert_eq!(
+            find_warnings(WARNINGS).collect::<Vec<_>>(),
+            [
+                "warning: unused variable y",
+                "Warning: unused import std::hash"
+            ]
+        );
+        assert_eq!(
+            find_warnings("[my_circuit] Constraint system successfully built!").count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_deny_warnings_fails_on_warnings() {
+        let error = run_deny_warnings(&config_with_check_output(WARNINGS)).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("nargo check reported 2 warning(s)")
+        );
+    }
+
+    #[test]
+    fn test_deny_warnings_passes_clean_output() {
+        assert!(run_deny_warnings(&config_with_check_output("")).is_ok());
+    }
+}
@@ -1,67 +1,292 @@
 use color_eyre::Result;
 use color_eyre::eyre::WrapErr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use tracing::info;
 
 use crate::{
     cli::Backend,
     config::Config,
-    util::{info as info_msg, success},
+    util::{
+        self, Flavour, LOCK_FILE, format_age, format_file_size, format_size, info as info_msg,
+        success,
+    },
 };
 
 pub fn run(cfg: &Config, backend: Backend) -> Result<()> {
-    if cfg.verbose {
+    if cfg.is_verbose() {
         info!("Cleaning artifacts for backend: {:?}", backend);
     }
 
-    match backend {
-        Backend::All => {
-            if cfg.dry_run {
-                println!("Would run: rm -rf target/");
-                return Ok(());
-            }
-
-            if std::path::Path::new("target").exists() {
-                std::fs::remove_dir_all("target").wrap_err("removing target directory")?;
-                if !cfg.quiet {
-                    println!("{}", success("Removed target/"));
-                }
-            } else if !cfg.quiet {
-                println!("{}", info_msg("target/ already clean"));
-            }
+    remove_dir(cfg, &backend_dir(backend))
+}
+
+/// Remove only the files under the backend's target directory that were last
+/// modified more than `max_age` ago
+///
+/// Directories and newer artifacts stay in place, so periodic housekeeping
+/// keeps whatever is still in use.
+pub fn run_older_than(cfg: &Config, backend: Backend, max_age: Duration) -> Result<()> {
+    if cfg.is_verbose() {
+        info!(
+            "Cleaning artifacts older than {} for backend: {:?}",
+            format_age(max_age),
+            backend
+        );
+    }
+
+    let dir = backend_dir(backend);
+    let cutoff = SystemTime::now()
+        .checked_sub(max_age)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let mut entries = Vec::new();
+    collect_entries(&dir, &mut entries)?;
+    let stale: Vec<Entry> = entries
+        .into_iter()
+        .filter(|entry| !entry.is_dir && entry.path.file_name() != Some(LOCK_FILE.as_ref()))
+        .filter(|entry| {
+            std::fs::symlink_metadata(&entry.path)
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified < cutoff)
+        })
+        .collect();
+
+    if stale.is_empty() {
+        if !cfg.quiet {
+            println!(
+                "{}",
+                info_msg(&format!(
+                    "No artifacts in {}/ older than {}",
+                    dir.display(),
+                    format_age(max_age)
+                ))
+            );
+        }
+        return Ok(());
+    }
+
+    let _lock = (!cfg.dry_run)
+        .then(util::ArtifactLock::acquire)
+        .transpose()?;
+
+    for entry in &stale {
+        if cfg.dry_run && !cfg.quiet {
+            println!(
+                "Would remove: {} ({})",
+                entry.path.display(),
+                format_file_size(&entry.path)
+            );
         }
-        Backend::Bb => {
-            if cfg.dry_run {
-                println!("Would run: rm -rf target/bb/");
-                return Ok(());
-            }
-
-            if std::path::Path::new("target/bb").exists() {
-                std::fs::remove_dir_all("target/bb").wrap_err("removing target/bb directory")?;
-                if !cfg.quiet {
-                    println!("{}", success("Removed target/bb/"));
-                }
-            } else if !cfg.quiet {
-                println!("{}", info_msg("target/bb/ already clean"));
-            }
+        cfg.fs
+            .remove_file(&entry.path)
+            .wrap_err_with(|| format!("removing {}", entry.path.display()))?;
+    }
+
+    if !cfg.quiet {
+        let total = stale.iter().map(|entry| entry.size).sum();
+        let noun = if stale.len() == 1 { "file" } else { "files" };
+        if cfg.dry_run {
+            println!(
+                "Would reclaim {} ({} {noun})",
+                format_size(total),
+                stale.len()
+            );
+        } else {
+            println!(
+                "{}",
+                success(&format!(
+                    "Removed {} {noun} older than {}, reclaimed {}",
+                    stale.len(),
+                    format_age(max_age),
+                    format_size(total)
+                ))
+            );
         }
+    }
+
+    Ok(())
+}
+
+/// Target directory covered by `backend`
+fn backend_dir(backend: Backend) -> PathBuf {
+    match backend {
+        Backend::All => util::target_base(),
+        Backend::Bb => util::target_dir(Flavour::Bb),
+        #[cfg(feature = "cairo")]
+        Backend::Starknet => util::target_dir(Flavour::Starknet),
+    }
+}
+
+/// Remove only the cache files under the backend's target directories
+///
+/// Fingerprints and manifests are deleted while proofs and vks stay in place;
+/// `include_vks` also drops the cached vks. The bb backend covers both the
+/// shared `target/bb/` build output and the `target/evm/` proofs it writes.
+pub fn run_cache(cfg: &Config, backend: Backend, include_vks: bool) -> Result<()> {
+    if cfg.is_verbose() {
+        info!("Cleaning cache for backend: {:?}", backend);
+    }
+
+    let flavours: &[Flavour] = match backend {
+        Backend::All => &[Flavour::Bb, Flavour::Evm, Flavour::Starknet],
+        Backend::Bb => &[Flavour::Bb, Flavour::Evm],
         #[cfg(feature = "cairo")]
-        Backend::Starknet => {
-            if cfg.dry_run {
-                println!("Would run: rm -rf target/starknet/");
-                return Ok(());
-            }
-
-            if std::path::Path::new("target/starknet").exists() {
-                std::fs::remove_dir_all("target/starknet")
-                    .wrap_err("removing target/starknet directory")?;
-                if !cfg.quiet {
-                    println!("{}", success("Removed target/starknet/"));
-                }
-            } else if !cfg.quiet {
-                println!("{}", info_msg("target/starknet/ already clean"));
-            }
+        Backend::Starknet => &[Flavour::Starknet],
+    };
+    let files: Vec<PathBuf> = flavours
+        .iter()
+        .flat_map(|&flavour| util::cache_files(flavour, include_vks))
+        .filter(|path| path.is_file())
+        .collect();
+
+    if files.is_empty() {
+        if !cfg.quiet {
+            println!("{}", info_msg("Cache already clean"));
+        }
+        return Ok(());
+    }
+
+    let _lock = (!cfg.dry_run)
+        .then(util::ArtifactLock::acquire)
+        .transpose()?;
+
+    for file in &files {
+        if cfg.dry_run && !cfg.quiet {
+            println!(
+                "Would remove: {} ({})",
+                file.display(),
+                format_file_size(file)
+            );
         }
+        cfg.fs
+            .remove_file(file)
+            .wrap_err_with(|| format!("removing cache file {}", file.display()))?;
+    }
+
+    if !cfg.dry_run && !cfg.quiet {
+        let noun = if files.len() == 1 { "file" } else { "files" };
+        println!(
+            "{}",
+            success(&format!("Removed {} cache {noun}", files.len()))
+        );
     }
 
     Ok(())
 }
+
+/// Remove an artifact directory, reporting whether there was anything to clean
+fn remove_dir(cfg: &Config, dir: &Path) -> Result<()> {
+    if cfg.dry_run {
+        if !cfg.quiet {
+            print_removal_plan(dir)?;
+        }
+        if dir.exists() {
+            cfg.fs.remove_dir_all(dir)?;
+        }
+        return Ok(());
+    }
+
+    if dir.exists() {
+        let _lock = util::ArtifactLock::acquire()?;
+        cfg.fs
+            .remove_dir_all(dir)
+            .wrap_err_with(|| format!("removing {} directory", dir.display()))?;
+        if !cfg.quiet {
+            println!("{}", success(&format!("Removed {}/", dir.display())));
+        }
+    } else if !cfg.quiet {
+        println!("{}", info_msg(&format!("{}/ already clean", dir.display())));
+    }
+
+    Ok(())
+}
+
+/// A file or directory that `clean` would delete
+struct Entry {
+    path: PathBuf,
+    /// File size, or the total size of everything inside a directory
+    size: u64,
+    is_dir: bool,
+}
+
+/// Print every file and directory under `dir` with its size, plus the total
+fn print_removal_plan(dir: &Path) -> Result<()> {
+    println!("Would run: rm -rf {}/", dir.display());
+
+    if !dir.exists() {
+        println!("  {}/ does not exist, nothing to remove", dir.display());
+        return Ok(());
+    }
+
+    let mut entries = Vec::new();
+    let total = collect_entries(dir, &mut entries)?;
+
+    for entry in &entries {
+        if entry.is_dir {
+            println!(
+                "  📁 {}/ ({})",
+                entry.path.display(),
+                format_size(entry.size)
+            );
+        } else {
+            println!(
+                "  📄 {} ({})",
+                entry.path.display(),
+                format_file_size(&entry.path)
+            );
+        }
+    }
+
+    let files = entries.iter().filter(|entry| !entry.is_dir).count();
+    let noun = if files == 1 { "file" } else { "files" };
+    println!("Would reclaim {} ({files} {noun})", format_size(total));
+
+    Ok(())
+}
+
+/// Recursively collect the entries under `dir` in sorted order
+///
+/// Each directory is listed before its contents. Symlinks are reported as
+/// files and not followed, and entries that disappear while walking are
+/// skipped. Returns the total size of all files found.
+fn collect_entries(dir: &Path, entries: &mut Vec<Entry>) -> Result<u64> {
+    let mut children = match std::fs::read_dir(dir) {
+        Ok(read_dir) => read_dir
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect::<Vec<_>>(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => {
+            return Err(e).wrap_err_with(|| format!("reading directory {}", dir.display()));
+        }
+    };
+    children.sort();
+
+    let mut total = 0;
+    for path in children {
+        let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            let index = entries.len();
+            entries.push(Entry {
+                path: path.clone(),
+                size: 0,
+                is_dir: true,
+            });
+            let size = collect_entries(&path, entries)?;
+            entries[index].size = size;
+            total += size;
+        } else {
+            total += metadata.len();
+            entries.push(Entry {
+                path,
+                size: metadata.len(),
+                is_dir: false,
+            });
+        }
+    }
+
+    Ok(total)
+}
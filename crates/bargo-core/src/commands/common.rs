@@ -1,8 +1,55 @@
 use color_eyre::Result;
-use std::path::Path;
-use tracing::info;
+use color_eyre::eyre::WrapErr;
+use std::collections::BTreeMap;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
 
-use crate::{config::Config, runner::CmdSpec};
+use crate::{
+    backends,
+    cli::{BbProfile, OracleHash, ProofOutputFormat},
+    config::{Config, Network, ProveConfig},
+    runner::{CapturedOutput, CmdSpec, RetryPolicy},
+    util::{self, BargoError, Flavour, NargoTomlKind},
+};
+
+/// Create a command specification carrying the global execution settings
+///
+/// Resolves pinned tool binaries (see `backends::resolve_tool`) and applies
+/// the working directory and the `--timeout` limit from the configuration so
+/// every helper in this module builds specs the same way. Without an explicit
+/// working directory, tools run in `--package-dir` when one is given.
+fn tool_spec(cfg: &Config, tool: &str, args: Vec<String>, working_dir: Option<&Path>) -> CmdSpec {
+    let mut spec = CmdSpec::new(backends::resolve_tool(tool, &cfg.tools), args);
+
+    if let Some(dir) = working_dir.or(cfg.package_dir.as_deref()) {
+        spec = spec.with_cwd(dir.to_path_buf());
+    }
+
+    if let Some(seconds) = cfg.timeout {
+        spec = spec.with_timeout(seconds);
+    }
+
+    spec
+}
+
+/// Name the failed invocation in an error returned by the runner
+///
+/// Runners describe how a command failed (exit code, output, missing binary);
+/// this adds which command it was, so every helper in this module produces
+/// the same error chain whichever runner is configured. Environment values
+/// are left out and secret arguments masked, since the message is printed,
+/// logged and sent to `--summary-file` and `--notify-url`.
+fn with_command_context<T>(spec: &CmdSpec, result: Result<T>) -> Result<T> {
+    result.wrap_err_with(|| {
+        let redacted = spec.redacted();
+        format!(
+            "Command execution failed: {} {}",
+            redacted.cmd,
+            redacted.args.join(" ")
+        )
+    })
+}
 
 /// Build argument list for nargo commands based on global config
 ///
@@ -54,52 +101,57 @@ pub fn build_nargo_args(cfg: &Config, base_args: &[&str]) -> Result<Vec<String>>
 /// run_nargo_command(&config, &["check"])?;
 /// ```
 pub fn run_nargo_command(cfg: &Config, base_args: &[&str]) -> Result<()> {
-    let args = build_nargo_args(cfg, base_args)?;
-
-    if cfg.verbose && !cfg.quiet {
-        info!("Running: nargo {}", args.join(" "));
-    }
-
-    // Create command specification for nargo
-    let spec = CmdSpec::new("nargo".to_string(), args);
-
-    // Use the runner to execute the command (handles dry-run automatically)
-    cfg.runner.run(&spec)
+    run_nargo_command_with_passthrough(cfg, base_args, &[])
 }
 
-/// Run a nargo command in a specific working directory
+/// Run a nargo command like [`run_nargo_command`], appending `passthrough`
 ///
-/// This is similar to `run_nargo_command` but allows specifying a working directory
-/// for the command execution. If `working_dir` is None, behaves like `run_nargo_command`.
+/// The passthrough arguments come after every flag bargo adds, including
+/// `--package`, so users can reach nargo options bargo does not expose.
 ///
 /// # Arguments
 /// * `cfg` - The global configuration containing all flags
 /// * `base_args` - Base command arguments to pass to nargo
-/// * `working_dir` - Optional working directory for command execution
+/// * `passthrough` - Arguments appended verbatim at the end
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from command execution
-pub fn run_nargo_command_in_directory(
+pub fn run_nargo_command_with_passthrough(
     cfg: &Config,
     base_args: &[&str],
-    working_dir: Option<&Path>,
+    passthrough: &[String],
 ) -> Result<()> {
-    let args = build_nargo_args(cfg, base_args)?;
+    let mut args = build_nargo_args(cfg, base_args)?;
+    args.extend_from_slice(passthrough);
 
-    if cfg.verbose && !cfg.quiet {
+    if cfg.is_verbose() && !cfg.quiet {
         info!("Running: nargo {}", args.join(" "));
     }
 
     // Create command specification for nargo
-    let mut spec = CmdSpec::new("nargo".to_string(), args);
+    let spec = tool_spec(cfg, "nargo", args, None);
 
-    // Set working directory if provided
-    if let Some(dir) = working_dir {
-        spec = spec.with_cwd(dir.to_path_buf());
+    // Use the runner to execute the command (handles dry-run automatically)
+    with_command_context(&spec, cfg.runner.run(&spec))
+}
+
+/// Run a nargo command like [`run_nargo_command`] and capture its output
+///
+/// # Arguments
+/// * `cfg` - The global configuration containing all flags
+/// * `base_args` - Base command arguments to pass to nargo
+///
+/// # Returns
+/// * `Result<CapturedOutput>` - Captured output from command execution or error
+pub fn run_nargo_command_capture(cfg: &Config, base_args: &[&str]) -> Result<CapturedOutput> {
+    let args = build_nargo_args(cfg, base_args)?;
+
+    if cfg.is_verbose() && !cfg.quiet {
+        info!("Running (capturing output): nargo {}", args.join(" "));
     }
 
-    // Use the runner to execute the command (handles dry-run automatically)
-    cfg.runner.run(&spec)
+    let spec = tool_spec(cfg, "nargo", args, None);
+    with_command_context(&spec, cfg.runner.run_capture(&spec))
 }
 
 /// Run any external tool in a specific working directory
@@ -123,20 +175,15 @@ pub fn run_tool_in_directory(
 ) -> Result<()> {
     let args_vec: Vec<String> = args.iter().map(|s| s.to_string()).collect();
 
-    if cfg.verbose && !cfg.quiet {
+    if cfg.is_verbose() && !cfg.quiet {
         info!("Running: {} {}", tool, args_vec.join(" "));
     }
 
     // Create command specification for the tool
-    let mut spec = CmdSpec::new(tool.to_string(), args_vec);
-
-    // Set working directory if provided
-    if let Some(dir) = working_dir {
-        spec = spec.with_cwd(dir.to_path_buf());
-    }
+    let spec = tool_spec(cfg, tool, args_vec, working_dir);
 
     // Use the runner to execute the command (handles dry-run automatically)
-    cfg.runner.run(&spec)
+    with_command_context(&spec, cfg.runner.run(&spec))
 }
 
 /// Run any external tool with unified command execution
@@ -166,21 +213,372 @@ pub fn run_tool_in_directory(
 pub fn run_tool(cfg: &Config, tool: &str, args: &[&str]) -> Result<()> {
     let args_vec: Vec<String> = args.iter().map(|s| s.to_string()).collect();
 
-    if cfg.verbose && !cfg.quiet {
+    if cfg.is_verbose() && !cfg.quiet {
         info!("Running: {} {}", tool, args_vec.join(" "));
     }
 
     // Create command specification for the tool
-    let spec = CmdSpec::new(tool.to_string(), args_vec);
+    let spec = tool_spec(cfg, tool, args_vec, None);
 
     // Use the runner to execute the command (handles dry-run automatically)
-    cfg.runner.run(&spec)
+    with_command_context(&spec, cfg.runner.run(&spec))
+}
+
+/// Extra bb argument for `prove` and `write_vk` when `--recursive` is set
+pub const BB_RECURSIVE_ARGS: [&str; 1] = ["--recursive"];
+
+/// bb output format for recursive artifacts when none was chosen
+///
+/// Alongside the usual binary artifacts, bb then also writes `proof_fields.json`,
+/// `vk_fields.json` and `public_inputs_fields.json`, the field-element encoding a
+/// Noir circuit takes as input to verify the proof recursively.
+pub const BB_RECURSIVE_OUTPUT_FORMAT: ProofOutputFormat = ProofOutputFormat::Both;
+
+/// bb's honk recursion flag, added to `prove` and `write_vk` by `--recursive-friendly`
+///
+/// The vk then describes a circuit meant to be verified inside another Honk
+/// circuit, so its proofs are not interchangeable with standard ones.
+pub const BB_HONK_RECURSION_ARGS: [&str; 2] = ["--honk_recursion", "1"];
+
+/// bb's recursion and output format flags for `prove` or `write_vk`
+///
+/// `--recursive` and `--honk_recursion 1` follow `prove_cfg`, followed by
+/// `--output_format <output_format>`. Each flag appears at most once, and
+/// flags already given in `passthrough` are left to the passthrough copy.
+pub fn bb_recursion_args(
+    prove_cfg: &ProveConfig,
+    output_format: Option<ProofOutputFormat>,
+    passthrough: &[String],
+) -> Vec<&'static str> {
+    let supplied = |flag: &str| {
+        passthrough.iter().any(|arg| {
+            arg.strip_prefix(flag)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('='))
+        })
+    };
+
+    let mut args = Vec::new();
+    if prove_cfg.recursive && !supplied("--recursive") {
+        args.extend(BB_RECURSIVE_ARGS);
+    }
+    if prove_cfg.recursive_friendly && !supplied("--honk_recursion") {
+        args.extend(BB_HONK_RECURSION_ARGS);
+    }
+    if let Some(format) = output_format.filter(|_| !supplied("--output_format")) {
+        args.extend(["--output_format", format.as_str()]);
+    }
+    args
+}
+
+/// bb arguments selecting the transcript hash for a flavour's proof and vk
+///
+/// EVM artifacts default to `-t evm` (Keccak) and Starknet ones to
+/// `--oracle_hash starknet`; an override replaces either with
+/// `--oracle_hash <hash>`.
+pub fn bb_oracle_args(flavour: Flavour, oracle_hash: Option<OracleHash>) -> Vec<&'static str> {
+    match (oracle_hash, flavour) {
+        (Some(hash), _) => vec!["--oracle_hash", hash.as_str()],
+        (None, Flavour::Evm) => vec!["-t", "evm"],
+        (None, Flavour::Starknet) => vec!["--oracle_hash", "starknet"],
+        (None, Flavour::Bb) => Vec::new(),
+    }
+}
+
+/// Oracle hash override recorded in the manifest next to `proof`
+///
+/// Proofs without a manifest, or generated with the backend's default hash,
+/// yield `None`.
+pub fn recorded_oracle_hash(proof: &Path) -> Result<Option<OracleHash>> {
+    let manifest = proof
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(util::MANIFEST_FILE);
+    let Some(name) = util::read_manifest(&manifest)?.and_then(|manifest| manifest.oracle_hash)
+    else {
+        return Ok(None);
+    };
+
+    OracleHash::from_name(&name).map(Some).ok_or_else(|| {
+        BargoError::ParseFailure {
+            path: manifest,
+            message: format!("unknown oracle hash `{name}`"),
+        }
+        .into()
+    })
+}
+
+/// File name bb gives the field-element encoding of the public inputs
+pub const PUBLIC_INPUTS_FIELDS_FILE: &str = "public_inputs_fields.json";
+
+/// Public inputs file to pass to `bb verify -i`, adapting to bb's layouts
+///
+/// Most bb releases write a binary `public_inputs` file next to the proof.
+/// Some write only `public_inputs_fields.json`, from which the missing binary
+/// file is re-created (only logged in a dry run). Others keep the public
+/// inputs inside the proof; then `None` is returned and `-i` must be left
+/// out. A dry run without any artifacts keeps the usual `-i` path.
+pub fn resolve_public_inputs(
+    cfg: &Config,
+    proof: &Path,
+    public_inputs: &Path,
+) -> Result<Option<PathBuf>> {
+    if public_inputs.exists() {
+        return Ok(Some(public_inputs.to_path_buf()));
+    }
+
+    let fields = public_inputs.with_file_name(PUBLIC_INPUTS_FIELDS_FILE);
+    if fields.is_file() {
+        if cfg.dry_run {
+            info!(
+                "Would write {} from {}",
+                public_inputs.display(),
+                fields.display()
+            );
+        } else {
+            let bytes = crate::commands::convert::fields_to_bytes(
+                &crate::commands::convert::read_fields(&fields)?,
+            )?;
+            std::fs::write(public_inputs, bytes)
+                .wrap_err_with(|| format!("writing public inputs {}", public_inputs.display()))?;
+            info!(
+                "Wrote missing {} from {}",
+                public_inputs.display(),
+                fields.display()
+            );
+        }
+        return Ok(Some(public_inputs.to_path_buf()));
+    }
+
+    if proof.exists() {
+        debug!(
+            "No public inputs next to {}; bb reads them from the proof",
+            proof.display()
+        );
+        return Ok(None);
+    }
+    Ok(Some(public_inputs.to_path_buf()))
+}
+
+/// Record the oracle hash override a prove step used in `manifest`
+///
+/// Skipped in dry-run mode, like [`record_manifest`].
+pub fn record_oracle_hash(
+    cfg: &Config,
+    manifest: &Path,
+    oracle_hash: Option<OracleHash>,
+) -> Result<()> {
+    if cfg.dry_run {
+        return Ok(());
+    }
+    util::set_manifest_oracle_hash(manifest, oracle_hash.as_ref().map(OracleHash::as_str))
+}
+
+/// Record whether a prove step used `--recursive-friendly` in `manifest`
+///
+/// Skipped in dry-run mode, like [`record_manifest`].
+pub fn record_recursive_friendly(
+    cfg: &Config,
+    manifest: &Path,
+    recursive_friendly: bool,
+) -> Result<()> {
+    if cfg.dry_run {
+        return Ok(());
+    }
+    util::set_manifest_recursive_friendly(manifest, recursive_friendly)
+}
+
+/// Carry the recorded `--recursive-friendly` mode of `flavour` into `prove_cfg`
+///
+/// `gen` proves again without the prove flags, so the verifier it writes
+/// would otherwise come from a standard vk while `target/<flavour>/` held a
+/// recursion-friendly proof. Keeping the recorded mode gives a contract
+/// matching the vk the user asked for.
+pub fn with_recorded_recursion(
+    cfg: &Config,
+    flavour: Flavour,
+    prove_cfg: &ProveConfig,
+) -> Result<ProveConfig> {
+    let recorded = util::read_manifest(&util::manifest_path(flavour))?
+        .is_some_and(|manifest| manifest.recursive_friendly);
+    if recorded && !prove_cfg.recursive_friendly && !prove_cfg.no_prove {
+        if !cfg.quiet {
+            info!("Keeping the recursion-friendly vk recorded by 'prove --recursive-friendly'");
+        }
+        return Ok(prove_cfg.clone().with_recursive_friendly(true));
+    }
+    Ok(prove_cfg.clone())
+}
+
+/// Record the bb profile a prove step used in `manifest`
+///
+/// Skipped in dry-run mode, like [`record_manifest`].
+pub fn record_bb_profile(cfg: &Config, manifest: &Path, profile: Option<BbProfile>) -> Result<()> {
+    if cfg.dry_run {
+        return Ok(());
+    }
+    util::set_manifest_bb_profile(manifest, profile.as_ref().map(BbProfile::as_str))
+}
+
+/// Whether the proof and vk already in `target/<flavour>/` can be kept
+///
+/// True when `prove_cfg` allows reuse, the existing proof was made with the
+/// same oracle hash and compression and without `--recursive-friendly`, and
+/// it is newer than the bytecode and witness it was proved from. Dry runs
+/// always prove.
+pub fn proof_is_current(
+    cfg: &Config,
+    pkg_name: &str,
+    flavour: Flavour,
+    prove_cfg: &ProveConfig,
+) -> Result<bool> {
+    if cfg.dry_run || !prove_cfg.may_reuse_proof() {
+        return Ok(false);
+    }
+
+    let compressed = util::is_gzip(&util::resolve_compressed(&util::get_proof_path(flavour)));
+    if compressed != prove_cfg.compress {
+        return Ok(false);
+    }
+
+    let manifest = util::read_manifest(&util::manifest_path(flavour))?.unwrap_or_default();
+    if manifest.oracle_hash.as_deref() != prove_cfg.oracle_hash.as_ref().map(OracleHash::as_str) {
+        return Ok(false);
+    }
+    // Recursion-friendly and standard proofs are not interchangeable
+    if manifest.recursive_friendly {
+        return Ok(false);
+    }
+
+    Ok(!util::needs_reprove(pkg_name, flavour)?)
+}
+
+/// Gzip the proof and public inputs of `flavour` for `prove --compress`
+///
+/// Without `compress`, `.gz` copies left by an earlier compressed run are
+/// removed so they cannot be mistaken for the new proof. Dry runs touch
+/// nothing.
+///
+/// # Returns
+/// * `Result<Vec<util::CompressedFile>>` - Sizes before and after for each compressed file
+pub fn compress_proof_artifacts(
+    cfg: &Config,
+    flavour: Flavour,
+    compress: bool,
+) -> Result<Vec<util::CompressedFile>> {
+    if cfg.dry_run {
+        return Ok(Vec::new());
+    }
+
+    let artifacts = [
+        util::get_proof_path(flavour),
+        util::get_public_inputs_path(flavour),
+    ];
+    if !compress {
+        for stale in artifacts.iter().map(|path| util::gz_path(path)) {
+            if stale.is_file() {
+                std::fs::remove_file(&stale)
+                    .wrap_err_with(|| format!("removing stale {}", stale.display()))?;
+            }
+        }
+        return Ok(Vec::new());
+    }
+
+    artifacts
+        .iter()
+        .filter(|path| path.is_file())
+        .map(|path| util::compress_file(path))
+        .collect()
+}
+
+/// Proof, vk and public inputs a prove step left for `flavour`, compressed or not
+pub fn proof_artifacts(flavour: Flavour) -> [PathBuf; 3] {
+    [
+        util::resolve_compressed(&util::get_proof_path(flavour)),
+        util::get_vk_path(flavour),
+        util::resolve_compressed(&util::get_public_inputs_path(flavour)),
+    ]
+}
+
+/// Record which artifacts `prove --compress` replaced in `manifest`
+///
+/// Skipped in dry-run mode, like [`record_manifest`].
+pub fn record_compression(
+    cfg: &Config,
+    manifest: &Path,
+    compressed: &[util::CompressedFile],
+) -> Result<()> {
+    if cfg.dry_run {
+        return Ok(());
+    }
+    let paths: Vec<PathBuf> = compressed.iter().map(|file| file.path.clone()).collect();
+    util::set_manifest_compression(manifest, &paths)
+}
+
+/// One-line summary of the space `prove --compress` saved
+pub fn compression_summary(compressed: &[util::CompressedFile]) -> Option<String> {
+    if compressed.is_empty() {
+        return None;
+    }
+    let original: u64 = compressed.iter().map(|file| file.original_size).sum();
+    let packed: u64 = compressed.iter().map(|file| file.compressed_size).sum();
+    Some(format!(
+        "Compressed: {} → {} (saved {})",
+        util::format_size(original),
+        util::format_size(packed),
+        util::format_size(original.saturating_sub(packed))
+    ))
+}
+
+/// Run `bb write_vk`, reusing the existing vk when the bytecode is unchanged
+///
+/// The vk for `flavour` is kept when its recorded fingerprint matches the
+/// bytecode and `args` (see `util::cache`); `force` always regenerates it.
+/// Dry runs never read or update the cache.
+///
+/// # Arguments
+/// * `cfg` - The global configuration containing all flags and runner
+/// * `flavour` - Target whose `vk` and `vk.hash` are checked
+/// * `bytecode` - Bytecode file the vk is derived from
+/// * `args` - Arguments to pass to bb
+/// * `force` - Regenerate the vk even if the cache is current
+///
+/// # Returns
+/// * `Result<()>` - Success or error from command execution
+pub fn run_bb_write_vk(
+    cfg: &Config,
+    flavour: Flavour,
+    bytecode: &Path,
+    args: &[&str],
+    force: bool,
+) -> Result<()> {
+    if cfg.dry_run {
+        return run_tool(cfg, "bb", args);
+    }
+
+    let vk_path = util::get_vk_path(flavour);
+    let hash_path = util::vk_hash_path(flavour);
+    let fingerprint = util::vk_fingerprint(bytecode, args)?;
+
+    if !force && util::is_vk_cached(&vk_path, &hash_path, &fingerprint) {
+        if !cfg.quiet {
+            info!(
+                "Bytecode unchanged, reusing verification key {}",
+                vk_path.display()
+            );
+        }
+        return Ok(());
+    }
+
+    run_tool(cfg, "bb", args)?;
+    util::apply_artifact_names(cfg.fs.as_ref(), flavour)?;
+    util::store_vk_fingerprint(&hash_path, &fingerprint)
 }
 
-/// Run any external tool and capture its stdout
+/// Run any external tool and capture its output
 ///
 /// This is the unified helper for executing external tools that need to capture output.
-/// It handles the same features as `run_tool` but returns the stdout as a string.
+/// It handles the same features as `run_tool` but returns the captured stdout and stderr.
+/// Failures carry the same `Command execution failed: <tool> <args>` context.
 ///
 /// # Arguments
 /// * `cfg` - The global configuration containing all flags and runner
@@ -188,17 +586,18 @@ pub fn run_tool(cfg: &Config, tool: &str, args: &[&str]) -> Result<()> {
 /// * `args` - Arguments to pass to the tool
 ///
 /// # Returns
-/// * `Result<String>` - Stdout from command execution or error
+/// * `Result<CapturedOutput>` - Captured output from command execution or error
 ///
 /// # Example
 /// ```ignore
 /// // Execute "garaga calldata ..." and capture output
 /// let output = run_tool_capture(&config, "garaga", &["calldata", "--system", "ultra_starknet_zk_honk"])?;
+/// println!("{}", output.stdout);
 /// ```
-pub fn run_tool_capture(cfg: &Config, tool: &str, args: &[&str]) -> Result<String> {
+pub fn run_tool_capture(cfg: &Config, tool: &str, args: &[&str]) -> Result<CapturedOutput> {
     let args_vec: Vec<String> = args.iter().map(|s| s.to_string()).collect();
 
-    if cfg.verbose && !cfg.quiet {
+    if cfg.is_verbose() && !cfg.quiet {
         info!(
             "Running (capturing output): {} {}",
             tool,
@@ -207,13 +606,42 @@ pub fn run_tool_capture(cfg: &Config, tool: &str, args: &[&str]) -> Result<Strin
     }
 
     // Create command specification for the tool
-    let spec = CmdSpec::new(tool.to_string(), args_vec);
+    let spec = tool_spec(cfg, tool, args_vec, None);
 
     // Use the runner to execute the command and capture output
-    cfg.runner.run_capture(&spec)
+    with_command_context(&spec, cfg.runner.run_capture(&spec))
+}
+
+/// Run `bb verify` and report whether bb accepted the proof
+///
+/// Used when a rejected proof is the expected outcome (`verify --expect false`).
+/// Output is captured so that bb failing the proof is returned as `Ok(false)`
+/// rather than an error, and bb versions that print `verified: 0` while exiting
+/// zero are treated as rejections too. Failures that say nothing about the
+/// proof, such as a missing bb binary or a timeout, are still errors.
+pub fn run_bb_verify(cfg: &Config, args: &[&str]) -> Result<bool> {
+    match run_tool_capture(cfg, "bb", args) {
+        Ok(output) => Ok(!reports_rejection(&output.stdout) && !reports_rejection(&output.stderr)),
+        Err(e) => match util::find_bargo_error(&e) {
+            Some(BargoError::CommandFailed { code, stderr, .. }) => {
+                debug!(
+                    "bb verify rejected the proof (exit code {code:?}): {}",
+                    stderr.trim()
+                );
+                Ok(false)
+            }
+            _ => Err(e),
+        },
+    }
+}
+
+/// Whether bb output reports a failed verification
+fn reports_rejection(output: &str) -> bool {
+    let output = output.to_lowercase();
+    output.contains("verified: 0") || output.contains("verification failed")
 }
 
-/// Run any external tool in a specific working directory and capture its stdout
+/// Run any external tool in a specific working directory and capture its output
 ///
 /// This is similar to `run_tool_capture` but allows specifying a working directory
 /// for the command execution. If `working_dir` is None, behaves like `run_tool_capture`.
@@ -225,16 +653,16 @@ pub fn run_tool_capture(cfg: &Config, tool: &str, args: &[&str]) -> Result<Strin
 /// * `working_dir` - Optional working directory for command execution
 ///
 /// # Returns
-/// * `Result<String>` - Stdout from command execution or error
+/// * `Result<CapturedOutput>` - Captured output from command execution or error
 pub fn run_tool_capture_in_directory(
     cfg: &Config,
     tool: &str,
     args: &[&str],
     working_dir: Option<&Path>,
-) -> Result<String> {
+) -> Result<CapturedOutput> {
     let args_vec: Vec<String> = args.iter().map(|s| s.to_string()).collect();
 
-    if cfg.verbose && !cfg.quiet {
+    if cfg.is_verbose() && !cfg.quiet {
         info!(
             "Running (capturing output): {} {}",
             tool,
@@ -243,13 +671,364 @@ pub fn run_tool_capture_in_directory(
     }
 
     // Create command specification for the tool
-    let mut spec = CmdSpec::new(tool.to_string(), args_vec);
+    let spec = tool_spec(cfg, tool, args_vec, working_dir);
 
-    // Set working directory if provided
-    if let Some(dir) = working_dir {
-        spec = spec.with_cwd(dir.to_path_buf());
+    // Use the runner to execute the command and capture output
+    with_command_context(&spec, cfg.runner.run_capture(&spec))
+}
+
+/// First line of `<tool> --version`, or `None` if the tool cannot report one
+pub fn tool_version(cfg: &Config, tool: &str) -> Option<String> {
+    run_tool_capture(cfg, tool, &["--version"])
+        .ok()
+        .and_then(|output| {
+            output
+                .stdout
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_string)
+        })
+}
+
+/// Record the artifacts an operation produced in a provenance manifest
+///
+/// Each artifact is hashed into `manifest` (see `util::manifest`) together
+/// with the versions of `tools`. Dry runs write no artifacts, so nothing is
+/// recorded.
+///
+/// # Arguments
+/// * `cfg` - The global configuration containing all flags and runner
+/// * `manifest` - Manifest to update, usually `target/<flavour>/manifest.json`
+/// * `operation` - Operation name stored with each entry (e.g. `evm prove`)
+/// * `artifacts` - Files the operation wrote
+/// * `tools` - Tools whose versions are recorded
+///
+/// # Returns
+/// * `Result<()>` - Success or error reading artifacts or writing the manifest
+pub fn record_manifest(
+    cfg: &Config,
+    manifest: &Path,
+    operation: &str,
+    artifacts: &[PathBuf],
+    tools: &[&str],
+) -> Result<()> {
+    if cfg.dry_run {
+        return Ok(());
     }
 
-    // Use the runner to execute the command and capture output
-    cfg.runner.run_capture(&spec)
+    util::write_manifest(manifest, operation, artifacts, &tool_versions(cfg, tools))
+}
+
+/// Versions of the `tools` that can report one, by tool name
+fn tool_versions(cfg: &Config, tools: &[&str]) -> BTreeMap<String, String> {
+    tools
+        .iter()
+        .filter_map(|tool| tool_version(cfg, tool).map(|version| (tool.to_string(), version)))
+        .collect()
+}
+
+/// Commit checked out in the project, or `None` outside a git repository
+///
+/// A missing git binary or a directory git does not track is not an error:
+/// the proof metadata simply records no commit.
+pub fn git_commit(cfg: &Config) -> Option<String> {
+    match run_tool_capture(cfg, "git", &["rev-parse", "HEAD"]) {
+        Ok(output) => util::parse_git_commit(&output.stdout),
+        Err(e) => {
+            debug!("No git commit for the proof metadata: {e}");
+            None
+        }
+    }
+}
+
+/// Write the metadata sidecar next to the proof of `flavour`
+///
+/// Records the package, git commit, versions of `tools` and the current time
+/// in `<proof>.meta.json` (see `util::meta`). Dry runs write no proof, so
+/// nothing is recorded.
+///
+/// # Arguments
+/// * `cfg` - The global configuration containing all flags and runner
+/// * `flavour` - Target directory holding the proof
+/// * `pkg_name` - Package the proof was generated for
+/// * `tools` - Tools whose versions are recorded
+///
+/// # Returns
+/// * `Result<()>` - Success or error writing the sidecar
+pub fn record_proof_meta(
+    cfg: &Config,
+    flavour: Flavour,
+    pkg_name: &str,
+    tools: &[&str],
+) -> Result<()> {
+    if cfg.dry_run {
+        return Ok(());
+    }
+
+    let meta = util::ProofMeta::new(pkg_name, git_commit(cfg), tool_versions(cfg, tools));
+    util::write_meta(&util::meta_path(&util::get_proof_path(flavour)), &meta)
+}
+
+/// Run a network-facing tool and capture its output, retrying transient failures
+///
+/// The command is marked retryable and re-run according to the `--retries`
+/// setting with exponential backoff. Environment values are never included in
+/// verbose logging, so this is also the helper for passing secrets such as
+/// private keys.
+///
+/// # Arguments
+/// * `cfg` - The global configuration containing all flags and runner
+/// * `tool` - The tool command to run (forge, cast, starkli)
+/// * `args` - Arguments to pass to the tool
+/// * `env` - Environment variables to set for the tool (key, value pairs)
+///
+/// # Returns
+/// * `Result<CapturedOutput>` - Captured output from the first successful attempt or error
+pub fn run_tool_capture_with_retry(
+    cfg: &Config,
+    tool: &str,
+    args: &[&str],
+    env: &[(&str, &str)],
+) -> Result<CapturedOutput> {
+    let args_vec: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+
+    if cfg.is_verbose() && !cfg.quiet {
+        info!(
+            "Running (capturing output): {} {}",
+            tool,
+            args_vec.join(" ")
+        );
+    }
+
+    let spec = tool_spec(cfg, tool, args_vec, None)
+        .with_envs(
+            env.iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        )
+        .retryable();
+
+    let result = cfg
+        .runner
+        .run_with_retry(&spec, &RetryPolicy::from_retries(cfg.retries));
+    with_command_context(&spec, result)
+}
+
+/// Run a command once for every member of the current Nargo workspace
+///
+/// Each invocation receives a copy of the configuration with `pkg` set to the
+/// member's package name. Outside a workspace the command runs once with the
+/// configuration unchanged, so `--all` is harmless in single-package projects.
+/// A failing member stops the rest unless `--keep-going` is set.
+pub fn run_for_each_member(cfg: &Config, command: impl Fn(&Config) -> Result<()>) -> Result<()> {
+    let nargo_toml = util::find_project_root(&cfg.working_dir()?)?.join("Nargo.toml");
+
+    if util::detect_nargo_toml_kind(&nargo_toml)? != NargoTomlKind::Workspace {
+        return command(cfg);
+    }
+
+    run_steps(
+        cfg,
+        util::list_workspace_members(&nargo_toml)?,
+        |member| format!("package {}", member.name),
+        |member| {
+            if !cfg.quiet {
+                println!("📦 {}", member.name);
+            }
+            let member_cfg = Config {
+                pkg: Some(member.name),
+                ..cfg.clone()
+            };
+            command(&member_cfg)
+        },
+    )
+}
+
+/// Run `step` on each item in order, honouring `--keep-going`
+///
+/// By default the first failure stops the batch. With `--keep-going` the
+/// remaining items still run and every failure is reported together at the
+/// end, so the command still exits non-zero.
+///
+/// # Arguments
+/// * `cfg` - The global configuration containing the keep-going policy
+/// * `items` - Independent steps, such as backends or workspace members
+/// * `name` - Label for an item in warnings and the failure report
+/// * `step` - Work to run for each item
+pub fn run_steps<T>(
+    cfg: &Config,
+    items: Vec<T>,
+    name: impl Fn(&T) -> String,
+    mut step: impl FnMut(T) -> Result<()>,
+) -> Result<()> {
+    let total = items.len();
+    let mut failures = Vec::new();
+
+    for item in items {
+        let label = name(&item);
+        match step(item) {
+            Ok(()) => {}
+            Err(e) if cfg.keep_going => {
+                warn!("{label} failed, continuing because of --keep-going: {e:#}");
+                failures.push((label, e));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    match failures.len() {
+        0 => Ok(()),
+        1 => {
+            let (label, error) = failures.remove(0);
+            Err(error).wrap_err(format!("{label} failed"))
+        }
+        failed => {
+            let details = failures
+                .iter()
+                .map(|(label, error)| format!("  • {label}: {error:#}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Err(color_eyre::eyre::eyre!(
+                "{failed} of {total} steps failed:\n{details}"
+            ))
+        }
+    }
+}
+
+/// Make the user confirm an operation that spends funds on mainnet
+///
+/// Other networks, dry runs and `--yes` pass straight through. Otherwise the
+/// user has to type the network name at the prompt; in JSON mode or without
+/// an interactive terminal there is nobody to ask, so `--yes` is required.
+///
+/// # Arguments
+/// * `cfg` - The global configuration (dry-run and JSON mode)
+/// * `network` - Network the operation targets
+/// * `yes` - Whether `--yes` was given
+/// * `action` - What is about to happen, e.g. "deploy the verifier contract"
+pub fn confirm_mainnet(cfg: &Config, network: &Network, yes: bool, action: &str) -> Result<()> {
+    let interactive = !cfg.json && std::io::stdin().is_terminal();
+    check_mainnet_confirmation(network, yes || cfg.dry_run, interactive, action, || {
+        print!(
+            "⚠️  This will {action} on mainnet and spend real funds.\nType 'mainnet' to confirm: "
+        );
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        Ok(answer)
+    })
+}
+
+/// Decide whether a mainnet operation may go ahead, asking through `ask`
+fn check_mainnet_confirmation(
+    network: &Network,
+    confirmed: bool,
+    interactive: bool,
+    action: &str,
+    ask: impl FnOnce() -> Result<String>,
+) -> Result<()> {
+    if *network != Network::Mainnet || confirmed {
+        return Ok(());
+    }
+
+    if !interactive {
+        return Err(util::create_smart_error(
+            &format!("Refusing to {action} on mainnet without confirmation"),
+            &[
+                "Re-run with --yes to confirm the mainnet operation",
+                "Or run bargo from an interactive terminal to be prompted",
+            ],
+        ));
+    }
+
+    if ask()?.trim() != Network::Mainnet.name() {
+        return Err(util::create_smart_error(
+            "Mainnet operation not confirmed",
+            &["Type the network name exactly (mainnet) to confirm, or pass --yes"],
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mainnet_needs_the_network_name_typed() {
+        let ask = |answer: &'static str| move || Ok(answer.to_string());
+        let confirm = |network: &Network, confirmed, interactive, answer| {
+            check_mainnet_confirmation(network, confirmed, interactive, "deploy", ask(answer))
+        };
+
+        // Only mainnet asks, and --yes or a dry run skips the question
+        assert!(confirm(&Network::Sepolia, false, false, "").is_ok());
+        assert!(confirm(&Network::Mainnet, true, false, "").is_ok());
+
+        assert!(confirm(&Network::Mainnet, false, true, "mainnet\n").is_ok());
+        let error = confirm(&Network::Mainnet, false, true, "y\n").unwrap_err();
+        assert!(error.to_string().contains("not confirmed"), "{error}");
+
+        let error = confirm(&Network::Mainnet, false, false, "mainnet\n").unwrap_err();
+        assert!(
+            error.to_string().contains("without confirmation"),
+            "{error}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_context_masks_secret_arguments() {
+        let args = ["-c", "exit 1", "forge", "--private-key", "0xdeadbeefSECRET"];
+        let spec = CmdSpec::new(
+            "sh".to_string(),
+            args.iter().map(|arg| arg.to_string()).collect(),
+        );
+        use crate::runner::{RealRunner, Runner};
+        let runner = RealRunner::new();
+        let err = with_command_context(&spec, runner.run_capture(&spec)).unwrap_err();
+
+        let message = format!("{err:#}");
+        assert!(!message.contains("0xdeadbeefSECRET"), "{message}");
+        assert!(message.contains("--private-key ***"), "{message}");
+    }
+
+    #[test]
+    fn test_bb_recursion_args_name_each_flag_once() {
+        let friendly = ProveConfig::default().with_recursive_friendly(true);
+        let passthrough =
+            |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert!(bb_recursion_args(&ProveConfig::default(), None, &[]).is_empty());
+        assert_eq!(
+            bb_recursion_args(&friendly, Some(BB_RECURSIVE_OUTPUT_FORMAT), &[]),
+            [
+                "--recursive",
+                "--honk_recursion",
+                "1",
+                "--output_format",
+                "bytes_and_fields"
+            ]
+        );
+
+        // Flags the user passes through are not repeated
+        assert_eq!(
+            bb_recursion_args(
+                &friendly,
+                Some(ProofOutputFormat::Fields),
+                &passthrough(&["--output_format=bytes", "--honk_recursion", "1"]),
+            ),
+            ["--recursive"]
+        );
+        assert_eq!(
+            bb_recursion_args(
+                &friendly,
+                None,
+                &passthrough(&["--recursive", "--recursive_extra"])
+            ),
+            ["--honk_recursion", "1"]
+        );
+    }
 }
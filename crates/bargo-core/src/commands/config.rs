@@ -0,0 +1,189 @@
+//! Read and write project defaults in `bargo.toml`
+//!
+//! `bargo config get <key>`, `set <key> <value>` and `list` edit the
+//! `bargo.toml` next to the project's Nargo.toml. Keys use the dotted
+//! `<section>.<name>` form (`evm.network`, `tools.bb`); only the settings in
+//! [`PROJECT_SETTINGS`] are accepted, and values are validated before the file
+//! is written.
+
+use color_eyre::Result;
+use serde_json::json;
+use std::path::PathBuf;
+
+use crate::{
+    config::{
+        Config, PROJECT_CONFIG_FILE, PROJECT_SETTINGS, ProjectSetting, get_project_setting,
+        set_project_setting,
+    },
+    util::{self, success},
+};
+
+/// `bargo.toml` of the project containing the working directory
+fn config_path(cfg: &Config) -> Result<PathBuf> {
+    Ok(util::find_project_root(&cfg.working_dir()?)?.join(PROJECT_CONFIG_FILE))
+}
+
+/// Print the value of `key`, failing if it is not set
+pub fn get(cfg: &Config, key: &str) -> Result<()> {
+    let setting = ProjectSetting::find(key)?;
+    let path = config_path(cfg)?;
+    let value = get_project_setting(&path, setting)?;
+
+    if cfg.json {
+        println!("{}", json!({ "key": setting.key, "value": value }));
+        return Ok(());
+    }
+
+    match value {
+        Some(value) => {
+            println!("{value}");
+            Ok(())
+        }
+        None => Err(util::create_smart_error(
+            &format!("{} is not set in {}", setting.key, path.display()),
+            &[&format!(
+                "Set it with 'bargo config set {} <value>'",
+                setting.key
+            )],
+        )),
+    }
+}
+
+/// Validate `value` and store it as `key`
+pub fn set(cfg: &Config, key: &str, value: &str) -> Result<()> {
+    let setting = ProjectSetting::find(key)?;
+    let path = config_path(cfg)?;
+
+    if cfg.dry_run {
+        let value = setting.validate(value)?;
+        println!(
+            "Would set {} = \"{value}\" in {}",
+            setting.key,
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let value = set_project_setting(&path, setting, value)?;
+    if cfg.json {
+        println!("{}", json!({ "key": setting.key, "value": value }));
+    } else if !cfg.quiet {
+        println!(
+            "{}",
+            success(&format!(
+                "Set {} = \"{value}\" in {}",
+                setting.key,
+                path.display()
+            ))
+        );
+    }
+    Ok(())
+}
+
+/// Print every known key with its current value
+pub fn list(cfg: &Config) -> Result<()> {
+    let path = config_path(cfg)?;
+    let values = PROJECT_SETTINGS
+        .iter()
+        .map(|setting| Ok((setting, get_project_setting(&path, setting)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    if cfg.json {
+        let object: serde_json::Map<String, serde_json::Value> = values
+            .into_iter()
+            .map(|(setting, value)| (setting.key.to_string(), json!(value)))
+            .collect();
+        println!("{}", serde_json::Value::Object(object));
+        return Ok(());
+    }
+
+    if !cfg.quiet {
+        println!("📄 {}", path.display());
+        for (setting, value) in values {
+            let value =
+                value.map_or_else(|| "(not set)".to_string(), |value| format!("\"{value}\""));
+            println!(
+                "  {:<14} {:<24} {}",
+                setting.key, value, setting.description
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project() -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Nargo.toml"),
+            "[package]\nname = \"config_pkg\"\ntype = \"bin\"\n",
+        )
+        .unwrap();
+        let path = temp_dir.path().join(PROJECT_CONFIG_FILE);
+        (temp_dir, path)
+    }
+
+    #[test]
+    fn test_set_then_get_round_trips_and_keeps_comments() {
+        let (temp_dir, path) = project();
+        std::fs::write(&path, "# team defaults\n[evm]\nnetwork = \"mainnet\"\n").unwrap();
+
+        let evm = ProjectSetting::find("evm.network").unwrap();
+        let bb = ProjectSetting::find("tools.bb").unwrap();
+        assert_eq!(
+            set_project_setting(&path, evm, "Sepolia").unwrap(),
+            "sepolia"
+        );
+        set_project_setting(&path, bb, "tools/bb").unwrap();
+
+        assert_eq!(
+            get_project_setting(&path, evm).unwrap().as_deref(),
+            Some("sepolia")
+        );
+        assert_eq!(
+            get_project_setting(&path, bb).unwrap().as_deref(),
+            Some("tools/bb")
+        );
+        let pkg = ProjectSetting::find("defaults.pkg").unwrap();
+        assert_eq!(get_project_setting(&path, pkg).unwrap(), None);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# team defaults\n"), "{content}");
+
+        // The file still loads as the project config
+        let project = crate::config::load_project_config(temp_dir.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(project.evm.network.as_deref(), Some("sepolia"));
+        assert_eq!(project.tools.bb, Some(temp_dir.path().join("tools/bb")));
+    }
+
+    #[test]
+    fn test_invalid_keys_and_values_are_rejected() {
+        let (_temp_dir, path) = project();
+
+        let error = ProjectSetting::find("evm.netwrok").unwrap_err();
+        let message = format!("{error:?}");
+        assert!(
+            message.contains("Unknown bargo.toml key 'evm.netwrok'"),
+            "{message}"
+        );
+        assert!(message.contains("Did you mean 'evm.network'?"), "{message}");
+        assert!(ProjectSetting::find("network").is_err());
+
+        let evm = ProjectSetting::find("evm.network").unwrap();
+        assert!(set_project_setting(&path, evm, "sepolai").is_err());
+        let pkg = ProjectSetting::find("defaults.pkg").unwrap();
+        assert!(set_project_setting(&path, pkg, "my pkg").is_err());
+        assert!(!path.exists());
+
+        // A key whose section is not a table is reported, not overwritten
+        std::fs::write(&path, "evm = \"sepolia\"\n").unwrap();
+        let error = set_project_setting(&path, evm, "mainnet").unwrap_err();
+        assert!(error.to_string().contains("[evm]"), "{error}");
+    }
+}
@@ -0,0 +1,299 @@
+//! `bargo convert`: switch a proof between bb's byte and field encodings
+//!
+//! bb writes a proof either as `proof`, the field elements concatenated as
+//! 32-byte big-endian words, or as `proof_fields.json`, a JSON array of the
+//! same elements as `0x`-prefixed hex strings. Both carry the same data, so
+//! converting only re-encodes the file; nothing is proved again.
+//!
+//! ```text
+//! bargo convert --to fields                  # target/evm/proof -> target/evm/proof_fields.json
+//! bargo convert --to bytes -i fields.json -o proof
+//! ```
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    cli::ProofEncoding,
+    config::Config,
+    util::{self, Flavour, create_smart_error, success},
+};
+
+/// Size of one field element in the byte encoding
+const FIELD_BYTES: usize = 32;
+
+/// Convert the proof at `input` to the `to` encoding and write it to `output`
+pub fn run(
+    cfg: &Config,
+    input: Option<&Path>,
+    output: Option<&Path>,
+    to: ProofEncoding,
+) -> Result<()> {
+    let (input, output) = resolve_paths(input, output, to);
+
+    if input == output {
+        return Err(create_smart_error(
+            &format!("Refusing to convert {} onto itself", input.display()),
+            &["Pass --output to write the converted proof elsewhere"],
+        ));
+    }
+    util::validate_files_exist(std::slice::from_ref(&input))
+        .map_err(util::enhance_error_with_suggestions)?;
+
+    if cfg.dry_run {
+        if !cfg.quiet {
+            println!(
+                "Would convert {} to {} ({})",
+                input.display(),
+                output.display(),
+                to.file_name()
+            );
+        }
+        return Ok(());
+    }
+
+    let contents = match to {
+        ProofEncoding::Fields => {
+            let bytes = std::fs::read(&input)
+                .wrap_err_with(|| format!("reading proof file {}", input.display()))?;
+            let fields = bytes_to_fields(&bytes)?;
+            util::record_json_detail("fields", fields.len());
+            serde_json::to_vec_pretty(&fields)?
+        }
+        ProofEncoding::Bytes => {
+            let fields = read_fields(&input)?;
+            util::record_json_detail("fields", fields.len());
+            fields_to_bytes(&fields)?
+        }
+    };
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)
+            .wrap_err_with(|| format!("creating directory {}", parent.display()))?;
+    }
+    std::fs::write(&output, contents)
+        .wrap_err_with(|| format!("writing converted proof {}", output.display()))?;
+
+    if !cfg.quiet {
+        println!(
+            "{}",
+            success(&format!(
+                "Converted {} to {}",
+                input.display(),
+                output.display()
+            ))
+        );
+    }
+
+    Ok(())
+}
+
+/// Input and output paths, filling in the defaults
+///
+/// The input defaults to the EVM proof in the encoding being converted from,
+/// and the output to bb's file name for `to` next to the input.
+pub fn resolve_paths(
+    input: Option<&Path>,
+    output: Option<&Path>,
+    to: ProofEncoding,
+) -> (PathBuf, PathBuf) {
+    let input = match input {
+        Some(path) => util::project_path(path),
+        None => util::target_dir(Flavour::Evm).join(to.other().file_name()),
+    };
+    let output = match output {
+        Some(path) => util::project_path(path),
+        None => input.parent().unwrap_or(Path::new("")).join(to.file_name()),
+    };
+    (input, output)
+}
+
+/// Read a `proof_fields.json` array
+pub fn read_fields(path: &Path) -> Result<Vec<String>> {
+    let json = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("reading proof fields {}", path.display()))?;
+    serde_json::from_str(&json).map_err(|e| {
+        create_smart_error(
+            &format!(
+                "{} is not a JSON array of field elements: {e}",
+                path.display()
+            ),
+            &["Pass the proof_fields.json written by `bb prove --output_format fields`"],
+        )
+    })
+}
+
+/// Split a byte-encoded proof into `0x`-prefixed field elements
+pub fn bytes_to_fields(bytes: &[u8]) -> Result<Vec<String>> {
+    if !bytes.len().is_multiple_of(FIELD_BYTES) {
+        return Err(create_smart_error(
+            &format!(
+                "Proof length {} is not a multiple of {FIELD_BYTES} bytes",
+                bytes.len()
+            ),
+            &["Pass the binary proof written by bb, not a compressed or JSON file"],
+        ));
+    }
+
+    Ok(bytes
+        .chunks(FIELD_BYTES)
+        .map(|chunk| format!("0x{}", hex::encode(chunk)))
+        .collect())
+}
+
+/// Encode field elements as 32-byte big-endian words
+///
+/// Elements may omit the `0x` prefix and leading zeros.
+pub fn fields_to_bytes(fields: &[String]) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(fields.len() * FIELD_BYTES);
+    for (index, field) in fields.iter().enumerate() {
+        let digits = field.strip_prefix("0x").unwrap_or(field);
+        let invalid = |reason: &str| {
+            create_smart_error(
+                &format!("Field element {index} ({field}) {reason}"),
+                &["Each element must be a hex string of at most 32 bytes"],
+            )
+        };
+        if digits.len() > FIELD_BYTES * 2 {
+            return Err(invalid("is longer than 32 bytes"));
+        }
+        let padded = format!("{digits:0>width$}", width = FIELD_BYTES * 2);
+        let word = hex::decode(&padded).map_err(|_| invalid("is not valid hex"))?;
+        bytes.extend_from_slice(&word);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{ArtifactSuffix, ColorChoice};
+    use crate::config::ToolPaths;
+    use crate::fs::DryRunFs;
+    use crate::runner::DryRunRunner;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_bytes_and_fields_round_trip() {
+        let mut bytes = vec![0u8; 64];
+        bytes[31] = 1;
+        bytes[32] = 0xff;
+
+        let fields = bytes_to_fields(&bytes).unwrap();
+        assert_eq!(
+            fields,
+            [
+                format!("0x{}01", "0".repeat(62)),
+                format!("0xff{}", "0".repeat(62)),
+            ]
+        );
+        assert_eq!(fields_to_bytes(&fields).unwrap(), bytes);
+
+        // Short elements are left-padded like any big-endian number
+        let short = fields_to_bytes(&["0x1".to_string(), "2a".to_string()]).unwrap();
+        assert_eq!(short.len(), 64);
+        assert_eq!((short[31], short[63]), (1, 0x2a));
+    }
+
+    #[test]
+    fn test_malformed_proofs_are_rejected() {
+        let error = bytes_to_fields(&[0; 33]).unwrap_err().to_string();
+        assert!(error.contains("not a multiple of 32 bytes"), "{error}");
+
+        let error = fields_to_bytes(&["0xzz".to_string()])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Field element 0 (0xzz) is not valid hex"),
+            "{error}"
+        );
+
+        let error = fields_to_bytes(&[format!("0x1{}", "0".repeat(64))])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("longer than 32 bytes"), "{error}");
+    }
+
+    #[test]
+    fn test_default_paths_follow_target_encoding() {
+        let (input, output) = resolve_paths(None, None, ProofEncoding::Fields);
+        assert_eq!(input, Path::new("target/evm/proof"));
+        assert_eq!(output, Path::new("target/evm/proof_fields.json"));
+
+        let (input, output) = resolve_paths(None, None, ProofEncoding::Bytes);
+        assert_eq!(input, Path::new("target/evm/proof_fields.json"));
+        assert_eq!(output, Path::new("target/evm/proof"));
+
+        let (input, output) = resolve_paths(
+            Some(Path::new("exports/fields.json")),
+            Some(Path::new("out/proof.bin")),
+            ProofEncoding::Bytes,
+        );
+        assert_eq!(input, Path::new("exports/fields.json"));
+        assert_eq!(output, Path::new("out/proof.bin"));
+
+        let (_, output) =
+            resolve_paths(Some(Path::new("other/proof")), None, ProofEncoding::Fields);
+        assert_eq!(output, Path::new("other/proof_fields.json"));
+    }
+
+    #[test]
+    fn test_dry_run_resolves_paths_without_writing() {
+        let temp_dir = tempdir().unwrap();
+        let runner = Arc::new(DryRunRunner::new());
+        let cfg = Config {
+            verbose: 0,
+            dry_run: true,
+            pkg: None,
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: Some(PathBuf::from("artifacts")),
+            package_dir: Some(temp_dir.path().to_path_buf()),
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: runner.clone(),
+            fs: Arc::new(DryRunFs::new()),
+        };
+        cfg.apply_paths().unwrap();
+
+        // The input must exist even in a dry run
+        let error = run(&cfg, None, None, ProofEncoding::Fields)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("artifacts/evm/proof"), "{error}");
+
+        let proof = temp_dir.path().join("artifacts/evm/proof");
+        std::fs::create_dir_all(proof.parent().unwrap()).unwrap();
+        std::fs::write(&proof, [0u8; 32]).unwrap();
+        run(&cfg, None, None, ProofEncoding::Fields).unwrap();
+
+        assert!(
+            !temp_dir
+                .path()
+                .join("artifacts/evm/proof_fields.json")
+                .exists()
+        );
+        assert!(runner.history().is_empty());
+
+        let error = run(
+            &cfg,
+            None,
+            Some(Path::new("artifacts/evm/proof")),
+            ProofEncoding::Fields,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("onto itself"), "{error}");
+
+        util::set_target_base(None);
+        util::set_project_dir(None);
+    }
+}
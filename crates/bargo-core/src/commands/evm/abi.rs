@@ -0,0 +1,262 @@
+//! ABI extraction for the generated Solidity verifier
+//!
+//! `bb write_solidity_verifier` emits a contract exposing
+//! `verify(bytes calldata proof, bytes32[] calldata publicInputs)`. Rather than
+//! hard-coding that shape, the calldata workflow reads the signature back out
+//! of `Verifier.sol` so the encoded calldata always matches the contract that
+//! was actually generated. The parse is deliberately minimal: comments are
+//! stripped and the first `function verify(...)` declaration is read.
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    commands::common,
+    config::Config,
+    util::{self, Flavour},
+};
+
+/// Path of the extracted ABI (`target/evm/abi.json`)
+pub fn abi_path() -> PathBuf {
+    util::target_dir(Flavour::Evm).join("abi.json")
+}
+
+/// A named, typed parameter in an ABI entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AbiParam {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// The verifier's `verify` function as declared in `Verifier.sol`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerifyFunction {
+    pub inputs: Vec<AbiParam>,
+    pub outputs: Vec<AbiParam>,
+    #[serde(rename = "stateMutability")]
+    pub state_mutability: String,
+}
+
+impl VerifyFunction {
+    /// Canonical signature as accepted by `cast`, e.g. `verify(bytes,bytes32[])`
+    pub fn signature(&self) -> String {
+        let types: Vec<&str> = self.inputs.iter().map(|param| param.ty.as_str()).collect();
+        format!("verify({})", types.join(","))
+    }
+
+    /// JSON ABI for a contract exposing only this function
+    pub fn abi(&self) -> serde_json::Value {
+        serde_json::json!([{
+            "type": "function",
+            "name": "verify",
+            "inputs": self.inputs,
+            "outputs": self.outputs,
+            "stateMutability": self.state_mutability,
+        }])
+    }
+}
+
+/// Find the `verify` function declaration in Solidity source
+///
+/// Returns `None` if no `function verify(...)` declaration is present.
+pub fn extract_verify_function(source: &str) -> Option<VerifyFunction> {
+    let source = strip_comments(source);
+    let start = source.find("function verify(")? + "function verify(".len();
+    let (params, rest) = source[start..].split_once(')')?;
+
+    // Modifiers run up to the body or, for interface declarations, the `;`
+    let header_end = rest.find(['{', ';']).unwrap_or(rest.len());
+    let header = &rest[..header_end];
+    let (modifiers, outputs) = match header.split_once("returns") {
+        Some((modifiers, returns)) => {
+            let returns = returns.trim().strip_prefix('(')?;
+            let (outputs, _) = returns.split_once(')')?;
+            (modifiers, parse_params(outputs))
+        }
+        None => (header, Vec::new()),
+    };
+
+    let state_mutability = ["pure", "view", "payable"]
+        .into_iter()
+        .find(|keyword| modifiers.split_whitespace().any(|word| word == *keyword))
+        .unwrap_or("nonpayable");
+
+    Some(VerifyFunction {
+        inputs: parse_params(params),
+        outputs,
+        state_mutability: state_mutability.to_string(),
+    })
+}
+
+/// Read `Verifier.sol` at `path` and extract its `verify` function
+pub fn read_verify_function(path: &Path) -> Result<Option<VerifyFunction>> {
+    let source = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("reading verifier contract {}", path.display()))?;
+    Ok(extract_verify_function(&source))
+}
+
+/// Write the ABI of `function` to `path`
+pub fn write_abi(path: &Path, function: &VerifyFunction) -> Result<()> {
+    util::ensure_parent_dir(path)?;
+    let json =
+        serde_json::to_string_pretty(&function.abi()).wrap_err("serializing verifier ABI")?;
+    std::fs::write(path, format!("{json}\n"))
+        .wrap_err_with(|| format!("writing verifier ABI to {}", path.display()))
+}
+
+/// ABI-encode a call to `function` with `cast calldata`
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+/// * `function` - Verifier function whose signature selects the call
+/// * `proof` - Hex-encoded proof bytes
+/// * `public_inputs` - Hex-encoded bytes32 public inputs
+///
+/// # Returns
+/// * `Result<String>` - `0x`-prefixed calldata, or error from cast
+pub fn encode_verify_call(
+    cfg: &Config,
+    function: &VerifyFunction,
+    proof: &str,
+    public_inputs: &[String],
+) -> Result<String> {
+    let signature = function.signature();
+    let public_inputs_arg = format!("[{}]", public_inputs.join(","));
+    let output = common::run_tool_capture(
+        cfg,
+        "cast",
+        &["calldata", &signature, proof, &public_inputs_arg],
+    )?;
+
+    let encoded = output.stdout.trim();
+    if !encoded.starts_with("0x") {
+        return Err(color_eyre::eyre::eyre!(
+            "Unexpected output from cast calldata: {encoded}"
+        ));
+    }
+    Ok(encoded.to_string())
+}
+
+/// Parse a comma-separated Solidity parameter list
+fn parse_params(params: &str) -> Vec<AbiParam> {
+    params
+        .split(',')
+        .filter_map(|param| {
+            let words: Vec<&str> = param
+                .split_whitespace()
+                .filter(|word| !matches!(*word, "memory" | "calldata" | "storage"))
+                .collect();
+            let (ty, name) = match words.as_slice() {
+                [ty] => (ty, ""),
+                [ty, name, ..] => (ty, *name),
+                [] => return None,
+            };
+            Some(AbiParam {
+                name: name.to_string(),
+                ty: ty.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Remove `//` and `/* */` comments so commented-out code is never matched
+fn strip_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut rest = source;
+    loop {
+        let (start, is_line_comment) = match (rest.find("//"), rest.find("/*")) {
+            (Some(line), Some(block)) if block < line => (block, false),
+            (Some(line), _) => (line, true),
+            (None, Some(block)) => (block, false),
+            (None, None) => {
+                stripped.push_str(rest);
+                return stripped;
+            }
+        };
+
+        stripped.push_str(&rest[..start]);
+        let comment = &rest[start..];
+        rest = if is_line_comment {
+            comment.find('\n').map_or("", |end| &comment[end..])
+        } else {
+            stripped.push(' ');
+            comment.find("*/").map_or("", |end| &comment[end + 2..])
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_VERIFIER: &str = r#"
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity >=0.8.21;
+
+/* function verify(uint256 ignored) external; */
+interface IVerifier {
+    function verify(bytes calldata _proof, bytes32[] calldata _publicInputs) external view returns (bool);
+}
+
+contract HonkVerifier is BaseHonkVerifier(N, LOG_N, NUMBER_OF_PUBLIC_INPUTS) {
+    // function verify(bytes memory old) public returns (bool)
+    function verify(bytes calldata proof, bytes32[] calldata publicInputs)
+        public
+        view
+        override
+        returns (bool verified)
+    {
+        return true;
+    }
+}
+"#;
+
+    #[test]
+    fn test_extract_verify_function_from_sample_verifier() {
+        let function = extract_verify_function(SAMPLE_VERIFIER).unwrap();
+
+        assert_eq!(function.signature(), "verify(bytes,bytes32[])");
+        assert_eq!(
+            function.inputs,
+            [
+                AbiParam {
+                    name: "_proof".to_string(),
+                    ty: "bytes".to_string()
+                },
+                AbiParam {
+                    name: "_publicInputs".to_string(),
+                    ty: "bytes32[]".to_string()
+                },
+            ]
+        );
+        assert_eq!(function.outputs[0].ty, "bool");
+        assert_eq!(function.state_mutability, "view");
+
+        let abi = function.abi();
+        assert_eq!(abi[0]["name"], "verify");
+        assert_eq!(abi[0]["inputs"][1]["type"], "bytes32[]");
+        assert_eq!(abi[0]["outputs"][0]["type"], "bool");
+        assert_eq!(abi[0]["stateMutability"], "view");
+    }
+
+    #[test]
+    fn test_extract_verify_function_reads_multiline_implementation() {
+        let source = "contract V {\n    function verify(bytes calldata proof, bytes32[] calldata publicInputs)\n        public\n        returns (bool verified)\n    {}\n}";
+        let function = extract_verify_function(source).unwrap();
+
+        assert_eq!(function.signature(), "verify(bytes,bytes32[])");
+        assert_eq!(function.outputs[0].name, "verified");
+        assert_eq!(function.state_mutability, "nonpayable");
+    }
+
+    #[test]
+    fn test_extract_verify_function_missing() {
+        assert!(extract_verify_function("contract Empty {}").is_none());
+        assert!(
+            extract_verify_function("// function verify(bytes calldata proof) external;").is_none()
+        );
+    }
+}
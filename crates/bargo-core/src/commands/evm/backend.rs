@@ -7,9 +7,12 @@ use color_eyre::Result;
 
 use crate::{
     backend::{Backend, BackendConfig},
-    config::Config,
+    config::{CalldataConfig, Config, FoundryConfig, ProveConfig, VerifyConfig},
 };
 
+#[cfg(feature = "evm-foundry")]
+use crate::config::EvmDeployConfig;
+
 #[cfg(not(feature = "evm-foundry"))]
 use crate::util::create_smart_error;
 
@@ -17,34 +20,48 @@ use super::workflow;
 
 /// EVM backend implementation for Ethereum-based proof systems
 #[derive(Debug)]
-pub struct EvmBackend;
+pub struct EvmBackend {
+    prove_config: ProveConfig,
+    calldata_config: CalldataConfig,
+    verify_config: VerifyConfig,
+    foundry_config: FoundryConfig,
+    #[cfg(feature = "evm-foundry")]
+    deploy_config: EvmDeployConfig,
+}
 
 impl EvmBackend {
     /// Create a new EVM backend instance
     pub fn new() -> Self {
-        Self
+        Self {
+            prove_config: ProveConfig::default(),
+            calldata_config: CalldataConfig::default(),
+            verify_config: VerifyConfig::default(),
+            foundry_config: FoundryConfig::default(),
+            #[cfg(feature = "evm-foundry")]
+            deploy_config: EvmDeployConfig::default(),
+        }
     }
 }
 
 impl Backend for EvmBackend {
     /// Generate Solidity verifier contract and setup Foundry project structure
     fn generate(&mut self, cfg: &Config) -> Result<()> {
-        workflow::run_gen(cfg)
+        workflow::run_gen_with_config(cfg, &self.prove_config, &self.foundry_config)
     }
 
     /// Generate proof using EVM/Keccak proof system
     fn prove(&mut self, cfg: &Config) -> Result<()> {
-        workflow::run_prove(cfg)
+        workflow::run_prove_with_config(cfg, &self.prove_config)
     }
 
     /// Verify a generated EVM proof
     fn verify(&mut self, cfg: &Config) -> Result<()> {
-        workflow::run_verify(cfg)
+        workflow::run_verify_with_config(cfg, &self.verify_config)
     }
 
     /// Generate calldata for EVM proof verification
     fn calldata(&mut self, cfg: &Config) -> Result<()> {
-        workflow::run_calldata(cfg)
+        workflow::run_calldata_with_config(cfg, &self.calldata_config)
     }
 
     /// Deploy Solidity verifier contract to EVM network
@@ -52,7 +69,7 @@ impl Backend for EvmBackend {
     fn deploy(&mut self, cfg: &Config, network: Option<&str>) -> Result<()> {
         // Use provided network or default to "sepolia"
         let network_str = network.unwrap_or("sepolia");
-        workflow::run_deploy(cfg, network_str)
+        workflow::run_deploy_with_config(cfg, network_str, &self.deploy_config)
     }
 
     /// Verify proof on-chain using deployed EVM verifier
@@ -87,10 +104,33 @@ impl Backend for EvmBackend {
     }
 
     /// Configure backend with backend-specific settings
-    fn configure(&mut self, _config: BackendConfig) -> Result<()> {
-        // EVM backend currently doesn't need any configuration
-        // This could be extended in the future for EVM-specific settings
-        Ok(())
+    fn configure(&mut self, config: BackendConfig) -> Result<()> {
+        match config {
+            BackendConfig::Prove(prove_config) => {
+                self.prove_config = prove_config;
+                Ok(())
+            }
+            BackendConfig::Calldata(calldata_config) => {
+                self.calldata_config = calldata_config;
+                Ok(())
+            }
+            BackendConfig::Verify(verify_config) => {
+                self.verify_config = verify_config;
+                Ok(())
+            }
+            BackendConfig::Foundry(foundry_config) => {
+                self.foundry_config = foundry_config;
+                Ok(())
+            }
+            #[cfg(feature = "evm-foundry")]
+            BackendConfig::EvmDeploy(deploy_config) => {
+                self.deploy_config = deploy_config;
+                Ok(())
+            }
+            // Cairo deploy and garaga settings don't apply to the EVM backend
+            #[cfg(feature = "cairo")]
+            BackendConfig::CairoDeploy(_) | BackendConfig::Garaga(_) => Ok(()),
+        }
     }
 }
 
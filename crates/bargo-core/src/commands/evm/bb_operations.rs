@@ -4,10 +4,11 @@
 //! specifically for EVM keccak oracle hash operations.
 
 use color_eyre::Result;
+use std::path::Path;
 
 use crate::{
     commands::common,
-    config::Config,
+    config::{Config, ProveConfig},
     util::{self, Flavour},
 };
 
@@ -16,57 +17,80 @@ use crate::{
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
 /// * `pkg` - Package name for locating bytecode and witness files
+/// * `prove_cfg` - Scheme (appended as `--scheme` when set), oracle hash
+///   override, recursion settings, `--output_format`, bb profile flags and
+///   passthrough arguments, which come last
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from BB execution
-pub fn generate_evm_proof(cfg: &Config, pkg: &str) -> Result<()> {
+pub fn generate_evm_proof(cfg: &Config, pkg: &str, prove_cfg: &ProveConfig) -> Result<()> {
     let bytecode = util::get_bytecode_path(pkg, Flavour::Bb);
-    let witness = util::get_witness_path(pkg, Flavour::Bb);
+    let witness = prove_cfg.witness_path(pkg);
     let vk_path = util::get_vk_path(Flavour::Evm);
+    let output_dir = util::target_dir(Flavour::Evm);
+    let bytecode_str = util::tool_path_string(&bytecode);
+    let witness_str = util::tool_path_string(&witness);
+    let vk_str = util::tool_path_string(&vk_path);
+    let output_str = util::tool_path_string(&output_dir);
 
-    common::run_tool(
-        cfg,
-        "bb",
-        &[
-            "prove",
-            "-b",
-            &bytecode.to_string_lossy(),
-            "-w",
-            &witness.to_string_lossy(),
-            "-o",
-            "./target/evm/",
-            "-k",
-            &vk_path.to_string_lossy(),
-            "-t",
-            "evm",
-        ],
-    )
+    let mut args = vec![
+        "prove",
+        "-b",
+        &bytecode_str,
+        "-w",
+        &witness_str,
+        "-o",
+        &output_str,
+        "-k",
+        &vk_str,
+    ];
+    args.extend(common::bb_oracle_args(Flavour::Evm, prove_cfg.oracle_hash));
+    if let Some(scheme) = prove_cfg.scheme {
+        args.extend(["--scheme", scheme.as_str()]);
+    }
+    args.extend(common::bb_recursion_args(
+        prove_cfg,
+        prove_cfg.bb_output_format(),
+        &prove_cfg.extra_args,
+    ));
+    if let Some(profile) = prove_cfg.bb_profile {
+        args.extend(profile.bb_args());
+    }
+    args.extend(prove_cfg.extra_args.iter().map(String::as_str));
+
+    common::run_tool(cfg, "bb", &args)?;
+    util::apply_artifact_names(cfg.fs.as_ref(), Flavour::Evm)
 }
 
 /// Generate an EVM-compatible verification key using BB
 ///
+/// The existing key is reused when the bytecode has not changed since it was
+/// written, unless `prove_cfg.force` is set.
+///
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
 /// * `pkg` - Package name for locating bytecode file
+/// * `prove_cfg` - Cache bypass, oracle hash override and recursion settings
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from BB execution
-pub fn generate_evm_vk(cfg: &Config, pkg: &str) -> Result<()> {
+pub fn generate_evm_vk(cfg: &Config, pkg: &str, prove_cfg: &ProveConfig) -> Result<()> {
     let bytecode = util::get_bytecode_path(pkg, Flavour::Bb);
+    let bytecode_str = util::tool_path_string(&bytecode);
+    let output_dir = util::target_dir(Flavour::Evm);
+    let output_str = util::tool_path_string(&output_dir);
 
-    common::run_tool(
-        cfg,
-        "bb",
-        &[
-            "write_vk",
-            "-b",
-            &bytecode.to_string_lossy(),
-            "-o",
-            "./target/evm/",
-            "-t",
-            "evm",
-        ],
-    )
+    let mut args = vec!["write_vk", "-b", &bytecode_str, "-o", &output_str];
+    args.extend(common::bb_oracle_args(Flavour::Evm, prove_cfg.oracle_hash));
+    args.extend(common::bb_recursion_args(
+        prove_cfg,
+        prove_cfg
+            .recursive
+            .then_some(common::BB_RECURSIVE_OUTPUT_FORMAT),
+        &[],
+    ));
+
+    common::run_bb_write_vk(cfg, Flavour::Evm, &bytecode, &args, prove_cfg.force)
 }
 
 /// Verify an EVM proof using BB
@@ -81,25 +105,67 @@ pub fn generate_evm_vk(cfg: &Config, pkg: &str) -> Result<()> {
 /// # Returns
 /// * `Result<()>` - Success or error from BB execution
 pub fn verify_evm_proof(cfg: &Config, _pkg: &str) -> Result<()> {
-    let proof_path = util::get_proof_path(Flavour::Evm);
-    let vk_path = util::get_vk_path(Flavour::Evm);
-    let public_inputs_path = util::get_public_inputs_path(Flavour::Evm);
-
-    common::run_tool(
+    verify_evm_proof_at(
         cfg,
-        "bb",
-        &[
-            "verify",
-            "-p",
-            &proof_path.to_string_lossy(),
-            "-k",
-            &vk_path.to_string_lossy(),
-            "-i",
-            &public_inputs_path.to_string_lossy(),
-            "-t",
-            "evm",
-        ],
+        &util::get_proof_path(Flavour::Evm),
+        &util::get_vk_path(Flavour::Evm),
+        &util::get_public_inputs_path(Flavour::Evm),
+        true,
     )
+    .map(|_| ())
+}
+
+/// Verify an EVM proof using BB with explicit artifact paths
+///
+/// Uses the oracle hash recorded in the manifest next to the proof, if the
+/// proof was generated with `--oracle-hash`. Artifacts written by
+/// `prove --compress` (`.gz`) are decompressed before bb sees them.
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+/// * `proof_path` - Proof file to verify
+/// * `vk_path` - Verification key file
+/// * `public_inputs_path` - Public inputs file; when missing, see
+///   [`common::resolve_public_inputs`]
+/// * `expect_valid` - Whether a rejected proof is an error; with `false` bb's
+///   verdict is captured and returned instead
+///
+/// # Returns
+/// * `Result<bool>` - Whether bb accepted the proof, or error from BB execution
+pub fn verify_evm_proof_at(
+    cfg: &Config,
+    proof_path: &Path,
+    vk_path: &Path,
+    public_inputs_path: &Path,
+    expect_valid: bool,
+) -> Result<bool> {
+    // Older and newer bb releases lay the public inputs out differently
+    let public_inputs = common::resolve_public_inputs(cfg, proof_path, public_inputs_path)?;
+
+    // bb reads only uncompressed files; expanded copies are removed on return
+    let mut inputs = vec![proof_path, vk_path];
+    inputs.extend(public_inputs.as_deref());
+    let expanded = util::decompress_artifacts(&inputs)?;
+    let paths = expanded.paths();
+    let proof_str = util::tool_path_string(&paths[0]);
+    let vk_str = util::tool_path_string(&paths[1]);
+    let public_inputs_str = paths.get(2).map(|path| util::tool_path_string(path));
+
+    let mut args = vec!["verify", "-p", &proof_str, "-k", &vk_str];
+    if let Some(public_inputs_str) = &public_inputs_str {
+        args.extend(["-i", public_inputs_str]);
+    }
+    args.extend(common::bb_oracle_args(
+        Flavour::Evm,
+        common::recorded_oracle_hash(proof_path)?,
+    ));
+
+    if expect_valid {
+        common::run_tool(cfg, "bb", &args)?;
+        Ok(true)
+    } else {
+        common::run_bb_verify(cfg, &args)
+    }
 }
 
 /// Generate both EVM proof and verification key in a single operation
@@ -110,12 +176,13 @@ pub fn verify_evm_proof(cfg: &Config, _pkg: &str) -> Result<()> {
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
 /// * `pkg` - Package name for locating bytecode and witness files
+/// * `prove_cfg` - Scheme, oracle hash, vk cache bypass and recursion settings
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from either operation
-pub fn generate_evm_proof_and_vk(cfg: &Config, pkg: &str) -> Result<()> {
-    generate_evm_vk(cfg, pkg)?;
-    generate_evm_proof(cfg, pkg)?;
+pub fn generate_evm_proof_and_vk(cfg: &Config, pkg: &str, prove_cfg: &ProveConfig) -> Result<()> {
+    generate_evm_vk(cfg, pkg, prove_cfg)?;
+    generate_evm_proof(cfg, pkg, prove_cfg)?;
     Ok(())
 }
 
@@ -128,15 +195,19 @@ pub fn generate_evm_proof_and_vk(cfg: &Config, pkg: &str) -> Result<()> {
 /// * `cfg` - Configuration containing runner and flags
 /// * `vk_path` - Path to the verification key file
 /// * `output_path` - Path where the Solidity contract should be written
+/// * `extra_args` - Passthrough arguments appended after bargo's own flags
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from BB execution
-pub fn write_solidity_verifier(cfg: &Config, vk_path: &str, output_path: &str) -> Result<()> {
-    common::run_tool(
-        cfg,
-        "bb",
-        &["write_solidity_verifier", "-k", vk_path, "-o", output_path],
-    )
+pub fn write_solidity_verifier(
+    cfg: &Config,
+    vk_path: &str,
+    output_path: &str,
+    extra_args: &[String],
+) -> Result<()> {
+    let mut args = vec!["write_solidity_verifier", "-k", vk_path, "-o", output_path];
+    args.extend(extra_args.iter().map(String::as_str));
+    common::run_tool(cfg, "bb", &args)
 }
 
 /// Write Solidity verifier contract using default EVM VK path
@@ -147,12 +218,22 @@ pub fn write_solidity_verifier(cfg: &Config, vk_path: &str, output_path: &str) -
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
 /// * `output_path` - Path where the Solidity contract should be written
+/// * `extra_args` - Passthrough arguments appended after bargo's own flags
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from BB execution
-pub fn write_solidity_verifier_from_evm_vk(cfg: &Config, output_path: &str) -> Result<()> {
+pub fn write_solidity_verifier_from_evm_vk(
+    cfg: &Config,
+    output_path: &str,
+    extra_args: &[String],
+) -> Result<()> {
     let vk_path = util::get_vk_path(Flavour::Evm);
-    write_solidity_verifier(cfg, &vk_path.to_string_lossy(), output_path)
+    write_solidity_verifier(
+        cfg,
+        &util::tool_path_string(&vk_path),
+        output_path,
+        extra_args,
+    )
 }
 
 /// Validate that required EVM artifacts exist for BB operations
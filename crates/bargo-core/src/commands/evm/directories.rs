@@ -22,15 +22,16 @@ pub fn ensure_evm_target_dir() -> Result<()> {
 
 /// Ensure the EVM contracts directory exists
 ///
-/// This function creates the contracts/evm directory if it doesn't exist,
-/// which is where Foundry projects and Solidity verifier contracts are stored.
+/// This function creates the contracts/evm directory, or `output_dir` and its
+/// parents, if it doesn't exist. That is where Foundry projects and Solidity
+/// verifier contracts are stored.
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from directory creation
-pub fn ensure_evm_contracts_dir() -> Result<()> {
-    let evm_dir = Path::new("./contracts/evm");
+pub fn ensure_evm_contracts_dir(output_dir: Option<&Path>) -> Result<()> {
+    let evm_dir = evm_contracts_dir(output_dir);
     if !evm_dir.exists() {
-        std::fs::create_dir_all(evm_dir).wrap_err_with(|| {
+        std::fs::create_dir_all(&evm_dir).wrap_err_with(|| {
             format!("creating EVM contracts directory at {}", evm_dir.display())
         })?;
     }
@@ -39,14 +40,15 @@ pub fn ensure_evm_contracts_dir() -> Result<()> {
 
 /// Ensure the EVM contracts source directory exists
 ///
-/// This function creates the contracts/evm/src directory for Solidity contracts.
+/// This function creates the contracts/evm/src directory, or `src` inside
+/// `output_dir`, for Solidity contracts.
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from directory creation
-pub fn ensure_evm_contracts_src_dir() -> Result<()> {
-    let src_dir = Path::new("./contracts/evm/src");
+pub fn ensure_evm_contracts_src_dir(output_dir: Option<&Path>) -> Result<()> {
+    let src_dir = evm_contracts_dir(output_dir).join("src");
     if !src_dir.exists() {
-        std::fs::create_dir_all(src_dir).wrap_err_with(|| {
+        std::fs::create_dir_all(&src_dir).wrap_err_with(|| {
             format!(
                 "creating EVM contracts source directory at {}",
                 src_dir.display()
@@ -59,15 +61,18 @@ pub fn ensure_evm_contracts_src_dir() -> Result<()> {
 /// Validate EVM workflow directory structure
 ///
 /// This function checks that all necessary directories exist for the EVM workflow
-/// and creates them if they don't exist.
+/// and creates them if they don't exist. With `output_dir` the contracts are
+/// written there, so `contracts/` is left alone.
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from validation/creation
-pub fn validate_evm_directory_structure() -> Result<()> {
+pub fn validate_evm_directory_structure(output_dir: Option<&Path>) -> Result<()> {
     ensure_evm_target_dir()?;
-    util::ensure_contracts_dir()?;
-    ensure_evm_contracts_dir()?;
-    ensure_evm_contracts_src_dir()?;
+    if output_dir.is_none() {
+        util::ensure_contracts_dir()?;
+    }
+    ensure_evm_contracts_dir(output_dir)?;
+    ensure_evm_contracts_src_dir(output_dir)?;
     Ok(())
 }
 
@@ -76,7 +81,25 @@ pub fn validate_evm_directory_structure() -> Result<()> {
 /// # Returns
 /// * `PathBuf` - Path to the EVM contracts directory
 pub fn get_evm_contracts_dir() -> PathBuf {
-    PathBuf::from("./contracts/evm")
+    evm_contracts_dir(None)
+}
+
+/// Get the directory the Foundry project is created in
+///
+/// `output_dir` comes from `evm gen --output-dir` and, like other paths given
+/// on the command line, resolves against `--package-dir`.
+///
+/// # Returns
+/// * `PathBuf` - `output_dir`, or the default EVM contracts directory
+pub fn evm_contracts_dir(output_dir: Option<&Path>) -> PathBuf {
+    util::project_path(output_dir.unwrap_or(Path::new("./contracts/evm")))
+}
+
+/// Check whether `dir` holds a project `evm gen` generated
+///
+/// Only a project with the generated `src/Verifier.sol` is safe to regenerate.
+pub fn is_generated_foundry_project(dir: &Path) -> bool {
+    dir.join("src/Verifier.sol").is_file()
 }
 
 /// Get the path to the EVM contracts source directory
@@ -84,7 +107,7 @@ pub fn get_evm_contracts_dir() -> PathBuf {
 /// # Returns
 /// * `PathBuf` - Path to the EVM contracts source directory
 pub fn get_evm_contracts_src_dir() -> PathBuf {
-    PathBuf::from("./contracts/evm/src")
+    util::project_path("./contracts/evm/src")
 }
 
 /// Check if the standard Verifier.sol contract exists
@@ -104,5 +127,13 @@ pub fn verifier_contract_exists() -> bool {
 /// # Returns
 /// * `PathBuf` - Path to the Verifier.sol contract
 pub fn get_verifier_contract_path() -> PathBuf {
-    get_evm_contracts_src_dir().join("Verifier.sol")
+    verifier_contract_path(None)
+}
+
+/// Get the path to the Verifier.sol contract inside `output_dir`
+///
+/// # Returns
+/// * `PathBuf` - Path to the Verifier.sol contract, by default in contracts/evm/src
+pub fn verifier_contract_path(output_dir: Option<&Path>) -> PathBuf {
+    evm_contracts_dir(output_dir).join("src/Verifier.sol")
 }
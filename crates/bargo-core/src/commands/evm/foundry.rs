@@ -4,8 +4,25 @@
 //! for EVM contract compilation, deployment, and verification.
 
 use color_eyre::Result;
+use color_eyre::eyre::{WrapErr, eyre};
+use std::path::Path;
 
-use crate::{backends, commands::common, config::Config};
+use crate::{
+    backends,
+    commands::{common, keystore::Keystore},
+    config::{Config, FoundryConfig},
+    util::{self, BargoError, create_smart_error},
+};
+
+use super::{EvmError, directories};
+
+/// Credentials `forge create` signs the deployment with
+pub enum EvmSigner {
+    /// Raw private key, passed as `--private-key`
+    PrivateKey(String),
+    /// Encrypted keystore, passed as `--keystore` with a `--password-file`
+    Keystore(Keystore),
+}
 
 /// Initialize a new Foundry project
 ///
@@ -25,15 +42,78 @@ pub fn init_foundry_project(cfg: &Config, project_path: &str) -> Result<()> {
 /// Initialize Foundry project at the default EVM contracts location
 ///
 /// Convenience function that initializes a Foundry project at the standard
-/// location used by the Bargo workflow.
+/// location used by the Bargo workflow, or `foundry_cfg.output_dir`, then
+/// writes the compiler settings from `foundry_cfg` into its `foundry.toml`
+/// (skipped in dry-run mode).
 ///
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
+/// * `foundry_cfg` - Destination, solc version and optimizer runs for the project
 ///
 /// # Returns
 /// * `Result<()>` - Success or error from initialization
-pub fn init_default_foundry_project(cfg: &Config) -> Result<()> {
-    init_foundry_project(cfg, "contracts/evm")
+pub fn init_default_foundry_project(cfg: &Config, foundry_cfg: &FoundryConfig) -> Result<()> {
+    let project_path = foundry_cfg
+        .output_dir
+        .as_deref()
+        .unwrap_or(Path::new("contracts/evm"));
+    init_foundry_project(cfg, &util::tool_path_string(project_path))?;
+    if cfg.dry_run || foundry_cfg.is_empty() {
+        return Ok(());
+    }
+    write_foundry_settings(
+        &directories::evm_contracts_dir(foundry_cfg.output_dir.as_deref()).join("foundry.toml"),
+        foundry_cfg,
+    )
+}
+
+/// Set the solc version and optimizer runs in the default profile of `foundry_toml`
+///
+/// Other settings, such as the `src`/`out`/`libs` written by `forge init`, are
+/// kept; comments are not. A missing file is created.
+pub fn write_foundry_settings(foundry_toml: &Path, foundry_cfg: &FoundryConfig) -> Result<()> {
+    let contents = match std::fs::read_to_string(foundry_toml) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).wrap_err_with(|| format!("reading {}", foundry_toml.display()));
+        }
+    };
+    let mut config: toml::Table =
+        contents
+            .parse()
+            .map_err(|e: toml::de::Error| BargoError::ParseFailure {
+                path: foundry_toml.to_path_buf(),
+                message: e.to_string(),
+            })?;
+
+    let profile = config
+        .entry("profile")
+        .or_insert_with(|| toml::Table::new().into())
+        .as_table_mut()
+        .and_then(|profile| {
+            profile
+                .entry("default")
+                .or_insert_with(|| toml::Table::new().into())
+                .as_table_mut()
+        })
+        .ok_or_else(|| {
+            eyre!(
+                "[profile.default] in {} is not a table",
+                foundry_toml.display()
+            )
+        })?;
+    if let Some(version) = &foundry_cfg.solc_version {
+        profile.insert("solc_version".to_string(), version.clone().into());
+    }
+    if let Some(runs) = foundry_cfg.optimizer_runs {
+        profile.insert("optimizer".to_string(), true.into());
+        profile.insert("optimizer_runs".to_string(), i64::from(runs).into());
+    }
+
+    let contents = toml::to_string(&config).wrap_err("serializing foundry.toml")?;
+    std::fs::write(foundry_toml, contents)
+        .wrap_err_with(|| format!("writing {}", foundry_toml.display()))
 }
 
 /// Deploy a contract using Foundry
@@ -45,7 +125,7 @@ pub fn init_default_foundry_project(cfg: &Config) -> Result<()> {
 /// * `contract_path` - Path to the contract source file
 /// * `contract_name` - Name of the contract to deploy
 /// * `rpc_url` - RPC URL for the target network
-/// * `private_key` - Private key for deployment (should be env var)
+/// * `signer` - Private key or keystore to sign the deployment with
 /// * `constructor_args` - Optional constructor arguments
 ///
 /// # Returns
@@ -55,17 +135,28 @@ pub fn deploy_contract(
     contract_path: &str,
     _contract_name: &str,
     rpc_url: &str,
-    private_key: &str,
+    signer: &EvmSigner,
     constructor_args: Option<&[&str]>,
 ) -> Result<String> {
-    let mut args = vec![
-        "create",
-        contract_path,
-        "--rpc-url",
-        rpc_url,
-        "--private-key",
-        private_key,
-    ];
+    let mut args = vec!["create", contract_path, "--rpc-url", rpc_url];
+
+    // The password file must outlive the forge invocation
+    let password_file;
+    let password_path;
+    let keystore_path;
+    match signer {
+        EvmSigner::PrivateKey(private_key) => args.extend(["--private-key", private_key]),
+        EvmSigner::Keystore(keystore) => {
+            keystore_path = util::tool_path_string(&keystore.path);
+            args.extend(["--keystore", &keystore_path]);
+            password_file = keystore.password_file()?;
+            if let Some(file) = &password_file {
+                password_path = util::tool_path_string(file.path());
+                args.extend(["--password-file", &password_path]);
+            }
+        }
+    }
+    args.push("--json");
 
     if let Some(constructor_args) = constructor_args {
         args.push("--constructor-args");
@@ -73,21 +164,112 @@ pub fn deploy_contract(
     }
 
     // Use runner to capture stdout for contract address parsing
-    let stdout = common::run_tool_capture(cfg, "forge", &args)?;
-
-    // Parse contract address from forge output
-    // forge create outputs: "Deployed to: 0x..."
-    for line in stdout.lines() {
-        if line.contains("Deployed to:")
-            && let Some(address) = line.split_whitespace().last()
-        {
-            return Ok(address.to_string());
-        }
+    let stdout = common::run_tool_capture_with_retry(cfg, "forge", &args, &[])?.stdout;
+
+    parse_deployed_address(&stdout).ok_or_else(|| {
+        EvmError::contract_address_error(format!(
+            "Could not parse deployed address from forge create output: {}",
+            stdout.trim()
+        ))
+        .into()
+    })
+}
+
+/// Extract the deployed contract address from `forge create` output
+///
+/// With `--json`, forge prints an object such as
+/// `{"deployer": "0x...", "deployedTo": "0x...", "transactionHash": "0x..."}`,
+/// possibly after compiler progress lines. If no such object is found, the
+/// legacy `Deployed to: 0x...` text output is scanned instead.
+pub fn parse_deployed_address(stdout: &str) -> Option<String> {
+    let from_json = |text: &str| {
+        serde_json::from_str::<serde_json::Value>(text.trim())
+            .ok()?
+            .get("deployedTo")?
+            .as_str()
+            .map(str::to_string)
+    };
+
+    from_json(stdout)
+        .or_else(|| stdout.lines().find_map(from_json))
+        .or_else(|| {
+            stdout
+                .lines()
+                .filter(|line| line.contains("Deployed to:"))
+                .find_map(|line| line.split_whitespace().last())
+                .map(str::to_string)
+        })
+}
+
+/// Estimate the gas needed to deploy a contract
+///
+/// The creation bytecode is read with `forge inspect` and passed to
+/// `cast estimate --create`, so nothing is broadcast.
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+/// * `contract_path` - Path to the contract source file
+/// * `rpc_url` - RPC URL for the target network
+///
+/// # Returns
+/// * `Result<u64>` - Estimated gas or error
+pub fn estimate_deploy_gas(cfg: &Config, contract_path: &str, rpc_url: &str) -> Result<u64> {
+    let bytecode =
+        common::run_tool_capture(cfg, "forge", &["inspect", contract_path, "bytecode"])?.stdout;
+    let bytecode = bytecode.trim();
+
+    let args = ["estimate", "--rpc-url", rpc_url, "--create", bytecode];
+    let stdout = common::run_tool_capture_with_retry(cfg, "cast", &args, &[])?.stdout;
+
+    parse_gas_estimate(&stdout).ok_or_else(|| {
+        EvmError::deploy_failed(format!(
+            "Could not parse gas estimate from cast estimate output: {}",
+            stdout.trim()
+        ))
+        .into()
+    })
+}
+
+/// Estimate the gas needed to deploy the default Verifier contract
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+/// * `rpc_url` - RPC URL for the target network
+///
+/// # Returns
+/// * `Result<u64>` - Estimated gas or error
+pub fn estimate_verifier_deploy_gas(cfg: &Config, rpc_url: &str) -> Result<u64> {
+    estimate_deploy_gas(cfg, "contracts/evm/src/Verifier.sol:Verifier", rpc_url)
+}
+
+/// Extract the gas figure from `cast estimate` output
+///
+/// cast prints a single decimal number; a `0x`-prefixed hex value is accepted
+/// as well. The last non-empty line is used so warnings printed before the
+/// result are skipped.
+pub fn parse_gas_estimate(stdout: &str) -> Option<u64> {
+    let line = stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())?;
+    match line.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => line.parse().ok(),
     }
+}
 
-    Err(color_eyre::eyre::eyre!(
-        "Could not parse contract address from forge output"
-    ))
+/// Abort when `estimate` exceeds the `--max-gas` limit, if one was given
+pub fn ensure_gas_within_limit(estimate: u64, max_gas: Option<u64>) -> Result<()> {
+    match max_gas {
+        Some(max_gas) if estimate > max_gas => Err(create_smart_error(
+            &format!("Estimated deployment gas {estimate} exceeds --max-gas {max_gas}"),
+            &[
+                "Raise the limit with --max-gas if the cost is expected",
+                "Regenerate the verifier (bargo evm gen) if the contract grew unexpectedly",
+            ],
+        )),
+        _ => Ok(()),
+    }
 }
 
 /// Deploy the default Verifier contract
@@ -98,27 +280,335 @@ pub fn deploy_contract(
 /// # Arguments
 /// * `cfg` - Configuration containing runner and flags
 /// * `rpc_url` - RPC URL for the target network
-/// * `private_key` - Private key for deployment
+/// * `signer` - Private key or keystore to sign the deployment with
+/// * `constructor_args` - Constructor arguments; empty for argument-free verifiers
 ///
 /// # Returns
 /// * `Result<String>` - Contract address or error
-pub fn deploy_verifier_contract(cfg: &Config, rpc_url: &str, private_key: &str) -> Result<String> {
+pub fn deploy_verifier_contract(
+    cfg: &Config,
+    rpc_url: &str,
+    signer: &EvmSigner,
+    constructor_args: &[String],
+) -> Result<String> {
+    let constructor_args: Vec<&str> = constructor_args.iter().map(String::as_str).collect();
     deploy_contract(
         cfg,
         "contracts/evm/src/Verifier.sol:Verifier",
         "Verifier",
         rpc_url,
-        private_key,
-        None,
+        signer,
+        (!constructor_args.is_empty()).then_some(&constructor_args[..]),
     )
 }
 
+/// Call the verifier contract's `verify` function using cast
+///
+/// This performs a read-only `cast call` against the deployed verifier, so no
+/// transaction is sent and no private key is needed.
+///
+/// # Arguments
+/// * `cfg` - Configuration containing runner and flags
+/// * `contract_address` - Address of the deployed verifier
+/// * `rpc_url` - RPC URL for the target network
+/// * `proof` - Hex-encoded proof bytes
+/// * `public_inputs` - Hex-encoded bytes32 public inputs
+///
+/// # Returns
+/// * `Result<bool>` - Whether the contract accepted the proof, or error
+pub fn verify_proof_onchain(
+    cfg: &Config,
+    contract_address: &str,
+    rpc_url: &str,
+    proof: &str,
+    public_inputs: &[String],
+) -> Result<bool> {
+    let public_inputs_arg = format!("[{}]", public_inputs.join(","));
+    let args = [
+        "call",
+        contract_address,
+        "verify(bytes,bytes32[])",
+        proof,
+        &public_inputs_arg,
+        "--rpc-url",
+        rpc_url,
+    ];
+
+    let stdout = common::run_tool_capture_with_retry(cfg, "cast", &args, &[])?.stdout;
+
+    parse_verify_result(&stdout).ok_or_else(|| {
+        color_eyre::eyre::eyre!(
+            "Could not parse verification result from cast output: {}",
+            stdout.trim()
+        )
+    })
+}
+
+/// Parse the boolean returned by the verifier's `verify` function
+///
+/// cast prints either a decoded `true`/`false` or the raw ABI-encoded word
+/// (`0x...01` / `0x...00`) depending on whether a return type was given.
+pub fn parse_verify_result(stdout: &str) -> Option<bool> {
+    let value = stdout.trim();
+    match value {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => {
+            let digits = value.strip_prefix("0x")?.trim_start_matches('0');
+            match digits {
+                "" => Some(false),
+                "1" => Some(true),
+                _ => None,
+            }
+        }
+    }
+}
+
 /// Validate that Foundry tools are available
 ///
-/// This function checks that forge and cast are installed and accessible.
+/// This function checks that forge and cast are installed and accessible,
+/// honouring a forge binary pinned in the configuration.
 ///
 /// # Returns
 /// * `Result<()>` - Success if tools are available, error otherwise
-pub fn validate_foundry_installation() -> Result<()> {
-    backends::foundry::ensure_available()
+pub fn validate_foundry_installation(cfg: &Config) -> Result<()> {
+    backends::foundry::ensure_available(&cfg.tools)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{ArtifactSuffix, ColorChoice};
+    use crate::config::ToolPaths;
+    use crate::fs::RealFs;
+    use crate::runner::DryRunRunner;
+    use std::sync::Arc;
+
+    const ADDRESS: &str = "0x742d35Cc6634C0532925a3b8D400d1b0fB000000";
+
+    #[test]
+    fn test_parse_deployed_address_json() {
+        let stdout = format!(
+            r#"{{"deployer":"0x1111111111111111111111111111111111111111","deployedTo":"{ADDRESS}","transactionHash":"0xabc"}}"#
+        );
+        assert_eq!(parse_deployed_address(&stdout).as_deref(), Some(ADDRESS));
+
+        // Compiler progress may precede the JSON object
+        let noisy = format!("Compiling 1 files with Solc 0.8.27\n{stdout}\n");
+        assert_eq!(parse_deployed_address(&noisy).as_deref(), Some(ADDRESS));
+    }
+
+    #[test]
+    fn test_parse_deployed_address_legacy_text() {
+        let stdout = format!(
+            "Deployer: 0x1111111111111111111111111111111111111111\nDeployed to: {ADDRESS}\nTransaction hash: 0xabc\n"
+        );
+        assert_eq!(parse_deployed_address(&stdout).as_deref(), Some(ADDRESS));
+    }
+
+    #[test]
+    fn test_parse_deployed_address_missing() {
+        assert_eq!(parse_deployed_address("Error: insufficient funds"), None);
+        assert_eq!(parse_deployed_address(r#"{"deployer":"0x1"}"#), None);
+    }
+
+    #[test]
+    fn test_deploy_contract_requests_json_output() {
+        let runner = Arc::new(DryRunRunner::new());
+        let cfg = Config {
+            verbose: 0,
+            dry_run: false,
+            pkg: None,
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: runner.clone(),
+            fs: Arc::new(RealFs),
+        };
+
+        let signer = EvmSigner::PrivateKey("0xkey".to_string());
+        let address =
+            deploy_verifier_contract(&cfg, "http://localhost:8545", &signer, &[]).unwrap();
+        assert_eq!(address, ADDRESS);
+
+        let history = runner.history();
+        assert_eq!(history[0].0.cmd, "forge");
+        assert!(history[0].0.args.contains(&"--json".to_string()));
+        assert!(
+            !history[0]
+                .0
+                .args
+                .contains(&"--constructor-args".to_string())
+        );
+
+        let constructor_args = ["42".to_string(), "0xabc".to_string()];
+        deploy_verifier_contract(&cfg, "http://localhost:8545", &signer, &constructor_args)
+            .unwrap();
+        let args = &runner.history()[1].0.args;
+        assert_eq!(
+            args[args.len() - 3..],
+            ["--constructor-args", "42", "0xabc"]
+        );
+    }
+
+    #[test]
+    fn test_deploy_contract_with_keystore_passes_password_file() {
+        let runner = Arc::new(DryRunRunner::new());
+        let cfg = Config {
+            verbose: 0,
+            dry_run: false,
+            pkg: None,
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: runner.clone(),
+            fs: Arc::new(RealFs),
+        };
+
+        let keystore = Keystore::new("keystores/deployer".into(), "hunter2".to_string());
+        let signer = EvmSigner::Keystore(keystore);
+        deploy_verifier_contract(&cfg, "http://localhost:8545", &signer, &[]).unwrap();
+
+        let args = &runner.history()[0].0.args;
+        let flag = |name: &str| {
+            args.iter()
+                .position(|arg| arg == name)
+                .map(|i| &args[i + 1])
+        };
+        assert_eq!(
+            flag("--keystore").map(String::as_str),
+            Some("keystores/deployer")
+        );
+        assert!(flag("--password-file").is_some());
+        assert!(
+            !args
+                .iter()
+                .any(|arg| arg == "--private-key" || arg == "hunter2")
+        );
+    }
+
+    #[test]
+    fn test_parse_gas_estimate() {
+        assert_eq!(parse_gas_estimate("1843207\n"), Some(1_843_207));
+        assert_eq!(
+            parse_gas_estimate("Warning: This is a nightly build of Foundry.\n1843207\n\n"),
+            Some(1_843_207)
+        );
+        assert_eq!(parse_gas_estimate("0x1c2007"), Some(1_843_207));
+        assert_eq!(parse_gas_estimate("Error: execution reverted"), None);
+        assert_eq!(parse_gas_estimate(""), None);
+    }
+
+    #[test]
+    fn test_gas_limit_guard() {
+        assert!(ensure_gas_within_limit(1_843_207, None).is_ok());
+        assert!(ensure_gas_within_limit(1_843_207, Some(1_843_207)).is_ok());
+
+        let error = ensure_gas_within_limit(1_843_207, Some(1_000_000)).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Estimated deployment gas 1843207 exceeds --max-gas 1000000")
+        );
+    }
+
+    #[test]
+    fn test_estimate_verifier_deploy_gas_composes_commands() {
+        let runner = Arc::new(DryRunRunner::new());
+        let cfg = Config {
+            verbose: 0,
+            dry_run: false,
+            pkg: None,
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: runner.clone(),
+            fs: Arc::new(RealFs),
+        };
+
+        let estimate = estimate_verifier_deploy_gas(&cfg, "http://localhost:8545").unwrap();
+        assert_eq!(estimate, 1_843_207);
+
+        let history = runner.history();
+        assert_eq!(history[0].0.args[0], "inspect");
+        let (spec, _) = &history[1];
+        assert_eq!(spec.cmd, "cast");
+        assert_eq!(
+            spec.args[..4],
+            ["estimate", "--rpc-url", "http://localhost:8545", "--create"]
+        );
+        assert_eq!(Some(&spec.args[4]), history[0].1.as_ref());
+    }
+
+    #[test]
+    fn test_parse_verify_result_decoded() {
+        assert_eq!(parse_verify_result("true\n"), Some(true));
+        assert_eq!(parse_verify_result("false"), Some(false));
+    }
+
+    #[test]
+    fn test_parse_verify_result_raw_word() {
+        let one = format!("0x{}1", "0".repeat(63));
+        let zero = format!("0x{}", "0".repeat(64));
+        assert_eq!(parse_verify_result(&one), Some(true));
+        assert_eq!(parse_verify_result(&zero), Some(false));
+    }
+
+    #[test]
+    fn test_parse_verify_result_unexpected() {
+        assert_eq!(parse_verify_result("Error: execution reverted"), None);
+        assert_eq!(parse_verify_result("0x02"), None);
+    }
+
+    #[test]
+    fn test_write_foundry_settings_keeps_forge_defaults() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let foundry_toml = temp_dir.path().join("foundry.toml");
+        std::fs::write(
+            &foundry_toml,
+            "[profile.default]\nsrc = \"src\"\nout = \"out\"\nlibs = [\"lib\"]\n",
+        )
+        .unwrap();
+
+        let foundry_cfg = FoundryConfig::new(Some("0.8.27".to_string()), Some(200));
+        write_foundry_settings(&foundry_toml, &foundry_cfg).unwrap();
+
+        let written: toml::Table = std::fs::read_to_string(&foundry_toml)
+            .unwrap()
+            .parse()
+            .unwrap();
+        let profile = written["profile"]["default"].as_table().unwrap();
+        assert_eq!(profile["solc_version"].as_str(), Some("0.8.27"));
+        assert_eq!(profile["optimizer"].as_bool(), Some(true));
+        assert_eq!(profile["optimizer_runs"].as_integer(), Some(200));
+        assert_eq!(profile["src"].as_str(), Some("src"));
+        assert!(profile.contains_key("libs"));
+    }
 }
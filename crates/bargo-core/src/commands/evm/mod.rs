@@ -3,6 +3,7 @@
 //! This module provides a clean, modular interface for EVM/Ethereum operations
 //! including proof generation, verification, contract management, and deployment.
 
+pub mod abi;
 pub mod backend;
 pub mod bb_operations;
 pub mod directories;
@@ -13,10 +14,13 @@ pub mod workflow;
 pub mod foundry;
 
 // Re-export main workflow functions for use by main.rs
-pub use workflow::{run_calldata, run_gen, run_prove, run_verify};
+pub use workflow::{
+    run_calldata, run_calldata_with_config, run_gen, run_gen_with_config, run_prove,
+    run_prove_with_config, run_verify, run_verify_with_config,
+};
 
 #[cfg(feature = "evm-foundry")]
-pub use workflow::{run_deploy, run_verify_onchain};
+pub use workflow::{run_deploy, run_deploy_with_config, run_verify_onchain};
 
 // Re-export error types for convenience
 pub use error::{EvmError, Result};
@@ -25,9 +29,10 @@ pub use error::{EvmError, Result};
 // (Currently none are needed externally, but modules are available for import)
 
 // Helper function to load environment variables (shared across workflow)
-pub fn load_env_vars() {
-    dotenv::dotenv().ok();
-    if std::path::Path::new(".env").exists() {
-        let _ = dotenv::from_filename(".env");
-    }
+//
+// `.env` is read from the project root rather than the process working
+// directory, so `--package-dir` picks up the project's deploy settings.
+// `--env-file` replaces it with the named file.
+pub fn load_env_vars(project_root: &std::path::Path) {
+    crate::util::load_dotenv_files(project_root, &[".env"]);
 }
@@ -6,20 +6,30 @@
 use color_eyre::Result;
 use color_eyre::eyre::WrapErr;
 use serde_json::json;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::{
-    config::Config,
+    api::{ProveResult, VerifyResult},
+    commands::common,
+    config::{CalldataConfig, Config, FoundryConfig, ProveConfig, VerifyConfig},
     util::{
-        self, Flavour, OperationSummary, Timer, create_smart_error, enhance_error_with_suggestions,
-        format_operation_result, success,
+        self, Flavour, OperationSummary, Spinner, Timer, create_smart_error,
+        enhance_error_with_suggestions, format_operation_duration, format_operation_result,
+        success,
     },
 };
 
-use super::{bb_operations, directories, load_env_vars};
+use super::{abi, bb_operations, directories, load_env_vars};
 
 #[cfg(feature = "evm-foundry")]
-use super::foundry;
+use super::foundry::{self, EvmSigner};
+
+#[cfg(feature = "evm-foundry")]
+use crate::{
+    commands::keystore::{self, Keystore},
+    config::{EvmDeployConfig, Network},
+    util::Deployment,
+};
 
 /// Run the EVM gen workflow
 ///
@@ -35,38 +45,86 @@ use super::foundry;
 /// # Returns
 /// * `Result<()>` - Success or error from workflow
 pub fn run_gen(cfg: &Config) -> Result<()> {
-    let pkg_name = util::get_package_name(cfg.pkg.as_ref())?;
-    load_env_vars();
+    run_gen_with_config(cfg, &ProveConfig::default(), &FoundryConfig::default())
+}
 
-    if cfg.verbose {
+/// Run the EVM gen workflow with prove settings such as an oracle hash override
+///
+/// With `prove_cfg.no_prove` the proof and vk are not regenerated and the
+/// verifier is written from the existing `target/evm/vk`.
+/// The same happens when the existing proof is newer than the build
+/// artifacts, unless `prove_cfg.force` is set.
+///
+/// The Foundry project is created in `foundry_cfg.output_dir`, or
+/// `contracts/evm`. A destination holding files bargo did not generate is only
+/// replaced with `foundry_cfg.overwrite`.
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `prove_cfg` - Settings for the proof and VK generated along the way
+/// * `foundry_cfg` - Destination and compiler settings for the generated Foundry project
+///
+/// # Returns
+/// * `Result<()>` - Success or error from workflow
+#[cfg_attr(not(feature = "evm-foundry"), allow(unused_variables))]
+pub fn run_gen_with_config(
+    cfg: &Config,
+    prove_cfg: &ProveConfig,
+    foundry_cfg: &FoundryConfig,
+) -> Result<()> {
+    foundry_cfg.validate()?;
+    let pkg_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)?;
+    load_env_vars(&cfg.project_root()?);
+    let prove_cfg = &common::with_recorded_recursion(cfg, Flavour::Evm, prove_cfg)?;
+
+    if cfg.is_verbose() {
         info!("Starting EVM verifier generation workflow");
     }
 
-    // Validate required files exist
-    let required_files = vec![
-        util::get_bytecode_path(&pkg_name, Flavour::Bb),
-        util::get_witness_path(&pkg_name, Flavour::Bb),
-    ];
+    // Validate required files exist; --no-prove only needs the existing vk
+    let required_files = if prove_cfg.no_prove {
+        vec![util::get_vk_path(Flavour::Evm)]
+    } else {
+        vec![
+            util::get_bytecode_path(&pkg_name, Flavour::Bb),
+            util::get_witness_path(&pkg_name, Flavour::Bb),
+        ]
+    };
+
+    // Checked up front, so a bad destination fails before anything is proved
+    let output_dir = foundry_cfg.output_dir.as_deref();
+    util::ensure_replaceable_dir(
+        &directories::evm_contracts_dir(output_dir),
+        directories::is_generated_foundry_project,
+        foundry_cfg.overwrite,
+    )?;
 
     if !cfg.dry_run {
         util::validate_files_exist(&required_files).map_err(enhance_error_with_suggestions)?;
-        directories::validate_evm_directory_structure().map_err(enhance_error_with_suggestions)?;
+        directories::validate_evm_directory_structure(output_dir)
+            .map_err(enhance_error_with_suggestions)?;
     }
 
-    let mut summary = OperationSummary::new();
+    let reuse_proof =
+        prove_cfg.no_prove || common::proof_is_current(cfg, &pkg_name, Flavour::Evm, prove_cfg)?;
+    let mut summary = OperationSummary::new().verbose(cfg.is_verbose());
 
     // Step 1: Initialize project structure
-    if cfg.verbose {
+    if cfg.is_verbose() {
         info!("Initializing EVM project structure");
     }
     let foundry_timer = Timer::start();
 
     #[cfg(feature = "evm-foundry")]
     {
-        foundry::init_default_foundry_project(cfg).map_err(enhance_error_with_suggestions)?;
+        let spinner = Spinner::start("Initializing Foundry project", cfg.show_spinner());
+        foundry::init_default_foundry_project(cfg, foundry_cfg)
+            .map_err(enhance_error_with_suggestions)?;
+        spinner.finish();
+        summary.add_timed_operation("Foundry init", foundry_timer.duration());
 
         if !cfg.quiet {
-            let foundry_dir = directories::get_evm_contracts_dir();
+            let foundry_dir = directories::evm_contracts_dir(output_dir);
             println!(
                 "{}",
                 success(&format_operation_result(
@@ -81,11 +139,14 @@ pub fn run_gen(cfg: &Config) -> Result<()> {
 
     #[cfg(not(feature = "evm-foundry"))]
     {
-        directories::ensure_evm_contracts_dir().map_err(enhance_error_with_suggestions)?;
-        directories::ensure_evm_contracts_src_dir().map_err(enhance_error_with_suggestions)?;
+        directories::ensure_evm_contracts_dir(output_dir)
+            .map_err(enhance_error_with_suggestions)?;
+        directories::ensure_evm_contracts_src_dir(output_dir)
+            .map_err(enhance_error_with_suggestions)?;
+        summary.add_timed_operation("Contracts directory", foundry_timer.duration());
 
         if !cfg.quiet {
-            let contracts_dir = directories::get_evm_contracts_dir();
+            let contracts_dir = directories::evm_contracts_dir(output_dir);
             println!(
                 "{}",
                 success(&format_operation_result(
@@ -98,60 +159,112 @@ pub fn run_gen(cfg: &Config) -> Result<()> {
         }
     }
 
-    // Step 2: Generate EVM VK
-    if cfg.verbose {
-        info!("Generating EVM verification key");
-    }
-    let vk_timer = Timer::start();
-    bb_operations::generate_evm_vk(cfg, &pkg_name).map_err(enhance_error_with_suggestions)?;
+    if reuse_proof {
+        if !cfg.quiet {
+            summary.add_operation(&format!(
+                "Reused verification key ({})",
+                util::format_file_size(&util::get_vk_path(Flavour::Evm))
+            ));
+        }
+    } else {
+        // Step 2: Generate EVM VK
+        if cfg.is_verbose() {
+            info!("Generating EVM verification key");
+        }
+        let vk_timer = Timer::start();
+        let spinner = Spinner::start("Generating EVM verification key", cfg.show_spinner());
+        bb_operations::generate_evm_vk(cfg, &pkg_name, prove_cfg)
+            .map_err(enhance_error_with_suggestions)?;
+        spinner.finish();
+        summary.add_timed_operation("VK generation", vk_timer.duration());
 
-    if !cfg.quiet {
-        let vk_path = util::get_vk_path(Flavour::Evm);
-        println!(
-            "{}",
-            success(&format_operation_result(
-                "EVM VK generated",
-                &vk_path,
-                &vk_timer
-            ))
-        );
-        summary.add_operation(&format!(
-            "Verification key ({})",
-            util::format_file_size(&vk_path)
-        ));
-    }
+        if !cfg.quiet {
+            let vk_path = util::get_vk_path(Flavour::Evm);
+            println!(
+                "{}",
+                success(&format_operation_result(
+                    "EVM VK generated",
+                    &vk_path,
+                    &vk_timer
+                ))
+            );
+            summary.add_operation(&format!(
+                "Verification key ({})",
+                util::format_file_size(&vk_path)
+            ));
+        }
 
-    // Step 3: Generate EVM proof
-    if cfg.verbose {
-        info!("Generating EVM proof");
-    }
-    let proof_timer = Timer::start();
-    bb_operations::generate_evm_proof(cfg, &pkg_name).map_err(enhance_error_with_suggestions)?;
+        // Step 3: Generate EVM proof
+        if cfg.is_verbose() {
+            info!("Generating EVM proof");
+        }
+        let proof_timer = Timer::start();
+        let spinner = Spinner::start("Generating EVM proof", cfg.show_spinner());
+        bb_operations::generate_evm_proof(cfg, &pkg_name, prove_cfg)
+            .map_err(enhance_error_with_suggestions)?;
+        spinner.finish();
+        summary.add_timed_operation("Proof generation", proof_timer.duration());
 
-    if !cfg.quiet {
-        let proof_path = util::get_proof_path(Flavour::Evm);
-        println!(
-            "{}",
-            success(&format_operation_result(
-                "EVM proof generated",
-                &proof_path,
-                &proof_timer
-            ))
-        );
-        summary.add_operation(&format!(
-            "EVM proof ({})",
-            util::format_file_size(&proof_path)
-        ));
+        if !cfg.quiet {
+            let proof_path = util::get_proof_path(Flavour::Evm);
+            println!(
+                "{}",
+                success(&format_operation_result(
+                    "EVM proof generated",
+                    &proof_path,
+                    &proof_timer
+                ))
+            );
+            summary.add_operation(&format!(
+                "EVM proof ({})",
+                util::format_file_size(&proof_path)
+            ));
+        }
     }
 
     // Step 4: Generate Solidity verifier contract
-    if cfg.verbose {
+    if cfg.is_verbose() {
         info!("Generating Solidity verifier contract");
     }
     let contract_timer = Timer::start();
-    let verifier_path = directories::get_verifier_contract_path();
-    bb_operations::write_solidity_verifier_from_evm_vk(cfg, &verifier_path.to_string_lossy())
-        .map_err(enhance_error_with_suggestions)?;
+    let verifier_path = directories::verifier_contract_path(output_dir);
+    let spinner = Spinner::start("Generating Solidity verifier contract", cfg.show_spinner());
+    bb_operations::write_solidity_verifier_from_evm_vk(
+        cfg,
+        &util::tool_path_string(&verifier_path),
+        &foundry_cfg.extra_args,
+    )
+    .map_err(enhance_error_with_suggestions)?;
+    spinner.finish();
+    summary.add_timed_operation("Verifier contract", contract_timer.duration());
+    if reuse_proof {
+        // Reused artifacts keep the provenance recorded when they were proved
+        common::record_manifest(
+            cfg,
+            &util::manifest_path(Flavour::Evm),
+            "evm gen",
+            std::slice::from_ref(&verifier_path),
+            &["bb"],
+        )?;
+    } else {
+        common::record_manifest(
+            cfg,
+            &util::manifest_path(Flavour::Evm),
+            "evm gen",
+            &[
+                util::get_proof_path(Flavour::Evm),
+                util::get_vk_path(Flavour::Evm),
+                util::get_public_inputs_path(Flavour::Evm),
+                verifier_path.clone(),
+            ],
+            &["bb"],
+        )?;
+        common::record_oracle_hash(
+            cfg,
+            &util::manifest_path(Flavour::Evm),
+            prove_cfg.oracle_hash,
+        )?;
+    }
 
     if !cfg.quiet {
         println!(
@@ -185,40 +298,105 @@ pub fn run_gen(cfg: &Config) -> Result<()> {
 /// # Returns
 /// * `Result<()>` - Success or error from workflow
 pub fn run_prove(cfg: &Config) -> Result<()> {
-    let pkg_name =
-        util::get_package_name(cfg.pkg.as_ref()).map_err(enhance_error_with_suggestions)?;
+    run_prove_with_config(cfg, &ProveConfig::default())
+}
+
+/// Run the EVM prove workflow with prove-specific settings
+///
+/// Proving is skipped when the proof in `target/evm/` is newer than the
+/// bytecode and witness, unless `prove_cfg.force` is set.
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `prove_cfg` - Prove settings such as the proving scheme
+///
+/// # Returns
+/// * `Result<()>` - Success or error from workflow
+pub fn run_prove_with_config(cfg: &Config, prove_cfg: &ProveConfig) -> Result<()> {
+    let result = generate_proof(cfg, prove_cfg)?;
+
+    if !cfg.quiet {
+        if result.reused {
+            println!(
+                "{}",
+                success(
+                    "EVM proof is newer than the build artifacts, skipping (use --force to regenerate)"
+                )
+            );
+        } else {
+            println!(
+                "{}",
+                success(&format_operation_duration(
+                    "EVM proof and VK generated",
+                    &result.proof,
+                    result.elapsed
+                ))
+            );
+            println!("  • Proof: {}", result.proof.display());
+            println!("  • VK: {}", result.vk.display());
+            if let Some(summary) = common::compression_summary(&result.compressed) {
+                println!("  • {summary}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate the EVM proof and vk without printing a summary
+///
+/// The existing proof is kept when [`common::proof_is_current`] allows it.
+pub fn generate_proof(cfg: &Config, prove_cfg: &ProveConfig) -> Result<ProveResult> {
+    let pkg_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)
+        .map_err(enhance_error_with_suggestions)?;
 
     // Validate that required build files exist
-    let required_files = vec![
-        util::get_bytecode_path(&pkg_name, Flavour::Bb),
-        util::get_witness_path(&pkg_name, Flavour::Bb),
-    ];
+    let bytecode = util::get_bytecode_path(&pkg_name, Flavour::Bb);
+    let witness = prove_cfg.witness_path(&pkg_name);
+    let required_files = vec![bytecode.clone(), witness.clone()];
 
     if !cfg.dry_run {
         util::validate_files_exist(&required_files).map_err(enhance_error_with_suggestions)?;
+        util::validate_artifact_files(&bytecode, &witness)?;
         directories::ensure_evm_target_dir().map_err(enhance_error_with_suggestions)?;
     }
 
     let timer = Timer::start();
-    bb_operations::generate_evm_proof_and_vk(cfg, &pkg_name)
-        .map_err(enhance_error_with_suggestions)?;
-
-    if !cfg.quiet {
-        let proof_path = util::get_proof_path(Flavour::Evm);
-        let vk_path = util::get_vk_path(Flavour::Evm);
-        println!(
-            "{}",
-            success(&format_operation_result(
-                "EVM proof and VK generated",
-                &proof_path,
-                &timer
-            ))
+    let reused = common::proof_is_current(cfg, &pkg_name, Flavour::Evm, prove_cfg)?;
+    let mut compressed = Vec::new();
+    if !reused {
+        let spinner = Spinner::start(
+            "Generating EVM proof and verification key",
+            cfg.show_spinner(),
         );
-        println!("  • Proof: {}", proof_path.display());
-        println!("  • VK: {}", vk_path.display());
-    }
-
-    Ok(())
+        bb_operations::generate_evm_proof_and_vk(cfg, &pkg_name, prove_cfg)
+            .map_err(enhance_error_with_suggestions)?;
+        spinner.finish();
+        compressed = common::compress_proof_artifacts(cfg, Flavour::Evm, prove_cfg.compress)?;
+        let manifest = util::manifest_path(Flavour::Evm);
+        common::record_manifest(
+            cfg,
+            &manifest,
+            "evm prove",
+            &common::proof_artifacts(Flavour::Evm),
+            &["bb"],
+        )?;
+        common::record_compression(cfg, &manifest, &compressed)?;
+        common::record_oracle_hash(cfg, &manifest, prove_cfg.oracle_hash)?;
+        common::record_bb_profile(cfg, &manifest, prove_cfg.bb_profile)?;
+        common::record_recursive_friendly(cfg, &manifest, prove_cfg.recursive_friendly)?;
+        common::record_proof_meta(cfg, Flavour::Evm, &pkg_name, &["nargo", "bb"])?;
+    }
+
+    let [proof, vk, public_inputs] = common::proof_artifacts(Flavour::Evm);
+    Ok(ProveResult {
+        proof,
+        vk,
+        public_inputs,
+        reused,
+        compressed,
+        elapsed: timer.duration(),
+    })
 }
 
 /// Run the EVM verify workflow
@@ -229,28 +407,113 @@ pub fn run_prove(cfg: &Config) -> Result<()> {
 /// # Returns
 /// * `Result<()>` - Success or error from workflow
 pub fn run_verify(cfg: &Config) -> Result<()> {
-    let pkg_name =
-        util::get_package_name(cfg.pkg.as_ref()).map_err(enhance_error_with_suggestions)?;
+    run_verify_with_config(cfg, &VerifyConfig::default())
+}
+
+/// Run the EVM verify workflow with optional artifact path overrides
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `verify_cfg` - Proof, vk and public inputs paths to use instead of `target/evm/`,
+///   and whether the proof is expected to verify
+///
+/// # Returns
+/// * `Result<()>` - Success or error from workflow
+pub fn run_verify_with_config(cfg: &Config, verify_cfg: &VerifyConfig) -> Result<()> {
+    let result = verify_proof(cfg, verify_cfg)?;
+
+    if !cfg.quiet {
+        let message = if verify_cfg.expect_valid {
+            "EVM proof verified successfully"
+        } else {
+            "EVM proof rejected as expected"
+        };
+        let detail = if result.cached {
+            "cached, inputs unchanged; use --force to re-run".to_string()
+        } else {
+            util::format_duration(result.elapsed)
+        };
+        println!("{}", success(&format!("{message} ({detail})")));
+    }
+
+    Ok(())
+}
+
+/// Verify the EVM proof without printing the outcome
+///
+/// The last outcome is reused while the proof, vk and public inputs are
+/// unchanged, unless `verify_cfg.force` is set. Fails when bb's verdict does
+/// not match `verify_cfg.expect_valid`.
+pub fn verify_proof(cfg: &Config, verify_cfg: &VerifyConfig) -> Result<VerifyResult> {
+    util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)
+        .map_err(enhance_error_with_suggestions)?;
+
+    // Validate that required EVM artifacts exist; default public inputs
+    // may be derived from their field encoding or embedded in the proof
+    let [proof_path, vk_path, public_inputs_path] = &verify_cfg.paths(Flavour::Evm);
 
-    // Validate that required EVM artifacts exist
     if !cfg.dry_run {
-        bb_operations::validate_evm_artifacts().map_err(enhance_error_with_suggestions)?;
+        let mut required_files = vec![proof_path, vk_path];
+        if verify_cfg.public_inputs.is_some() {
+            required_files.push(public_inputs_path);
+        }
+        util::validate_files_exist(&required_files).map_err(enhance_error_with_suggestions)?;
     }
 
     let timer = Timer::start();
-    bb_operations::verify_evm_proof(cfg, &pkg_name).map_err(enhance_error_with_suggestions)?;
 
-    if !cfg.quiet {
-        println!(
-            "{}",
-            success(&format!(
-                "EVM proof verified successfully ({})",
-                timer.elapsed()
-            ))
-        );
+    // Reuse the last outcome while the proof, vk and public inputs are unchanged
+    let cache_path = util::verify_cache_path(Flavour::Evm);
+    let inputs = (!cfg.dry_run)
+        .then(|| util::VerifyInputs::from_files(proof_path, vk_path, public_inputs_path))
+        .transpose()?;
+    if !verify_cfg.force
+        && inputs
+            .as_ref()
+            .and_then(|inputs| util::cached_verification(&cache_path, inputs))
+            == Some(verify_cfg.expect_valid)
+    {
+        return Ok(VerifyResult {
+            accepted: verify_cfg.expect_valid,
+            cached: true,
+            elapsed: timer.duration(),
+        });
+    }
+
+    let spinner = Spinner::start("Verifying EVM proof", cfg.show_spinner());
+    let accepted = bb_operations::verify_evm_proof_at(
+        cfg,
+        proof_path,
+        vk_path,
+        public_inputs_path,
+        verify_cfg.expect_valid,
+    )
+    .map_err(enhance_error_with_suggestions)?;
+    spinner.finish();
+
+    // With --expect false the proof must be rejected; a dry run has no verdict
+    if !verify_cfg.expect_valid && accepted && !cfg.dry_run {
+        return Err(create_smart_error(
+            &format!(
+                "Expected the EVM proof to fail verification, but bb accepted {}",
+                proof_path.display()
+            ),
+            &[
+                "Check that the proof, vk or public inputs were altered as intended",
+                "Drop '--expect false' to require a valid proof instead",
+            ],
+        ));
     }
 
-    Ok(())
+    if let Some(inputs) = inputs {
+        util::store_verification(&cache_path, inputs, accepted)?;
+    }
+
+    Ok(VerifyResult {
+        accepted,
+        cached: false,
+        elapsed: timer.duration(),
+    })
 }
 
 /// Run the EVM deploy workflow
@@ -263,11 +526,41 @@ pub fn run_verify(cfg: &Config) -> Result<()> {
 /// * `Result<()>` - Success or error from workflow
 #[cfg(feature = "evm-foundry")]
 pub fn run_deploy(cfg: &Config, network: &str) -> Result<()> {
-    load_env_vars();
+    run_deploy_with_config(cfg, network, &EvmDeployConfig::default())
+}
+
+/// Run the EVM deploy workflow with deploy-specific settings
+///
+/// The deployment gas is estimated first and printed, and the deploy is
+/// aborted if the estimate exceeds `deploy_cfg.max_gas`. With
+/// `deploy_cfg.account` the contract is signed with that foundry keystore
+/// instead of `PRIVATE_KEY`.
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `network` - Target network for deployment
+/// * `deploy_cfg` - Deploy settings such as the gas limit and signing account
+///
+/// # Returns
+/// * `Result<()>` - Success or error from workflow
+#[cfg(feature = "evm-foundry")]
+pub fn run_deploy_with_config(
+    cfg: &Config,
+    network: &str,
+    deploy_cfg: &EvmDeployConfig,
+) -> Result<()> {
+    let network = Network::parse(network)?;
+    common::confirm_mainnet(
+        cfg,
+        &network,
+        deploy_cfg.yes,
+        "deploy the verifier contract",
+    )?;
+    load_env_vars(&cfg.project_root()?);
 
     // Validate Foundry installation
     if !cfg.dry_run {
-        foundry::validate_foundry_installation().map_err(enhance_error_with_suggestions)?;
+        foundry::validate_foundry_installation(cfg).map_err(enhance_error_with_suggestions)?;
     }
 
     // Check that verifier contract exists
@@ -281,52 +574,78 @@ pub fn run_deploy(cfg: &Config, network: &str) -> Result<()> {
         ));
     }
 
-    // Get environment variables
-    let rpc_url = std::env::var("RPC_URL").map_err(|_| {
-        create_smart_error(
-            "RPC_URL environment variable not found",
-            &[
-                "Add to your .env file: RPC_URL=https://eth-mainnet.g.alchemy.com/v2/your_key",
-                "Ensure the .env file is loaded in your environment",
-            ],
-        )
-    })?;
+    // Localhost and custom networks carry their own RPC URL
+    let rpc_url = match network.evm_rpc_url() {
+        Some(rpc_url) => rpc_url,
+        None => std::env::var("RPC_URL").map_err(|_| missing_rpc_url_error(&network))?,
+    };
 
-    let private_key = std::env::var("PRIVATE_KEY").map_err(|_| {
-        create_smart_error(
-            "PRIVATE_KEY environment variable not found",
-            &[
-                "Add to your .env file: PRIVATE_KEY=your_private_key",
-                "Ensure the .env file is loaded in your environment",
-                "⚠️  Keep your private key secure and never commit it to version control",
-            ],
-        )
-    })?;
+    let signer = match &deploy_cfg.account {
+        Some(account) => EvmSigner::Keystore(
+            Keystore::open(keystore::foundry_keystore_path(account), cfg.dry_run)
+                .map_err(enhance_error_with_suggestions)?,
+        ),
+        None => {
+            let private_key = std::env::var("PRIVATE_KEY").map_err(|_| {
+                create_smart_error(
+                    "PRIVATE_KEY environment variable not found",
+                    &[
+                        "Add to your .env file: PRIVATE_KEY=your_private_key",
+                        "Or sign with a foundry keystore: bargo evm deploy --account <name>",
+                        "⚠️  Keep your private key secure and never commit it to version control",
+                    ],
+                )
+            })?;
+            // Never echo the key in dry-run output
+            if cfg.dry_run {
+                EvmSigner::PrivateKey("$PRIVATE_KEY".to_string())
+            } else {
+                EvmSigner::PrivateKey(private_key)
+            }
+        }
+    };
 
     if cfg.dry_run {
+        println!("Would estimate deployment gas for Verifier contract");
+        foundry::estimate_verifier_deploy_gas(cfg, &rpc_url)
+            .map_err(enhance_error_with_suggestions)?;
         println!("Would deploy Verifier contract to network: {network}");
         println!("Would use RPC URL: {rpc_url}");
+        foundry::deploy_verifier_contract(cfg, &rpc_url, &signer, &deploy_cfg.constructor_args)
+            .map_err(enhance_error_with_suggestions)?;
         return Ok(());
     }
 
-    if cfg.verbose {
+    let gas_estimate = foundry::estimate_verifier_deploy_gas(cfg, &rpc_url)
+        .map_err(enhance_error_with_suggestions)?;
+    if !cfg.quiet {
+        println!("Estimated deployment gas: {gas_estimate}");
+    }
+    util::record_json_detail("gas_estimate", gas_estimate);
+    foundry::ensure_gas_within_limit(gas_estimate, deploy_cfg.max_gas)?;
+
+    let pkg_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)?;
+
+    if cfg.is_verbose() {
         info!("Deploying Verifier contract to {}", network);
     }
 
     let deploy_timer = Timer::start();
-    let contract_address = foundry::deploy_verifier_contract(cfg, &rpc_url, &private_key)
-        .map_err(enhance_error_with_suggestions)?;
+    let spinner = Spinner::start("Deploying Verifier contract", cfg.show_spinner());
+    let contract_address =
+        foundry::deploy_verifier_contract(cfg, &rpc_url, &signer, &deploy_cfg.constructor_args)
+            .map_err(enhance_error_with_suggestions)?;
+    spinner.finish();
 
-    // Save contract address for future commands
-    let address_file = std::path::Path::new("target/evm/.bargo_contract_address");
-    if let Some(parent) = address_file.parent() {
-        std::fs::create_dir_all(parent)
-            .wrap_err_with(|| format!("creating directory {}", parent.display()))
-            .ok();
+    // Record the address for verify-onchain; the contract is deployed either way
+    if let Err(e) = util::record_deployment(
+        Flavour::Evm,
+        &network.to_string(),
+        Deployment::new(&contract_address, None, &pkg_name)
+            .with_vk_hash(util::vk_hash(&util::get_vk_path(Flavour::Evm))),
+    ) {
+        warn!("Could not record contract address: {e}");
     }
-    std::fs::write(address_file, &contract_address)
-        .wrap_err_with(|| format!("writing contract address to {}", address_file.display()))
-        .ok();
 
     if !cfg.quiet {
         println!(
@@ -360,7 +679,21 @@ pub fn run_deploy(cfg: &Config, network: &str) -> Result<()> {
 /// # Returns
 /// * `Result<()>` - Success or error from workflow
 pub fn run_calldata(cfg: &Config) -> Result<()> {
-    load_env_vars();
+    run_calldata_with_config(cfg, &CalldataConfig::default())
+}
+
+/// Run the EVM calldata workflow with calldata-specific settings
+///
+/// # Arguments
+/// * `cfg` - CLI configuration
+/// * `calldata_cfg` - Calldata settings such as the output path
+///
+/// # Returns
+/// * `Result<()>` - Success or error from workflow
+pub fn run_calldata_with_config(cfg: &Config, calldata_cfg: &CalldataConfig) -> Result<()> {
+    load_env_vars(&cfg.project_root()?);
+
+    let calldata_path = calldata_cfg.output_path(Flavour::Evm);
 
     // Check that proof and public inputs exist (BB output for EVM)
     let proof_path = util::get_proof_path(Flavour::Evm);
@@ -371,13 +704,20 @@ pub fn run_calldata(cfg: &Config) -> Result<()> {
     }
 
     if cfg.dry_run {
-        println!("Would generate calldata from proof and public inputs");
-        println!("Would read: {}", proof_path.display());
-        println!("Would read: {}", public_inputs_path.display());
+        if !cfg.quiet {
+            println!("Would generate calldata from proof and public inputs");
+            println!("Would read: {}", proof_path.display());
+            println!("Would read: {}", public_inputs_path.display());
+            println!(
+                "Would extract the verify() ABI from: {}",
+                directories::get_verifier_contract_path().display()
+            );
+            println!("Would write calldata to: {}", calldata_path.display());
+        }
         return Ok(());
     }
 
-    if cfg.verbose {
+    if cfg.is_verbose() {
         info!("Generating calldata for EVM proof verification");
     }
 
@@ -406,13 +746,38 @@ pub fn run_calldata(cfg: &Config) -> Result<()> {
         .map(|chunk| format!("0x{}", hex::encode(chunk)))
         .collect();
 
-    let calldata = json!({
-        "proof": format!("0x{}", hex::encode(proof_bytes)),
+    let proof_hex = format!("0x{}", hex::encode(proof_bytes));
+    let mut calldata = json!({
+        "proof": proof_hex,
         "public_inputs": public_inputs_hex,
     });
 
+    // With the verifier's signature known, also store a ready-to-send call
+    let verifier_path = directories::get_verifier_contract_path();
+    let verify_function = if verifier_path.is_file() {
+        abi::read_verify_function(&verifier_path)?
+    } else {
+        None
+    };
+    match verify_function {
+        Some(function) => {
+            abi::write_abi(&abi::abi_path(), &function)?;
+            match abi::encode_verify_call(cfg, &function, &proof_hex, &public_inputs_hex) {
+                Ok(encoded) => {
+                    calldata["signature"] = json!(function.signature());
+                    calldata["calldata"] = json!(encoded);
+                }
+                Err(e) => warn!("Could not encode verify() calldata with cast: {e}"),
+            }
+        }
+        None => warn!(
+            "No verify() function found in {}; writing proof and public inputs only",
+            verifier_path.display()
+        ),
+    }
+
     // Save formatted calldata
-    let calldata_path = std::path::PathBuf::from("./target/evm/calldata.json");
+    util::ensure_parent_dir(&calldata_path)?;
     std::fs::write(
         &calldata_path,
         serde_json::to_vec_pretty(&calldata).unwrap(),
@@ -447,6 +812,14 @@ pub fn run_calldata(cfg: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Calldata saved by `bargo evm calldata`
+#[cfg(feature = "evm-foundry")]
+#[derive(Debug, serde::Deserialize)]
+struct EvmCalldata {
+    proof: String,
+    public_inputs: Vec<String>,
+}
+
 /// Run the EVM verify-onchain workflow
 ///
 /// # Arguments
@@ -456,84 +829,151 @@ pub fn run_calldata(cfg: &Config) -> Result<()> {
 /// * `Result<()>` - Success or error from workflow
 #[cfg(feature = "evm-foundry")]
 pub fn run_verify_onchain(cfg: &Config) -> Result<()> {
-    load_env_vars();
+    load_env_vars(&cfg.project_root()?);
 
     // Validate Foundry installation
     if !cfg.dry_run {
-        foundry::validate_foundry_installation().map_err(enhance_error_with_suggestions)?;
-    }
-
-    // Get contract address from saved file or environment
-    let contract_address = match std::fs::read_to_string("target/evm/.bargo_contract_address")
-        .wrap_err("reading saved contract address from target/evm/.bargo_contract_address")
-    {
-        Ok(saved_address) => saved_address.trim().to_string(),
-        Err(_) => std::env::var("CONTRACT_ADDRESS").map_err(|_| {
-            create_smart_error(
-                "Contract address not found",
-                &[
-                    "Run 'bargo evm deploy' first to deploy and save contract address",
-                    "Or set CONTRACT_ADDRESS environment variable",
-                    "The deploy command saves the contract address for verification",
-                ],
-            )
-        })?,
+        foundry::validate_foundry_installation(cfg).map_err(enhance_error_with_suggestions)?;
+    }
+
+    // Get contract address from the address book, a pre-book saved file or environment
+    let network = Network::parse(cfg.evm_network.as_deref().unwrap_or("sepolia"))?;
+    let address_file = util::target_dir(Flavour::Evm).join(".bargo_contract_address");
+    let saved_address = match util::latest_deployment(Flavour::Evm, &network.to_string())? {
+        Some(deployment) => {
+            let vk_path = util::get_vk_path(Flavour::Evm);
+            if let Some(warning) = util::stale_vk_warning(&deployment, &vk_path, "evm") {
+                warn!("{warning}");
+            }
+            Some(deployment.address)
+        }
+        None => std::fs::read_to_string(&address_file)
+            .ok()
+            .map(|saved| saved.trim().to_string()),
+    };
+    let contract_address = match saved_address {
+        Some(address) => address,
+        None => match std::env::var("CONTRACT_ADDRESS") {
+            Ok(address) => address,
+            Err(_) if cfg.dry_run => "<contract_address>".to_string(), // Placeholder for dry-run
+            Err(_) => {
+                return Err(create_smart_error(
+                    &format!("Contract address not found for network {network}"),
+                    &[
+                        &format!(
+                            "Run 'bargo evm deploy --network {network}' first to record the contract address"
+                        ),
+                        "Or set CONTRACT_ADDRESS environment variable",
+                        "List recorded deployments with 'bargo addresses'",
+                    ],
+                ));
+            }
+        },
     };
 
     // Check that calldata exists
-    let calldata_path = std::path::PathBuf::from("./target/evm/calldata.json");
+    let calldata_path = util::target_dir(Flavour::Evm).join("calldata.json");
     if !cfg.dry_run && !calldata_path.exists() {
         return Err(create_smart_error(
             "Calldata file not found",
             &[
                 "Run 'bargo evm calldata' first to generate calldata",
-                "Ensure the target/evm/calldata.json file exists",
+                &format!("Ensure the {} file exists", calldata_path.display()),
             ],
         ));
     }
 
-    let rpc_url = std::env::var("RPC_URL").map_err(|_| {
-        create_smart_error(
-            "RPC_URL environment variable not found",
-            &[
-                "Add to your .env file: RPC_URL=https://eth-mainnet.g.alchemy.com/v2/your_key",
-                "Ensure the .env file is loaded in your environment",
-            ],
-        )
-    })?;
+    let rpc_url = match (network.evm_rpc_url(), std::env::var("RPC_URL")) {
+        (Some(rpc_url), _) | (None, Ok(rpc_url)) => rpc_url,
+        (None, Err(_)) if cfg.dry_run => "$RPC_URL".to_string(),
+        (None, Err(_)) => return Err(missing_rpc_url_error(&network)),
+    };
+
+    // Read calldata for verification
+    let calldata = if calldata_path.exists() {
+        let calldata_content = std::fs::read_to_string(&calldata_path)
+            .wrap_err_with(|| format!("reading calldata file {}", calldata_path.display()))?;
+        serde_json::from_str::<EvmCalldata>(&calldata_content).map_err(|e| {
+            create_smart_error(
+                &format!("Invalid calldata file {}: {e}", calldata_path.display()),
+                &["Regenerate calldata with 'bargo evm calldata'"],
+            )
+        })?
+    } else {
+        // Only reachable in dry-run mode
+        EvmCalldata {
+            proof: "<proof>".to_string(),
+            public_inputs: vec!["<public_inputs>".to_string()],
+        }
+    };
 
     if cfg.dry_run {
         println!("Would verify proof on-chain at contract: {contract_address}");
         println!("Would use calldata from: {}", calldata_path.display());
-        return Ok(());
-    }
-
-    if cfg.verbose {
+    } else if cfg.is_verbose() {
         info!("Verifying proof on-chain at contract: {}", contract_address);
     }
 
-    // Read calldata for verification
-    let calldata_content = std::fs::read_to_string(&calldata_path)
-        .wrap_err_with(|| format!("reading calldata file {}", calldata_path.display()))?;
+    let verify_timer = Timer::start();
+    let spinner = Spinner::start("Verifying proof on-chain", cfg.show_spinner());
+    let verified = foundry::verify_proof_onchain(
+        cfg,
+        &contract_address,
+        &rpc_url,
+        &calldata.proof,
+        &calldata.public_inputs,
+    )
+    .map_err(enhance_error_with_suggestions)?;
+    spinner.finish();
 
-    if cfg.verbose {
-        info!("Using calldata: {}", calldata_content.trim());
+    if cfg.dry_run {
+        return Ok(());
     }
 
-    // This is a placeholder for actual on-chain verification
-    // The actual implementation would depend on the specific verifier contract interface
-    println!("🚧 On-chain verification functionality coming soon");
-    println!("Contract address: {contract_address}");
-    println!("RPC URL: {rpc_url}");
-    println!("Calldata: {}", calldata_path.display());
+    if !verified {
+        return Err(create_smart_error(
+            &format!("Verifier contract at {contract_address} rejected the proof"),
+            &[
+                "Regenerate the proof and calldata: bargo evm prove && bargo evm calldata",
+                "Ensure the deployed verifier was generated from the same circuit (bargo evm gen)",
+                "Check that RPC_URL points at the network the contract was deployed to",
+            ],
+        ));
+    }
+    util::record_verification(Flavour::Evm, &network.to_string(), &contract_address)?;
 
     if !cfg.quiet {
+        println!(
+            "{}",
+            success(&format!(
+                "Proof verified on-chain ({})",
+                verify_timer.elapsed()
+            ))
+        );
+
         let mut summary = OperationSummary::new();
         summary.add_operation(&format!(
-            "On-chain verification prepared for contract: {contract_address}"
+            "Proof verified on-chain by contract: {contract_address}"
         ));
         summary.print();
     }
 
     Ok(())
 }
+
+/// Error for a missing `RPC_URL`, with an example for `network`
+#[cfg(feature = "evm-foundry")]
+fn missing_rpc_url_error(network: &Network) -> color_eyre::eyre::Error {
+    let chain = network
+        .evm_chain_id()
+        .map(|id| format!(" (chain id {id})"))
+        .unwrap_or_default();
+    create_smart_error(
+        "RPC_URL environment variable not found",
+        &[
+            &format!("Add to your .env file: {}", network.evm_rpc_hint()),
+            &format!("RPC_URL must point at a {network} node{chain}"),
+            "Ensure the .env file is loaded in your environment",
+        ],
+    )
+}
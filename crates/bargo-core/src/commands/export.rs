@@ -0,0 +1,235 @@
+//! `bargo export`: bundle proof artifacts into a `.tar.gz` for sharing
+//!
+//! The archive holds one directory per exported flavour (`evm/`, `starknet/`)
+//! with the proof, vk, public inputs and, when present, the provenance
+//! manifest, plus a `bargo-export.json` header at the root:
+//!
+//! ```text
+//! bargo-export.json
+//! evm/proof
+//! evm/vk
+//! evm/public_inputs
+//! evm/manifest.json
+//! ```
+//!
+//! Unpacking a bundle back into `target/` (`bargo import`) is left to a
+//! follow-up; for now archives can be inspected with `tar -xzf`.
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use flate2::{Compression, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::{
+    cli::Backend,
+    commands::prove,
+    config::Config,
+    util::{self, Flavour, success},
+};
+
+/// Name of the metadata entry at the root of every export archive
+pub const EXPORT_HEADER_FILE: &str = "bargo-export.json";
+
+/// Contents of `bargo-export.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportHeader {
+    pub package: String,
+    /// UTC time the archive was written, in RFC 3339 format
+    pub created_at: String,
+    /// Archive paths of the bundled artifacts
+    pub files: Vec<String>,
+}
+
+/// Bundle the proof artifacts of each flavour selected by `backend` into `output`
+pub fn run(cfg: &Config, backend: Backend, output: &Path) -> Result<()> {
+    let pkg_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)?;
+    let flavours = prove::artifact_flavours(backend);
+    let output = util::project_path(output);
+
+    // Proofs are only worth sharing complete, so every flavour must be there
+    let required: Vec<PathBuf> = flavours
+        .iter()
+        .flat_map(|&flavour| {
+            [
+                util::get_proof_path(flavour),
+                util::get_vk_path(flavour),
+                util::get_public_inputs_path(flavour),
+            ]
+        })
+        .collect();
+    util::validate_files_exist(&required).map_err(util::enhance_error_with_suggestions)?;
+
+    let files = bundle_entries(&flavours);
+
+    if cfg.dry_run {
+        if !cfg.quiet {
+            println!("Would write {} with:", output.display());
+            for (_, name) in &files {
+                println!("  • {name}");
+            }
+        }
+        return Ok(());
+    }
+
+    write_bundle(&output, &pkg_name, &files)?;
+
+    if !cfg.quiet {
+        println!(
+            "{}",
+            success(&format!(
+                "Exported {} files to {}",
+                files.len(),
+                output.display()
+            ))
+        );
+    }
+
+    Ok(())
+}
+
+/// Files to bundle for each flavour, paired with their path inside the archive
+fn bundle_entries(flavours: &[Flavour]) -> Vec<(PathBuf, String)> {
+    flavours
+        .iter()
+        .flat_map(|&flavour| {
+            let dir = util::target_dir(flavour);
+            let prefix = dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            ["proof", "vk", "public_inputs", util::MANIFEST_FILE]
+                .into_iter()
+                .map(move |name| (dir.join(name), format!("{prefix}/{name}")))
+        })
+        // The manifest is optional; the rest were validated by the caller
+        .filter(|(path, _)| path.is_file())
+        .collect()
+}
+
+/// Write `files` and a `bargo-export.json` header to a gzipped tarball
+pub fn write_bundle(
+    output: &Path,
+    package: &str,
+    files: &[(PathBuf, String)],
+) -> Result<ExportHeader> {
+    let header = ExportHeader {
+        package: package.to_string(),
+        created_at: util::utc_timestamp(SystemTime::now()),
+        files: files.iter().map(|(_, name)| name.clone()).collect(),
+    };
+
+    util::ensure_parent_dir(output)?;
+    let archive = std::fs::File::create(output)
+        .wrap_err_with(|| format!("creating export archive {}", output.display()))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(archive, Compression::default()));
+
+    let header_json = serde_json::to_vec_pretty(&header).wrap_err("serializing export header")?;
+    let mut entry = tar::Header::new_gnu();
+    entry.set_size(header_json.len() as u64);
+    entry.set_mode(0o644);
+    entry.set_mtime(
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+    );
+    builder
+        .append_data(&mut entry, EXPORT_HEADER_FILE, header_json.as_slice())
+        .wrap_err("adding export header to archive")?;
+
+    for (path, name) in files {
+        builder
+            .append_path_with_name(path, name)
+            .wrap_err_with(|| format!("adding {} to archive", path.display()))?;
+    }
+
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .wrap_err_with(|| format!("finishing export archive {}", output.display()))?;
+
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::collections::BTreeMap;
+    use std::io::Read;
+    use tempfile::tempdir;
+
+    fn read_archive(path: &Path) -> BTreeMap<String, Vec<u8>> {
+        let mut archive = tar::Archive::new(GzDecoder::new(std::fs::File::open(path).unwrap()));
+        archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let name = entry.path().unwrap().to_string_lossy().into_owned();
+                let mut contents = Vec::new();
+                entry.read_to_end(&mut contents).unwrap();
+                (name, contents)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_write_bundle_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let evm = temp_dir.path().join("evm");
+        std::fs::create_dir_all(&evm).unwrap();
+        std::fs::write(evm.join("proof"), [0xab; 16]).unwrap();
+        std::fs::write(evm.join("vk"), [0xcd; 8]).unwrap();
+        std::fs::write(evm.join("public_inputs"), [0x01; 32]).unwrap();
+        let files: Vec<(PathBuf, String)> = ["proof", "vk", "public_inputs"]
+            .into_iter()
+            .map(|name| (evm.join(name), format!("evm/{name}")))
+            .collect();
+
+        let output = temp_dir.path().join("out/bundle.tar.gz");
+        let header = write_bundle(&output, "export_pkg", &files).unwrap();
+        assert_eq!(header.files, ["evm/proof", "evm/vk", "evm/public_inputs"]);
+
+        let entries = read_archive(&output);
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries["evm/proof"], [0xab; 16]);
+        assert_eq!(entries["evm/vk"], [0xcd; 8]);
+        assert_eq!(entries["evm/public_inputs"], [0x01; 32]);
+
+        let stored: ExportHeader = serde_json::from_slice(&entries[EXPORT_HEADER_FILE]).unwrap();
+        assert_eq!(stored, header);
+        assert_eq!(stored.package, "export_pkg");
+    }
+
+    #[test]
+    fn test_bundle_entries_include_manifest_when_present() {
+        let temp_dir = tempdir().unwrap();
+        util::set_project_dir(Some(temp_dir.path().to_path_buf()));
+        let evm = util::target_dir(Flavour::Evm);
+        std::fs::create_dir_all(&evm).unwrap();
+        for name in ["proof", "vk", "public_inputs"] {
+            std::fs::write(evm.join(name), name).unwrap();
+        }
+
+        let names = |entries: Vec<(PathBuf, String)>| {
+            entries
+                .into_iter()
+                .map(|(_, name)| name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(bundle_entries(&[Flavour::Evm])),
+            ["evm/proof", "evm/vk", "evm/public_inputs"]
+        );
+
+        std::fs::write(evm.join(util::MANIFEST_FILE), "{}").unwrap();
+        assert_eq!(
+            names(bundle_entries(&[Flavour::Evm])).last().unwrap(),
+            "evm/manifest.json"
+        );
+        util::set_project_dir(None);
+    }
+}
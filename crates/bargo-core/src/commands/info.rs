@@ -0,0 +1,345 @@
+//! Report what bargo has inferred about the current project
+//!
+//! `bargo info` is a read-only command that shows the resolved project root,
+//! package name, target directories, artifact status, recorded provenance
+//! manifests and proof metadata, followed by the external tool versions found
+//! on `PATH`.
+
+use color_eyre::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::{
+    commands::doctor::{self, ToolStatus},
+    config::Config,
+    util::{
+        self, Cell, Flavour, Manifest, NargoTomlKind, ProofMeta, Table, colors, format_file_size,
+        get_bytecode_path, get_proof_path, get_public_inputs_path, get_vk_path, get_witness_path,
+    },
+};
+
+/// Everything bargo has resolved about a project
+#[derive(Debug, Serialize)]
+pub struct ProjectInfo {
+    pub project_root: PathBuf,
+    pub package_name: String,
+    pub nargo_toml: NargoTomlKind,
+    pub targets: Vec<TargetInfo>,
+    pub tools: Vec<ToolStatus>,
+}
+
+/// Status of a single backend target directory
+#[derive(Debug, Serialize)]
+pub struct TargetInfo {
+    pub flavour: &'static str,
+    /// Directory relative to the project root
+    pub directory: PathBuf,
+    pub exists: bool,
+    pub artifacts: Vec<ArtifactInfo>,
+    /// Contents of `manifest.json`, or `null` if none has been written
+    pub manifest: Option<Manifest>,
+    /// Contents of the proof's `.meta.json` sidecar, or `null` if there is none
+    pub proof_meta: Option<ProofMeta>,
+}
+
+/// Status of a single artifact inside a target directory
+#[derive(Debug, Serialize)]
+pub struct ArtifactInfo {
+    pub name: &'static str,
+    /// Path relative to the project root
+    pub path: PathBuf,
+    pub exists: bool,
+    /// Size in bytes, or `null` if the file does not exist
+    pub size: Option<u64>,
+}
+
+/// Print resolved project metadata
+pub fn run(cfg: &Config) -> Result<()> {
+    let info = collect(cfg, &cfg.working_dir()?)?;
+
+    if cfg.json {
+        println!("{}", serde_json::to_string(&info)?);
+    } else if !cfg.quiet {
+        print_info(&info);
+    }
+
+    Ok(())
+}
+
+/// Resolve project metadata starting from `current_dir`
+pub fn collect(cfg: &Config, current_dir: &Path) -> Result<ProjectInfo> {
+    let project_root = util::find_project_root(current_dir)?;
+    let nargo_toml = util::detect_nargo_toml_kind(&project_root.join("Nargo.toml"))?;
+    let package_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &project_root)?;
+
+    let targets = flavours()
+        .into_iter()
+        .map(|(flavour, name)| target_info(&project_root, &package_name, flavour, name))
+        .collect();
+
+    Ok(ProjectInfo {
+        project_root,
+        package_name,
+        nargo_toml,
+        targets,
+        tools: doctor::probe_tools(cfg),
+    })
+}
+
+/// Backend flavours available with the enabled features, with display names
+fn flavours() -> Vec<(Flavour, &'static str)> {
+    #[allow(unused_mut)]
+    let mut flavours = vec![(Flavour::Bb, "bb"), (Flavour::Evm, "evm")];
+    #[cfg(feature = "cairo")]
+    flavours.push((Flavour::Starknet, "starknet"));
+    flavours
+}
+
+/// Describe the target directory and artifacts for one flavour
+fn target_info(
+    project_root: &Path,
+    package_name: &str,
+    flavour: Flavour,
+    name: &'static str,
+) -> TargetInfo {
+    let directory = util::target_dir(flavour);
+    let artifacts = [
+        ("bytecode", get_bytecode_path(package_name, flavour)),
+        ("witness", get_witness_path(package_name, flavour)),
+        ("proof", get_proof_path(flavour)),
+        ("vk", get_vk_path(flavour)),
+        ("public_inputs", get_public_inputs_path(flavour)),
+    ]
+    .into_iter()
+    .map(|(name, path)| {
+        let size = std::fs::metadata(project_root.join(&path))
+            .ok()
+            .map(|metadata| metadata.len());
+        ArtifactInfo {
+            name,
+            path,
+            exists: size.is_some(),
+            size,
+        }
+    })
+    .collect();
+
+    // A damaged manifest should not stop info from reporting everything else
+    let manifest_path = project_root.join(util::manifest_path(flavour));
+    let manifest = util::read_manifest(&manifest_path).unwrap_or_else(|e| {
+        warn!("Ignoring {}: {e}", manifest_path.display());
+        None
+    });
+    let meta_path = project_root.join(util::meta_path(&get_proof_path(flavour)));
+    let proof_meta = util::read_meta(&meta_path).unwrap_or_else(|e| {
+        warn!("Ignoring {}: {e}", meta_path.display());
+        None
+    });
+
+    TargetInfo {
+        flavour: name,
+        exists: project_root.join(&directory).is_dir(),
+        directory,
+        artifacts,
+        manifest,
+        proof_meta,
+    }
+}
+
+/// Print project metadata in human-readable form
+fn print_info(info: &ProjectInfo) {
+    println!("📁 Project root: {}", info.project_root.display());
+    println!("📦 Package: {}", info.package_name);
+    println!("📄 Nargo.toml: {}", info.nargo_toml.as_str());
+
+    println!("\n🎯 Targets:");
+    for target in &info.targets {
+        let status = if target.exists { "" } else { " (not created)" };
+        println!(
+            "  {} → {}{}",
+            target.flavour,
+            target.directory.display(),
+            status
+        );
+        let mut artifacts = Table::new(&["ARTIFACT", "PATH", "SIZE", "STATUS"]).with_indent(4);
+        for artifact in &target.artifacts {
+            let (size, status) = if artifact.exists {
+                (
+                    format_file_size(&info.project_root.join(&artifact.path)),
+                    Cell::colored("ok", colors::GREEN),
+                )
+            } else {
+                ("-".to_string(), Cell::colored("missing", colors::RED))
+            };
+            artifacts.row(vec![
+                artifact.name.into(),
+                artifact.path.display().to_string().into(),
+                size.into(),
+                status,
+            ]);
+        }
+        artifacts.print();
+        if let Some(manifest) = &target.manifest {
+            println!("    📜 manifest:");
+            for entry in &manifest.artifacts {
+                println!(
+                    "       {} sha256:{} ({}, {})",
+                    entry.path.display(),
+                    &entry.sha256[..entry.sha256.len().min(12)],
+                    entry.operation,
+                    entry.created_at
+                );
+            }
+        }
+        if let Some(meta) = &target.proof_meta {
+            let commit = meta
+                .commit
+                .as_deref()
+                .map_or("no git commit", |commit| &commit[..commit.len().min(12)]);
+            println!(
+                "    🧾 proof: {} at {} ({})",
+                meta.package, commit, meta.created_at
+            );
+        }
+    }
+
+    println!("\n🔧 Tools:");
+    let mut tools = Table::new(&["TOOL", "VERSION", "PATH", "STATUS"]).with_indent(2);
+    for tool in &info.tools {
+        let row: Vec<Cell> = match &tool.path {
+            Some(path) => vec![
+                tool.name.into(),
+                tool.version.as_deref().unwrap_or("unknown").into(),
+                path.display().to_string().into(),
+                Cell::colored("ok", colors::GREEN),
+            ],
+            None => vec![
+                tool.name.into(),
+                "-".into(),
+                "-".into(),
+                Cell::colored("not found", colors::RED),
+            ],
+        };
+        tools.row(row);
+    }
+    tools.print();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{ArtifactSuffix, ColorChoice};
+    use crate::config::ToolPaths;
+    use crate::fs::DryRunFs;
+    use crate::runner::DryRunRunner;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn dry_run_config() -> Config {
+        Config {
+            verbose: 0,
+            dry_run: true,
+            pkg: None,
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: Arc::new(DryRunRunner::new()),
+            fs: Arc::new(DryRunFs::new()),
+        }
+    }
+
+    #[test]
+    fn test_collect_reports_artifacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("Nargo.toml"),
+            "[package]\nname = \"info_pkg\"\ntype = \"bin\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("target/bb")).unwrap();
+        std::fs::write(root.join("target/bb/info_pkg.json"), [0u8; 16]).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+
+        let info = collect(&dry_run_config(), &root.join("src")).unwrap();
+
+        assert_eq!(info.project_root, root);
+        assert_eq!(info.package_name, "info_pkg");
+        assert_eq!(info.nargo_toml, NargoTomlKind::Package);
+
+        let bb = &info.targets[0];
+        assert_eq!(bb.flavour, "bb");
+        assert!(bb.exists);
+        assert_eq!(bb.artifacts[0].name, "bytecode");
+        assert_eq!(bb.artifacts[0].size, Some(16));
+        assert!(!bb.artifacts[1].exists);
+
+        let evm = &info.targets[1];
+        assert!(!evm.exists);
+        assert!(evm.artifacts.iter().all(|artifact| !artifact.exists));
+        assert!(bb.manifest.is_none());
+    }
+
+    #[test]
+    fn test_collect_reads_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("Nargo.toml"),
+            "[package]\nname = \"info_pkg\"\ntype = \"bin\"\n",
+        )
+        .unwrap();
+        let bytecode = root.join("target/bb/info_pkg.json");
+        std::fs::create_dir_all(bytecode.parent().unwrap()).unwrap();
+        std::fs::write(&bytecode, "{}").unwrap();
+        util::write_manifest(
+            &root.join("target/bb/manifest.json"),
+            "build",
+            std::slice::from_ref(&bytecode),
+            &Default::default(),
+        )
+        .unwrap();
+
+        let info = collect(&dry_run_config(), root).unwrap();
+        let manifest = info.targets[0].manifest.as_ref().unwrap();
+        assert_eq!(manifest.artifacts.len(), 1);
+        assert_eq!(manifest.artifacts[0].path, bytecode);
+        assert_eq!(manifest.artifacts[0].operation, "build");
+        assert!(info.targets[0].proof_meta.is_none());
+    }
+
+    #[test]
+    fn test_collect_reads_proof_meta() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("Nargo.toml"),
+            "[package]\nname = \"info_pkg\"\ntype = \"bin\"\n",
+        )
+        .unwrap();
+        let meta = ProofMeta::new("info_pkg", Some("ab".repeat(20)), Default::default());
+        util::write_meta(&root.join("target/evm/proof.meta.json"), &meta).unwrap();
+
+        let info = collect(&dry_run_config(), root).unwrap();
+        assert_eq!(info.targets[1].flavour, "evm");
+        assert_eq!(info.targets[1].proof_meta, Some(meta));
+        assert!(info.targets[0].proof_meta.is_none());
+    }
+
+    #[test]
+    fn test_collect_outside_project_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let error = collect(&dry_run_config(), temp_dir.path()).unwrap_err();
+        assert!(error.to_string().contains("Nargo.toml"));
+    }
+}
@@ -0,0 +1,205 @@
+//! Encrypted keystore accounts used to sign deployments
+//!
+//! `--account <name>` selects a foundry (`~/.foundry/keystores/<name>`) or
+//! starkli (`~/.starkli-wallets/<name>/keystore.json`) keystore instead of a
+//! raw private key from `.env` / `.secrets`. A path to a keystore file is
+//! accepted as well. The password is read from `BARGO_KEYSTORE_PASSWORD` or
+//! prompted for without echo, and is only ever handed to the signing tool
+//! through a private file or environment variable.
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::util::{self, BargoError, create_smart_error};
+
+/// Environment variable checked for the keystore password before prompting
+pub const PASSWORD_ENV: &str = "BARGO_KEYSTORE_PASSWORD";
+
+/// An encrypted keystore file and, once unlocked, its password
+#[derive(Clone, PartialEq, Eq)]
+pub struct Keystore {
+    pub path: PathBuf,
+    password: Option<String>,
+}
+
+impl std::fmt::Debug for Keystore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Keystore")
+            .field("path", &self.path)
+            .field("password", &self.password.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
+
+impl Keystore {
+    /// Keystore whose password is already known
+    pub fn new(path: PathBuf, password: String) -> Self {
+        Self {
+            path,
+            password: Some(password),
+        }
+    }
+
+    /// Check that `path` holds an encrypted keystore and obtain its password
+    ///
+    /// The password comes from `BARGO_KEYSTORE_PASSWORD` when set, otherwise
+    /// it is prompted for on the terminal without echo.
+    pub fn unlock(path: PathBuf) -> Result<Self> {
+        validate_keystore(&path)?;
+
+        let password = match std::env::var(PASSWORD_ENV) {
+            Ok(password) => password,
+            Err(_) => {
+                rpassword::prompt_password(format!("Password for keystore {}: ", path.display()))
+                    .map_err(|_| {
+                        create_smart_error(
+                            "Could not read the keystore password",
+                            &[
+                                &format!("Set {PASSWORD_ENV} when running without a terminal"),
+                                "Run bargo from an interactive terminal to be prompted",
+                            ],
+                        )
+                    })?
+            }
+        };
+
+        Ok(Self::new(path, password))
+    }
+
+    /// Unlock `path`, or use a placeholder in dry-run mode
+    pub fn open(path: PathBuf, dry_run: bool) -> Result<Self> {
+        if dry_run {
+            Ok(Self::placeholder(path))
+        } else {
+            Self::unlock(path)
+        }
+    }
+
+    /// Keystore used in dry-run mode: the file is not read and no password is
+    /// requested
+    pub fn placeholder(path: PathBuf) -> Self {
+        Self {
+            path,
+            password: None,
+        }
+    }
+
+    /// Password of an unlocked keystore
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+
+    /// Write the password to a private temporary file for `--password-file`
+    ///
+    /// The file is removed when the returned handle is dropped.
+    pub fn password_file(&self) -> Result<Option<tempfile::NamedTempFile>> {
+        let Some(password) = &self.password else {
+            return Ok(None);
+        };
+        let mut file =
+            tempfile::NamedTempFile::new().wrap_err("creating keystore password file")?;
+        file.write_all(password.as_bytes())
+            .wrap_err("writing keystore password file")?;
+        Ok(Some(file))
+    }
+}
+
+/// Keystore file for a foundry account name or keystore path
+pub fn foundry_keystore_path(account: &str) -> PathBuf {
+    existing_file(account)
+        .unwrap_or_else(|| home_dir().join(".foundry").join("keystores").join(account))
+}
+
+/// Keystore file for a starkli account name or keystore path
+pub fn starkli_keystore_path(account: &str) -> PathBuf {
+    existing_file(account).unwrap_or_else(|| {
+        home_dir()
+            .join(".starkli-wallets")
+            .join(account)
+            .join("keystore.json")
+    })
+}
+
+/// `account` resolved against the project directory, if it names a file
+fn existing_file(account: &str) -> Option<PathBuf> {
+    let path = util::project_path(account);
+    path.is_file().then_some(path)
+}
+
+fn home_dir() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+}
+
+/// Ensure `path` is an encrypted JSON keystore (Web3 Secret Storage format)
+fn validate_keystore(path: &Path) -> Result<()> {
+    if !path.is_file() {
+        return Err(create_smart_error(
+            &format!("Keystore not found: {}", path.display()),
+            &[
+                "Create a foundry keystore with 'cast wallet import <name> --interactive'",
+                "Create a starkli keystore with 'starkli signer keystore new <path>'",
+                "Pass a path to the keystore file with --account",
+            ],
+        ));
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("reading keystore {}", path.display()))?;
+    let is_keystore = serde_json::from_str::<serde_json::Value>(&contents)
+        .ok()
+        .is_some_and(|json| {
+            json.get("crypto")
+                .or_else(|| json.get("Crypto"))
+                .is_some_and(serde_json::Value::is_object)
+        });
+    if !is_keystore {
+        return Err(BargoError::ParseFailure {
+            path: path.to_path_buf(),
+            message: "expected an encrypted JSON keystore with a crypto section".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_keystore() {
+        let temp_dir = TempDir::new().unwrap();
+        let keystore = temp_dir.path().join("deployer");
+        std::fs::write(
+            &keystore,
+            r#"{"version":3,"crypto":{"cipher":"aes-128-ctr"}}"#,
+        )
+        .unwrap();
+        assert!(validate_keystore(&keystore).is_ok());
+
+        let not_keystore = temp_dir.path().join("account.json");
+        std::fs::write(&not_keystore, r#"{"version":1,"variant":{}}"#).unwrap();
+        let error = validate_keystore(&not_keystore).unwrap_err();
+        assert!(error.to_string().contains("encrypted JSON keystore"));
+
+        let missing = validate_keystore(&temp_dir.path().join("missing")).unwrap_err();
+        assert!(missing.to_string().contains("Keystore not found"));
+    }
+
+    #[test]
+    fn test_password_file_and_debug_hide_password() {
+        let keystore = Keystore::new(PathBuf::from("deployer"), "hunter2".to_string());
+        assert!(!format!("{keystore:?}").contains("hunter2"));
+
+        let file = keystore.password_file().unwrap().unwrap();
+        assert_eq!(std::fs::read_to_string(file.path()).unwrap(), "hunter2");
+
+        let placeholder = Keystore::placeholder(PathBuf::from("deployer"));
+        assert!(placeholder.password_file().unwrap().is_none());
+    }
+}
@@ -1,11 +1,23 @@
+pub mod bench;
 pub mod build;
 pub mod evm;
 
 pub mod check;
 pub mod clean;
+pub mod config;
 pub mod rebuild;
+pub mod prove;
+pub mod verify;
 pub mod doctor;
+pub mod info;
+pub mod status;
+pub mod addresses;
+pub mod export;
+pub mod convert;
+pub mod report;
 pub mod common;
+pub mod keystore;
+pub mod watch;
 
 #[cfg(feature = "cairo")]
 pub mod cairo;
@@ -0,0 +1,258 @@
+//! Backend-neutral `bargo prove`
+//!
+//! Dispatches to the EVM or Cairo prove workflow through the [`Backend`](crate::backend::Backend)
+//! trait, so simple projects can run `bargo prove` without picking a target chain.
+//! Artifacts land in the chosen backend's target directory:
+//!
+//! - `--backend bb` (default): `target/evm/proof`, `target/evm/vk`, `target/evm/public_inputs`
+//! - `--backend starknet`: `target/starknet/proof`, `target/starknet/vk`, `target/starknet/public_inputs`
+//! - `--backend all`: both of the above, EVM first
+//!
+//! Without `--parallel` the backends prove in turn and the first failure stops
+//! the rest, unless `--keep-going` is set.
+//!
+//! With `--parallel` each backend proves on its own thread. The backends only
+//! read the shared `target/bb/` build output and write to their own target
+//! directory, so they never touch the same files. Per-backend output is
+//! suppressed and a combined summary is printed once every backend finishes.
+//!
+//! With `--recursive` bb also writes `proof_fields.json`, `vk_fields.json` and
+//! `public_inputs_fields.json` next to each backend's binary artifacts, ready to
+//! pass into a Noir circuit that verifies the proof recursively.
+
+use color_eyre::Result;
+use color_eyre::eyre::{WrapErr, eyre};
+
+use crate::{
+    backend::{BackendConfig, BackendKind, backend_for},
+    cli::Backend,
+    commands::common,
+    config::{Config, ProveConfig},
+    util::{self, Flavour, OperationSummary},
+};
+
+/// Generate a proof with each backend selected by `backend`
+pub fn run(cfg: &Config, backend: Backend, prove_cfg: &ProveConfig, parallel: bool) -> Result<()> {
+    prove_cfg.validate()?;
+    let kinds = backend_kinds(backend);
+    if parallel && kinds.len() > 1 {
+        return run_parallel(cfg, &kinds, prove_cfg);
+    }
+
+    common::run_steps(
+        cfg,
+        kinds,
+        |kind| format!("{} prove", label(*kind)),
+        |kind| prove_with(cfg, kind, prove_cfg),
+    )
+}
+
+/// Prove with every backend in `kinds` concurrently
+///
+/// Every backend runs to completion even if another one fails; failures are
+/// reported together once all threads have joined.
+fn run_parallel(cfg: &Config, kinds: &[BackendKind], prove_cfg: &ProveConfig) -> Result<()> {
+    let mut summary = OperationSummary::new();
+    // Spinners and success lines from concurrent workflows would interleave
+    let thread_cfg = Config {
+        quiet: true,
+        ..cfg.clone()
+    };
+
+    let results: Vec<(BackendKind, Result<()>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = kinds
+            .iter()
+            .map(|&kind| {
+                let thread_cfg = &thread_cfg;
+                let handle = scope.spawn(move || {
+                    // Path settings are stored per thread
+                    thread_cfg.apply_paths()?;
+                    prove_with(thread_cfg, kind, prove_cfg)
+                });
+                (kind, handle)
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|(kind, handle)| {
+                let result = handle
+                    .join()
+                    .unwrap_or_else(|_| Err(eyre!("{} prover thread panicked", label(kind))));
+                (kind, result)
+            })
+            .collect()
+    });
+
+    let mut failures = Vec::new();
+    for (kind, result) in results {
+        match result {
+            Ok(()) => summary.add_operation(&format!(
+                "{} proof and VK generated ({})",
+                label(kind),
+                util::target_dir(flavour(kind)).display()
+            )),
+            Err(e) => failures.push((kind, e)),
+        }
+    }
+
+    if !cfg.quiet {
+        summary.print();
+    }
+
+    match failures.len() {
+        0 => Ok(()),
+        1 => {
+            let (kind, error) = failures.remove(0);
+            Err(error).wrap_err(format!("{} proof generation failed", label(kind)))
+        }
+        _ => {
+            let details = failures
+                .iter()
+                .map(|(kind, error)| format!("  • {}: {error:#}", label(*kind)))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Err(eyre!(
+                "Proof generation failed for {} backends:\n{details}",
+                failures.len()
+            ))
+        }
+    }
+}
+
+/// Configure and run a single backend's prove step
+pub fn prove_with(cfg: &Config, kind: BackendKind, prove_cfg: &ProveConfig) -> Result<()> {
+    let mut backend = backend_for(kind);
+    backend.configure(BackendConfig::Prove(prove_cfg.clone()))?;
+    backend.prove(cfg)
+}
+
+/// Human-readable backend name for summaries and errors
+pub fn label(kind: BackendKind) -> &'static str {
+    match kind {
+        #[cfg(feature = "cairo")]
+        BackendKind::Cairo => "Starknet",
+        BackendKind::Evm => "EVM",
+    }
+}
+
+/// Artifact flavour a backend writes its proof to
+pub fn flavour(kind: BackendKind) -> Flavour {
+    match kind {
+        #[cfg(feature = "cairo")]
+        BackendKind::Cairo => Flavour::Starknet,
+        BackendKind::Evm => Flavour::Evm,
+    }
+}
+
+/// Backend implementations that a `--backend` choice proves with
+pub fn backend_kinds(backend: Backend) -> Vec<BackendKind> {
+    match backend {
+        Backend::Bb => vec![BackendKind::Evm],
+        #[cfg(feature = "cairo")]
+        Backend::Starknet => vec![BackendKind::Cairo],
+        #[cfg(feature = "cairo")]
+        Backend::All => vec![BackendKind::Evm, BackendKind::Cairo],
+        #[cfg(not(feature = "cairo"))]
+        Backend::All => vec![BackendKind::Evm],
+    }
+}
+
+/// Artifact flavours written by each backend a `--backend` choice proves with
+pub fn artifact_flavours(backend: Backend) -> Vec<Flavour> {
+    backend_kinds(backend).into_iter().map(flavour).collect()
+}
+
+#[cfg(all(test, feature = "cairo"))]
+mod tests {
+    use super::*;
+    use crate::cli::{ArtifactSuffix, ColorChoice};
+    use crate::config::ToolPaths;
+    use crate::fs::DryRunFs;
+    use crate::runner::{CapturedOutput, CmdSpec, DryRunRunner, Runner};
+    use std::sync::Arc;
+
+    fn dry_run_config(runner: Arc<dyn Runner>, keep_going: bool) -> Config {
+        Config {
+            verbose: 0,
+            dry_run: true,
+            pkg: Some("parallel_pkg".to_string()),
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going,
+            runner,
+            fs: Arc::new(DryRunFs::new()),
+        }
+    }
+
+    /// Records every command like a dry run, but fails those writing to `target/evm`
+    #[derive(Debug, Default)]
+    struct EvmFailsRunner(DryRunRunner);
+
+    impl Runner for EvmFailsRunner {
+        fn run(&self, spec: &CmdSpec) -> Result<()> {
+            self.run_capture(spec).map(|_| ())
+        }
+
+        fn run_capture(&self, spec: &CmdSpec) -> Result<CapturedOutput> {
+            self.0.run_capture(spec)?;
+            if spec.args.iter().any(|arg| arg.starts_with("target/evm")) {
+                return Err(eyre!("bb crashed"));
+            }
+            Ok(CapturedOutput::default())
+        }
+    }
+
+    impl EvmFailsRunner {
+        fn ran_starknet(&self) -> bool {
+            self.0.history().iter().any(|(spec, _)| {
+                spec.args
+                    .iter()
+                    .any(|arg| arg.starts_with("target/starknet"))
+            })
+        }
+    }
+
+    #[test]
+    fn test_parallel_prove_records_both_backends() {
+        let runner = Arc::new(DryRunRunner::new());
+        let cfg = dry_run_config(runner.clone(), false);
+
+        run(&cfg, Backend::All, &ProveConfig::default(), true).unwrap();
+
+        let outputs: Vec<String> = runner
+            .history()
+            .iter()
+            .flat_map(|(spec, _)| spec.args.clone())
+            .filter(|arg| arg.starts_with("target/"))
+            .collect();
+        assert!(outputs.iter().any(|arg| arg.starts_with("target/evm")));
+        assert!(outputs.iter().any(|arg| arg.starts_with("target/starknet")));
+    }
+
+    #[test]
+    fn test_keep_going_proves_remaining_backend_after_failure() {
+        // Fail-fast: the EVM failure stops before Starknet runs
+        let runner = Arc::new(EvmFailsRunner::default());
+        let cfg = dry_run_config(runner.clone(), false);
+        assert!(run(&cfg, Backend::All, &ProveConfig::default(), false).is_err());
+        assert!(!runner.ran_starknet());
+
+        // --keep-going: Starknet still proves and the EVM failure is reported
+        let runner = Arc::new(EvmFailsRunner::default());
+        let cfg = dry_run_config(runner.clone(), true);
+        let error = run(&cfg, Backend::All, &ProveConfig::default(), false).unwrap_err();
+        assert!(runner.ran_starknet());
+        assert_eq!(error.to_string(), "EVM prove failed");
+        assert!(format!("{error:#}").contains("bb crashed"));
+    }
+}
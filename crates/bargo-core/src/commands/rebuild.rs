@@ -14,7 +14,7 @@ pub fn run(cfg: &Config, backend: Backend) -> Result<()> {
     let mut summary = OperationSummary::new();
 
     // Step 1: Clean
-    if cfg.verbose {
+    if cfg.is_verbose() {
         info!("Step 1/2: Cleaning artifacts for backend: {:?}", backend);
     }
 
@@ -36,7 +36,7 @@ pub fn run(cfg: &Config, backend: Backend) -> Result<()> {
     }
 
     // Step 2: Build
-    if cfg.verbose {
+    if cfg.is_verbose() {
         info!("Step 2/2: Building from scratch");
     }
 
@@ -44,19 +44,22 @@ pub fn run(cfg: &Config, backend: Backend) -> Result<()> {
         println!("\n🔨 Building circuit...");
     }
 
-    let pkg_name =
-        util::get_package_name(cfg.pkg.as_ref()).map_err(util::enhance_error_with_suggestions)?;
+    let pkg_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &cfg.working_dir()?)
+        .map_err(util::enhance_error_with_suggestions)?;
 
     if cfg.dry_run {
-        return run_nargo_command(cfg, &["execute"]);
+        run_nargo_command(cfg, &["execute"])?;
+        return util::organize_build_artifacts(cfg.fs.as_ref(), &pkg_name, Flavour::Bb);
     }
 
     let timer = Timer::start();
+    let partial = util::PartialArtifacts::track(util::nargo_output_paths(&pkg_name));
     let result = run_nargo_command(cfg, &["execute"]);
 
     match result {
         Ok(()) => {
-            util::organize_build_artifacts(&pkg_name, Flavour::Bb)?;
+            partial.finish();
+            util::organize_build_artifacts(cfg.fs.as_ref(), &pkg_name, Flavour::Bb)?;
 
             if !cfg.quiet {
                 let bytecode_path = util::get_bytecode_path(&pkg_name, Flavour::Bb);
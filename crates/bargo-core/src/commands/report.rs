@@ -0,0 +1,45 @@
+//! `bargo report`: summarize a `--summary-file` session log
+//!
+//! Each command run with `--summary-file` appends one JSON line recording its
+//! package, backend, outcome and timings. A script that chains several
+//! commands can then print the whole session at once:
+//!
+//! ```text
+//! bargo --summary-file session.jsonl build
+//! bargo --summary-file session.jsonl evm prove
+//! bargo report session.jsonl
+//! ```
+
+use color_eyre::Result;
+use std::path::Path;
+
+use crate::{
+    config::Config,
+    util::{self, create_smart_error},
+};
+
+/// Print the records in the session log at `file`
+pub fn run(cfg: &Config, file: &Path) -> Result<()> {
+    let path = util::project_path(file);
+    if !path.exists() {
+        return Err(create_smart_error(
+            &format!("Session log {} not found", path.display()),
+            &["Run commands with --summary-file <PATH> to record a session"],
+        ));
+    }
+    let records = util::read_session_records(&path)?;
+
+    if cfg.json {
+        println!("{}", serde_json::to_string(&records)?);
+    } else if !cfg.quiet {
+        if records.is_empty() {
+            println!("No commands recorded in {}", path.display());
+            return Ok(());
+        }
+        for line in util::format_session_report(&records) {
+            println!("{line}");
+        }
+    }
+
+    Ok(())
+}
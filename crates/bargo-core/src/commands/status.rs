@@ -0,0 +1,349 @@
+//! Summarize how far each backend has progressed through the pipeline
+//!
+//! `bargo status` is read-only: it inspects the artifacts on disk and the
+//! address book to show, per backend, whether the circuit has been built and
+//! proved, whether a verifier contract has been generated, deployed and
+//! accepted a proof on-chain, and which command comes next.
+
+use color_eyre::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::{
+    config::Config,
+    util::{
+        self, Cell, Flavour, Table, colors, get_bytecode_path, get_proof_path, get_vk_path,
+        get_witness_path, resolve_compressed,
+    },
+};
+
+/// Pipeline progress of every backend in a project
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    pub project_root: PathBuf,
+    pub package_name: String,
+    pub backends: Vec<BackendStatus>,
+}
+
+/// Pipeline progress of a single backend
+#[derive(Debug, Serialize)]
+pub struct BackendStatus {
+    pub backend: &'static str,
+    pub steps: Vec<StepStatus>,
+    /// Command that completes the first unfinished step, or `null` when done
+    pub next: Option<String>,
+}
+
+/// One step of the pipeline
+#[derive(Debug, Serialize)]
+pub struct StepStatus {
+    pub name: &'static str,
+    pub done: bool,
+    /// What was found, or what is missing
+    pub detail: String,
+}
+
+impl StepStatus {
+    fn new(name: &'static str, done: bool, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            done,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Print the pipeline checklist
+pub fn run(cfg: &Config) -> Result<()> {
+    let report = collect(cfg, &cfg.working_dir()?)?;
+
+    if cfg.json {
+        println!("{}", serde_json::to_string(&report)?);
+    } else if !cfg.quiet {
+        print_status(&report);
+    }
+
+    Ok(())
+}
+
+/// Inspect the project found from `current_dir`
+pub fn collect(cfg: &Config, current_dir: &Path) -> Result<StatusReport> {
+    let project_root = util::find_project_root(current_dir)?;
+    let package_name = util::get_package_name_in_directory(cfg.pkg.as_ref(), &project_root)?;
+
+    #[allow(unused_mut)]
+    let mut backends = vec![backend_status(
+        &project_root,
+        &package_name,
+        Flavour::Evm,
+        "evm",
+        crate::commands::evm::directories::get_verifier_contract_path(),
+    )];
+    #[cfg(feature = "cairo")]
+    backends.push(backend_status(
+        &project_root,
+        &package_name,
+        Flavour::Starknet,
+        "cairo",
+        crate::commands::cairo::directories::get_cairo_contracts_dir().join("Scarb.toml"),
+    ));
+
+    Ok(StatusReport {
+        project_root,
+        package_name,
+        backends,
+    })
+}
+
+/// Check each pipeline step of one backend
+///
+/// `command` is the bargo subcommand for the backend, and `verifier` the file
+/// `<command> gen` writes.
+fn backend_status(
+    project_root: &Path,
+    package_name: &str,
+    flavour: Flavour,
+    command: &'static str,
+    verifier: PathBuf,
+) -> BackendStatus {
+    let exists = |path: &Path| project_root.join(path).is_file();
+    let missing = |paths: &[&PathBuf]| {
+        let missing: Vec<String> = paths
+            .iter()
+            .filter(|path| !exists(path))
+            .map(|path| path.display().to_string())
+            .collect();
+        format!("missing {}", missing.join(", "))
+    };
+
+    let bytecode = get_bytecode_path(package_name, Flavour::Bb);
+    let witness = get_witness_path(package_name, Flavour::Bb);
+    let built = exists(&bytecode) && exists(&witness);
+
+    let proof = resolve_compressed(&project_root.join(get_proof_path(flavour)));
+    let vk = get_vk_path(flavour);
+    let proved = proof.is_file() && exists(&vk);
+
+    // A damaged address book should not stop status from reporting the rest
+    let book_path = project_root.join(util::address_book_path(flavour));
+    let book = util::read_address_book(&book_path).unwrap_or_else(|e| {
+        warn!("Ignoring {}: {e}", book_path.display());
+        Default::default()
+    });
+    let deployed = book.newest();
+
+    let steps = vec![
+        StepStatus::new(
+            "built",
+            built,
+            if built {
+                bytecode.display().to_string()
+            } else {
+                missing(&[&bytecode, &witness])
+            },
+        ),
+        StepStatus::new(
+            "proved",
+            proved,
+            if proved {
+                proof
+                    .strip_prefix(project_root)
+                    .unwrap_or(&proof)
+                    .display()
+                    .to_string()
+            } else {
+                missing(&[&get_proof_path(flavour), &vk])
+            },
+        ),
+        StepStatus::new(
+            "verifier generated",
+            exists(&verifier),
+            if exists(&verifier) {
+                verifier.display().to_string()
+            } else {
+                missing(&[&verifier])
+            },
+        ),
+        StepStatus::new(
+            "deployed",
+            deployed.is_some(),
+            match deployed {
+                Some((network, deployment)) => format!("{} on {network}", deployment.address),
+                None => "no deployment recorded".to_string(),
+            },
+        ),
+        StepStatus::new(
+            "verified on-chain",
+            deployed.is_some_and(|(_, deployment)| deployment.verified_at.is_some()),
+            match deployed.and_then(|(_, deployment)| deployment.verified_at.as_deref()) {
+                Some(verified_at) => format!("at {verified_at}"),
+                None => "not verified yet".to_string(),
+            },
+        ),
+    ];
+
+    let next = steps.iter().position(|step| !step.done).map(|index| {
+        let network = deployed.map_or(String::new(), |(network, _)| {
+            format!(" --network {network}")
+        });
+        match index {
+            0 => "bargo build".to_string(),
+            1 => format!("bargo {command} prove"),
+            2 => format!("bargo {command} gen"),
+            3 => format!("bargo {command} deploy"),
+            _ => format!("bargo {command} verify-onchain{network}"),
+        }
+    });
+
+    BackendStatus {
+        backend: command,
+        steps,
+        next,
+    }
+}
+
+/// Print the checklist in human-readable form
+fn print_status(report: &StatusReport) {
+    println!("📦 Package: {}", report.package_name);
+
+    for backend in &report.backends {
+        println!("\n🎯 {}:", backend.backend);
+        let mut steps = Table::new(&["STEP", "STATUS", "DETAIL"]).with_indent(2);
+        for step in &backend.steps {
+            let status = if step.done {
+                Cell::colored("done", colors::GREEN)
+            } else {
+                Cell::colored("pending", colors::GRAY)
+            };
+            steps.row(vec![step.name.into(), status, step.detail.clone().into()]);
+        }
+        steps.print();
+        match &backend.next {
+            Some(next) => println!("  👉 Next: {next}"),
+            None => println!("  🎉 Pipeline complete"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{ArtifactSuffix, ColorChoice};
+    use crate::config::ToolPaths;
+    use crate::fs::DryRunFs;
+    use crate::runner::DryRunRunner;
+    use crate::util::{Deployment, append_deployment};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn dry_run_config() -> Config {
+        Config {
+            verbose: 0,
+            dry_run: true,
+            pkg: None,
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: Arc::new(DryRunRunner::new()),
+            fs: Arc::new(DryRunFs::new()),
+        }
+    }
+
+    fn project() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Nargo.toml"),
+            "[package]\nname = \"status_pkg\"\ntype = \"bin\"\n",
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    fn touch(root: &Path, path: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, b"artifact").unwrap();
+    }
+
+    fn done(backend: &BackendStatus) -> Vec<bool> {
+        backend.steps.iter().map(|step| step.done).collect()
+    }
+
+    #[test]
+    fn test_fresh_project_has_nothing_done() {
+        let temp_dir = project();
+        let report = collect(&dry_run_config(), temp_dir.path()).unwrap();
+
+        assert_eq!(report.package_name, "status_pkg");
+        let evm = &report.backends[0];
+        assert_eq!(evm.backend, "evm");
+        assert_eq!(done(evm), [false; 5]);
+        assert!(evm.steps[0].detail.contains("target/bb/status_pkg.json"));
+        assert_eq!(evm.next.as_deref(), Some("bargo build"));
+    }
+
+    #[test]
+    fn test_built_and_compressed_proof_count_as_proved() {
+        let temp_dir = project();
+        let root = temp_dir.path();
+        touch(root, "target/bb/status_pkg.json");
+        touch(root, "target/bb/status_pkg.gz");
+        touch(root, "target/evm/proof.gz");
+        touch(root, "target/evm/vk");
+
+        let report = collect(&dry_run_config(), root).unwrap();
+        let evm = &report.backends[0];
+        assert_eq!(done(evm), [true, true, false, false, false]);
+        assert_eq!(evm.steps[1].detail, "target/evm/proof.gz");
+        assert_eq!(evm.next.as_deref(), Some("bargo evm gen"));
+
+        #[cfg(feature = "cairo")]
+        {
+            let cairo = &report.backends[1];
+            assert_eq!(done(cairo), [true, false, false, false, false]);
+            assert_eq!(cairo.next.as_deref(), Some("bargo cairo prove"));
+        }
+    }
+
+    #[test]
+    fn test_deployment_and_verification_come_from_address_book() {
+        let temp_dir = project();
+        let root = temp_dir.path();
+        touch(root, "target/bb/status_pkg.json");
+        touch(root, "target/bb/status_pkg.gz");
+        touch(root, "target/evm/proof");
+        touch(root, "target/evm/vk");
+        touch(root, "contracts/evm/src/Verifier.sol");
+
+        let book = root.join("target/evm/addresses.json");
+        let mut deployment = Deployment::new("0xabc", None, "status_pkg");
+        append_deployment(&book, "sepolia", deployment.clone()).unwrap();
+
+        let report = collect(&dry_run_config(), root).unwrap();
+        let evm = &report.backends[0];
+        assert_eq!(done(evm), [true, true, true, true, false]);
+        assert_eq!(evm.steps[3].detail, "0xabc on sepolia");
+        assert_eq!(
+            evm.next.as_deref(),
+            Some("bargo evm verify-onchain --network sepolia")
+        );
+
+        deployment.verified_at = Some("2026-10-16T00:00:00Z".to_string());
+        append_deployment(&book, "sepolia", deployment).unwrap();
+
+        let report = collect(&dry_run_config(), root).unwrap();
+        let evm = &report.backends[0];
+        assert_eq!(done(evm), [true; 5]);
+        assert_eq!(evm.next, None);
+    }
+}
@@ -0,0 +1,217 @@
+//! Backend-neutral `bargo verify`
+//!
+//! Verifies the proof in the chosen backend's target directory, like
+//! `bargo evm verify` or `bargo cairo verify`:
+//!
+//! - `--backend bb` (default): `target/evm/`
+//! - `--backend starknet`: `target/starknet/`
+//! - `--backend all`: every backend with a proof in its target directory
+//!
+//! With `--backend all` a backend without a proof is skipped rather than
+//! reported as missing, and a failing backend does not stop the others. Once
+//! each proof has been checked a pass/fail table is printed, and the command
+//! fails if any verification failed or no backend had a proof.
+
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use std::time::Duration;
+
+use crate::{
+    api::VerifyResult,
+    backend::{BackendConfig, BackendKind, backend_for},
+    cli::Backend,
+    commands::{self, prove},
+    config::{Config, VerifyConfig},
+    util::{self, create_smart_error, format_duration},
+};
+
+/// Outcome of verifying one backend's proof with `--backend all`
+#[derive(Debug)]
+pub enum BackendOutcome {
+    /// bb accepted the proof, or the cached outcome was reused
+    Passed { cached: bool, elapsed: Duration },
+    /// Verification failed with this error
+    Failed(color_eyre::Report),
+    /// The backend's target directory holds no proof
+    Skipped,
+}
+
+/// Verify the proof of each backend selected by `backend`
+pub fn run(cfg: &Config, backend: Backend, verify_cfg: &VerifyConfig) -> Result<()> {
+    if backend != Backend::All {
+        let [kind] = prove::backend_kinds(backend)[..] else {
+            unreachable!("a single backend selects one implementation");
+        };
+        let mut backend = backend_for(kind);
+        backend.configure(BackendConfig::Verify(verify_cfg.clone()))?;
+        return backend.verify(cfg);
+    }
+
+    let outcomes = verify_all(cfg, verify_cfg);
+    if !cfg.quiet {
+        for line in format_outcomes(&outcomes) {
+            println!("{line}");
+        }
+    }
+
+    let failed: Vec<&str> = outcomes
+        .iter()
+        .filter(|(_, outcome)| matches!(outcome, BackendOutcome::Failed(_)))
+        .map(|(kind, _)| prove::label(*kind))
+        .collect();
+    if !failed.is_empty() {
+        return Err(eyre!("Verification failed for {}", failed.join(", ")));
+    }
+    if outcomes
+        .iter()
+        .all(|(_, outcome)| matches!(outcome, BackendOutcome::Skipped))
+    {
+        return Err(create_smart_error(
+            "No proofs found to verify",
+            &["Run 'bargo prove --backend all' to generate them"],
+        ));
+    }
+    Ok(())
+}
+
+/// Verify every backend that has a proof, without stopping at a failure
+pub fn verify_all(cfg: &Config, verify_cfg: &VerifyConfig) -> Vec<(BackendKind, BackendOutcome)> {
+    prove::backend_kinds(Backend::All)
+        .into_iter()
+        .map(|kind| {
+            let [proof, _, _] = verify_cfg.paths(prove::flavour(kind));
+            let outcome = if !proof.exists() {
+                BackendOutcome::Skipped
+            } else {
+                match verify_proof(cfg, kind, verify_cfg) {
+                    Ok(result) => BackendOutcome::Passed {
+                        cached: result.cached,
+                        elapsed: result.elapsed,
+                    },
+                    Err(e) => BackendOutcome::Failed(e),
+                }
+            };
+            (kind, outcome)
+        })
+        .collect()
+}
+
+/// Verify one backend's proof without printing the outcome
+fn verify_proof(
+    cfg: &Config,
+    kind: BackendKind,
+    verify_cfg: &VerifyConfig,
+) -> Result<VerifyResult> {
+    match kind {
+        BackendKind::Evm => commands::evm::workflow::verify_proof(cfg, verify_cfg),
+        #[cfg(feature = "cairo")]
+        BackendKind::Cairo => commands::cairo::workflow::verify_proof(cfg, verify_cfg),
+    }
+}
+
+/// Render one aligned row per backend with its outcome
+pub fn format_outcomes(outcomes: &[(BackendKind, BackendOutcome)]) -> Vec<String> {
+    let width = outcomes
+        .iter()
+        .map(|(kind, _)| prove::label(*kind).len())
+        .max()
+        .unwrap_or_default();
+
+    outcomes
+        .iter()
+        .map(|(kind, outcome)| {
+            let status = match outcome {
+                BackendOutcome::Passed { cached: true, .. } => "✅ verified (cached)".to_string(),
+                BackendOutcome::Passed { elapsed, .. } => {
+                    format!("✅ verified ({})", format_duration(*elapsed))
+                }
+                BackendOutcome::Failed(e) => format!("❌ failed: {e:#}"),
+                BackendOutcome::Skipped => format!(
+                    "⏭️  skipped, no proof in {}",
+                    util::target_dir(prove::flavour(*kind)).display()
+                ),
+            };
+            format!("{:<width$}  {status}", prove::label(*kind))
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "cairo"))]
+mod tests {
+    use super::*;
+    use crate::cli::{ArtifactSuffix, ColorChoice};
+    use crate::config::ToolPaths;
+    use crate::fs::DryRunFs;
+    use crate::runner::DryRunRunner;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_verify_all_checks_each_backend_with_a_proof() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for flavour in ["evm", "starknet"] {
+            let dir = temp_dir.path().join("target").join(flavour);
+            std::fs::create_dir_all(&dir).unwrap();
+            for file in ["proof", "vk", "public_inputs"] {
+                std::fs::write(dir.join(file), [0u8; 32]).unwrap();
+            }
+        }
+
+        let runner = Arc::new(DryRunRunner::new());
+        let cfg = Config {
+            verbose: 0,
+            dry_run: true,
+            pkg: Some("verify_pkg".to_string()),
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: Some(temp_dir.path().to_path_buf()),
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: runner.clone(),
+            fs: Arc::new(DryRunFs::new()),
+        };
+        cfg.apply_paths().unwrap();
+
+        let outcomes = verify_all(&cfg, &VerifyConfig::default());
+        run(&cfg, Backend::All, &VerifyConfig::default()).unwrap();
+
+        // Only the Starknet proof is missing now
+        std::fs::remove_file(temp_dir.path().join("target/starknet/proof")).unwrap();
+        let partial = verify_all(&cfg, &VerifyConfig::default());
+        util::set_project_dir(None);
+
+        assert!(
+            outcomes
+                .iter()
+                .all(|(_, outcome)| matches!(outcome, BackendOutcome::Passed { .. }))
+        );
+        let verified: Vec<String> = runner
+            .history()
+            .iter()
+            .filter(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "verify")
+            .filter_map(|(spec, _)| {
+                let proof = spec.args.iter().position(|arg| arg == "-p")? + 1;
+                spec.args.get(proof).cloned()
+            })
+            .collect();
+        assert_eq!(
+            verified[..2],
+            [
+                util::tool_path_string(&temp_dir.path().join("target/evm/proof")),
+                util::tool_path_string(&temp_dir.path().join("target/starknet/proof")),
+            ]
+        );
+
+        assert!(matches!(partial[0].1, BackendOutcome::Passed { .. }));
+        assert!(matches!(partial[1].1, BackendOutcome::Skipped));
+        let lines = format_outcomes(&partial);
+        assert!(lines[0].starts_with("EVM       ✅ verified"), "{lines:?}");
+        assert!(lines[1].starts_with("Starknet  ⏭️  skipped"), "{lines:?}");
+    }
+}
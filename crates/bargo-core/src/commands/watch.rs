@@ -0,0 +1,245 @@
+//! Re-run a command whenever its input files change (`--watch`)
+//!
+//! The parent directories of the watched files are observed rather than the
+//! files themselves, so artifacts that are replaced (written to a temporary
+//! file and renamed) keep being picked up. Bursts of writes - bb writing a
+//! proof and its public inputs back to back - are collapsed into one cycle.
+//! The loop runs until interrupted with Ctrl-C.
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use notify::{RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use crate::{
+    config::Config,
+    util::{Timer, create_smart_error, success},
+};
+
+/// How long the watched files must stay quiet before a new cycle starts
+pub const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Run `cycle` now and again every time one of `paths` changes
+///
+/// In dry-run mode the cycle runs once and nothing is watched.
+///
+/// # Arguments
+/// * `cfg` - Configuration passed to each cycle
+/// * `label` - Operation name used in the per-cycle pass/fail line
+/// * `paths` - Files whose changes trigger a new cycle
+/// * `cycle` - Operation to repeat, e.g. the backend's verify
+///
+/// # Returns
+/// * `Result<()>` - Error if the files cannot be watched
+pub fn run<F>(cfg: &Config, label: &str, paths: &[PathBuf], mut cycle: F) -> Result<()>
+where
+    F: FnMut(&Config) -> Result<()>,
+{
+    if cfg.dry_run {
+        cycle(cfg)?;
+        println!("Would watch for changes: {}", display_paths(paths));
+        return Ok(());
+    }
+
+    let (_watcher, changes) = watch_files(paths)?;
+    println!(
+        "Watching {} for changes (Ctrl-C to stop)",
+        display_paths(paths)
+    );
+    run_cycles(cfg, label, &changes, cycle);
+    Ok(())
+}
+
+/// Run `cycle` once, then once per debounced batch of messages on `changes`
+///
+/// Each cycle prints a single pass/fail line with its timing; a failing cycle
+/// does not stop the loop. Returns when `changes` is disconnected.
+pub fn run_cycles<F>(cfg: &Config, label: &str, changes: &Receiver<()>, mut cycle: F)
+where
+    F: FnMut(&Config) -> Result<()>,
+{
+    // The wrapped command's own success output would drown the cycle lines
+    let cycle_cfg = Config {
+        quiet: true,
+        ..cfg.clone()
+    };
+
+    run_cycle(&cycle_cfg, label, &mut cycle);
+    while changes.recv().is_ok() {
+        while changes.recv_timeout(DEBOUNCE).is_ok() {}
+        run_cycle(&cycle_cfg, label, &mut cycle);
+    }
+}
+
+/// Run a single cycle and print its outcome
+fn run_cycle<F>(cfg: &Config, label: &str, cycle: &mut F)
+where
+    F: FnMut(&Config) -> Result<()>,
+{
+    let timer = Timer::start();
+    match cycle(cfg) {
+        Ok(()) => println!(
+            "{}",
+            success(&format!("{label} passed ({})", timer.elapsed()))
+        ),
+        Err(error) => {
+            let reason = error.to_string();
+            let reason = reason.lines().next().unwrap_or_default();
+            println!("❌ {label} failed ({}): {reason}", timer.elapsed());
+        }
+    }
+}
+
+/// Start watching `paths`, sending a message for every change to one of them
+///
+/// The returned watcher must be kept alive for as long as changes are wanted.
+fn watch_files(paths: &[PathBuf]) -> Result<(notify::RecommendedWatcher, Receiver<()>)> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    let mut targets: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        let dir = parent_dir(path);
+        let dir = dir.canonicalize().map_err(|_| {
+            create_smart_error(
+                &format!("Cannot watch {}: directory does not exist", dir.display()),
+                &[
+                    "Generate the proof once before watching it",
+                    "Check the --proof, --vk and --public-inputs paths",
+                ],
+            )
+        })?;
+        targets.push(dir.join(path.file_name().unwrap_or_default()));
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+
+    let (sender, changes) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else { return };
+        if !(event.kind.is_create() || event.kind.is_modify()) {
+            return;
+        }
+        if event
+            .paths
+            .iter()
+            .any(|path| targets.contains(&canonical(path)))
+        {
+            let _ = sender.send(());
+        }
+    })
+    .wrap_err("starting file watcher")?;
+
+    for dir in &dirs {
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .wrap_err_with(|| format!("watching {}", dir.display()))?;
+    }
+    Ok((watcher, changes))
+}
+
+/// Directory containing `path`, `.` for a bare file name
+fn parent_dir(path: &Path) -> &Path {
+    path.parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+}
+
+/// `path` with its directory canonicalized, matching the watched targets
+fn canonical(path: &Path) -> PathBuf {
+    let dir = parent_dir(path);
+    dir.canonicalize()
+        .unwrap_or_else(|_| dir.to_path_buf())
+        .join(path.file_name().unwrap_or_default())
+}
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{ArtifactSuffix, ColorChoice};
+    use crate::config::ToolPaths;
+    use crate::fs::RealFs;
+    use crate::runner::DryRunRunner;
+    use std::sync::Arc;
+
+    fn test_config() -> Config {
+        Config {
+            verbose: 0,
+            dry_run: false,
+            pkg: None,
+            quiet: false,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: Arc::new(DryRunRunner::new()),
+            fs: Arc::new(RealFs),
+        }
+    }
+
+    #[test]
+    fn test_burst_of_changes_runs_one_cycle() {
+        let (sender, changes) = mpsc::channel();
+        for _ in 0..3 {
+            sender.send(()).unwrap();
+        }
+        drop(sender);
+
+        let mut cycles = 0;
+        run_cycles(&test_config(), "verify", &changes, |cfg| {
+            assert!(cfg.quiet);
+            cycles += 1;
+            Ok(())
+        });
+
+        // The initial cycle plus one for the debounced burst
+        assert_eq!(cycles, 2);
+    }
+
+    #[test]
+    fn test_failed_cycle_keeps_watching() {
+        let (sender, changes) = mpsc::channel();
+        sender.send(()).unwrap();
+        drop(sender);
+
+        let mut cycles = 0;
+        run_cycles(&test_config(), "verify", &changes, |_| {
+            cycles += 1;
+            if cycles == 1 {
+                Err(color_eyre::eyre::eyre!("proof rejected"))
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(cycles, 2);
+    }
+
+    #[test]
+    fn test_watch_files_reports_changes() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let proof = temp_dir.path().join("proof");
+        std::fs::write(&proof, b"old").unwrap();
+
+        let (_watcher, changes) = watch_files(std::slice::from_ref(&proof)).unwrap();
+        std::fs::write(temp_dir.path().join("unrelated"), b"x").unwrap();
+        std::fs::write(&proof, b"new").unwrap();
+
+        assert!(changes.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+}
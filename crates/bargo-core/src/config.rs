@@ -1,15 +1,1089 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::cli::Cli;
+use color_eyre::Result;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::cli::{
+    ArtifactSuffix, BbProfile, BbScheme, Cli, ColorChoice, OracleHash, ProofOutputFormat,
+};
+
+#[cfg(feature = "cairo")]
+use crate::cli::{CalldataFormat, GaragaSystem};
+use crate::fs::{DryRunFs, Fs, RealFs};
 use crate::runner::{DryRunRunner, RealRunner, Runner};
+use crate::util::{
+    self, Flavour, NamingStrategy, find_project_root, get_proof_path, get_public_inputs_path,
+    get_vk_path, project_path,
+};
+
+pub use crate::util::{WorkspaceMember, list_workspace_members};
+
+/// File name of the optional project configuration, placed next to Nargo.toml
+pub const PROJECT_CONFIG_FILE: &str = "bargo.toml";
+
+/// Unix time of the first `--artifact-suffix timestamp` lookup in this process
+static RUN_STARTED: std::sync::OnceLock<u64> = std::sync::OnceLock::new();
 
 #[derive(Clone, Debug)]
 pub struct Config {
-    pub verbose: bool,
+    /// Number of `-v` flags: 1 logs info, 2 debug and 3 or more trace
+    pub verbose: u8,
     pub dry_run: bool,
     pub pkg: Option<String>,
     pub quiet: bool,
+    pub timeout: Option<u64>,
+    /// Extra attempts for retryable network commands (`--retries`)
+    pub retries: u32,
+    /// Base directory for artifacts instead of `target/` (`--target-dir`)
+    pub target_dir: Option<PathBuf>,
+    /// Directory to run in instead of the current directory (`--package-dir`)
+    pub package_dir: Option<PathBuf>,
+    /// Default EVM deploy network from `bargo.toml` (`--network` takes precedence)
+    pub evm_network: Option<String>,
+    /// Default Starknet network from `bargo.toml`
+    pub cairo_network: Option<String>,
+    /// Emit a JSON report instead of banners and summaries (implies `quiet`)
+    pub json: bool,
+    /// When to color output (`--color`)
+    pub color: ColorChoice,
+    /// Tool binaries pinned in `bargo.toml` (`BARGO_<TOOL>` takes precedence)
+    pub tools: ToolPaths,
+    /// Suffix for proof artifact file names (`--artifact-suffix`)
+    pub artifact_suffix: ArtifactSuffix,
+    /// Run the remaining backends or workspace members after one fails (`--keep-going`)
+    pub keep_going: bool,
     pub runner: Arc<dyn Runner>,
+    /// Directory creation, moves and removals; only recorded in a dry run
+    pub fs: Arc<dyn Fs>,
+}
+
+impl Config {
+    /// Whether at least one `-v` was given
+    pub fn is_verbose(&self) -> bool {
+        self.verbose > 0
+    }
+
+    /// Whether to draw a progress spinner around external commands
+    ///
+    /// Spinners are suppressed in quiet, JSON and dry-run modes; the
+    /// `util::Spinner` itself additionally checks
+    /// `NO_COLOR` and whether stdout is a terminal.
+    pub fn show_spinner(&self) -> bool {
+        !self.quiet && !self.json && !self.dry_run
+    }
+
+    /// Directory the project root and package name are resolved from
+    ///
+    /// This is `--package-dir` when given, otherwise the process working
+    /// directory.
+    pub fn working_dir(&self) -> Result<PathBuf> {
+        match &self.package_dir {
+            Some(dir) => Ok(std::path::absolute(dir)?),
+            None => Ok(std::env::current_dir()?),
+        }
+    }
+
+    /// Directory containing `Nargo.toml`, found from [`Config::working_dir`]
+    ///
+    /// Outside a Nargo project this is the working directory itself, so
+    /// project files such as `.env` are still looked up where bargo runs.
+    pub fn project_root(&self) -> Result<PathBuf> {
+        let dir = self.working_dir()?;
+        Ok(util::find_project_root(&dir).unwrap_or(dir))
+    }
+
+    /// Apply `--package-dir`, `--target-dir` and `--artifact-suffix` to the
+    /// artifact path helpers
+    ///
+    /// The settings are stored per thread, so this must be called on every
+    /// thread that runs a workflow. Relative paths (artifacts, contracts,
+    /// `.env`, and paths given on the command line) then resolve against the
+    /// package directory, as if bargo had been started there.
+    pub fn apply_paths(&self) -> Result<()> {
+        let project_dir = match &self.package_dir {
+            Some(_) => Some(self.working_dir()?),
+            None => None,
+        };
+        util::set_project_dir(project_dir);
+        util::set_target_base(self.target_dir.clone());
+        util::set_naming_strategy(self.naming_strategy()?);
+        Ok(())
+    }
+
+    /// Naming strategy selected by `--artifact-suffix`
+    ///
+    /// The package suffix needs the package name, so it fails outside a Nargo
+    /// project unless `--pkg` is given. The timestamp is taken once per
+    /// process, so threads proving in parallel agree on it; later processes
+    /// read the newest timestamped artifacts rather than their own suffix.
+    pub fn naming_strategy(&self) -> Result<NamingStrategy> {
+        Ok(match self.artifact_suffix {
+            ArtifactSuffix::None => NamingStrategy::Plain,
+            ArtifactSuffix::Pkg => NamingStrategy::Package(util::get_package_name_in_directory(
+                self.pkg.as_ref(),
+                &self.working_dir()?,
+            )?),
+            ArtifactSuffix::Timestamp => {
+                NamingStrategy::Timestamp(*RUN_STARTED.get_or_init(|| {
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|elapsed| elapsed.as_secs())
+                        .unwrap_or_default()
+                }))
+            }
+        })
+    }
+
+    /// Fill in values not given on the command line from a project config
+    ///
+    /// CLI flags always win over `bargo.toml`, which in turn wins over the
+    /// built-in defaults applied later by the commands themselves.
+    pub fn with_project_config(mut self, project: ProjectConfig) -> Self {
+        if self.pkg.is_none() {
+            self.pkg = project.defaults.pkg;
+        }
+        if self.evm_network.is_none() {
+            self.evm_network = project.evm.network;
+        }
+        if self.cairo_network.is_none() {
+            self.cairo_network = project.cairo.network;
+        }
+        self.tools = project.tools;
+        self
+    }
+}
+
+/// Project-level defaults read from `bargo.toml`
+///
+/// ```toml
+/// [defaults]
+/// pkg = "my_circuit"
+///
+/// [evm]
+/// network = "sepolia"
+///
+/// [cairo]
+/// network = "mainnet"
+///
+/// [tools]
+/// bb = "/opt/bb/0.82.2/bb"
+///
+/// [inputs]
+/// happy = "inputs/happy.toml"
+/// edge = "inputs/edge.toml"
+///
+/// [ui]
+/// banners = false
+/// ```
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct ProjectConfig {
+    #[serde(default)]
+    pub defaults: DefaultsSection,
+    #[serde(default)]
+    pub evm: NetworkSection,
+    #[serde(default)]
+    pub cairo: NetworkSection,
+    #[serde(default)]
+    pub tools: ToolPaths,
+    /// `[inputs]` section: prover inputs files selected by name with `--profile`
+    #[serde(default)]
+    pub inputs: BTreeMap<String, PathBuf>,
+    #[serde(default)]
+    pub ui: UiSection,
+}
+
+impl ProjectConfig {
+    /// Prover inputs file of the `[inputs]` profile `name`
+    pub fn input_profile(&self, name: &str) -> Result<PathBuf> {
+        if let Some(path) = self.inputs.get(name) {
+            return Ok(path.clone());
+        }
+
+        let hint = if self.inputs.is_empty() {
+            format!("Add it to {PROJECT_CONFIG_FILE}: [inputs] {name} = \"inputs/{name}.toml\"")
+        } else {
+            let names: Vec<&str> = self.inputs.keys().map(String::as_str).collect();
+            format!("Available profiles: {}", names.join(", "))
+        };
+        Err(util::create_smart_error(
+            &format!("Input profile '{name}' not found in {PROJECT_CONFIG_FILE}"),
+            &[&hint],
+        ))
+    }
+}
+
+/// `[defaults]` section of `bargo.toml`
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct DefaultsSection {
+    pub pkg: Option<String>,
+}
+
+/// `[ui]` section of `bargo.toml`
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct UiSection {
+    /// Print the banner before each command (`--no-banner` turns it off too)
+    pub banners: Option<bool>,
+}
+
+/// `[evm]` / `[cairo]` sections of `bargo.toml`
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct NetworkSection {
+    pub network: Option<String>,
+}
+
+/// Deploy target selected with `--network` or a `bargo.toml` network entry
+///
+/// Names are matched case-insensitively. `custom:<rpc-url>` targets any other
+/// network through the given RPC endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Sepolia,
+    /// Local development node (anvil or starknet-devnet)
+    Localhost,
+    /// Arbitrary network reached through this RPC URL
+    Custom(String),
+}
+
+impl Network {
+    /// Names accepted besides the `custom:<rpc-url>` form
+    pub const KNOWN: &[&str] = &["mainnet", "sepolia", "localhost"];
+
+    /// Parse a network name, suggesting the closest known name on a typo
+    pub fn parse(name: &str) -> Result<Self> {
+        if let Some(rpc_url) = name.strip_prefix("custom:") {
+            if !(rpc_url.starts_with("http://") || rpc_url.starts_with("https://")) {
+                return Err(util::create_smart_error(
+                    &format!("Invalid custom network: {name}"),
+                    &["Give an http(s) RPC URL, e.g. custom:https://rpc.example.org"],
+                ));
+            }
+            return Ok(Self::Custom(rpc_url.to_string()));
+        }
+
+        match name.to_lowercase().as_str() {
+            "mainnet" => Ok(Self::Mainnet),
+            "sepolia" => Ok(Self::Sepolia),
+            "localhost" => Ok(Self::Localhost),
+            lowercase => {
+                let mut suggestions = Vec::new();
+                if let Some(known) = closest_network(lowercase) {
+                    suggestions.push(format!("Did you mean '{known}'?"));
+                }
+                suggestions.push(format!(
+                    "Use one of {} or custom:<rpc-url>",
+                    Self::KNOWN.join(", ")
+                ));
+                let suggestions: Vec<&str> = suggestions.iter().map(String::as_str).collect();
+                Err(util::create_smart_error(
+                    &format!("Unsupported network: {name}"),
+                    &suggestions,
+                ))
+            }
+        }
+    }
+
+    /// Name used for environment variable prefixes and address book entries
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Sepolia => "sepolia",
+            Self::Localhost => "localhost",
+            Self::Custom(_) => "custom",
+        }
+    }
+
+    /// EVM chain id, unknown for custom networks
+    pub fn evm_chain_id(&self) -> Option<u64> {
+        match self {
+            Self::Mainnet => Some(1),
+            Self::Sepolia => Some(11_155_111),
+            Self::Localhost => Some(31_337),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// Starknet chain id, unknown for local and custom networks
+    pub fn starknet_chain_id(&self) -> Option<&'static str> {
+        match self {
+            Self::Mainnet => Some("SN_MAIN"),
+            Self::Sepolia => Some("SN_SEPOLIA"),
+            Self::Localhost | Self::Custom(_) => None,
+        }
+    }
+
+    /// RPC URL implied by the network itself (anvil's default port for
+    /// localhost); `None` means it comes from `RPC_URL`
+    pub fn evm_rpc_url(&self) -> Option<String> {
+        match self {
+            Self::Localhost => Some("http://127.0.0.1:8545".to_string()),
+            Self::Custom(rpc_url) => Some(rpc_url.clone()),
+            Self::Mainnet | Self::Sepolia => None,
+        }
+    }
+
+    /// RPC URL implied by the network itself (starknet-devnet's default port
+    /// for localhost); `None` means it comes from `<NETWORK>_RPC_URL`
+    pub fn starknet_rpc_url(&self) -> Option<String> {
+        match self {
+            Self::Localhost => Some("http://127.0.0.1:5050".to_string()),
+            Self::Custom(rpc_url) => Some(rpc_url.clone()),
+            Self::Mainnet | Self::Sepolia => None,
+        }
+    }
+
+    /// Example `RPC_URL` line for the `.env` file
+    pub fn evm_rpc_hint(&self) -> String {
+        match self {
+            Self::Mainnet => "RPC_URL=https://eth-mainnet.g.alchemy.com/v2/<key>".to_string(),
+            Self::Sepolia => "RPC_URL=https://eth-sepolia.g.alchemy.com/v2/<key>".to_string(),
+            Self::Localhost | Self::Custom(_) => {
+                format!("RPC_URL={}", self.evm_rpc_url().unwrap_or_default())
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Network {
+    type Err = color_eyre::Report;
+
+    fn from_str(name: &str) -> Result<Self> {
+        Self::parse(name)
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Custom(rpc_url) => write!(f, "custom:{rpc_url}"),
+            network => f.write_str(network.name()),
+        }
+    }
+}
+
+/// Known network name within two edits of `name`, if any
+fn closest_network(name: &str) -> Option<&'static str> {
+    Network::KNOWN
+        .iter()
+        .map(|known| (edit_distance(name, known), *known))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// `[tools]` section of `bargo.toml`: binaries to run instead of those on `PATH`
+///
+/// Relative paths are resolved against the project root when loaded, so they
+/// keep working for commands run from a workspace member directory.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+pub struct ToolPaths {
+    pub nargo: Option<PathBuf>,
+    pub bb: Option<PathBuf>,
+    pub garaga: Option<PathBuf>,
+    pub forge: Option<PathBuf>,
+}
+
+impl ToolPaths {
+    /// Pinned binary for `tool`, if any
+    pub fn get(&self, tool: &str) -> Option<&Path> {
+        match tool {
+            "nargo" => self.nargo.as_deref(),
+            "bb" => self.bb.as_deref(),
+            "garaga" => self.garaga.as_deref(),
+            "forge" => self.forge.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Anchor relative paths (those with a directory part) at `root`
+    ///
+    /// Bare names such as `bb-0.82` are left alone and looked up on `PATH`.
+    fn relative_to(mut self, root: &Path) -> Self {
+        for path in [
+            &mut self.nargo,
+            &mut self.bb,
+            &mut self.garaga,
+            &mut self.forge,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if path.is_relative() && path.components().count() > 1 {
+                *path = root.join(&*path);
+            }
+        }
+        self
+    }
+}
+
+/// Load `bargo.toml` from the project root containing `current_dir`
+///
+/// Returns `Ok(None)` when not inside a Noir project or when the project has
+/// no `bargo.toml`. A file that exists but cannot be parsed is an error.
+pub fn load_project_config(current_dir: &Path) -> Result<Option<ProjectConfig>> {
+    let Ok(project_root) = find_project_root(current_dir) else {
+        return Ok(None);
+    };
+
+    let config_path = project_root.join(PROJECT_CONFIG_FILE);
+    if !config_path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&config_path).map_err(|e| {
+        color_eyre::eyre::eyre!(
+            "Failed to read {} at {}: {}",
+            PROJECT_CONFIG_FILE,
+            config_path.display(),
+            e
+        )
+    })?;
+
+    let mut config: ProjectConfig = toml::from_str(&content).map_err(|e| {
+        color_eyre::eyre::eyre!(
+            "Failed to parse {} at {}: {}",
+            PROJECT_CONFIG_FILE,
+            config_path.display(),
+            e
+        )
+    })?;
+
+    config.tools = config.tools.relative_to(&project_root);
+    for path in config.inputs.values_mut() {
+        *path = project_root.join(&*path);
+    }
+
+    debug!("Loaded project config from {}", config_path.display());
+    Ok(Some(config))
+}
+
+/// Type of value a `bargo.toml` setting holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    /// Nargo package name
+    Package,
+    /// Network name accepted by `--network`
+    Network,
+    /// Path or name of a tool binary
+    ToolPath,
+}
+
+/// A `bargo.toml` entry that `bargo config` can read and write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProjectSetting {
+    /// Dotted key, `<section>.<name>`
+    pub key: &'static str,
+    pub kind: SettingKind,
+    pub description: &'static str,
+}
+
+/// Every setting `ProjectConfig` reads, in `bargo config list` order
+pub const PROJECT_SETTINGS: &[ProjectSetting] = &[
+    ProjectSetting {
+        key: "defaults.pkg",
+        kind: SettingKind::Package,
+        description: "Package used when --pkg is not given",
+    },
+    ProjectSetting {
+        key: "evm.network",
+        kind: SettingKind::Network,
+        description: "Network for evm deploy and verify-onchain",
+    },
+    ProjectSetting {
+        key: "cairo.network",
+        kind: SettingKind::Network,
+        description: "Network for cairo deploy and verify-onchain",
+    },
+    ProjectSetting {
+        key: "tools.nargo",
+        kind: SettingKind::ToolPath,
+        description: "nargo binary to run instead of the one on PATH",
+    },
+    ProjectSetting {
+        key: "tools.bb",
+        kind: SettingKind::ToolPath,
+        description: "bb binary to run instead of the one on PATH",
+    },
+    ProjectSetting {
+        key: "tools.garaga",
+        kind: SettingKind::ToolPath,
+        description: "garaga binary to run instead of the one on PATH",
+    },
+    ProjectSetting {
+        key: "tools.forge",
+        kind: SettingKind::ToolPath,
+        description: "forge binary to run instead of the one on PATH",
+    },
+];
+
+impl ProjectSetting {
+    /// Look up a setting by its dotted key, suggesting the closest on a typo
+    pub fn find(key: &str) -> Result<&'static Self> {
+        if let Some(setting) = PROJECT_SETTINGS.iter().find(|setting| setting.key == key) {
+            return Ok(setting);
+        }
+
+        let mut suggestions = Vec::new();
+        if let Some(closest) = PROJECT_SETTINGS
+            .iter()
+            .map(|setting| (edit_distance(key, setting.key), setting.key))
+            .filter(|(distance, _)| *distance <= 2)
+            .min_by_key(|(distance, _)| *distance)
+        {
+            suggestions.push(format!("Did you mean '{}'?", closest.1));
+        }
+        suggestions.push("List the known keys with 'bargo config list'".to_string());
+        let suggestions: Vec<&str> = suggestions.iter().map(String::as_str).collect();
+        Err(util::create_smart_error(
+            &format!("Unknown {PROJECT_CONFIG_FILE} key '{key}'"),
+            &suggestions,
+        ))
+    }
+
+    /// Table the setting lives in and its name within that table
+    pub fn path(&self) -> (&'static str, &'static str) {
+        self.key
+            .split_once('.')
+            .expect("setting keys are <section>.<name>")
+    }
+
+    /// Check `value` fits the setting and return the form to store
+    ///
+    /// Network names are normalized, so `Sepolia` is stored as `sepolia`.
+    pub fn validate(&self, value: &str) -> Result<String> {
+        match self.kind {
+            SettingKind::Network => Ok(Network::parse(value)?.to_string()),
+            SettingKind::Package => {
+                let valid = !value.is_empty()
+                    && value
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+                if !valid {
+                    return Err(color_eyre::eyre::eyre!(
+                        "Invalid package name '{value}' for {}: use letters, digits, '_' and '-'",
+                        self.key
+                    ));
+                }
+                Ok(value.to_string())
+            }
+            SettingKind::ToolPath => {
+                if value.trim().is_empty() {
+                    return Err(color_eyre::eyre::eyre!(
+                        "{} needs a binary path or name",
+                        self.key
+                    ));
+                }
+                Ok(value.to_string())
+            }
+        }
+    }
+}
+
+/// Read `bargo.toml` at `path` for editing, or an empty document if missing
+fn read_config_document(path: &Path) -> Result<toml_edit::DocumentMut> {
+    if !path.exists() {
+        return Ok(toml_edit::DocumentMut::new());
+    }
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        color_eyre::eyre::eyre!(
+            "Failed to read {PROJECT_CONFIG_FILE} at {}: {e}",
+            path.display()
+        )
+    })?;
+    content.parse().map_err(|e| {
+        color_eyre::eyre::eyre!(
+            "Failed to parse {PROJECT_CONFIG_FILE} at {}: {e}",
+            path.display()
+        )
+    })
+}
+
+/// Value of `setting` in the `bargo.toml` at `path`, or `None` if unset
+pub fn get_project_setting(path: &Path, setting: &ProjectSetting) -> Result<Option<String>> {
+    let document = read_config_document(path)?;
+    let (section, name) = setting.path();
+    let Some(item) = document.get(section).and_then(|table| table.get(name)) else {
+        return Ok(None);
+    };
+    item.as_str()
+        .map(|value| Some(value.to_string()))
+        .ok_or_else(|| {
+            color_eyre::eyre::eyre!("{} in {} is not a string", setting.key, path.display())
+        })
+}
+
+/// Validate `value` and store it as `setting` in the `bargo.toml` at `path`
+///
+/// The file is created if needed; comments and other entries are kept.
+/// Returns the value as stored.
+pub fn set_project_setting(path: &Path, setting: &ProjectSetting, value: &str) -> Result<String> {
+    let value = setting.validate(value)?;
+    let mut document = read_config_document(path)?;
+    let (section, name) = setting.path();
+    if document
+        .get(section)
+        .is_some_and(|item| !item.is_table_like())
+    {
+        return Err(color_eyre::eyre::eyre!(
+            "[{section}] in {} is not a table",
+            path.display()
+        ));
+    }
+    document.entry(section).or_insert_with(toml_edit::table)[name] =
+        toml_edit::value(value.as_str());
+
+    // Never leave behind a file that load_project_config would reject
+    let content = document.to_string();
+    toml::from_str::<ProjectConfig>(&content).map_err(|e| {
+        color_eyre::eyre::eyre!(
+            "Setting {} would make {} invalid: {e}",
+            setting.key,
+            path.display()
+        )
+    })?;
+    std::fs::write(path, content).map_err(|e| {
+        color_eyre::eyre::eyre!(
+            "Failed to write {PROJECT_CONFIG_FILE} at {}: {e}",
+            path.display()
+        )
+    })?;
+
+    debug!("Set {} = {value} in {}", setting.key, path.display());
+    Ok(value)
+}
+
+/// Configuration specific to prove operations
+#[derive(Clone, Debug, Default)]
+pub struct ProveConfig {
+    /// Proving scheme override; `None` keeps the backend's default
+    pub scheme: Option<BbScheme>,
+    /// Regenerate the proof and verification key even if they are current
+    pub force: bool,
+    /// Generate a recursion-friendly proof and verification key
+    pub recursive: bool,
+    /// Also pass bb's honk recursion flag to `prove` and `write_vk`
+    pub recursive_friendly: bool,
+    /// Oracle hash override; `None` keeps the backend's default
+    pub oracle_hash: Option<OracleHash>,
+    /// Skip proving in `gen` and generate the verifier from the existing vk
+    pub no_prove: bool,
+    /// Witness to prove instead of the package's `target/bb/<pkg>.gz`
+    pub witness: Option<PathBuf>,
+    /// Gzip the proof and public inputs after proving
+    pub compress: bool,
+    /// Arguments appended verbatim to `bb prove`, after bargo's own flags
+    pub extra_args: Vec<String>,
+    /// `bb prove --output_format` override; `None` keeps bb's default
+    pub output_format: Option<ProofOutputFormat>,
+    /// bb performance profile; `None` keeps bb's default flags
+    pub bb_profile: Option<BbProfile>,
+}
+
+impl ProveConfig {
+    pub fn new(scheme: Option<BbScheme>) -> Self {
+        Self {
+            scheme,
+            force: false,
+            recursive: false,
+            recursive_friendly: false,
+            oracle_hash: None,
+            no_prove: false,
+            witness: None,
+            compress: false,
+            extra_args: Vec::new(),
+            output_format: None,
+            bb_profile: None,
+        }
+    }
+
+    /// Bypass the verification key cache and the proof freshness check
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Pass bb's recursion flags to both `prove` and `write_vk`
+    pub fn with_recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Prove for a recursive verifier with bb's honk recursion flag
+    ///
+    /// Implies [`with_recursive`](Self::with_recursive), so the field-element
+    /// artifacts are written as well.
+    pub fn with_recursive_friendly(mut self, recursive_friendly: bool) -> Self {
+        self.recursive_friendly = recursive_friendly;
+        self.recursive |= recursive_friendly;
+        self
+    }
+
+    /// Use `oracle_hash` for `prove` and `write_vk` instead of the backend default
+    pub fn with_oracle_hash(mut self, oracle_hash: Option<OracleHash>) -> Self {
+        self.oracle_hash = oracle_hash;
+        self
+    }
+
+    /// Reuse the proof and vk already in the target directory when generating
+    /// a verifier contract
+    pub fn with_no_prove(mut self, no_prove: bool) -> Self {
+        self.no_prove = no_prove;
+        self
+    }
+
+    /// Prove `witness` against the package's bytecode instead of its own witness
+    pub fn with_witness(mut self, witness: Option<PathBuf>) -> Self {
+        self.witness = witness;
+        self
+    }
+
+    /// Replace the proof and public inputs with `.gz` copies after proving
+    pub fn with_compress(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Pass `extra_args` to `bb prove` after the flags bargo composes
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+
+    /// Write the proof in `output_format` instead of bb's default encoding
+    pub fn with_output_format(mut self, output_format: Option<ProofOutputFormat>) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    /// Prove with the flags of `bb_profile`, recorded in the manifest
+    pub fn with_bb_profile(mut self, bb_profile: Option<BbProfile>) -> Self {
+        self.bb_profile = bb_profile;
+        self
+    }
+
+    /// Encoding to request from `bb prove`, if any
+    ///
+    /// Recursive proofs need the field elements, so they default to both
+    /// encodings when no format was chosen.
+    pub fn bb_output_format(&self) -> Option<ProofOutputFormat> {
+        self.output_format
+            .or(self.recursive.then_some(ProofOutputFormat::Both))
+    }
+
+    /// Whether a proof left by an earlier run may stand in for a new one
+    ///
+    /// Only proofs made with the default witness and bb flags qualify, since
+    /// bargo does not record the scheme, recursion, output format or
+    /// passthrough arguments a proof was made with. The oracle hash and compression are checked
+    /// against the target directory separately. An explicit bb profile always
+    /// runs bb, so its flags take effect.
+    pub fn may_reuse_proof(&self) -> bool {
+        !self.force
+            && self.scheme.is_none()
+            && self.bb_profile.is_none()
+            && !self.recursive
+            && self.output_format.is_none()
+            && self.witness.is_none()
+            && self.extra_args.is_empty()
+    }
+
+    /// Witness to pass to `bb prove`, falling back to the package's build output
+    pub fn witness_path(&self, pkg_name: &str) -> PathBuf {
+        self.witness.as_ref().map_or_else(
+            || util::get_witness_path(pkg_name, Flavour::Bb),
+            project_path,
+        )
+    }
+
+    /// Reject flag combinations bb cannot honour and a missing `--witness` file
+    pub fn validate(&self) -> Result<()> {
+        if let Some(scheme) = self
+            .scheme
+            .filter(|scheme| self.recursive && !scheme.supports_recursion())
+        {
+            return Err(color_eyre::eyre::eyre!(
+                "--recursive is not supported with --scheme {}; use ultra_honk or omit --scheme",
+                scheme.as_str()
+            ));
+        }
+        if let Some(witness) = &self.witness {
+            util::validate_files_exist(&[project_path(witness)])?;
+        }
+        Ok(())
+    }
+}
+
+/// Configuration specific to `bargo build`
+#[derive(Clone, Debug, Default)]
+pub struct BuildConfig {
+    /// Prover inputs to execute with instead of the package's `Prover.toml`
+    pub input: Option<PathBuf>,
+    /// `[inputs]` profile in `bargo.toml` naming the prover inputs to use
+    pub profile: Option<String>,
+    /// Arguments appended verbatim to `nargo execute`, after bargo's own flags
+    pub extra_args: Vec<String>,
+    /// Measure the bytecode size and gate count after building
+    pub stats: bool,
+}
+
+impl BuildConfig {
+    pub fn new(input: Option<PathBuf>) -> Self {
+        Self {
+            input,
+            profile: None,
+            extra_args: Vec::new(),
+            stats: false,
+        }
+    }
+
+    /// Report the bytecode size and `bb gates` count after building
+    pub fn with_stats(mut self, stats: bool) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Execute with the prover inputs of a `bargo.toml` `[inputs]` profile
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Pass `extra_args` to `nargo execute` after the flags bargo composes
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+
+    /// Resolved `--input` path, or `None` for the package's `Prover.toml`
+    pub fn input_path(&self) -> Option<PathBuf> {
+        self.input.as_ref().map(project_path)
+    }
+}
+
+/// Configuration specific to local proof verification
+///
+/// Each path overrides the artifact bargo would otherwise read from the
+/// backend's target directory, so proofs produced elsewhere can be verified.
+#[derive(Clone, Debug)]
+pub struct VerifyConfig {
+    /// Proof file; `None` uses `target/<flavour>/proof`
+    pub proof: Option<PathBuf>,
+    /// Verification key; `None` uses `target/<flavour>/vk`
+    pub vk: Option<PathBuf>,
+    /// Public inputs; `None` uses `target/<flavour>/public_inputs`
+    pub public_inputs: Option<PathBuf>,
+    /// Whether the proof should verify; `false` succeeds only when bb rejects it
+    pub expect_valid: bool,
+    /// Run bb even if the last verification of the same inputs is cached
+    pub force: bool,
+}
+
+impl Default for VerifyConfig {
+    fn default() -> Self {
+        Self::new(None, None, None)
+    }
+}
+
+impl VerifyConfig {
+    pub fn new(
+        proof: Option<PathBuf>,
+        vk: Option<PathBuf>,
+        public_inputs: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            proof,
+            vk,
+            public_inputs,
+            expect_valid: true,
+            force: false,
+        }
+    }
+
+    /// Require bb to reject the proof instead of accepting it when `expect_valid` is false
+    pub fn with_expect(mut self, expect_valid: bool) -> Self {
+        self.expect_valid = expect_valid;
+        self
+    }
+
+    /// Ignore the cached outcome of an earlier verification
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Proof, vk and public inputs paths, falling back to the defaults for `flavour`
+    ///
+    /// The default proof and public inputs resolve to their `.gz` copies when
+    /// `prove --compress` replaced the uncompressed files.
+    pub fn paths(&self, flavour: Flavour) -> [PathBuf; 3] {
+        [
+            self.proof.as_ref().map_or_else(
+                || util::resolve_compressed(&get_proof_path(flavour)),
+                project_path,
+            ),
+            self.vk
+                .as_ref()
+                .map_or_else(|| get_vk_path(flavour), project_path),
+            self.public_inputs.as_ref().map_or_else(
+                || util::resolve_compressed(&get_public_inputs_path(flavour)),
+                project_path,
+            ),
+        ]
+    }
+}
+
+/// Configuration specific to calldata generation
+#[derive(Clone, Debug, Default)]
+pub struct CalldataConfig {
+    /// Destination for the calldata JSON; `None` keeps the backend's default path
+    pub output: Option<PathBuf>,
+}
+
+impl CalldataConfig {
+    pub fn new(output: Option<PathBuf>) -> Self {
+        Self { output }
+    }
+
+    /// Calldata destination, falling back to `target/<flavour>/calldata.json`
+    pub fn output_path(&self, flavour: Flavour) -> PathBuf {
+        self.output.as_ref().map_or_else(
+            || util::target_dir(flavour).join("calldata.json"),
+            project_path,
+        )
+    }
+}
+
+/// Compiler settings written to the generated Foundry project's `foundry.toml`
+#[derive(Clone, Debug, Default)]
+pub struct FoundryConfig {
+    /// solc version to pin, as `MAJOR.MINOR.PATCH`; `None` keeps forge's choice
+    pub solc_version: Option<String>,
+    /// Optimizer runs; setting this also enables the optimizer
+    pub optimizer_runs: Option<u32>,
+    /// Arguments appended verbatim to `bb write_solidity_verifier`
+    pub extra_args: Vec<String>,
+    /// Directory the Foundry project is created in instead of `contracts/evm`
+    pub output_dir: Option<PathBuf>,
+    /// Replace `output_dir` even if it does not hold a generated project
+    pub overwrite: bool,
+}
+
+impl FoundryConfig {
+    pub fn new(solc_version: Option<String>, optimizer_runs: Option<u32>) -> Self {
+        Self {
+            solc_version,
+            optimizer_runs,
+            ..Self::default()
+        }
+    }
+
+    /// Pass `extra_args` to `bb write_solidity_verifier` after the flags bargo composes
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+
+    /// Create the Foundry project in `output_dir` instead of `contracts/evm`
+    pub fn with_output_dir(mut self, output_dir: Option<PathBuf>) -> Self {
+        self.output_dir = output_dir;
+        self
+    }
+
+    /// Replace the output directory even if bargo did not generate it
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Whether forge's defaults are kept unchanged
+    pub fn is_empty(&self) -> bool {
+        self.solc_version.is_none() && self.optimizer_runs.is_none()
+    }
+
+    /// Reject a solc version that is not a full `MAJOR.MINOR.PATCH` release
+    pub fn validate(&self) -> Result<()> {
+        if let Some(version) = &self.solc_version {
+            let parts: Vec<&str> = version.split('.').collect();
+            let valid = parts.len() == 3
+                && parts
+                    .iter()
+                    .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+            if !valid {
+                return Err(color_eyre::eyre::eyre!(
+                    "Invalid solc version '{version}': expected MAJOR.MINOR.PATCH such as 0.8.27"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Configuration for garaga verifier and calldata generation
+#[cfg(feature = "cairo")]
+#[derive(Clone, Debug, Default)]
+pub struct GaragaConfig {
+    /// Proof system passed to `garaga --system`
+    pub system: GaragaSystem,
+    /// Encoding `cairo calldata` writes
+    pub calldata_format: CalldataFormat,
+    /// Arguments appended verbatim to `garaga gen`, after bargo's own flags
+    pub extra_args: Vec<String>,
+    /// Directory the verifier project is moved to instead of `contracts/cairo`
+    pub output_dir: Option<PathBuf>,
+    /// Replace `output_dir` even if it does not hold a generated project
+    pub overwrite: bool,
+}
+
+#[cfg(feature = "cairo")]
+impl GaragaConfig {
+    pub fn new(system: GaragaSystem) -> Self {
+        Self {
+            system,
+            ..Self::default()
+        }
+    }
+
+    /// Pass `extra_args` to `garaga gen` after the flags bargo composes
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+
+    /// Move the generated verifier project to `output_dir` instead of `contracts/cairo`
+    pub fn with_output_dir(mut self, output_dir: Option<PathBuf>) -> Self {
+        self.output_dir = output_dir;
+        self
+    }
+
+    /// Replace the output directory even if bargo did not generate it
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Write calldata in `format` instead of garaga's felt array
+    pub fn with_calldata_format(mut self, format: CalldataFormat) -> Self {
+        self.calldata_format = format;
+        self
+    }
 }
 
 /// Configuration specific to Cairo deploy operations
@@ -19,6 +1093,11 @@ pub struct CairoDeployConfig {
     pub class_hash: Option<String>,
     pub auto_declare: bool,
     pub no_declare: bool,
+    pub constructor_calldata: Vec<String>,
+    /// starkli keystore account used to sign instead of a raw private key
+    pub account: Option<String>,
+    /// Deploy to mainnet without the confirmation prompt
+    pub yes: bool,
 }
 
 #[cfg(feature = "cairo")]
@@ -28,29 +1107,360 @@ impl CairoDeployConfig {
             class_hash,
             auto_declare,
             no_declare,
+            constructor_calldata: Vec::new(),
+            account: None,
+            yes: false,
         }
     }
 
+    /// Set the constructor calldata passed when deploying the contract
+    pub fn with_constructor_calldata(mut self, calldata: Vec<String>) -> Self {
+        self.constructor_calldata = calldata;
+        self
+    }
+
+    /// Sign with the keystore for `account` instead of a raw private key
+    pub fn with_account(mut self, account: Option<String>) -> Self {
+        self.account = account;
+        self
+    }
+
+    /// Skip the mainnet confirmation prompt
+    pub fn with_yes(mut self, yes: bool) -> Self {
+        self.yes = yes;
+        self
+    }
+
     /// Returns true if auto-declare should be performed
     pub fn should_auto_declare(&self) -> bool {
         self.auto_declare && !self.no_declare
     }
 }
 
+/// Configuration specific to EVM deploy operations
+#[cfg(feature = "evm-foundry")]
+#[derive(Clone, Debug, Default)]
+pub struct EvmDeployConfig {
+    /// Abort the deployment when the gas estimate exceeds this limit
+    pub max_gas: Option<u64>,
+    /// Foundry keystore account used to sign instead of `PRIVATE_KEY`
+    pub account: Option<String>,
+    /// Arguments for the verifier's constructor (`forge create --constructor-args`)
+    pub constructor_args: Vec<String>,
+    /// Deploy to mainnet without the confirmation prompt
+    pub yes: bool,
+}
+
+#[cfg(feature = "evm-foundry")]
+impl EvmDeployConfig {
+    pub fn new(max_gas: Option<u64>) -> Self {
+        Self {
+            max_gas,
+            account: None,
+            constructor_args: Vec::new(),
+            yes: false,
+        }
+    }
+
+    /// Pass `args` to the verifier's constructor
+    pub fn with_constructor_args(mut self, args: Vec<String>) -> Self {
+        self.constructor_args = args;
+        self
+    }
+
+    /// Sign with the keystore for `account` instead of `PRIVATE_KEY`
+    pub fn with_account(mut self, account: Option<String>) -> Self {
+        self.account = account;
+        self
+    }
+
+    /// Skip the mainnet confirmation prompt
+    pub fn with_yes(mut self, yes: bool) -> Self {
+        self.yes = yes;
+        self
+    }
+}
+
 impl From<&Cli> for Config {
     fn from(cli: &Cli) -> Self {
+        // In JSON mode stdout is reserved for the final report; in quiet mode
+        // command output is held back unless the command fails
         let runner: Arc<dyn Runner> = if cli.dry_run {
-            Arc::new(DryRunRunner::new())
+            let mut runner = DryRunRunner::new();
+            if cli.json {
+                runner = runner.with_stdout_to_stderr();
+            }
+            if cli.quiet {
+                runner = runner.with_quiet();
+            }
+            Arc::new(runner)
         } else {
-            Arc::new(RealRunner::new())
+            let mut runner = RealRunner::new();
+            if cli.json {
+                runner = runner.with_stdout_to_stderr();
+            }
+            if cli.quiet {
+                runner = runner.with_quiet();
+            }
+            Arc::new(runner)
         };
 
         Self {
             verbose: cli.verbose,
             dry_run: cli.dry_run,
             pkg: cli.pkg.clone(),
-            quiet: cli.quiet,
+            quiet: cli.quiet || cli.json,
+            timeout: cli.timeout,
+            retries: cli.retries,
+            target_dir: cli.target_dir.clone(),
+            package_dir: cli.package_dir.clone(),
+            evm_network: None,
+            cairo_network: None,
+            json: cli.json,
+            color: cli.color,
+            tools: ToolPaths::default(),
+            artifact_suffix: cli.artifact_suffix,
+            keep_going: cli.keep_going,
             runner,
+            fs: if cli.dry_run {
+                Arc::new(DryRunFs::new())
+            } else {
+                Arc::new(RealFs)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_project(temp_dir: &TempDir, bargo_toml: Option<&str>) -> std::path::PathBuf {
+        let project_dir = temp_dir.path().to_path_buf();
+        fs::write(
+            project_dir.join("Nargo.toml"),
+            "[package]\nname = \"from_nargo\"\ntype = \"bin\"\n",
+        )
+        .unwrap();
+        if let Some(content) = bargo_toml {
+            fs::write(project_dir.join(PROJECT_CONFIG_FILE), content).unwrap();
+        }
+        project_dir
+    }
+
+    fn base_config(pkg: Option<&str>) -> Config {
+        Config {
+            verbose: 0,
+            dry_run: true,
+            pkg: pkg.map(str::to_string),
+            quiet: true,
+            timeout: None,
+            retries: 0,
+            target_dir: None,
+            package_dir: None,
+            evm_network: None,
+            cairo_network: None,
+            json: false,
+            color: ColorChoice::Auto,
+            tools: ToolPaths::default(),
+            artifact_suffix: ArtifactSuffix::None,
+            keep_going: false,
+            runner: Arc::new(DryRunRunner::new()),
+            fs: Arc::new(DryRunFs::new()),
+        }
+    }
+
+    #[test]
+    fn test_naming_strategy_from_artifact_suffix() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = create_project(&temp_dir, None);
+        let config = |suffix, pkg| Config {
+            artifact_suffix: suffix,
+            package_dir: Some(project_dir.clone()),
+            ..base_config(pkg)
+        };
+
+        assert_eq!(
+            config(ArtifactSuffix::None, None)
+                .naming_strategy()
+                .unwrap(),
+            NamingStrategy::Plain
+        );
+        assert_eq!(
+            config(ArtifactSuffix::Pkg, None).naming_strategy().unwrap(),
+            NamingStrategy::Package("from_nargo".to_string())
+        );
+        assert_eq!(
+            config(ArtifactSuffix::Pkg, Some("override"))
+                .naming_strategy()
+                .unwrap(),
+            NamingStrategy::Package("override".to_string())
+        );
+
+        // Every lookup in a run shares one timestamp
+        let first = config(ArtifactSuffix::Timestamp, None).naming_strategy();
+        let second = config(ArtifactSuffix::Timestamp, None).naming_strategy();
+        assert!(matches!(first, Ok(NamingStrategy::Timestamp(_))));
+        assert_eq!(first.unwrap(), second.unwrap());
+    }
+
+    #[test]
+    fn test_load_project_config_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = create_project(&temp_dir, None);
+
+        assert!(load_project_config(&project_dir).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_load_project_config_from_subdirectory() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = create_project(
+            &temp_dir,
+            Some("[defaults]\npkg = \"from_file\"\n\n[evm]\nnetwork = \"mainnet\"\n"),
+        );
+        let src_dir = project_dir.join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        let project = load_project_config(&src_dir).unwrap().unwrap();
+        assert_eq!(project.defaults.pkg.as_deref(), Some("from_file"));
+        assert_eq!(project.evm.network.as_deref(), Some("mainnet"));
+        assert!(project.cairo.network.is_none());
+    }
+
+    #[test]
+    fn test_project_config_fills_unset_values() {
+        let project: ProjectConfig = toml::from_str(
+            "[defaults]\npkg = \"from_file\"\n[evm]\nnetwork = \"mainnet\"\n[cairo]\nnetwork = \"sepolia\"\n",
+        )
+        .unwrap();
+
+        let cfg = base_config(None).with_project_config(project);
+        assert_eq!(cfg.pkg.as_deref(), Some("from_file"));
+        assert_eq!(cfg.evm_network.as_deref(), Some("mainnet"));
+        assert_eq!(cfg.cairo_network.as_deref(), Some("sepolia"));
+    }
+
+    #[test]
+    fn test_cli_flags_take_precedence_over_project_config() {
+        let project: ProjectConfig = toml::from_str("[defaults]\npkg = \"from_file\"\n").unwrap();
+
+        let cfg = base_config(Some("from_cli")).with_project_config(project);
+        assert_eq!(cfg.pkg.as_deref(), Some("from_cli"));
+        assert!(cfg.evm_network.is_none());
+    }
+
+    #[test]
+    fn test_show_spinner_only_for_interactive_runs() {
+        let mut cfg = base_config(None);
+        cfg.quiet = false;
+        assert!(!cfg.show_spinner(), "dry runs execute nothing to wait for");
+
+        cfg.dry_run = false;
+        assert!(cfg.show_spinner());
+
+        cfg.quiet = true;
+        assert!(!cfg.show_spinner());
+
+        cfg.quiet = false;
+        cfg.json = true;
+        assert!(!cfg.show_spinner());
+    }
+
+    #[test]
+    fn test_malformed_project_config_reports_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = create_project(&temp_dir, Some("[defaults\npkg = "));
+
+        let error = load_project_config(&project_dir).unwrap_err().to_string();
+        assert!(error.contains("Failed to parse bargo.toml"));
+        assert!(error.contains(&project_dir.join("bargo.toml").display().to_string()));
+    }
+
+    #[test]
+    fn test_tool_paths_resolve_relative_to_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = create_project(
+            &temp_dir,
+            Some("[tools]\nbb = \"tools/bb\"\nnargo = \"nargo-beta4\"\nforge = \"/opt/forge\"\n"),
+        );
+        let src_dir = project_dir.join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+
+        let tools = load_project_config(&src_dir).unwrap().unwrap().tools;
+        assert_eq!(
+            tools.get("bb"),
+            Some(project_dir.join("tools/bb").as_path())
+        );
+        assert_eq!(tools.get("nargo"), Some(Path::new("nargo-beta4")));
+        assert_eq!(tools.get("forge"), Some(Path::new("/opt/forge")));
+        assert_eq!(tools.get("garaga"), None);
+        assert_eq!(tools.get("cast"), None);
+    }
+
+    #[test]
+    fn test_foundry_config_validates_solc_version() {
+        for version in ["0.8.27", "0.8.4", "1.0.0"] {
+            let foundry_cfg = FoundryConfig::new(Some(version.to_string()), None);
+            assert!(
+                foundry_cfg.validate().is_ok(),
+                "{version} should be accepted"
+            );
+        }
+        for version in ["0.8", "v0.8.27", "0.8.x", "0..27", "^0.8.0"] {
+            let foundry_cfg = FoundryConfig::new(Some(version.to_string()), None);
+            assert!(
+                foundry_cfg.validate().is_err(),
+                "{version} should be rejected"
+            );
         }
+        assert!(FoundryConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_network_parses_known_names() {
+        assert_eq!(Network::parse("mainnet").unwrap(), Network::Mainnet);
+        assert_eq!(Network::parse("Sepolia").unwrap(), Network::Sepolia);
+        assert_eq!(Network::parse("localhost").unwrap(), Network::Localhost);
+
+        assert_eq!(Network::Sepolia.evm_chain_id(), Some(11_155_111));
+        assert_eq!(Network::Mainnet.starknet_chain_id(), Some("SN_MAIN"));
+        assert_eq!(
+            Network::Localhost.evm_rpc_url().as_deref(),
+            Some("http://127.0.0.1:8545")
+        );
+        assert_eq!(Network::Sepolia.evm_rpc_url(), None);
+    }
+
+    #[test]
+    fn test_network_typo_suggests_closest_name() {
+        let error = Network::parse("seploia").unwrap_err().to_string();
+        assert!(error.contains("Unsupported network: seploia"));
+        assert!(error.contains("Did you mean 'sepolia'?"));
+
+        let error = Network::parse("goerli").unwrap_err().to_string();
+        assert!(!error.contains("Did you mean"));
+        assert!(error.contains("custom:<rpc-url>"));
+    }
+
+    #[test]
+    fn test_network_custom_rpc() {
+        let network: Network = "custom:https://rpc.example.org".parse().unwrap();
+        assert_eq!(
+            network,
+            Network::Custom("https://rpc.example.org".to_string())
+        );
+        assert_eq!(network.to_string(), "custom:https://rpc.example.org");
+        assert_eq!(network.name(), "custom");
+        assert_eq!(
+            network.starknet_rpc_url().as_deref(),
+            Some("https://rpc.example.org")
+        );
+        assert_eq!(network.evm_chain_id(), None);
+
+        assert!(Network::parse("custom:").is_err());
+        assert!(Network::parse("custom:rpc.example.org").is_err());
     }
 }
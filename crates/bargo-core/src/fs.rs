@@ -0,0 +1,151 @@
+//! Filesystem operations that honour `--dry-run`
+//!
+//! Workflows move and delete artifacts as well as running tools. Just as
+//! every command goes through a [`Runner`](crate::runner::Runner), directory
+//! creation, moves and removals go through the [`Fs`] held by the
+//! configuration: [`RealFs`] performs them, while [`DryRunFs`] only records
+//! them, so a dry run leaves the project untouched and tests can assert on
+//! what would have changed.
+//!
+//! ## Examples
+//!
+//! ```
+//! use bargo_core::fs::{DryRunFs, Fs, FsOp};
+//! use std::path::{Path, PathBuf};
+//!
+//! let fs = DryRunFs::new();
+//! fs.rename(Path::new("target/pkg.json"), Path::new("target/bb/pkg.json"))?;
+//!
+//! assert_eq!(
+//!     fs.history(),
+//!     [FsOp::Rename {
+//!         from: PathBuf::from("target/pkg.json"),
+//!         to: PathBuf::from("target/bb/pkg.json"),
+//!     }]
+//! );
+//! assert!(!Path::new("target/bb/pkg.json").exists());
+//! # Ok::<(), color_eyre::Report>(())
+//! ```
+
+use color_eyre::Result;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Strategy for changing the filesystem
+///
+/// Reads are not part of the trait; only operations that change the
+/// filesystem need to be simulated. Callers add context to the errors.
+pub trait Fs: std::fmt::Debug + Send + Sync {
+    /// Create `path` and any missing parents
+    fn create_dir_all(&self, path: &Path) -> Result<()>;
+
+    /// Move a file or directory
+    fn rename(&self, from: &Path, to: &Path) -> Result<()>;
+
+    /// Delete a file
+    fn remove_file(&self, path: &Path) -> Result<()>;
+
+    /// Delete a directory and everything in it
+    fn remove_dir_all(&self, path: &Path) -> Result<()>;
+
+    /// Whether operations are only recorded, so callers can skip work such as
+    /// locking that only matters when files really change
+    fn is_dry_run(&self) -> bool {
+        false
+    }
+}
+
+/// Filesystem that performs every operation
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealFs;
+
+impl Fs for RealFs {
+    fn create_dir_all(&self, path: &Path) -> Result<()> {
+        Ok(std::fs::create_dir_all(path)?)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        Ok(std::fs::rename(from, to)?)
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        Ok(std::fs::remove_file(path)?)
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> Result<()> {
+        Ok(std::fs::remove_dir_all(path)?)
+    }
+}
+
+/// An operation recorded by [`DryRunFs`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FsOp {
+    CreateDir(PathBuf),
+    Rename { from: PathBuf, to: PathBuf },
+    RemoveFile(PathBuf),
+    RemoveDir(PathBuf),
+}
+
+impl std::fmt::Display for FsOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreateDir(path) => write!(f, "create {}", path.display()),
+            Self::Rename { from, to } => write!(f, "move {} -> {}", from.display(), to.display()),
+            Self::RemoveFile(path) => write!(f, "remove {}", path.display()),
+            Self::RemoveDir(path) => write!(f, "remove {}/", path.display()),
+        }
+    }
+}
+
+/// Filesystem that records operations without performing them
+#[derive(Debug, Default)]
+pub struct DryRunFs {
+    history: std::sync::Mutex<Vec<FsOp>>,
+}
+
+impl DryRunFs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Operations that would have been performed, in order
+    pub fn history(&self) -> Vec<FsOp> {
+        self.history.lock().unwrap().clone()
+    }
+
+    /// Forget recorded operations
+    pub fn clear_history(&self) {
+        self.history.lock().unwrap().clear();
+    }
+
+    fn record(&self, op: FsOp) -> Result<()> {
+        debug!("Would {op}");
+        self.history.lock().unwrap().push(op);
+        Ok(())
+    }
+}
+
+impl Fs for DryRunFs {
+    fn create_dir_all(&self, path: &Path) -> Result<()> {
+        self.record(FsOp::CreateDir(path.to_path_buf()))
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        self.record(FsOp::Rename {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        })
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<()> {
+        self.record(FsOp::RemoveFile(path.to_path_buf()))
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> Result<()> {
+        self.record(FsOp::RemoveDir(path.to_path_buf()))
+    }
+
+    fn is_dry_run(&self) -> bool {
+        true
+    }
+}
@@ -1,45 +1,117 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use clap::Parser;
 use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
 use tracing::{info, warn};
+use tracing_appender::non_blocking::WorkerGuard;
 
 mod backends;
 mod util;
 
+pub mod api;
 pub mod backend;
 pub mod cli;
 pub mod commands;
 pub mod config;
+pub mod fs;
 pub mod runner;
 
-use backend::{BackendKind, backend_for};
+use backend::{BackendConfig, BackendKind, backend_for};
+use config::{BuildConfig, CalldataConfig, FoundryConfig, ProveConfig, VerifyConfig};
 
 #[cfg(feature = "cairo")]
-use backend::BackendConfig;
+use config::{CairoDeployConfig, GaragaConfig};
 
-#[cfg(feature = "cairo")]
-use config::CairoDeployConfig;
+#[cfg(feature = "evm-foundry")]
+use config::EvmDeployConfig;
 
 pub use cli::Cli;
 pub use config::Config;
+pub use util::{BargoError, ExitCode, SuggestedError, find_bargo_error};
+
+/// Run bargo and report how it finished as a process exit code
+///
+/// Errors are printed through color-eyre as `main` returning them would, but
+/// the process exits with the [`ExitCode`] matching the error instead of 1.
+/// [`BargoError::Reported`] errors were already reported and are not printed.
+pub fn main() -> std::process::ExitCode {
+    match run() {
+        Ok(()) => ExitCode::Success.into(),
+        Err(error) => {
+            if !matches!(find_bargo_error(&error), Some(BargoError::Reported { .. })) {
+                eprintln!("Error: {error:?}");
+            }
+            ExitCode::for_error(&error).into()
+        }
+    }
+}
 
 pub fn run() -> Result<()> {
     color_eyre::install()?;
-    dotenv::dotenv().ok();
 
     let cli = Cli::parse();
-    setup_logging(cli.verbose, cli.quiet)?;
+    let _log_guard = util::LogFileGuard::register(setup_logging(&cli)?);
+    util::install_interrupt_handler();
 
-    if cli.verbose {
+    if cli.verbose > 0 {
         info!("🚀 Starting bargo");
         if cli.dry_run {
             warn!("🔍 Dry run mode - commands will be printed but not executed");
         }
     }
 
-    let cfg = Config::from(&cli);
-    dispatch(&cli, &cfg)?;
+    let mut cfg = Config::from(&cli);
+    if let Some(path) = &cli.replay {
+        cfg.runner = Arc::new(runner::ReplayRunner::from_file(path)?);
+    }
+    if let Some(path) = &cli.record {
+        cfg.runner = Arc::new(runner::RecordingRunner::new(cfg.runner.clone(), path)?);
+    }
+    let mut banners = !cli.no_banner;
+    if let Some(project) = config::load_project_config(&cfg.working_dir()?)? {
+        banners &= project.ui.banners.unwrap_or(true);
+        cfg = cfg.with_project_config(project);
+    }
+    cfg.apply_paths()?;
+    util::set_color_choice(cfg.color);
+    util::set_banners_enabled(banners);
+    if let Some(path) = &cli.env_file {
+        util::use_env_file(path)?;
+    }
+    // Variables in the project's .env apply wherever bargo was started from
+    commands::evm::load_env_vars(&cfg.project_root()?);
 
-    if cli.verbose {
+    let timer = util::Timer::start();
+    let result = dispatch(&cli, &cfg).map_err(util::enhance_error_with_suggestions);
+    if let Some(url) = &cli.notify_url {
+        notify_completion(url, &cli.command, &cfg, &result, &timer);
+    }
+    if let Some(path) = &cli.summary_file {
+        record_session(path, &cli.command, &cfg, &result, &timer);
+    }
+
+    if cfg.json {
+        match result {
+            Ok(()) => {
+                if let Some((operation, artifacts)) = json_operation(&cli.command, &cfg) {
+                    util::emit_json(operation, &artifacts, &timer);
+                }
+            }
+            Err(error) => {
+                let code = ExitCode::for_error(&error);
+                if !matches!(find_bargo_error(&error), Some(BargoError::Reported { .. })) {
+                    util::emit_json_error(&error);
+                }
+                return Err(BargoError::Reported { code }.into());
+            }
+        }
+    } else {
+        result?;
+    }
+
+    if cli.verbose > 0 {
         info!("✨ bargo completed successfully");
     }
 
@@ -51,23 +123,61 @@ fn dispatch(cli: &Cli, cfg: &Config) -> Result<()> {
     use util::print_banner;
 
     match &cli.command {
-        Commands::Check => {
+        Commands::Check {
+            workspace,
+            deny_warnings,
+        } => {
             if !cfg.quiet {
                 print_banner("check");
             }
-            commands::check::run(cfg)
+            if *workspace {
+                commands::check::run_workspace(cfg, *deny_warnings)
+            } else if *deny_warnings {
+                commands::check::run_deny_warnings(cfg)
+            } else {
+                commands::check::run(cfg)
+            }
         }
-        Commands::Build => {
+        Commands::Build {
+            all,
+            check,
+            input,
+            profile,
+            stats,
+            extra_args,
+        } => {
             if !cfg.quiet {
                 print_banner("build");
             }
-            commands::build::run(cfg)
+            let build_cfg = BuildConfig::new(input.clone())
+                .with_profile(profile.clone())
+                .with_stats(*stats)
+                .with_extra_args(extra_args.clone());
+            if *check {
+                commands::build::check(cfg, &build_cfg)
+            } else if *all {
+                commands::build::run_all(cfg, &build_cfg)
+            } else {
+                commands::build::run_with_config(cfg, &build_cfg)
+            }
         }
-        Commands::Clean { backend } => {
+        Commands::Clean {
+            backend,
+            cache,
+            all,
+            older_than,
+        } => {
             if !cfg.quiet {
                 print_banner("clean");
             }
-            commands::clean::run(cfg, backend.unwrap_or(Backend::All))
+            let backend = backend.unwrap_or(Backend::All);
+            if *cache {
+                commands::clean::run_cache(cfg, backend, *all)
+            } else if let Some(max_age) = older_than {
+                commands::clean::run_older_than(cfg, backend, *max_age)
+            } else {
+                commands::clean::run(cfg, backend)
+            }
         }
         Commands::Rebuild { backend } => {
             if !cfg.quiet {
@@ -75,34 +185,138 @@ fn dispatch(cli: &Cli, cfg: &Config) -> Result<()> {
             }
             commands::rebuild::run(cfg, backend.unwrap_or(Backend::All))
         }
+        Commands::Prove {
+            backend,
+            scheme,
+            force,
+            parallel,
+            recursive,
+            recursive_friendly,
+            oracle_hash,
+            witness,
+            profile,
+            compress,
+            bb_profile,
+            extra_args,
+        } => {
+            if !cfg.quiet {
+                print_banner("prove");
+            }
+            // Bring the witness up to date with the profile's inputs first
+            if let Some(profile) = profile {
+                commands::build::run_with_config(
+                    cfg,
+                    &BuildConfig::default().with_profile(Some(profile.clone())),
+                )?;
+            }
+            commands::prove::run(
+                cfg,
+                backend.unwrap_or(Backend::Bb),
+                &ProveConfig::new(*scheme)
+                    .with_force(*force)
+                    .with_recursive(*recursive)
+                    .with_recursive_friendly(*recursive_friendly)
+                    .with_oracle_hash(*oracle_hash)
+                    .with_witness(witness.clone())
+                    .with_compress(*compress)
+                    .with_bb_profile(*bb_profile)
+                    .with_extra_args(extra_args.clone()),
+                *parallel,
+            )
+        }
+        Commands::Verify { backend, force } => {
+            if !cfg.quiet {
+                print_banner("verify");
+            }
+            commands::verify::run(
+                cfg,
+                backend.unwrap_or(Backend::Bb),
+                &VerifyConfig::default().with_force(*force),
+            )
+        }
         #[cfg(feature = "cairo")]
         Commands::Cairo { command } => match command {
-            cli::CairoCommands::Gen => {
+            cli::CairoCommands::Gen {
+                system,
+                oracle_hash,
+                no_prove,
+                force,
+                output_dir,
+                extra_args,
+            } => {
                 if !cfg.quiet {
                     print_banner("cairo gen");
                 }
                 let mut backend = backend_for(BackendKind::Cairo);
+                backend.configure(BackendConfig::Garaga(
+                    GaragaConfig::new(*system)
+                        .with_output_dir(output_dir.clone())
+                        .with_overwrite(*force)
+                        .with_extra_args(extra_args.clone()),
+                ))?;
+                backend.configure(BackendConfig::Prove(
+                    ProveConfig::default()
+                        .with_oracle_hash(*oracle_hash)
+                        .with_no_prove(*no_prove)
+                        .with_force(*force),
+                ))?;
                 backend.generate(cfg)
             }
-            cli::CairoCommands::Prove => {
+            cli::CairoCommands::Prove {
+                scheme,
+                force,
+                oracle_hash,
+                extra_args,
+            } => {
                 if !cfg.quiet {
                     print_banner("cairo prove");
                 }
                 let mut backend = backend_for(BackendKind::Cairo);
+                backend.configure(BackendConfig::Prove(
+                    ProveConfig::new(*scheme)
+                        .with_force(*force)
+                        .with_oracle_hash(*oracle_hash)
+                        .with_extra_args(extra_args.clone()),
+                ))?;
                 backend.prove(cfg)
             }
-            cli::CairoCommands::Verify => {
+            cli::CairoCommands::Verify {
+                proof,
+                vk,
+                public_inputs,
+                expect,
+                force,
+                watch,
+            } => {
                 if !cfg.quiet {
                     print_banner("cairo verify");
                 }
                 let mut backend = backend_for(BackendKind::Cairo);
-                backend.verify(cfg)
+                let verify_config =
+                    VerifyConfig::new(proof.clone(), vk.clone(), public_inputs.clone())
+                        .with_expect(*expect)
+                        .with_force(*force);
+                let paths = verify_config.paths(util::Flavour::Starknet);
+                backend.configure(BackendConfig::Verify(verify_config))?;
+                if *watch {
+                    commands::watch::run(cfg, "verify", &paths, |cfg| backend.verify(cfg))
+                } else {
+                    backend.verify(cfg)
+                }
             }
-            cli::CairoCommands::Calldata => {
+            cli::CairoCommands::Calldata {
+                output,
+                system,
+                format,
+            } => {
                 if !cfg.quiet {
                     print_banner("cairo calldata");
                 }
                 let mut backend = backend_for(BackendKind::Cairo);
+                backend.configure(BackendConfig::Calldata(CalldataConfig::new(output.clone())))?;
+                backend.configure(BackendConfig::Garaga(
+                    GaragaConfig::new(*system).with_calldata_format(*format),
+                ))?;
                 backend.calldata(cfg)
             }
 
@@ -110,6 +324,10 @@ fn dispatch(cli: &Cli, cfg: &Config) -> Result<()> {
                 class_hash,
                 auto_declare,
                 no_declare,
+                network,
+                constructor_calldata,
+                account,
+                yes,
             } => {
                 if !cfg.quiet {
                     print_banner("cairo deploy");
@@ -118,100 +336,518 @@ fn dispatch(cli: &Cli, cfg: &Config) -> Result<()> {
 
                 // Configure the backend with deploy-specific settings
                 let deploy_config =
-                    CairoDeployConfig::new(class_hash.clone(), *auto_declare, *no_declare);
+                    CairoDeployConfig::new(class_hash.clone(), *auto_declare, *no_declare)
+                        .with_constructor_calldata(constructor_calldata.clone())
+                        .with_account(account.clone())
+                        .with_yes(*yes);
                 backend.configure(BackendConfig::CairoDeploy(deploy_config))?;
 
-                backend.deploy(cfg, None)
+                backend.deploy(cfg, network.as_deref().or(cfg.cairo_network.as_deref()))
             }
-            cli::CairoCommands::VerifyOnchain { address } => {
+            cli::CairoCommands::VerifyOnchain {
+                address,
+                system,
+                network,
+            } => {
                 if !cfg.quiet {
                     print_banner("cairo verify-onchain");
                 }
+                let cfg = &Config {
+                    cairo_network: network.clone().or_else(|| cfg.cairo_network.clone()),
+                    ..cfg.clone()
+                };
                 let mut backend = backend_for(BackendKind::Cairo);
+                backend.configure(BackendConfig::Garaga(GaragaConfig::new(*system)))?;
                 backend.verify_onchain(cfg, address.as_deref())
             }
         },
         Commands::Evm { command } => match command {
-            EvmCommands::Gen => {
+            EvmCommands::Gen {
+                oracle_hash,
+                no_prove,
+                force,
+                output_dir,
+                solc_version,
+                optimizer_runs,
+                extra_args,
+            } => {
                 if !cfg.quiet {
                     print_banner("evm gen");
                 }
                 let mut backend = backend_for(BackendKind::Evm);
+                backend.configure(BackendConfig::Prove(
+                    ProveConfig::default()
+                        .with_oracle_hash(*oracle_hash)
+                        .with_no_prove(*no_prove)
+                        .with_force(*force),
+                ))?;
+                backend.configure(BackendConfig::Foundry(
+                    FoundryConfig::new(solc_version.clone(), *optimizer_runs)
+                        .with_output_dir(output_dir.clone())
+                        .with_overwrite(*force)
+                        .with_extra_args(extra_args.clone()),
+                ))?;
                 backend.generate(cfg)
             }
-            EvmCommands::Prove => {
+            EvmCommands::Prove {
+                scheme,
+                force,
+                oracle_hash,
+                output_format,
+                extra_args,
+            } => {
                 if !cfg.quiet {
                     print_banner("evm prove");
                 }
                 let mut backend = backend_for(BackendKind::Evm);
+                backend.configure(BackendConfig::Prove(
+                    ProveConfig::new(*scheme)
+                        .with_force(*force)
+                        .with_oracle_hash(*oracle_hash)
+                        .with_output_format(*output_format)
+                        .with_extra_args(extra_args.clone()),
+                ))?;
                 backend.prove(cfg)
             }
-            EvmCommands::Verify => {
+            EvmCommands::Verify {
+                proof,
+                vk,
+                public_inputs,
+                expect,
+                force,
+                watch,
+            } => {
                 if !cfg.quiet {
                     print_banner("evm verify");
                 }
                 let mut backend = backend_for(BackendKind::Evm);
-                backend.verify(cfg)
+                let verify_config =
+                    VerifyConfig::new(proof.clone(), vk.clone(), public_inputs.clone())
+                        .with_expect(*expect)
+                        .with_force(*force);
+                let paths = verify_config.paths(util::Flavour::Evm);
+                backend.configure(BackendConfig::Verify(verify_config))?;
+                if *watch {
+                    commands::watch::run(cfg, "verify", &paths, |cfg| backend.verify(cfg))
+                } else {
+                    backend.verify(cfg)
+                }
             }
             #[cfg(feature = "evm-foundry")]
-            EvmCommands::Deploy { network } => {
+            EvmCommands::Deploy {
+                network,
+                max_gas,
+                account,
+                constructor_args,
+                yes,
+            } => {
                 if !cfg.quiet {
                     print_banner("evm deploy");
                 }
                 let mut backend = backend_for(BackendKind::Evm);
-                backend.deploy(cfg, Some(network))
+                let deploy_config = EvmDeployConfig::new(*max_gas)
+                    .with_account(account.clone())
+                    .with_constructor_args(constructor_args.clone())
+                    .with_yes(*yes);
+                backend.configure(BackendConfig::EvmDeploy(deploy_config))?;
+                backend.deploy(cfg, network.as_deref().or(cfg.evm_network.as_deref()))
             }
-            EvmCommands::Calldata => {
+            EvmCommands::Calldata { output } => {
                 if !cfg.quiet {
                     print_banner("evm calldata");
                 }
                 let mut backend = backend_for(BackendKind::Evm);
+                backend.configure(BackendConfig::Calldata(CalldataConfig::new(output.clone())))?;
                 backend.calldata(cfg)
             }
             #[cfg(feature = "evm-foundry")]
-            EvmCommands::VerifyOnchain => {
+            EvmCommands::VerifyOnchain { network } => {
                 if !cfg.quiet {
                     print_banner("evm verify-onchain");
                 }
+                let cfg = &Config {
+                    evm_network: network.clone().or_else(|| cfg.evm_network.clone()),
+                    ..cfg.clone()
+                };
                 let mut backend = backend_for(BackendKind::Evm);
                 backend.verify_onchain(cfg, None)
             }
         },
-        Commands::Doctor => {
+        Commands::Doctor {
+            env,
+            verify_binaries,
+            write_lock,
+            fix,
+            versions,
+            require,
+        } => {
             if !cfg.quiet {
                 print_banner("doctor");
             }
-            commands::doctor::run(cfg)
+            if *write_lock {
+                commands::doctor::write_lock(cfg)
+            } else {
+                commands::doctor::run(
+                    cfg,
+                    *env,
+                    *verify_binaries,
+                    *fix,
+                    *versions,
+                    require.as_deref(),
+                )
+                .and_then(commands::doctor::DoctorOutcome::into_result)
+            }
+        }
+        Commands::Info => {
+            if !cfg.quiet {
+                print_banner("info");
+            }
+            commands::info::run(cfg)
+        }
+        Commands::Status => {
+            if !cfg.quiet {
+                print_banner("status");
+            }
+            commands::status::run(cfg)
         }
+        Commands::Bench {
+            backend,
+            iterations,
+        } => {
+            if !cfg.quiet {
+                print_banner("bench");
+            }
+            commands::bench::run(cfg, backend.unwrap_or(Backend::Bb), *iterations)
+        }
+        Commands::Addresses { network } => {
+            if !cfg.quiet {
+                print_banner("addresses");
+            }
+            commands::addresses::run(cfg, network.as_deref())
+        }
+        Commands::Config { command } => match command {
+            cli::ConfigCommands::Get { key } => commands::config::get(cfg, key),
+            cli::ConfigCommands::Set { key, value } => commands::config::set(cfg, key, value),
+            cli::ConfigCommands::List => commands::config::list(cfg),
+        },
+        Commands::Export { backend, output } => {
+            if !cfg.quiet {
+                print_banner("export");
+            }
+            commands::export::run(cfg, backend.unwrap_or(Backend::Bb), output)
+        }
+        Commands::Convert { input, output, to } => {
+            if !cfg.quiet {
+                print_banner("convert");
+            }
+            commands::convert::run(cfg, input.as_deref(), output.as_deref(), *to)
+        }
+        Commands::Report { file } => {
+            if !cfg.quiet {
+                print_banner("report");
+            }
+            commands::report::run(cfg, file)
+        }
+    }
+}
+
+/// POST the outcome of the command to `--notify-url`, except in dry-run mode
+fn notify_completion(
+    url: &str,
+    command: &cli::Commands,
+    cfg: &Config,
+    result: &Result<()>,
+    timer: &util::Timer,
+) {
+    if cfg.dry_run {
+        info!("Dry run: skipping notification to {url}");
+        return;
+    }
+
+    let (operation, artifacts) =
+        json_operation(command, cfg).unwrap_or_else(|| (command_name(command), Vec::new()));
+    let payload = match result {
+        Ok(()) => util::NotifyPayload::success(operation, &artifacts, timer),
+        Err(error) => util::NotifyPayload::failure(operation, error, timer),
+    };
+    util::notify(url, &payload);
+}
+
+/// Append the outcome of the command to the `--summary-file` session log,
+/// except in dry-run mode and for `bargo report` itself
+fn record_session(
+    path: &std::path::Path,
+    command: &cli::Commands,
+    cfg: &Config,
+    result: &Result<()>,
+    timer: &util::Timer,
+) {
+    let path = util::project_path(path);
+    if cfg.dry_run {
+        info!("Dry run: skipping session record in {}", path.display());
+        return;
+    }
+    if matches!(command, cli::Commands::Report { .. }) {
+        return;
+    }
+
+    let (operation, _) =
+        json_operation(command, cfg).unwrap_or_else(|| (command_name(command), Vec::new()));
+    let package = cfg
+        .working_dir()
+        .ok()
+        .and_then(|dir| util::get_package_name_in_directory(cfg.pkg.as_ref(), &dir).ok());
+    let record = util::SessionRecord::finish(
+        operation,
+        package,
+        command_backend(command).map(str::to_string),
+        result.as_ref().err(),
+        timer,
+    );
+    if let Err(e) = util::append_session_record(&path, &record) {
+        warn!("⚠️  Could not record session summary: {e:#}");
+    }
+}
+
+/// Backend a command targets, for the session log
+fn command_backend(command: &cli::Commands) -> Option<&'static str> {
+    use cli::{Backend, Commands};
+
+    let name = |backend: Backend| match backend {
+        Backend::Bb => "bb",
+        #[cfg(feature = "cairo")]
+        Backend::Starknet => "starknet",
+        Backend::All => "all",
+    };
+    match command {
+        #[cfg(feature = "cairo")]
+        Commands::Cairo { .. } => Some("cairo"),
+        Commands::Evm { .. } => Some("evm"),
+        Commands::Prove { backend, .. } => Some(name(backend.unwrap_or(Backend::Bb))),
+        Commands::Verify { backend, .. } => Some(name(backend.unwrap_or(Backend::Bb))),
+        Commands::Export { backend, .. } => Some(name(backend.unwrap_or(Backend::Bb))),
+        _ => None,
+    }
+}
+
+/// Top-level subcommand name, for commands without a JSON operation name
+fn command_name(command: &cli::Commands) -> &'static str {
+    use cli::Commands;
+
+    match command {
+        Commands::Check { .. } => "check",
+        Commands::Build { .. } => "build",
+        Commands::Clean { .. } => "clean",
+        Commands::Rebuild { .. } => "rebuild",
+        Commands::Prove { .. } => "prove",
+        Commands::Verify { .. } => "verify",
+        Commands::Bench { .. } => "bench",
+        #[cfg(feature = "cairo")]
+        Commands::Cairo { .. } => "cairo",
+        Commands::Evm { .. } => "evm",
+        Commands::Doctor { .. } => "doctor",
+        Commands::Info => "info",
+        Commands::Status => "status",
+        Commands::Addresses { .. } => "addresses",
+        Commands::Export { .. } => "export",
+        Commands::Convert { .. } => "convert",
+        Commands::Report { .. } => "report",
+        Commands::Config { .. } => "config",
     }
 }
 
-fn setup_logging(verbose: bool, quiet: bool) -> Result<()> {
-    use tracing_subscriber::{EnvFilter, fmt};
-
-    if quiet {
-        let subscriber = fmt()
-            .with_max_level(tracing::Level::ERROR)
-            .with_target(false)
-            .with_level(true)
-            .finish();
-        tracing::subscriber::set_global_default(subscriber)?;
-    } else if verbose {
-        let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
-        let subscriber = fmt()
-            .with_env_filter(filter)
-            .with_target(false)
-            .with_level(true)
-            .finish();
-        tracing::subscriber::set_global_default(subscriber)?;
+/// Operation name and produced artifacts reported for a command in `--json` mode
+///
+/// Returns `None` for commands that print their own JSON object.
+fn json_operation(command: &cli::Commands, cfg: &Config) -> Option<(&'static str, Vec<PathBuf>)> {
+    use cli::{Commands, EvmCommands};
+    use util::{Flavour, get_bytecode_path};
+
+    // Compressed proofs are reported by their `.gz` paths
+    let proof_artifacts = |flavour| commands::common::proof_artifacts(flavour).to_vec();
+
+    let report = match command {
+        Commands::Build { check: true, .. } => return None,
+        Commands::Check { .. } => ("check", Vec::new()),
+        Commands::Build { .. } | Commands::Rebuild { .. } => {
+            let operation = if matches!(command, Commands::Build { .. }) {
+                "build"
+            } else {
+                "rebuild"
+            };
+            let packages = match command {
+                Commands::Build { all: true, .. } => cfg
+                    .working_dir()
+                    .ok()
+                    .and_then(|dir| util::find_project_root(&dir).ok())
+                    .and_then(|root| util::list_workspace_members(&root.join("Nargo.toml")).ok())
+                    .map(|members| members.into_iter().map(|member| member.name).collect()),
+                _ => None,
+            }
+            .or_else(|| {
+                let working_dir = cfg.working_dir().ok()?;
+                util::get_package_name_in_directory(cfg.pkg.as_ref(), &working_dir)
+                    .ok()
+                    .map(|pkg| vec![pkg])
+            })
+            .unwrap_or_default();
+            let artifacts = packages
+                .iter()
+                .flat_map(|pkg| {
+                    [
+                        get_bytecode_path(pkg, Flavour::Bb),
+                        util::get_witness_path(pkg, Flavour::Bb),
+                    ]
+                })
+                .collect();
+            (operation, artifacts)
+        }
+        Commands::Clean { .. } => ("clean", Vec::new()),
+        Commands::Prove { backend, .. } => (
+            "prove",
+            commands::prove::artifact_flavours(backend.unwrap_or(cli::Backend::Bb))
+                .into_iter()
+                .flat_map(proof_artifacts)
+                .collect(),
+        ),
+        #[cfg(feature = "cairo")]
+        Commands::Cairo { command } => match command {
+            cli::CairoCommands::Gen { output_dir, .. } => (
+                "cairo gen",
+                vec![commands::cairo::directories::cairo_contracts_dir(
+                    output_dir.as_deref(),
+                )],
+            ),
+            cli::CairoCommands::Prove { .. } => ("cairo prove", proof_artifacts(Flavour::Starknet)),
+            cli::CairoCommands::Verify { .. } => ("cairo verify", Vec::new()),
+            cli::CairoCommands::Calldata { output, .. } => (
+                "cairo calldata",
+                vec![CalldataConfig::new(output.clone()).output_path(Flavour::Starknet)],
+            ),
+            cli::CairoCommands::Deploy { .. } => ("cairo deploy", Vec::new()),
+            cli::CairoCommands::VerifyOnchain { .. } => ("cairo verify-onchain", Vec::new()),
+        },
+        Commands::Evm { command } => match command {
+            EvmCommands::Gen { output_dir, .. } => (
+                "evm gen",
+                vec![commands::evm::directories::verifier_contract_path(
+                    output_dir.as_deref(),
+                )],
+            ),
+            EvmCommands::Prove { .. } => ("evm prove", proof_artifacts(Flavour::Evm)),
+            EvmCommands::Verify { .. } => ("evm verify", Vec::new()),
+            #[cfg(feature = "evm-foundry")]
+            EvmCommands::Deploy { .. } => ("evm deploy", Vec::new()),
+            EvmCommands::Calldata { output } => (
+                "evm calldata",
+                vec![CalldataConfig::new(output.clone()).output_path(Flavour::Evm)],
+            ),
+            #[cfg(feature = "evm-foundry")]
+            EvmCommands::VerifyOnchain { .. } => ("evm verify-onchain", Vec::new()),
+        },
+        Commands::Verify { .. } => ("verify", Vec::new()),
+        Commands::Export { output, .. } => ("export", vec![util::project_path(output)]),
+        Commands::Convert { input, output, to } => (
+            "convert",
+            vec![commands::convert::resolve_paths(input.as_deref(), output.as_deref(), *to).1],
+        ),
+        Commands::Doctor { .. }
+        | Commands::Info
+        | Commands::Status
+        | Commands::Addresses { .. }
+        | Commands::Bench { .. }
+        | Commands::Report { .. }
+        | Commands::Config { .. } => return None,
+    };
+
+    Some(report)
+}
+
+/// Install the console logger and, with `--log-file`, a file logger
+///
+/// The console level follows [`console_level`]; with `-v` or more, `RUST_LOG`
+/// takes precedence when set. Console events go to stderr, so stdout only
+/// carries command output such as the `--json` report. They are colored
+/// following `--color` and `NO_COLOR`, like the rest of bargo's output. The log file records every event at every
+/// level, including each command line handed to the runner, so a failed run
+/// can be diagnosed after the fact. The returned guard flushes the file
+/// writer when dropped and must live until bargo exits.
+fn setup_logging(cli: &Cli) -> Result<Option<WorkerGuard>> {
+    use tracing_subscriber::{EnvFilter, filter::LevelFilter, fmt, prelude::*};
+
+    let level = console_level(cli.verbose, cli.quiet);
+    let console_filter = if cli.verbose > 0 && !cli.quiet {
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level.to_string()))
     } else {
-        let subscriber = fmt()
-            .with_max_level(tracing::Level::WARN)
-            .with_target(false)
-            .with_level(false)
-            .finish();
-        tracing::subscriber::set_global_default(subscriber)?;
+        EnvFilter::new(level.to_string())
+    };
+    let ansi = util::resolve_color(
+        cli.color,
+        |name| std::env::var(name).ok(),
+        atty::is(atty::Stream::Stderr),
+    );
+    let console = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_ansi(ansi)
+        .with_target(false)
+        .with_level(cli.verbose > 0 || cli.quiet)
+        .with_filter(console_filter);
+
+    let (file, guard) = match &cli.log_file {
+        Some(path) => {
+            let log = std::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(cli.log_append)
+                .truncate(!cli.log_append)
+                .open(path)
+                .wrap_err_with(|| format!("opening log file {}", path.display()))?;
+            let (writer, guard) = tracing_appender::non_blocking(log);
+            let layer = fmt::layer()
+                .with_writer(writer)
+                .with_ansi(false)
+                .with_filter(LevelFilter::TRACE);
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing::subscriber::set_global_default(
+        tracing_subscriber::registry().with(console).with(file),
+    )?;
+
+    Ok(guard)
+}
+
+/// Console log level for the number of `-v` flags
+///
+/// Warnings by default, then info, debug (each command line) and trace
+/// (each full command spec). `--quiet` always limits the console to errors.
+fn console_level(verbose: u8, quiet: bool) -> tracing_subscriber::filter::LevelFilter {
+    use tracing_subscriber::filter::LevelFilter;
+
+    match (quiet, verbose) {
+        (true, _) => LevelFilter::ERROR,
+        (false, 0) => LevelFilter::WARN,
+        (false, 1) => LevelFilter::INFO,
+        (false, 2) => LevelFilter::DEBUG,
+        (false, _) => LevelFilter::TRACE,
     }
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::filter::LevelFilter;
+
+    #[test]
+    fn test_console_level_follows_verbosity() {
+        assert_eq!(console_level(0, false), LevelFilter::WARN);
+        assert_eq!(console_level(1, false), LevelFilter::INFO);
+        assert_eq!(console_level(2, false), LevelFilter::DEBUG);
+        assert_eq!(console_level(3, false), LevelFilter::TRACE);
+        assert_eq!(console_level(7, false), LevelFilter::TRACE);
+        assert_eq!(console_level(0, true), LevelFilter::ERROR);
+        assert_eq!(console_level(3, true), LevelFilter::ERROR);
+    }
 }
@@ -0,0 +1,340 @@
+//! Deployed contract addresses, kept per network across deploys
+//!
+//! Every `evm deploy` and `cairo deploy` appends an entry to
+//! `target/<flavour>/addresses.json`, grouped by network, so earlier
+//! deployments are not lost when the verifier is redeployed. `verify-onchain`
+//! uses the most recent entry for the network it targets, and
+//! `bargo addresses` lists the whole book.
+//!
+//! Each entry also stores the sha256 of the vk at deploy time, so
+//! `verify-onchain` can warn when the key has been regenerated since and the
+//! deployed verifier no longer matches it, and the time `verify-onchain` last
+//! saw the verifier accept a proof, which `bargo status` reports.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! use bargo_core::util::address_book::{Deployment, record_deployment, latest_deployment};
+//!
+//! record_deployment(Flavour::Evm, "sepolia", Deployment::new("0x742d…", None, "my_pkg"))?;
+//! let latest = latest_deployment(Flavour::Evm, "sepolia")?;
+//! ```
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::util::{BargoError, Flavour, sha256_file, target_dir, utc_timestamp};
+
+/// File name of the address book kept in each target directory
+pub const ADDRESS_BOOK_FILE: &str = "addresses.json";
+
+/// Path of the address book for a flavour (`target/<flavour>/addresses.json`)
+pub fn address_book_path(flavour: Flavour) -> PathBuf {
+    target_dir(flavour).join(ADDRESS_BOOK_FILE)
+}
+
+/// Contents of an `addresses.json`: deployments keyed by network, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AddressBook {
+    pub networks: BTreeMap<String, Vec<Deployment>>,
+}
+
+/// A single deployed verifier contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deployment {
+    pub address: String,
+    /// Class hash the contract was deployed from (Starknet only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_hash: Option<String>,
+    /// UTC time of the deploy, in RFC 3339 format
+    pub timestamp: String,
+    /// Package whose verifier was deployed
+    pub package: String,
+    /// sha256 of the verification key at deploy time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vk_hash: Option<String>,
+    /// UTC time `verify-onchain` last saw this verifier accept a proof
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<String>,
+}
+
+impl Deployment {
+    /// Deployment made just now
+    pub fn new(address: &str, class_hash: Option<&str>, package: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            class_hash: class_hash.map(str::to_string),
+            timestamp: utc_timestamp(SystemTime::now()),
+            package: package.to_string(),
+            vk_hash: None,
+            verified_at: None,
+        }
+    }
+
+    /// Record the hash of the vk the deployed verifier was generated from
+    pub fn with_vk_hash(mut self, vk_hash: Option<String>) -> Self {
+        self.vk_hash = vk_hash;
+        self
+    }
+
+    /// Whether the verifier was deployed for a vk other than `current_vk_hash`
+    ///
+    /// Entries recorded without a vk hash are never considered stale.
+    pub fn is_stale(&self, current_vk_hash: &str) -> bool {
+        self.vk_hash
+            .as_deref()
+            .is_some_and(|hash| hash != current_vk_hash)
+    }
+}
+
+impl AddressBook {
+    /// Append a deployment to the history of `network`
+    pub fn append(&mut self, network: &str, deployment: Deployment) {
+        self.networks
+            .entry(network.to_string())
+            .or_default()
+            .push(deployment);
+    }
+
+    /// Most recent deployment on `network`
+    pub fn latest(&self, network: &str) -> Option<&Deployment> {
+        self.networks
+            .get(network)
+            .and_then(|entries| entries.last())
+    }
+
+    /// Whether no deployment has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.networks.values().all(Vec::is_empty)
+    }
+
+    /// Most recent deployment on any network, with its network
+    pub fn newest(&self) -> Option<(&str, &Deployment)> {
+        self.networks
+            .iter()
+            .filter_map(|(network, entries)| Some((network.as_str(), entries.last()?)))
+            .max_by(|(_, a), (_, b)| a.timestamp.cmp(&b.timestamp))
+    }
+
+    /// Stamp the latest deployment of `address` on `network` as verified at `timestamp`
+    ///
+    /// Returns `false` if the address is not in the book, for example when it
+    /// came from `CONTRACT_ADDRESS` or `--address`.
+    pub fn mark_verified(&mut self, network: &str, address: &str, timestamp: &str) -> bool {
+        let Some(deployment) = self
+            .networks
+            .get_mut(network)
+            .and_then(|entries| entries.iter_mut().rev().find(|d| d.address == address))
+        else {
+            return false;
+        };
+        deployment.verified_at = Some(timestamp.to_string());
+        true
+    }
+}
+
+/// Read the address book at `path`, or an empty book if it does not exist
+pub fn read_address_book(path: &Path) -> Result<AddressBook> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(AddressBook::default()),
+        Err(e) => {
+            return Err(e).wrap_err_with(|| format!("reading address book {}", path.display()));
+        }
+    };
+
+    serde_json::from_str(&contents).map_err(|e| {
+        BargoError::ParseFailure {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Append `deployment` on `network` to the address book at `path`
+#[cfg_attr(not(any(feature = "cairo", feature = "evm-foundry")), allow(dead_code))]
+pub fn append_deployment(path: &Path, network: &str, deployment: Deployment) -> Result<()> {
+    let mut book = read_address_book(path)?;
+    book.append(network, deployment);
+    write_address_book(path, &book)
+}
+
+#[cfg_attr(not(any(feature = "cairo", feature = "evm-foundry")), allow(dead_code))]
+fn write_address_book(path: &Path, book: &AddressBook) -> Result<()> {
+    crate::util::ensure_parent_dir(path)?;
+    let json = serde_json::to_string_pretty(book).wrap_err("serializing address book")?;
+    std::fs::write(path, format!("{json}\n"))
+        .wrap_err_with(|| format!("writing address book to {}", path.display()))
+}
+
+/// Append `deployment` on `network` to the address book of `flavour`
+#[cfg_attr(not(any(feature = "cairo", feature = "evm-foundry")), allow(dead_code))]
+pub fn record_deployment(flavour: Flavour, network: &str, deployment: Deployment) -> Result<()> {
+    append_deployment(&address_book_path(flavour), network, deployment)
+}
+
+/// Record that the verifier at `address` on `network` just accepted a proof
+///
+/// Addresses that are not in the address book of `flavour` are ignored.
+#[cfg_attr(not(any(feature = "cairo", feature = "evm-foundry")), allow(dead_code))]
+pub fn record_verification(flavour: Flavour, network: &str, address: &str) -> Result<()> {
+    let path = address_book_path(flavour);
+    let mut book = read_address_book(&path)?;
+    if book.mark_verified(network, address, &utc_timestamp(SystemTime::now())) {
+        write_address_book(&path, &book)?;
+    }
+    Ok(())
+}
+
+/// sha256 of the vk at `vk_path`, or `None` if it has not been generated
+pub fn vk_hash(vk_path: &Path) -> Option<String> {
+    sha256_file(vk_path).ok()
+}
+
+/// Warning for a deployment whose verifier no longer matches the vk at `vk_path`
+///
+/// `backend` is the subcommand (`evm` or `cairo`) named in the redeploy hint.
+/// Returns `None` when the hashes match or either one is unknown.
+#[cfg_attr(not(any(feature = "cairo", feature = "evm-foundry")), allow(dead_code))]
+pub fn stale_vk_warning(deployment: &Deployment, vk_path: &Path, backend: &str) -> Option<String> {
+    let current = vk_hash(vk_path)?;
+    if !deployment.is_stale(&current) {
+        return None;
+    }
+    Some(format!(
+        "The verifier at {} was deployed for a different verification key than {}\n  \
+         • Proofs generated with the current key will fail to verify against it\n  \
+         • Run 'bargo {backend} gen' and 'bargo {backend} deploy' to deploy a matching verifier",
+        deployment.address,
+        vk_path.display()
+    ))
+}
+
+/// Most recent deployment on `network` in the address book of `flavour`
+#[cfg_attr(not(any(feature = "cairo", feature = "evm-foundry")), allow(dead_code))]
+pub fn latest_deployment(flavour: Flavour, network: &str) -> Result<Option<Deployment>> {
+    Ok(read_address_book(&address_book_path(flavour))?
+        .latest(network)
+        .cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn deployment(address: &str, timestamp: &str) -> Deployment {
+        Deployment {
+            address: address.to_string(),
+            class_hash: None,
+            timestamp: timestamp.to_string(),
+            package: "pkg".to_string(),
+            vk_hash: None,
+            verified_at: None,
+        }
+    }
+
+    #[test]
+    fn test_append_keeps_history_per_network() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("evm").join(ADDRESS_BOOK_FILE);
+
+        let first = deployment("0x01", "2026-10-01T00:00:00Z");
+        let second = deployment("0x02", "2026-10-02T00:00:00Z");
+        let mainnet = deployment("0x03", "2026-10-03T00:00:00Z");
+        append_deployment(&path, "sepolia", first.clone()).unwrap();
+        append_deployment(&path, "sepolia", second.clone()).unwrap();
+        append_deployment(&path, "mainnet", mainnet.clone()).unwrap();
+
+        let book = read_address_book(&path).unwrap();
+        assert_eq!(book.networks["sepolia"], vec![first, second.clone()]);
+        assert_eq!(book.latest("sepolia"), Some(&second));
+        assert_eq!(book.latest("mainnet"), Some(&mainnet));
+        assert_eq!(book.latest("holesky"), None);
+    }
+
+    #[test]
+    fn test_missing_book_is_empty_and_damaged_book_errors() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join(ADDRESS_BOOK_FILE);
+        assert!(read_address_book(&path).unwrap().is_empty());
+
+        std::fs::write(&path, "not json").unwrap();
+        let error = read_address_book(&path).unwrap_err();
+        assert!(error.to_string().contains(ADDRESS_BOOK_FILE));
+    }
+
+    #[test]
+    fn test_mark_verified_stamps_latest_matching_deployment() {
+        let mut book = AddressBook::default();
+        book.append("sepolia", deployment("0x01", "2026-10-01T00:00:00Z"));
+        book.append("mainnet", deployment("0x02", "2026-10-03T00:00:00Z"));
+        book.append("sepolia", deployment("0x01", "2026-10-02T00:00:00Z"));
+
+        assert!(!book.mark_verified("sepolia", "0x99", "2026-10-04T00:00:00Z"));
+        assert!(!book.mark_verified("holesky", "0x01", "2026-10-04T00:00:00Z"));
+        assert!(book.mark_verified("sepolia", "0x01", "2026-10-04T00:00:00Z"));
+
+        let sepolia = &book.networks["sepolia"];
+        assert_eq!(sepolia[0].verified_at, None);
+        assert_eq!(
+            sepolia[1].verified_at.as_deref(),
+            Some("2026-10-04T00:00:00Z")
+        );
+
+        let (network, newest) = book.newest().unwrap();
+        assert_eq!((network, newest.address.as_str()), ("mainnet", "0x02"));
+        assert_eq!(AddressBook::default().newest(), None);
+    }
+
+    #[test]
+    fn test_class_hash_only_serialized_when_present() {
+        let mut book = AddressBook::default();
+        book.append("sepolia", deployment("0x01", "2026-10-01T00:00:00Z"));
+        let mut starknet = deployment("0x02", "2026-10-02T00:00:00Z");
+        starknet.class_hash = Some("0xc1a55".to_string());
+        book.append("mainnet", starknet);
+
+        let json = serde_json::to_value(&book).unwrap();
+        assert!(json["sepolia"][0].get("class_hash").is_none());
+        assert_eq!(json["mainnet"][0]["class_hash"], "0xc1a55");
+    }
+
+    #[test]
+    fn test_stale_vk_warning_only_when_hashes_differ() {
+        let temp_dir = tempdir().unwrap();
+        let vk = temp_dir.path().join("vk");
+        std::fs::write(&vk, b"vk-v1").unwrap();
+        let deployed = deployment("0x01", "2026-10-01T00:00:00Z").with_vk_hash(vk_hash(&vk));
+
+        assert!(!deployed.is_stale(&vk_hash(&vk).unwrap()));
+        assert_eq!(stale_vk_warning(&deployed, &vk, "evm"), None);
+
+        std::fs::write(&vk, b"vk-v2").unwrap();
+        assert!(deployed.is_stale(&vk_hash(&vk).unwrap()));
+        let warning = stale_vk_warning(&deployed, &vk, "evm").unwrap();
+        assert!(warning.contains("0x01"));
+        assert!(warning.contains("bargo evm deploy"));
+    }
+
+    #[test]
+    fn test_stale_vk_warning_skips_unknown_hashes() {
+        let temp_dir = tempdir().unwrap();
+        let vk = temp_dir.path().join("vk");
+        let recorded = deployment("0x01", "2026-10-01T00:00:00Z").with_vk_hash(Some("00".into()));
+
+        // No current vk to compare against
+        assert_eq!(stale_vk_warning(&recorded, &vk, "cairo"), None);
+
+        // Entry recorded before vk hashes were stored
+        std::fs::write(&vk, b"vk").unwrap();
+        let legacy = deployment("0x02", "2026-10-01T00:00:00Z");
+        assert_eq!(stale_vk_warning(&legacy, &vk, "cairo"), None);
+    }
+}
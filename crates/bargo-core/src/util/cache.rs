@@ -0,0 +1,237 @@
+//! Verification key cache for bargo
+//!
+//! Writing a verification key is the slowest step of repeated `prove` runs,
+//! yet the key only depends on the circuit bytecode and the `bb write_vk`
+//! arguments. A fingerprint of both is stored next to each generated key
+//! (`target/<flavour>/vk.hash`) so unchanged circuits can reuse it.
+//!
+//! Local verification is cached the same way: the outcome of the last
+//! `verify` is recorded in `target/<flavour>/verify_cache.json` together with
+//! the hashes of the proof, vk and public inputs it checked, and reused until
+//! one of them changes.
+//!
+//! `bargo clean --cache` removes these fingerprints together with the
+//! per-flavour `manifest.json`, forcing the next run to start fresh without
+//! deleting any proofs.
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::util::{
+    Flavour, ensure_parent_dir, get_vk_path, manifest_path, sha256_file, target_dir, utc_timestamp,
+};
+
+/// File name of the fingerprint stored next to a generated `vk`
+pub const VK_HASH_FILE: &str = "vk.hash";
+
+/// File name of the last local verification outcome
+pub const VERIFY_CACHE_FILE: &str = "verify_cache.json";
+
+/// Path of the vk fingerprint for a flavour (`target/<flavour>/vk.hash`)
+pub fn vk_hash_path(flavour: Flavour) -> PathBuf {
+    target_dir(flavour).join(VK_HASH_FILE)
+}
+
+/// Fingerprint the bytecode at `bytecode` together with the `bb` arguments
+///
+/// Including the arguments means a key written with a different oracle hash
+/// or output target is never mistaken for a cache hit.
+pub fn vk_fingerprint(bytecode: &Path, args: &[&str]) -> Result<String> {
+    let contents = std::fs::read(bytecode)
+        .wrap_err_with(|| format!("reading bytecode file {}", bytecode.display()))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(&contents);
+    for arg in args {
+        hasher.update([0u8]);
+        hasher.update(arg.as_bytes());
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Whether `vk_path` exists and was generated from `fingerprint`
+pub fn is_vk_cached(vk_path: &Path, hash_path: &Path, fingerprint: &str) -> bool {
+    vk_path.is_file()
+        && std::fs::read_to_string(hash_path)
+            .map(|stored| stored.trim() == fingerprint)
+            .unwrap_or(false)
+}
+
+/// Record the fingerprint of a freshly generated vk
+pub fn store_vk_fingerprint(hash_path: &Path, fingerprint: &str) -> Result<()> {
+    std::fs::write(hash_path, format!("{fingerprint}\n"))
+        .wrap_err_with(|| format!("writing vk fingerprint to {}", hash_path.display()))
+}
+
+/// Path of the verification cache for a flavour (`target/<flavour>/verify_cache.json`)
+pub fn verify_cache_path(flavour: Flavour) -> PathBuf {
+    target_dir(flavour).join(VERIFY_CACHE_FILE)
+}
+
+/// Hashes of the artifacts a verification checked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyInputs {
+    pub proof_hash: String,
+    pub vk_hash: String,
+    pub public_inputs_hash: String,
+}
+
+impl VerifyInputs {
+    /// Hash the proof, vk and public inputs as they are on disk
+    ///
+    /// Public inputs embedded in the proof have no file; their hash is empty.
+    pub fn from_files(proof: &Path, vk: &Path, public_inputs: &Path) -> Result<Self> {
+        Ok(Self {
+            proof_hash: sha256_file(proof)?,
+            vk_hash: sha256_file(vk)?,
+            public_inputs_hash: if public_inputs.exists() {
+                sha256_file(public_inputs)?
+            } else {
+                String::new()
+            },
+        })
+    }
+}
+
+/// Outcome of the last verification, as stored in `verify_cache.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyRecord {
+    #[serde(flatten)]
+    pub inputs: VerifyInputs,
+    /// Whether bb accepted the proof
+    pub accepted: bool,
+    pub verified_at: String,
+}
+
+/// Cached outcome for `inputs`, or `None` if they changed since the last run
+///
+/// A missing or unreadable cache is treated as a miss.
+pub fn cached_verification(cache_path: &Path, inputs: &VerifyInputs) -> Option<bool> {
+    let contents = std::fs::read_to_string(cache_path).ok()?;
+    let record: VerifyRecord = serde_json::from_str(&contents).ok()?;
+    (record.inputs == *inputs).then_some(record.accepted)
+}
+
+/// Record the outcome of verifying `inputs`, replacing the previous one
+pub fn store_verification(cache_path: &Path, inputs: VerifyInputs, accepted: bool) -> Result<()> {
+    let record = VerifyRecord {
+        inputs,
+        accepted,
+        verified_at: utc_timestamp(SystemTime::now()),
+    };
+    ensure_parent_dir(cache_path)?;
+    std::fs::write(cache_path, serde_json::to_string_pretty(&record)? + "\n")
+        .wrap_err_with(|| format!("writing verification cache {}", cache_path.display()))
+}
+
+/// Cache files kept for a flavour, whether or not they exist
+///
+/// These are the vk fingerprint, the verification cache and the artifact
+/// manifest; with `include_vks` the cached verification key itself is
+/// included as well.
+pub fn cache_files(flavour: Flavour, include_vks: bool) -> Vec<PathBuf> {
+    let mut files = vec![
+        vk_hash_path(flavour),
+        verify_cache_path(flavour),
+        manifest_path(flavour),
+    ];
+    if include_vks {
+        files.push(get_vk_path(flavour));
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_vk_cache_hit_and_miss_transitions() {
+        let temp_dir = tempdir().unwrap();
+        let bytecode = temp_dir.path().join("pkg.json");
+        let vk = temp_dir.path().join("vk");
+        let hash = temp_dir.path().join(VK_HASH_FILE);
+        std::fs::write(&bytecode, r#"{"bytecode":"v1"}"#).unwrap();
+
+        // No vk or fingerprint yet
+        let fingerprint = vk_fingerprint(&bytecode, &["write_vk", "-t", "evm"]).unwrap();
+        assert!(!is_vk_cached(&vk, &hash, &fingerprint));
+
+        // vk generated and fingerprint recorded
+        std::fs::write(&vk, [1u8; 8]).unwrap();
+        store_vk_fingerprint(&hash, &fingerprint).unwrap();
+        assert!(is_vk_cached(&vk, &hash, &fingerprint));
+
+        // Bytecode changed
+        std::fs::write(&bytecode, r#"{"bytecode":"v2"}"#).unwrap();
+        let changed = vk_fingerprint(&bytecode, &["write_vk", "-t", "evm"]).unwrap();
+        assert_ne!(changed, fingerprint);
+        assert!(!is_vk_cached(&vk, &hash, &changed));
+
+        // Same bytecode but different bb arguments
+        let other_args = vk_fingerprint(&bytecode, &["write_vk", "--oracle_hash", "starknet"]);
+        assert_ne!(other_args.unwrap(), changed);
+
+        // vk removed while the fingerprint is still present
+        store_vk_fingerprint(&hash, &changed).unwrap();
+        std::fs::remove_file(&vk).unwrap();
+        assert!(!is_vk_cached(&vk, &hash, &changed));
+    }
+
+    #[test]
+    fn test_vk_fingerprint_missing_bytecode_fails() {
+        let temp_dir = tempdir().unwrap();
+        let error = vk_fingerprint(&temp_dir.path().join("missing.json"), &[]).unwrap_err();
+        assert!(error.to_string().contains("reading bytecode file"));
+    }
+
+    #[test]
+    fn test_verification_cache_hit_and_invalidation() {
+        let temp_dir = tempdir().unwrap();
+        let [proof, vk, public_inputs] =
+            ["proof", "vk", "public_inputs"].map(|name| temp_dir.path().join(name));
+        std::fs::write(&proof, [1u8; 16]).unwrap();
+        std::fs::write(&vk, [2u8; 16]).unwrap();
+        std::fs::write(&public_inputs, [3u8; 4]).unwrap();
+        let cache = temp_dir.path().join("target/evm").join(VERIFY_CACHE_FILE);
+
+        // Nothing cached yet
+        let inputs = VerifyInputs::from_files(&proof, &vk, &public_inputs).unwrap();
+        assert_eq!(cached_verification(&cache, &inputs), None);
+
+        // Outcome recorded and reused while the inputs are unchanged
+        store_verification(&cache, inputs.clone(), true).unwrap();
+        let again = VerifyInputs::from_files(&proof, &vk, &public_inputs).unwrap();
+        assert_eq!(cached_verification(&cache, &again), Some(true));
+
+        // A new proof or vk invalidates the entry
+        std::fs::write(&proof, [4u8; 16]).unwrap();
+        let new_proof = VerifyInputs::from_files(&proof, &vk, &public_inputs).unwrap();
+        assert_ne!(new_proof.proof_hash, inputs.proof_hash);
+        assert_eq!(cached_verification(&cache, &new_proof), None);
+
+        std::fs::write(&vk, [5u8; 16]).unwrap();
+        let new_vk = VerifyInputs::from_files(&proof, &vk, &public_inputs).unwrap();
+        assert_eq!(cached_verification(&cache, &new_vk), None);
+
+        // Rejections are cached too, and a corrupt cache is a miss
+        store_verification(&cache, new_vk.clone(), false).unwrap();
+        assert_eq!(cached_verification(&cache, &new_vk), Some(false));
+        std::fs::write(&cache, "not json").unwrap();
+        assert_eq!(cached_verification(&cache, &new_vk), None);
+    }
+
+    #[test]
+    fn test_vk_hash_path_lives_next_to_vk() {
+        assert_eq!(
+            vk_hash_path(Flavour::Evm),
+            PathBuf::from("target/evm/vk.hash")
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Gzip compression of proof artifacts
+//!
+//! `prove --compress` replaces `proof` and `public_inputs` with `proof.gz` and
+//! `public_inputs.gz`. bb only reads uncompressed files, so `verify` falls
+//! back to the `.gz` copy when the plain file is missing and expands it into
+//! a temporary directory before calling bb.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! use bargo_core::util::compress::{compress_file, decompress_artifacts, resolve_compressed};
+//!
+//! let saved = compress_file(&get_proof_path(Flavour::Evm))?;
+//! println!("{} -> {} bytes", saved.original_size, saved.compressed_size);
+//!
+//! let proof = resolve_compressed(&get_proof_path(Flavour::Evm));
+//! let inputs = decompress_artifacts(&[&proof])?;
+//! run_bb_verify(&inputs.paths()[0])?;
+//! ```
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Compression recorded in the manifest for gzip-compressed artifacts
+pub const GZIP: &str = "gzip";
+
+/// Path of the compressed copy of `path` (`proof` -> `proof.gz`)
+pub fn gz_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".gz");
+    PathBuf::from(name)
+}
+
+/// Whether `path` names a gzip-compressed artifact
+pub fn is_gzip(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "gz")
+}
+
+/// `path`, or its `.gz` copy when only the compressed file exists
+pub fn resolve_compressed(path: &Path) -> PathBuf {
+    let compressed = gz_path(path);
+    if !path.exists() && compressed.is_file() {
+        compressed
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Sizes before and after compressing an artifact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedFile {
+    /// The `.gz` file that replaced the original
+    pub path: PathBuf,
+    pub original_size: u64,
+    pub compressed_size: u64,
+}
+
+impl CompressedFile {
+    /// Bytes saved by compressing, zero if the file grew
+    pub fn saved(&self) -> u64 {
+        self.original_size.saturating_sub(self.compressed_size)
+    }
+}
+
+/// Gzip `path` into `<path>.gz` and remove the original
+pub fn compress_file(path: &Path) -> Result<CompressedFile> {
+    let compressed = gz_path(path);
+    let mut input =
+        File::open(path).wrap_err_with(|| format!("opening {} to compress", path.display()))?;
+    let output =
+        File::create(&compressed).wrap_err_with(|| format!("creating {}", compressed.display()))?;
+
+    let mut encoder = GzEncoder::new(output, Compression::best());
+    std::io::copy(&mut input, &mut encoder)
+        .and_then(|_| encoder.finish())
+        .wrap_err_with(|| format!("compressing {}", path.display()))?;
+
+    let original_size = input.metadata()?.len();
+    let compressed_size = std::fs::metadata(&compressed)?.len();
+    std::fs::remove_file(path)
+        .wrap_err_with(|| format!("removing {} after compressing it", path.display()))?;
+
+    Ok(CompressedFile {
+        path: compressed,
+        original_size,
+        compressed_size,
+    })
+}
+
+/// Artifacts ready to pass to bb, with compressed ones expanded
+///
+/// Expanded copies live in a temporary directory that is removed on drop.
+#[derive(Debug)]
+pub struct DecompressedArtifacts {
+    paths: Vec<PathBuf>,
+    _scratch: Option<TempDir>,
+}
+
+impl DecompressedArtifacts {
+    /// Paths in the order they were given, `.gz` files replaced by their contents
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+}
+
+/// Expand each existing `.gz` file into a temporary file
+///
+/// Other paths, including `.gz` paths that do not exist (as in a dry run),
+/// are kept as-is.
+pub fn decompress_artifacts(paths: &[&Path]) -> Result<DecompressedArtifacts> {
+    let mut scratch: Option<TempDir> = None;
+    let mut expanded = Vec::with_capacity(paths.len());
+
+    for path in paths {
+        if !is_gzip(path) || !path.is_file() {
+            expanded.push(path.to_path_buf());
+            continue;
+        }
+
+        let dir = match &scratch {
+            Some(dir) => dir,
+            None => scratch.insert(
+                tempfile::tempdir().wrap_err("creating directory for decompressed artifacts")?,
+            ),
+        };
+        let target = dir.path().join(path.file_stem().unwrap_or_default());
+        let input = File::open(path).wrap_err_with(|| format!("opening {}", path.display()))?;
+        let mut output =
+            File::create(&target).wrap_err_with(|| format!("creating {}", target.display()))?;
+        std::io::copy(&mut GzDecoder::new(input), &mut output)
+            .wrap_err_with(|| format!("decompressing {}", path.display()))?;
+        expanded.push(target);
+    }
+
+    Ok(DecompressedArtifacts {
+        paths: expanded,
+        _scratch: scratch,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_compress_round_trip_shrinks_file() {
+        let temp_dir = tempdir().unwrap();
+        let proof = temp_dir.path().join("proof");
+        let contents: Vec<u8> = (0..4096u32).map(|i| (i % 7) as u8).collect();
+        std::fs::write(&proof, &contents).unwrap();
+
+        let compressed = compress_file(&proof).unwrap();
+        assert_eq!(compressed.path, temp_dir.path().join("proof.gz"));
+        assert_eq!(compressed.original_size, 4096);
+        assert!(compressed.compressed_size < compressed.original_size);
+        assert!(!proof.exists());
+
+        assert_eq!(resolve_compressed(&proof), compressed.path);
+        let vk = temp_dir.path().join("vk");
+        let expanded = decompress_artifacts(&[&compressed.path, &vk]).unwrap();
+        assert_eq!(std::fs::read(&expanded.paths()[0]).unwrap(), contents);
+        assert_eq!(expanded.paths()[1], vk);
+
+        // Missing files, as in a dry run, are passed through untouched
+        let missing = temp_dir.path().join("missing.gz");
+        let expanded = decompress_artifacts(&[&missing]).unwrap();
+        assert_eq!(expanded.paths(), [missing]);
+    }
+
+    #[test]
+    fn test_resolve_compressed_prefers_plain_file() {
+        let temp_dir = tempdir().unwrap();
+        let proof = temp_dir.path().join("proof");
+        std::fs::write(&proof, b"plain").unwrap();
+        std::fs::write(gz_path(&proof), b"stale").unwrap();
+
+        assert_eq!(resolve_compressed(&proof), proof);
+        assert!(is_gzip(&gz_path(&proof)));
+        assert!(!is_gzip(&proof));
+    }
+}
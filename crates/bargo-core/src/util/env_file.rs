@@ -0,0 +1,137 @@
+//! Loading of dotenv files with deploy settings and credentials
+//!
+//! By default the EVM workflows read `.env` and the Cairo ones `.env` and
+//! `.secrets` from the project root, silently skipping files that do not
+//! exist. The global `--env-file <path>` replaces that search with a single
+//! file, so each environment can keep its own credentials file. Unlike the
+//! defaults, a named file must exist: a typo fails the command up front
+//! instead of deploying with whatever happens to be in the environment.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! use bargo_core::util::env_file::{load_dotenv_files, use_env_file};
+//!
+//! use_env_file(Path::new("deploy/sepolia.env"))?;
+//! // Loads deploy/sepolia.env instead of <project>/.env
+//! load_dotenv_files(&project_root, &[".env"]);
+//! ```
+
+use color_eyre::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::debug;
+
+use crate::util::{BargoError, create_smart_error};
+
+/// Dotenv file set with `--env-file`, replacing the default search
+static ENV_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Load `path` and use it instead of the default dotenv files from now on
+///
+/// Called once from `run()` with the value of `--env-file`.
+pub fn use_env_file(path: &Path) -> Result<()> {
+    load_env_file(path)?;
+    *ENV_FILE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(path.to_path_buf());
+    Ok(())
+}
+
+/// Dotenv file set with `--env-file`, if any
+pub fn env_file() -> Option<PathBuf> {
+    ENV_FILE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Load the variables of `path`, failing if it does not exist or is malformed
+///
+/// Variables already set in the environment keep their value.
+pub fn load_env_file(path: &Path) -> Result<()> {
+    if !path.is_file() {
+        return Err(create_smart_error(
+            &format!("Env file not found: {}", path.display()),
+            &[
+                "Check the path passed to --env-file",
+                "Relative paths are resolved from the current directory",
+            ],
+        ));
+    }
+
+    dotenv::from_path(path).map_err(|e| BargoError::ParseFailure {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    debug!("Loaded environment from {}", path.display());
+    Ok(())
+}
+
+/// Load the `--env-file` file, or else each of `defaults` found in `project_root`
+pub fn load_dotenv_files(project_root: &Path, defaults: &[&str]) {
+    for path in dotenv_paths(project_root, defaults, env_file()) {
+        if path.exists() {
+            let _ = dotenv::from_path(path);
+        }
+    }
+}
+
+/// Dotenv files to read: `env_file` alone when set, otherwise the defaults
+fn dotenv_paths(project_root: &Path, defaults: &[&str], env_file: Option<PathBuf>) -> Vec<PathBuf> {
+    match env_file {
+        Some(path) => vec![path],
+        None => defaults
+            .iter()
+            .map(|name| project_root.join(name))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_env_file_replaces_default_search() {
+        let root = Path::new("/project");
+        assert_eq!(
+            dotenv_paths(root, &[".env", ".secrets"], None),
+            [root.join(".env"), root.join(".secrets")]
+        );
+        assert_eq!(
+            dotenv_paths(
+                root,
+                &[".env", ".secrets"],
+                Some(PathBuf::from("sepolia.env"))
+            ),
+            [PathBuf::from("sepolia.env")]
+        );
+    }
+
+    #[test]
+    fn test_load_env_file_applies_variables() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("staging.env");
+        std::fs::write(&path, "BARGO_TEST_ENV_FILE_VALUE=staging\n").unwrap();
+
+        load_env_file(&path).unwrap();
+
+        assert_eq!(
+            std::env::var("BARGO_TEST_ENV_FILE_VALUE").unwrap(),
+            "staging"
+        );
+    }
+
+    #[test]
+    fn test_load_missing_env_file_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("missing.env");
+
+        let error = load_env_file(&path).unwrap_err();
+
+        assert!(error.to_string().contains("Env file not found"), "{error}");
+        assert!(error.to_string().contains("missing.env"), "{error}");
+    }
+}
@@ -1,69 +1,240 @@
-/// Create a smart error with context and suggestions
-pub fn create_smart_error(message: &str, suggestions: &[&str]) -> color_eyre::eyre::Error {
-    let mut error_msg = format!("❌ {message}");
+//! Structured errors for the failures bargo knows how to explain
+//!
+//! Producers return a [`BargoError`] inside the usual `color_eyre` report, and
+//! [`enhance_error_with_suggestions`] looks it up in the error chain to attach
+//! suggestions for that variant.
+
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::Report;
+use thiserror::Error;
+
+use crate::util::ExitCode;
+
+/// Failures bargo recognises and can suggest a fix for
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BargoError {
+    /// Artifacts an earlier workflow step should have produced do not exist
+    #[error("Required files are missing: {}", display_paths(paths))]
+    MissingArtifact { paths: Vec<PathBuf> },
+
+    /// Build artifacts are older than their inputs, reported by `build --check`
+    #[error("Build artifacts are out of date:\n{}", bullet_list(reasons))]
+    StaleArtifacts { reasons: Vec<String> },
+
+    /// No Nargo.toml in the current directory or any parent directory
+    #[error(
+        "Could not find Nargo.toml in current directory or any parent directory.\n\
+         Make sure you're running bargo from within a Noir project."
+    )]
+    ProjectNotFound,
+
+    /// An external tool is not installed or not on `PATH`
+    #[error("Tool '{tool}' not found in PATH")]
+    MissingTool { tool: String },
 
-    if !suggestions.is_empty() {
-        error_msg.push_str("\n\n💡 Suggestions:");
-        for suggestion in suggestions {
-            error_msg.push_str(&format!("\n   • {suggestion}"));
+    /// A file bargo reads could not be parsed
+    #[error("Failed to parse {} at {}: {message}", file_name(path), path.display())]
+    ParseFailure { path: PathBuf, message: String },
+
+    /// An external tool exited unsuccessfully
+    #[error("Command '{tool}' failed with exit code {code:?}\nStdout: {stdout}\nStderr: {stderr}")]
+    CommandFailed {
+        tool: String,
+        code: Option<i32>,
+        stdout: String,
+        stderr: String,
+    },
+
+    /// An external tool ran longer than `--timeout` allows
+    #[error("command '{tool}' exceeded timeout of {seconds}s")]
+    Timeout { tool: String, seconds: u64 },
+
+    /// The command already printed its result and only needs to exit with `code`
+    ///
+    /// `main` prints nothing further for this error, so a `--json` report on
+    /// stdout stays the only output.
+    #[error("finished with exit code {}", code.code())]
+    Reported { code: ExitCode },
+}
+
+impl BargoError {
+    /// Suggested next steps for this failure
+    pub fn suggestions(&self) -> Vec<String> {
+        match self {
+            Self::MissingArtifact { paths } => {
+                let needs_build = paths.iter().any(|path| {
+                    matches!(
+                        path.extension().and_then(|ext| ext.to_str()),
+                        Some("json" | "gz")
+                    )
+                });
+                if needs_build {
+                    owned(&[
+                        "Run `bargo build` to generate bytecode and witness files",
+                        "Check if you're in the correct Noir project directory",
+                        "Verify that Nargo.toml exists in the current directory",
+                    ])
+                } else {
+                    owned(&[
+                        "Run `bargo prove` to generate proof and verification key",
+                        "Check if the proving step completed successfully",
+                        "Try running `bargo clean` and rebuilding from scratch",
+                    ])
+                }
+            }
+            Self::StaleArtifacts { .. } => owned(&[
+                "Run `bargo build` to regenerate bytecode and witness files",
+                "Commit the rebuilt artifacts if CI checks them in",
+            ]),
+            Self::ProjectNotFound => owned(&[
+                "Make sure you're in a Noir project directory",
+                "Initialize a new project with `nargo new <project_name>`",
+                "Check if you're in a subdirectory - try running from the project root",
+            ]),
+            Self::MissingTool { tool } => match tool.as_str() {
+                "nargo" => owned(&[
+                    "Install nargo: `curl -L https://raw.githubusercontent.com/noir-lang/noirup/main/install | bash`",
+                    "Add nargo to your PATH",
+                    "Verify installation with `nargo --version`",
+                ]),
+                "bb" => owned(&[
+                    "Install bb (Barretenberg): check Aztec installation docs",
+                    "Add bb to your PATH",
+                    "Verify installation with `bb --version`",
+                ]),
+                "forge" | "cast" => owned(&[
+                    "Install Foundry: `curl -L https://foundry.paradigm.xyz | bash` then run `foundryup`",
+                    "Check PATH includes ~/.foundry/bin",
+                    "Cairo/Starknet features work without Foundry",
+                ]),
+                "garaga" => owned(&[
+                    "Install garaga: `pip install garaga==0.18.1`",
+                    "Make sure the Python environment garaga was installed into is active",
+                    "Verify installation with `garaga --help`",
+                ]),
+                _ => vec![
+                    format!("Install {tool} and make sure it is on your PATH"),
+                    "Run `bargo doctor` to check all dependencies".to_string(),
+                ],
+            },
+            Self::ParseFailure { path, .. } if file_name(path) == "Nargo.toml" => owned(&[
+                "Check Nargo.toml syntax - it should be valid TOML format",
+                "Ensure the [package] section has a 'name' field",
+                "Compare with a working Nargo.toml from another project",
+            ]),
+            Self::ParseFailure { path, .. } => vec![
+                format!("Check that {} is well-formed", path.display()),
+                "Re-run the step that generated it, or `bargo rebuild` for build artifacts"
+                    .to_string(),
+            ],
+            Self::CommandFailed { tool, .. } => vec![
+                format!("Check the {tool} output above for the cause"),
+                "Re-run with `--verbose` to see the exact command line".to_string(),
+            ],
+            Self::Timeout { .. } => owned(&[
+                "Raise the limit with `--timeout <seconds>`",
+                "Omit `--timeout` to let the tool run to completion",
+                "Large circuits can take several minutes to prove",
+            ]),
+            Self::Reported { .. } => Vec::new(),
         }
     }
+}
 
-    color_eyre::eyre::eyre!(error_msg)
+/// A [`BargoError`] rendered together with its suggestions
+///
+/// Returned by [`enhance_error_with_suggestions`] so that enhancing an error
+/// twice does not repeat the suggestions.
+#[derive(Error, Debug)]
+#[error("{rendered}")]
+pub struct SuggestedError {
+    rendered: String,
+    cause: BargoError,
 }
 
-/// Enhanced error with suggestions for common issues
-pub fn enhance_error_with_suggestions(error: color_eyre::eyre::Error) -> color_eyre::eyre::Error {
-    let error_msg = format!("{error}");
-
-    // Check for common error patterns and add suggestions
-    if error_msg.contains("Required files are missing") {
-        if error_msg.contains(".json") || error_msg.contains(".gz") {
-            return color_eyre::eyre::eyre!(
-                "{}\n\n💡 Suggestions:\n   • Run `bargo build` to generate bytecode and witness files\n   • Check if you're in the correct Noir project directory\n   • Verify that Nargo.toml exists in the current directory",
-                error
-            );
-        }
-        if error_msg.contains("proof") || error_msg.contains("vk") {
-            return color_eyre::eyre::eyre!(
-                "{}\n\n💡 Suggestions:\n   • Run `bargo prove` to generate proof and verification key\n   • Check if the proving step completed successfully\n   • Try running `bargo clean` and rebuilding from scratch",
-                error
-            );
-        }
+impl SuggestedError {
+    /// The structured error the suggestions were derived from
+    pub fn cause(&self) -> &BargoError {
+        &self.cause
     }
+}
 
-    if error_msg.contains("Could not find Nargo.toml") {
-        return color_eyre::eyre::eyre!(
-            "{}\n\n💡 Suggestions:\n   • Make sure you're in a Noir project directory\n   • Initialize a new project with `nargo new <project_name>`\n   • Check if you're in a subdirectory - try running from the project root",
-            error
-        );
+/// Find the [`BargoError`] carried anywhere in an error chain
+pub fn find_bargo_error(error: &Report) -> Option<&BargoError> {
+    if let Some(suggested) = error.downcast_ref::<SuggestedError>() {
+        return Some(suggested.cause());
     }
+    error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<BargoError>())
+}
+
+/// Create a smart error with context and suggestions
+pub fn create_smart_error(message: &str, suggestions: &[&str]) -> color_eyre::eyre::Error {
+    let mut error_msg = format!("❌ {message}");
+    push_suggestions(&mut error_msg, suggestions);
+    color_eyre::eyre::eyre!(error_msg)
+}
 
-    if error_msg.contains("Failed to parse Nargo.toml") {
-        return color_eyre::eyre::eyre!(
-            "{}\n\n💡 Suggestions:\n   • Check Nargo.toml syntax - it should be valid TOML format\n   • Ensure the [package] section has a 'name' field\n   • Compare with a working Nargo.toml from another project",
-            error
-        );
+/// Attach suggestions for the [`BargoError`] carried by `error`, if any
+///
+/// The messages leading up to the structured error are kept, so context added
+/// by callers (such as the command line that failed) is still shown.
+pub fn enhance_error_with_suggestions(error: Report) -> Report {
+    if error.downcast_ref::<SuggestedError>().is_some() {
+        return error;
     }
+    let Some(cause) = find_bargo_error(&error).cloned() else {
+        return error;
+    };
 
-    if error_msg.contains("nargo")
-        && (error_msg.contains("not found") || error_msg.contains("command not found"))
-    {
-        return color_eyre::eyre::eyre!(
-            "{}\n\n💡 Suggestions:\n   • Install nargo: `curl -L https://raw.githubusercontent.com/noir-lang/noirup/main/install | bash`\n   • Add nargo to your PATH\n   • Verify installation with `nargo --version`",
-            error
-        );
+    let mut messages = Vec::new();
+    for link in error.chain() {
+        messages.push(link.to_string());
+        if link.downcast_ref::<BargoError>().is_some() {
+            break;
+        }
     }
+    let mut rendered = messages.join("\n");
+    push_suggestions(&mut rendered, &cause.suggestions());
+
+    Report::new(SuggestedError { rendered, cause })
+}
 
-    if error_msg.contains("bb")
-        && (error_msg.contains("not found") || error_msg.contains("command not found"))
-    {
-        return color_eyre::eyre::eyre!(
-            "{}\n\n💡 Suggestions:\n   • Install bb (Barretenberg): check Aztec installation docs\n   • Add bb to your PATH\n   • Verify installation with `bb --version`",
-            error
-        );
+/// Append a bulleted suggestion list to an error message
+fn push_suggestions<S: AsRef<str>>(message: &mut String, suggestions: &[S]) {
+    if suggestions.is_empty() {
+        return;
+    }
+    message.push_str("\n\n💡 Suggestions:");
+    for suggestion in suggestions {
+        message.push_str(&format!("\n   • {}", suggestion.as_ref()));
     }
+}
 
-    // Return original error if no patterns match
-    error
+fn owned(suggestions: &[&str]) -> Vec<String> {
+    suggestions.iter().map(|s| s.to_string()).collect()
+}
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn bullet_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|item| format!("   • {item}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
 }
@@ -0,0 +1,82 @@
+//! Process exit codes scripts can branch on
+//!
+//! Every way bargo finishes maps to one [`ExitCode`]. Errors returned from a
+//! command are classified by the [`BargoError`] in their chain, so a CI job
+//! can tell a missing tool from a failed proof without parsing stderr. Errors
+//! bargo has no structured variant for exit with [`ExitCode::Failure`].
+
+use color_eyre::eyre::Report;
+
+use crate::util::{BargoError, find_bargo_error};
+
+/// Exit status of a bargo process
+///
+/// The numeric values are part of the command-line interface and do not
+/// change between releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// The command completed (0)
+    Success = 0,
+    /// Any failure not covered by a more specific code (1)
+    Failure = 1,
+    /// `doctor` found only optional tools missing (2); clap also exits with 2
+    /// for an invalid command line
+    Incomplete = 2,
+    /// An external tool is not installed or not on `PATH` (3)
+    ToolMissing = 3,
+    /// Artifacts from an earlier step are missing or out of date (4)
+    ArtifactMissing = 4,
+    /// An external tool ran but exited unsuccessfully (5)
+    CommandFailed = 5,
+    /// A file bargo reads, such as Nargo.toml, could not be parsed (6)
+    ConfigError = 6,
+    /// No Nargo.toml in the current directory or any parent (7)
+    ProjectNotFound = 7,
+    /// An external tool ran longer than `--timeout` allows (8)
+    Timeout = 8,
+    /// Interrupted with Ctrl-C, as shells report for SIGINT (130)
+    Interrupted = 130,
+}
+
+impl ExitCode {
+    /// Exit code for the structured error carried by `error`, if any
+    pub fn for_error(error: &Report) -> Self {
+        find_bargo_error(error).map_or(Self::Failure, Self::for_bargo_error)
+    }
+
+    /// Exit code for a structured error
+    pub fn for_bargo_error(error: &BargoError) -> Self {
+        match error {
+            BargoError::MissingArtifact { .. } | BargoError::StaleArtifacts { .. } => {
+                Self::ArtifactMissing
+            }
+            BargoError::ProjectNotFound => Self::ProjectNotFound,
+            BargoError::MissingTool { .. } => Self::ToolMissing,
+            BargoError::ParseFailure { .. } => Self::ConfigError,
+            BargoError::CommandFailed { .. } => Self::CommandFailed,
+            BargoError::Timeout { .. } => Self::Timeout,
+            BargoError::Reported { code } => *code,
+        }
+    }
+
+    /// Numeric process status
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Terminate the process with this code
+    ///
+    /// Destructors do not run, so flush anything buffered before calling it.
+    /// Commands return [`BargoError::Reported`] instead, leaving the exit to
+    /// `main` so that notifications, session records and logs are written.
+    pub fn exit(self) -> ! {
+        std::process::exit(self.code())
+    }
+}
+
+impl From<ExitCode> for std::process::ExitCode {
+    fn from(code: ExitCode) -> Self {
+        // Every code fits in a byte
+        Self::from(code as u8)
+    }
+}
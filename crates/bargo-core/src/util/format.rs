@@ -39,34 +39,44 @@ use std::path::Path;
 /// Format file size in human-readable format
 pub fn format_file_size(path: &Path) -> String {
     match std::fs::metadata(path) {
-        Ok(metadata) => {
-            let size = metadata.len();
-            if size < 1024 {
-                format!("{size} B")
-            } else if size < 1024 * 1024 {
-                format!("{:.1} KB", size as f64 / 1024.0)
-            } else {
-                format!("{:.1} MB", size as f64 / (1024.0 * 1024.0))
-            }
-        }
+        Ok(metadata) => format_size(metadata.len()),
         Err(_) => "unknown size".to_string(),
     }
 }
 
+/// Format a byte count in human-readable format
+pub fn format_size(size: u64) -> String {
+    if size < 1024 {
+        format!("{size} B")
+    } else if size < 1024 * 1024 {
+        format!("{:.1} KB", size as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", size as f64 / (1024.0 * 1024.0))
+    }
+}
+
 /// Format operation result with file size and timing
 pub fn format_operation_result(
     operation: &str,
     file_path: &Path,
     timer: &crate::util::timer::Timer,
+) -> String {
+    format_operation_duration(operation, file_path, timer.duration())
+}
+
+/// Format operation result with file size and an already measured duration
+pub fn format_operation_duration(
+    operation: &str,
+    file_path: &Path,
+    elapsed: std::time::Duration,
 ) -> String {
     let size = format_file_size(file_path);
-    let elapsed = timer.elapsed();
     format!(
         "{} → {} ({}, {})",
         operation,
         file_path.display(),
         size,
-        elapsed
+        crate::util::timer::format_duration(elapsed)
     )
 }
 
@@ -87,6 +97,13 @@ mod tests {
         assert_eq!(result, "unknown size");
     }
 
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+    }
+
     #[test]
     fn test_format_operation_result() {
         let timer = crate::util::timer::Timer::start();
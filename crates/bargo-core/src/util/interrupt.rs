@@ -0,0 +1,196 @@
+//! Cleanup of partially written artifacts when bargo is interrupted
+//!
+//! A Ctrl-C while nargo writes `target/<package>.json` and `.gz`, or while
+//! `organize_build_artifacts` moves them, can leave a truncated or mismatched
+//! artifact behind that later fails proving with a confusing message. Each
+//! such phase holds a [`PartialArtifacts`] guard naming the files it writes.
+//! The handler installed by [`install_interrupt_handler`] deletes the files of
+//! every unfinished guard, flushes the `--log-file` writer registered with
+//! [`LogFileGuard`], then exits with status 130. A guard dropped without
+//! [`PartialArtifacts::finish`], because the phase failed, deletes its files
+//! too.
+//!
+//! The artifact lock needs no special handling: guards are created after the
+//! lock is taken, so an error path removes its files before the lock drops,
+//! and exiting from the handler closes the lock file, which releases it.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{debug, info, warn};
+use tracing_appender::non_blocking::WorkerGuard;
+
+use crate::util::ExitCode;
+
+/// Files of the unfinished phases, by guard id
+static IN_PROGRESS: Mutex<Vec<(u64, Vec<PathBuf>)>> = Mutex::new(Vec::new());
+
+static NEXT_GUARD: AtomicU64 = AtomicU64::new(0);
+
+/// Writer guard of the `--log-file` logger, flushed by the handler before exiting
+static LOG_FILE: Mutex<Option<WorkerGuard>> = Mutex::new(None);
+
+/// Keeps the `--log-file` writer open until dropped or bargo is interrupted
+///
+/// Exiting skips destructors, so the guard is held where the interrupt
+/// handler can flush it; dropping this handle flushes it on a normal exit.
+#[must_use = "the log file is flushed and closed as soon as the guard is dropped"]
+pub struct LogFileGuard(());
+
+impl LogFileGuard {
+    /// Hold `guard`, if logging to a file, until this handle is dropped
+    pub fn register(guard: Option<WorkerGuard>) -> Self {
+        *log_file() = guard;
+        Self(())
+    }
+}
+
+impl Drop for LogFileGuard {
+    fn drop(&mut self) {
+        flush_log_file();
+    }
+}
+
+fn log_file() -> std::sync::MutexGuard<'static, Option<WorkerGuard>> {
+    LOG_FILE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Flush and close the `--log-file` writer, if one is registered
+pub fn flush_log_file() {
+    // Dropping the guard waits for the writer thread to drain its buffer
+    let guard = log_file().take();
+    drop(guard);
+}
+
+/// Files a workflow phase is writing, deleted unless the phase finishes
+#[derive(Debug)]
+#[must_use = "the files are removed as soon as the guard is dropped"]
+pub struct PartialArtifacts {
+    id: u64,
+    finished: bool,
+}
+
+impl PartialArtifacts {
+    /// Register `paths` as in progress until [`finish`](Self::finish) is called
+    pub fn track(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let id = NEXT_GUARD.fetch_add(1, Ordering::Relaxed);
+        let paths: Vec<PathBuf> = paths.into_iter().collect();
+        debug!("Tracking in-progress artifacts: {paths:?}");
+        in_progress().push((id, paths));
+        Self {
+            id,
+            finished: false,
+        }
+    }
+
+    /// Mark the files as complete so they are kept
+    pub fn finish(mut self) {
+        self.finished = true;
+    }
+
+    fn untrack(&self) -> Vec<PathBuf> {
+        let mut entries = in_progress();
+        match entries.iter().position(|(id, _)| *id == self.id) {
+            Some(index) => entries.remove(index).1,
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Drop for PartialArtifacts {
+    fn drop(&mut self) {
+        let paths = self.untrack();
+        if !self.finished {
+            remove_existing(&paths);
+        }
+    }
+}
+
+fn in_progress() -> std::sync::MutexGuard<'static, Vec<(u64, Vec<PathBuf>)>> {
+    IN_PROGRESS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Delete the files of `paths` that exist, returning the ones removed
+fn remove_existing(paths: &[PathBuf]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter(|path| path.is_file())
+        .filter(|path| match std::fs::remove_file(path) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Could not remove partial artifact {}: {e}", path.display());
+                false
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+/// Delete the files of every unfinished phase, returning the ones removed
+///
+/// The registry stays locked while the files are removed, so no phase can
+/// finish or start halfway through.
+pub fn remove_partial_artifacts() -> Vec<PathBuf> {
+    let mut entries = in_progress();
+    let paths: Vec<PathBuf> = entries.drain(..).flat_map(|(_, paths)| paths).collect();
+    remove_existing(&paths)
+}
+
+/// Install the Ctrl-C handler that removes partial artifacts and exits
+///
+/// Failing to install it only loses the cleanup, so it is logged rather than
+/// returned.
+pub fn install_interrupt_handler() {
+    if let Err(e) = ctrlc::set_handler(|| {
+        let removed = remove_partial_artifacts();
+        let message = interrupt_message(&removed);
+        eprintln!("\n{message}");
+        info!("{message}");
+        flush_log_file();
+        ExitCode::Interrupted.exit();
+    }) {
+        warn!("Could not install the Ctrl-C handler: {e}");
+    }
+}
+
+/// Message printed when bargo is interrupted
+fn interrupt_message(removed: &[PathBuf]) -> String {
+    if removed.is_empty() {
+        return "❌ Interrupted".to_string();
+    }
+    let removed: Vec<String> = removed
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    format!(
+        "❌ Interrupted, removed partial artifacts: {}\nRun 'bargo build' to regenerate them",
+        removed.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_flush_log_file_drains_buffered_lines() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("bargo.log");
+        let (mut writer, guard) =
+            tracing_appender::non_blocking(std::fs::File::create(&path).unwrap());
+        let handle = LogFileGuard::register(Some(guard));
+
+        writer.write_all(b"Interrupted\n").unwrap();
+        flush_log_file();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Interrupted\n");
+
+        // The handle has nothing left to flush
+        drop(handle);
+        assert!(log_file().is_none());
+    }
+}
@@ -7,28 +7,33 @@
 //!
 //! - File existence validation
 //! - Directory creation and management
-//! - Smart rebuild detection
+//! - Smart rebuild detection and staleness reporting
 //! - Command specification macro helpers
 //!
 //! ## Examples
 //!
 //! ```ignore
-//! use bargo_core::util::io::{validate_files_exist, needs_rebuild};
+//! use bargo_core::util::io::{validate_files_exist, needs_rebuild_from_path};
 //!
 //! // Validate required files exist
 //! validate_files_exist(&["target/proof", "target/vk"])?;
 //!
 //! // Check if rebuild is needed
-//! if needs_rebuild("my_package")? {
+//! if needs_rebuild_from_path("my_package", &project_dir)? {
 //!     println!("Rebuild required");
 //! }
 //! ```
 
 use color_eyre::Result;
 use color_eyre::eyre::WrapErr;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::debug;
 
+#[cfg(feature = "cairo")]
+use crate::fs::Fs;
+
+use crate::util::BargoError;
+
 /// Macro for creating command specifications with convenient syntax
 ///
 /// This macro provides a convenient way to create `CmdSpec` instances
@@ -107,40 +112,104 @@ macro_rules! cmd_spec {
 
 /// Validate that required files exist for a given operation
 pub fn validate_files_exist<P: AsRef<Path>>(files: &[P]) -> Result<()> {
-    let mut missing_files = Vec::new();
+    let missing_files: Vec<PathBuf> = files
+        .iter()
+        .map(|file_path| file_path.as_ref())
+        .filter(|file_path| !file_path.exists())
+        .map(Path::to_path_buf)
+        .collect();
 
-    for file_path in files {
-        if !file_path.as_ref().exists() {
-            missing_files.push(file_path.as_ref().display().to_string());
-        }
+    if !missing_files.is_empty() {
+        return Err(crate::util::enhance_error_with_suggestions(
+            BargoError::MissingArtifact {
+                paths: missing_files,
+            }
+            .into(),
+        ));
     }
 
-    if !missing_files.is_empty() {
+    Ok(())
+}
+
+/// Gzip member header magic bytes (RFC 1952)
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Check that a bytecode JSON file parses and a witness file looks like gzip
+///
+/// An interrupted build can leave a truncated witness or half-written bytecode
+/// behind, which `bb` then rejects with an unhelpful message. This parses the
+/// bytecode JSON and checks the witness gzip magic bytes; the witness is not
+/// decompressed.
+pub fn validate_artifact_files(bytecode_path: &Path, witness_path: &Path) -> Result<()> {
+    let mut problems = Vec::new();
+
+    let bytecode = std::fs::File::open(bytecode_path)
+        .wrap_err_with(|| format!("opening bytecode file {}", bytecode_path.display()))?;
+    if let Err(e) =
+        serde_json::from_reader::<_, serde::de::IgnoredAny>(std::io::BufReader::new(bytecode))
+    {
+        problems.push(format!(
+            "{} is not valid JSON ({e})",
+            bytecode_path.display()
+        ));
+    }
+
+    let mut header = [0u8; 2];
+    let mut witness = std::fs::File::open(witness_path)
+        .wrap_err_with(|| format!("opening witness file {}", witness_path.display()))?;
+    let valid_header =
+        std::io::Read::read_exact(&mut witness, &mut header).is_ok() && header == GZIP_MAGIC;
+    if !valid_header {
+        problems.push(format!(
+            "{} is not a gzip file (bad header)",
+            witness_path.display()
+        ));
+    }
+
+    if !problems.is_empty() {
         return Err(crate::util::error::create_smart_error(
-            &format!("Required files are missing: {}", missing_files.join(", ")),
+            &format!("Build artifacts are corrupted: {}", problems.join("; ")),
             &[
-                "Run 'bargo build' to generate bytecode and witness files",
-                "Ensure the previous workflow steps completed successfully",
-                "Check that you're running from the correct directory",
-                "Verify the package name is correct",
+                "Run 'bargo rebuild' to regenerate bytecode and witness files",
+                "Make sure the previous build was not interrupted",
             ],
         ));
     }
 
+    debug!(
+        "Artifacts look intact: {}, {}",
+        bytecode_path.display(),
+        witness_path.display()
+    );
     Ok(())
 }
 
 /// Check if source files are newer than target files (for smart rebuilds)
-pub fn needs_rebuild(pkg_name: &str) -> Result<bool> {
-    let current_dir = std::env::current_dir()?;
-    needs_rebuild_from_path(pkg_name, &current_dir)
+///
+/// `start_path` is any directory inside the project, usually
+/// `Config::working_dir`. `input` is the `build --input` file, or `None` for
+/// the package's `Prover.toml`.
+pub fn needs_rebuild_from_path(
+    pkg_name: &str,
+    start_path: &Path,
+    input: Option<&Path>,
+) -> Result<bool> {
+    Ok(!rebuild_reasons_from_path(pkg_name, start_path, input)?.is_empty())
 }
 
-/// Check if source files are newer than target files from a specific starting path
+/// Explain why the build artifacts under a project are out of date
 ///
-/// This version accepts a path parameter for better testability while maintaining
-/// the same rebuild detection logic.
-pub fn needs_rebuild_from_path(pkg_name: &str, start_path: &Path) -> Result<bool> {
+/// Returns one entry per missing artifact or per input (Nargo.toml, the
+/// prover inputs, files under `src/`) that is newer than the artifacts, with
+/// paths relative to the project root. The prover inputs are `input` when
+/// given and `Prover.toml` otherwise; if the manifest shows the witness was
+/// executed with different inputs, that is a reason too. An empty list means
+/// the build is up to date.
+pub fn rebuild_reasons_from_path(
+    pkg_name: &str,
+    start_path: &Path,
+    input: Option<&Path>,
+) -> Result<Vec<String>> {
     let project_root = crate::util::paths::find_project_root(start_path)?;
 
     // Check if target files exist (relative to project root)
@@ -153,9 +222,14 @@ pub fn needs_rebuild_from_path(pkg_name: &str, start_path: &Path) -> Result<bool
         crate::util::paths::Flavour::Bb,
     ));
 
-    if !bytecode_path.exists() || !witness_path.exists() {
+    let missing: Vec<String> = [&bytecode_path, &witness_path]
+        .into_iter()
+        .filter(|path| !path.exists())
+        .map(|path| format!("{} is missing", relative_display(path, &project_root)))
+        .collect();
+    if !missing.is_empty() {
         debug!("Target files don't exist, rebuild needed");
-        return Ok(true);
+        return Ok(missing);
     }
 
     // Get the oldest target file time
@@ -179,75 +253,157 @@ pub fn needs_rebuild_from_path(pkg_name: &str, start_path: &Path) -> Result<bool
         .wrap_err("getting modification time for witness file")?;
     let target_time = bytecode_time.min(witness_time);
 
-    // Check Nargo.toml modification time
-    let nargo_toml = project_root.join("Nargo.toml");
-    if nargo_toml.exists() {
-        let nargo_time = std::fs::metadata(&nargo_toml)
-            .wrap_err_with(|| {
-                format!(
-                    "reading metadata for Nargo.toml at {}",
-                    nargo_toml.display()
-                )
-            })?
-            .modified()
-            .wrap_err("getting modification time for Nargo.toml")?;
-        if nargo_time > target_time {
-            debug!("Nargo.toml is newer than target files, rebuild needed");
-            return Ok(true);
-        }
+    let mut reasons = Vec::new();
+
+    // Switching `--input` files changes the witness even if nothing is newer
+    let manifest = project_root.join(crate::util::manifest::manifest_path(
+        crate::util::paths::Flavour::Bb,
+    ));
+    if let Some(recorded) = crate::util::manifest::read_manifest(&manifest)?
+        && recorded.prover_input.as_deref() != input
+    {
+        let describe = |input: Option<&Path>| {
+            input.map_or_else(
+                || "Prover.toml".to_string(),
+                |path| relative_display(path, &project_root),
+            )
+        };
+        debug!("Prover inputs changed since the last build, rebuild needed");
+        reasons.push(format!(
+            "witness was built from {}, not {}",
+            describe(recorded.prover_input.as_deref()),
+            describe(input)
+        ));
     }
 
-    // Check Prover.toml modification time (contains circuit inputs)
-    let prover_toml = project_root.join("Prover.toml");
-    if prover_toml.exists() {
-        let prover_time = std::fs::metadata(&prover_toml)
+    let mut newer = Vec::new();
+
+    // Nargo.toml and the prover inputs both affect the witness
+    let prover_input = input.map_or_else(|| project_root.join("Prover.toml"), Path::to_path_buf);
+    for manifest_path in [project_root.join("Nargo.toml"), prover_input] {
+        if !manifest_path.exists() {
+            continue;
+        }
+        let manifest = relative_display(&manifest_path, &project_root);
+        let manifest_time = std::fs::metadata(&manifest_path)
             .wrap_err_with(|| {
                 format!(
-                    "reading metadata for Prover.toml at {}",
-                    prover_toml.display()
+                    "reading metadata for {manifest} at {}",
+                    manifest_path.display()
                 )
             })?
             .modified()
-            .wrap_err("getting modification time for Prover.toml")?;
-        if prover_time > target_time {
-            debug!("Prover.toml is newer than target files, rebuild needed");
-            return Ok(true);
+            .wrap_err_with(|| format!("getting modification time for {manifest}"))?;
+        if manifest_time > target_time {
+            debug!("{manifest} is newer than target files, rebuild needed");
+            newer.push(manifest_path);
         }
     }
 
     // Check if any source files are newer
     let src_dir = project_root.join("src");
-    if src_dir.exists() && is_dir_newer_than(&src_dir, target_time)? {
-        debug!("Source files are newer than target files, rebuild needed");
+    if src_dir.exists() {
+        let count = newer.len();
+        collect_newer_files(&src_dir, target_time, &mut newer)?;
+        if newer.len() > count {
+            debug!("Source files are newer than target files, rebuild needed");
+        }
+    }
+
+    if reasons.is_empty() && newer.is_empty() {
+        debug!("Target files are up to date");
+    }
+    reasons.extend(newer.iter().map(|path| {
+        format!(
+            "{} is newer than the build artifacts",
+            relative_display(path, &project_root)
+        )
+    }));
+    Ok(reasons)
+}
+
+/// Check if a flavour's proof artifacts are older than the build artifacts
+///
+/// The proof and public inputs depend on both the bytecode and the witness in
+/// `target/bb/`, while the vk depends only on the bytecode, so a vk reused
+/// from the cache is not stale just because the witness changed. A missing
+/// artifact always needs proving; the `.gz` copies written by
+/// `prove --compress` count as present.
+pub fn needs_reprove(pkg_name: &str, flavour: crate::util::paths::Flavour) -> Result<bool> {
+    use crate::util::{compress::resolve_compressed, paths};
+
+    let bytecode = paths::get_bytecode_path(pkg_name, paths::Flavour::Bb);
+    let witness = paths::get_witness_path(pkg_name, paths::Flavour::Bb);
+    let proof = resolve_compressed(&paths::get_proof_path(flavour));
+    let public_inputs = resolve_compressed(&paths::get_public_inputs_path(flavour));
+    let vk = paths::get_vk_path(flavour);
+
+    if let Some(missing) = [&bytecode, &witness, &proof, &public_inputs, &vk]
+        .into_iter()
+        .find(|path| !path.exists())
+    {
+        debug!("{} is missing, proving needed", missing.display());
+        return Ok(true);
+    }
+
+    let bytecode_time = modified_time(&bytecode)?;
+    let inputs_time = bytecode_time.max(modified_time(&witness)?);
+    let proof_time = modified_time(&proof)?.min(modified_time(&public_inputs)?);
+
+    if inputs_time > proof_time || bytecode_time > modified_time(&vk)? {
+        debug!("Build artifacts are newer than the {flavour:?} proof, proving needed");
         return Ok(true);
     }
 
-    debug!("Target files are up to date");
+    debug!("{flavour:?} proof is up to date");
     Ok(false)
 }
 
-/// Recursively check if any file in a directory is newer than the given time
-fn is_dir_newer_than(dir: &Path, target_time: std::time::SystemTime) -> Result<bool> {
-    for entry in
-        std::fs::read_dir(dir).wrap_err_with(|| format!("reading directory {}", dir.display()))?
-    {
-        let entry =
-            entry.wrap_err_with(|| format!("reading directory entry in {}", dir.display()))?;
-        let path = entry.path();
+/// Modification time of `path`
+fn modified_time(path: &Path) -> Result<std::time::SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .wrap_err_with(|| format!("getting modification time for {}", path.display()))
+}
+
+/// Display `path` relative to `root` when it lies inside it
+fn relative_display(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
 
+/// Recursively collect the files in a directory that are newer than the given time
+fn collect_newer_files(
+    dir: &Path,
+    target_time: std::time::SystemTime,
+    newer: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let mut entries = std::fs::read_dir(dir)
+        .wrap_err_with(|| format!("reading directory {}", dir.display()))?
+        .map(|entry| {
+            entry
+                .map(|entry| entry.path())
+                .wrap_err_with(|| format!("reading directory entry in {}", dir.display()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    entries.sort();
+
+    for path in entries {
         if path.is_file() {
             let file_time = std::fs::metadata(&path)
                 .wrap_err_with(|| format!("reading metadata for file {}", path.display()))?
                 .modified()
                 .wrap_err("getting modification time for file")?;
             if file_time > target_time {
-                return Ok(true);
+                newer.push(path);
             }
-        } else if path.is_dir() && is_dir_newer_than(&path, target_time)? {
-            return Ok(true);
+        } else if path.is_dir() {
+            collect_newer_files(&path, target_time, newer)?;
         }
     }
-    Ok(false)
+    Ok(())
 }
 
 /// Ensure target directory exists for the given backend flavour
@@ -276,14 +432,33 @@ pub fn ensure_target_dir(flavour: crate::util::Flavour) -> Result<()> {
     Ok(())
 }
 
+/// Ensure the parent directory of a file path exists
+///
+/// Used before writing outputs to user-supplied locations such as
+/// `calldata --output`. Paths without a parent directory are left alone.
+pub fn ensure_parent_dir(path: &Path) -> Result<()> {
+    let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    else {
+        return Ok(());
+    };
+
+    std::fs::create_dir_all(parent)
+        .wrap_err_with(|| format!("creating output directory {}", parent.display()))?;
+
+    debug!("Ensured output directory: {}", parent.display());
+    Ok(())
+}
+
 /// Ensure contracts directory exists
 ///
 /// Creates the `contracts/` directory if it doesn't exist.
 /// This is used by both EVM and Cairo workflows.
 pub fn ensure_contracts_dir() -> Result<()> {
-    let contracts_path = Path::new("./contracts");
+    let contracts_path = crate::util::project_path("./contracts");
 
-    std::fs::create_dir_all(contracts_path).wrap_err_with(|| {
+    std::fs::create_dir_all(&contracts_path).wrap_err_with(|| {
         format!(
             "creating contracts directory at {}",
             contracts_path.display()
@@ -294,6 +469,47 @@ pub fn ensure_contracts_dir() -> Result<()> {
     Ok(())
 }
 
+/// Refuse to replace `dir` with a generated project unless that is safe
+///
+/// A missing directory, one holding no files, or one `is_generated` accepts
+/// may be replaced. Anything else needs `force`, so a mistyped `--output-dir`
+/// cannot wipe an unrelated directory.
+pub fn ensure_replaceable_dir(
+    dir: &Path,
+    is_generated: impl Fn(&Path) -> bool,
+    force: bool,
+) -> Result<()> {
+    if force || !dir.exists() || is_generated(dir) || !contains_files(dir)? {
+        return Ok(());
+    }
+    Err(crate::util::error::create_smart_error(
+        &format!(
+            "Refusing to replace {}: it was not generated by bargo",
+            dir.display()
+        ),
+        &[
+            "Pass --force to replace it anyway",
+            "Choose a new or empty directory with --output-dir",
+        ],
+    ))
+}
+
+/// Whether `dir` or any directory below it contains a file
+fn contains_files(dir: &Path) -> Result<bool> {
+    if !dir.is_dir() {
+        return Ok(true);
+    }
+    for entry in std::fs::read_dir(dir).wrap_err_with(|| format!("reading {}", dir.display()))? {
+        let path = entry
+            .wrap_err_with(|| format!("reading {}", dir.display()))?
+            .path();
+        if !path.is_dir() || contains_files(&path)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 /// Move a generated project directory from source to destination
 ///
 /// This is commonly used to move temporary generated directories
@@ -307,12 +523,14 @@ pub fn ensure_contracts_dir() -> Result<()> {
 /// - If destination exists, it will be removed first
 /// - Creates parent directories of destination if needed
 /// - Moves the entire directory tree
+/// - Only records the operations when `fs` is a dry run, where the source
+///   was never generated
 #[cfg(feature = "cairo")]
-pub fn move_generated_project(from: &str, to: &str) -> Result<()> {
+pub fn move_generated_project(fs: &dyn Fs, from: &str, to: &str) -> Result<()> {
     let source_path = Path::new(from);
     let dest_path = Path::new(to);
 
-    if !source_path.exists() {
+    if !source_path.exists() && !fs.is_dry_run() {
         return Err(
             color_eyre::eyre::eyre!("Source directory does not exist: {}", from)
                 .wrap_err("validating source directory for move operation"),
@@ -321,7 +539,7 @@ pub fn move_generated_project(from: &str, to: &str) -> Result<()> {
 
     // Remove destination directory if it exists
     if dest_path.exists() {
-        std::fs::remove_dir_all(dest_path).wrap_err_with(|| {
+        fs.remove_dir_all(dest_path).wrap_err_with(|| {
             format!(
                 "removing existing destination directory {}",
                 dest_path.display()
@@ -332,12 +550,12 @@ pub fn move_generated_project(from: &str, to: &str) -> Result<()> {
 
     // Create parent directory of destination if needed
     if let Some(parent) = dest_path.parent() {
-        std::fs::create_dir_all(parent)
+        fs.create_dir_all(parent)
             .wrap_err_with(|| format!("creating parent directory for {}", dest_path.display()))?;
     }
 
     // Move the directory
-    std::fs::rename(source_path, dest_path).wrap_err_with(|| {
+    fs.rename(source_path, dest_path).wrap_err_with(|| {
         format!(
             "moving directory from {} to {}",
             source_path.display(),
@@ -465,9 +683,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ensure_replaceable_dir_protects_unrelated_files() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path().join("verifier");
+        let generated = |dir: &Path| dir.join("marker").exists();
+
+        // Missing, or holding only empty directories
+        ensure_replaceable_dir(&dir, generated, false).unwrap();
+        fs::create_dir_all(dir.join("src")).unwrap();
+        ensure_replaceable_dir(&dir, generated, false).unwrap();
+
+        fs::write(dir.join("src/notes.txt"), "mine").unwrap();
+        let error = ensure_replaceable_dir(&dir, generated, false).unwrap_err();
+        assert!(
+            error.to_string().contains("not generated by bargo"),
+            "{error}"
+        );
+        assert!(error.to_string().contains("--force"), "{error}");
+        ensure_replaceable_dir(&dir, generated, true).unwrap();
+
+        fs::write(dir.join("marker"), "").unwrap();
+        ensure_replaceable_dir(&dir, generated, false).unwrap();
+    }
+
     #[cfg(feature = "cairo")]
     #[test]
     fn test_move_generated_project() {
+        use crate::fs::RealFs;
+
         let temp_dir = tempdir().unwrap();
 
         // Use absolute paths throughout
@@ -489,8 +733,11 @@ mod tests {
         assert!(source_file.exists(), "Source file should exist before move");
 
         // Move to destination using absolute paths
-        let result =
-            move_generated_project(&source_dir.to_string_lossy(), &dest_dir.to_string_lossy());
+        let result = move_generated_project(
+            &RealFs,
+            &source_dir.to_string_lossy(),
+            &dest_dir.to_string_lossy(),
+        );
         assert!(result.is_ok(), "Move should succeed: {:?}", result.err());
 
         // Verify move was successful using absolute paths
@@ -507,6 +754,7 @@ mod tests {
         let nonexistent = temp_dir.path().join("nonexistent");
         let should_fail = temp_dir.path().join("should_fail");
         let error_result = move_generated_project(
+            &RealFs,
             &nonexistent.to_string_lossy(),
             &should_fail.to_string_lossy(),
         );
@@ -515,4 +763,88 @@ mod tests {
             "Moving non-existent directory should fail"
         );
     }
+
+    #[cfg(feature = "cairo")]
+    #[test]
+    fn test_move_generated_project_dry_run_leaves_directories_alone() {
+        use crate::fs::{DryRunFs, FsOp};
+
+        let temp_dir = tempdir().unwrap();
+        let dest_dir = temp_dir.path().join("contracts/cairo");
+        fs::create_dir_all(&dest_dir).unwrap();
+        fs::write(dest_dir.join("Scarb.toml"), "old verifier").unwrap();
+        // Garaga never ran, so there is nothing to move
+        let source_dir = temp_dir.path().join("verifier");
+
+        let dry_fs = DryRunFs::new();
+        move_generated_project(
+            &dry_fs,
+            &source_dir.to_string_lossy(),
+            &dest_dir.to_string_lossy(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dest_dir.join("Scarb.toml")).unwrap(),
+            "old verifier"
+        );
+        assert!(!source_dir.exists());
+        assert_eq!(
+            dry_fs.history(),
+            [
+                FsOp::RemoveDir(dest_dir.clone()),
+                FsOp::CreateDir(temp_dir.path().join("contracts")),
+                FsOp::Rename {
+                    from: source_dir,
+                    to: dest_dir,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_artifact_files_accepts_valid_artifacts() {
+        let temp_dir = tempdir().unwrap();
+        let bytecode = temp_dir.path().join("pkg.json");
+        let witness = temp_dir.path().join("pkg.gz");
+        fs::write(&bytecode, r#"{"noir_version":"1.0.0","bytecode":"H4sI"}"#).unwrap();
+        fs::write(&witness, [0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00]).unwrap();
+
+        assert!(validate_artifact_files(&bytecode, &witness).is_ok());
+    }
+
+    #[test]
+    fn test_validate_artifact_files_rejects_corrupted_gzip() {
+        let temp_dir = tempdir().unwrap();
+        let bytecode = temp_dir.path().join("pkg.json");
+        let witness = temp_dir.path().join("pkg.gz");
+        fs::write(&bytecode, "{}").unwrap();
+        fs::write(&witness, b"not gzip").unwrap();
+
+        let message = validate_artifact_files(&bytecode, &witness)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("pkg.gz is not a gzip file"));
+        assert!(!message.contains("pkg.json"));
+        assert!(message.contains("bargo rebuild"));
+
+        // A truncated witness shorter than the magic bytes is also rejected
+        fs::write(&witness, [0x1f]).unwrap();
+        assert!(validate_artifact_files(&bytecode, &witness).is_err());
+    }
+
+    #[test]
+    fn test_validate_artifact_files_rejects_malformed_json() {
+        let temp_dir = tempdir().unwrap();
+        let bytecode = temp_dir.path().join("pkg.json");
+        let witness = temp_dir.path().join("pkg.gz");
+        fs::write(&bytecode, r#"{"noir_version":"1.0.0","byte"#).unwrap();
+        fs::write(&witness, [0x1f, 0x8b, 0x08]).unwrap();
+
+        let message = validate_artifact_files(&bytecode, &witness)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("pkg.json is not valid JSON"));
+        assert!(message.contains("bargo rebuild"));
+    }
 }
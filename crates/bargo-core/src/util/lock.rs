@@ -0,0 +1,90 @@
+//! Advisory lock serializing changes to the artifact directories
+//!
+//! `organize_build_artifacts` moves nargo's output out of `target/` and
+//! `clean` deletes target directories. Two bargo processes doing either at
+//! once, for example `watch` and a manual build, can leave half-moved
+//! artifacts behind. Both take an exclusive advisory lock on
+//! `target/.bargo.lock` first; a second process fails straight away instead
+//! of waiting.
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::util::{create_smart_error, ensure_parent_dir, target_base};
+
+/// File name of the lock kept in the target base directory
+pub const LOCK_FILE: &str = ".bargo.lock";
+
+/// Path of the artifact lock (`target/.bargo.lock`)
+pub fn lock_path() -> PathBuf {
+    target_base().join(LOCK_FILE)
+}
+
+/// Exclusive lock on the artifact directories, released when dropped
+#[derive(Debug)]
+pub struct ArtifactLock {
+    _file: File,
+}
+
+impl ArtifactLock {
+    /// Lock the artifact directories of the current project
+    pub fn acquire() -> Result<Self> {
+        Self::acquire_at(&lock_path())
+    }
+
+    /// Lock the file at `path`, creating it if needed
+    ///
+    /// Fails without blocking if another process, or another handle in this
+    /// one, already holds the lock.
+    pub fn acquire_at(path: &Path) -> Result<Self> {
+        ensure_parent_dir(path)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(path)
+            .wrap_err_with(|| format!("opening lock file {}", path.display()))?;
+
+        match file.try_lock() {
+            Ok(()) => {
+                debug!("Acquired artifact lock {}", path.display());
+                Ok(Self { _file: file })
+            }
+            Err(TryLockError::WouldBlock) => Err(create_smart_error(
+                &format!(
+                    "Another bargo process is modifying the artifacts (lock held on {})",
+                    path.display()
+                ),
+                &[
+                    "Wait for the other bargo command, such as 'bargo watch', to finish",
+                    "The lock is released automatically when that process exits",
+                ],
+            )),
+            Err(TryLockError::Error(e)) => {
+                Err(e).wrap_err_with(|| format!("locking {}", path.display()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_second_acquisition_fails_while_held() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("target").join(LOCK_FILE);
+
+        let held = ArtifactLock::acquire_at(&path).unwrap();
+        let error = ArtifactLock::acquire_at(&path).unwrap_err().to_string();
+        assert!(error.contains("Another bargo process"), "{error}");
+
+        drop(held);
+        assert!(ArtifactLock::acquire_at(&path).is_ok());
+    }
+}
@@ -5,7 +5,7 @@
 //!
 //! ## Key Features
 //!
-//! - Colored terminal output with automatic NO_COLOR support
+//! - Colored terminal output controlled by `--color`, NO_COLOR and CLICOLOR
 //! - Success, info, and error message formatting
 //! - ASCII art banners for different operations
 //! - TTY detection for proper color handling
@@ -25,26 +25,85 @@
 //! print_banner("build");
 //! ```
 
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use crate::cli::ColorChoice;
+
 /// ANSI color codes for terminal output
 pub mod colors {
     pub const RESET: &str = "\x1b[0m";
     pub const BOLD: &str = "\x1b[1m";
+    pub const RED: &str = "\x1b[31m";
     pub const GREEN: &str = "\x1b[32m";
+    pub const YELLOW: &str = "\x1b[33m";
     pub const GRAY: &str = "\x1b[90m";
     pub const BRIGHT_GREEN: &str = "\x1b[92m";
     pub const BRIGHT_BLUE: &str = "\x1b[94m";
     pub const BRIGHT_CYAN: &str = "\x1b[96m";
 }
 
-// Placeholder functions - these will be moved here from other modules
-// in Checkpoint B
+/// Color choice set from `--color`, stored as its discriminant
+static COLOR_CHOICE: AtomicU8 = AtomicU8::new(ColorChoice::Auto as u8);
+
+/// Set the color choice used by `colorize` and everything built on it
+///
+/// Called once from `run()` with the value of `--color`.
+pub fn set_color_choice(choice: ColorChoice) {
+    COLOR_CHOICE.store(choice as u8, Ordering::Relaxed);
+}
+
+/// Color choice currently in effect
+pub fn color_choice() -> ColorChoice {
+    match COLOR_CHOICE.load(Ordering::Relaxed) {
+        x if x == ColorChoice::Always as u8 => ColorChoice::Always,
+        x if x == ColorChoice::Never as u8 => ColorChoice::Never,
+        _ => ColorChoice::Auto,
+    }
+}
+
+/// Decide whether to emit ANSI colors
+///
+/// `always` and `never` are unconditional. In `auto` mode, in order:
+/// `NO_COLOR` disables color, `CLICOLOR_FORCE` (not `0`) enables it,
+/// `CLICOLOR=0` disables it, and otherwise color follows whether stdout is a
+/// terminal. `env` looks up an environment variable.
+pub fn resolve_color(
+    choice: ColorChoice,
+    env: impl Fn(&str) -> Option<String>,
+    stdout_is_tty: bool,
+) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            if env("NO_COLOR").is_some() {
+                false
+            } else if env("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+                true
+            } else if env("CLICOLOR").is_some_and(|value| value == "0") {
+                false
+            } else {
+                stdout_is_tty
+            }
+        }
+    }
+}
+
+/// Whether bargo's output should currently be colored
+pub fn colors_enabled() -> bool {
+    resolve_color(
+        color_choice(),
+        |name| std::env::var(name).ok(),
+        atty::is(atty::Stream::Stdout),
+    )
+}
 
 /// Format text with color
 pub fn colorize(text: &str, color: &str) -> String {
-    if std::env::var("NO_COLOR").is_ok() || !atty::is(atty::Stream::Stdout) {
-        text.to_string()
-    } else {
+    if colors_enabled() {
         format!("{}{}{}", color, text, colors::RESET)
+    } else {
+        text.to_string()
     }
 }
 
@@ -58,47 +117,78 @@ pub fn info(text: &str) -> String {
     colorize(&format!("ℹ️ {text}"), colors::BRIGHT_BLUE)
 }
 
-/// Print ASCII art banners for different operations
-pub fn print_banner(operation: &str) {
-    let banner = match operation {
-        "build" => {
-            "┌─────────────────────────────────┐\n\
-             │ 🔨 BUILDING NOIR CIRCUIT       │\n\
-             └─────────────────────────────────┘"
-        }
-        "prove" => {
-            "┌─────────────────────────────────┐\n\
-             │ 🔐 GENERATING PROOF & VK        │\n\
-             └─────────────────────────────────┘"
-        }
-        "verify" => {
-            "┌─────────────────────────────────┐\n\
-             │ ✅ VERIFYING PROOF              │\n\
-             └─────────────────────────────────┘"
-        }
-        "solidity" => {
-            "┌─────────────────────────────────┐\n\
-             │ 📄 GENERATING SOLIDITY VERIFIER │\n\
-             └─────────────────────────────────┘"
-        }
-        "clean" => {
-            "┌─────────────────────────────────┐\n\
-             │ 🧹 CLEANING BUILD ARTIFACTS    │\n\
-             └─────────────────────────────────┘"
-        }
-        "check" => {
-            "┌─────────────────────────────────┐\n\
-             │ 🔍 CHECKING CIRCUIT SYNTAX      │\n\
-             └─────────────────────────────────┘"
-        }
-        _ => {
-            "┌─────────────────────────────────┐\n\
-             │ 🚀 RUNNING BARGO OPERATION      │\n\
-             └─────────────────────────────────┘"
-        }
-    };
+/// Banner title for each operation passed to [`print_banner`]
+///
+/// Titles start with a single emoji, which terminals draw two columns wide.
+const BANNERS: &[(&str, &str)] = &[
+    ("build", "🔨 BUILDING NOIR CIRCUIT"),
+    ("rebuild", "🔨 REBUILDING NOIR CIRCUIT"),
+    ("check", "🔍 CHECKING CIRCUIT SYNTAX"),
+    ("clean", "🧹 CLEANING BUILD ARTIFACTS"),
+    ("prove", "🔐 GENERATING PROOF & VK"),
+    ("verify", "✅ VERIFYING PROOF"),
+    ("solidity", "📄 GENERATING SOLIDITY VERIFIER"),
+    ("bench", "📈 BENCHMARKING PROOF GENERATION"),
+    ("doctor", "🩺 CHECKING TOOLCHAIN"),
+    ("info", "📋 PROJECT INFORMATION"),
+    ("status", "📊 ARTIFACT STATUS"),
+    ("addresses", "📒 DEPLOYED VERIFIERS"),
+    ("export", "📦 EXPORTING PROOF BUNDLE"),
+    ("convert", "🔄 CONVERTING PROOF ENCODING"),
+    ("report", "📜 SESSION REPORT"),
+    ("evm gen", "📄 GENERATING SOLIDITY VERIFIER"),
+    ("evm prove", "🔐 GENERATING EVM PROOF & VK"),
+    ("evm verify", "✅ VERIFYING EVM PROOF"),
+    ("evm calldata", "📦 GENERATING EVM CALLDATA"),
+    ("evm deploy", "🚢 DEPLOYING EVM VERIFIER"),
+    ("evm verify-onchain", "🌐 VERIFYING EVM PROOF ON-CHAIN"),
+    ("cairo gen", "📄 GENERATING CAIRO VERIFIER"),
+    ("cairo prove", "🔐 GENERATING STARKNET PROOF & VK"),
+    ("cairo verify", "✅ VERIFYING STARKNET PROOF"),
+    ("cairo calldata", "📦 GENERATING STARKNET CALLDATA"),
+    ("cairo deploy", "🚢 DEPLOYING CAIRO VERIFIER"),
+    ("cairo verify-onchain", "🌐 VERIFYING PROOF ON STARKNET"),
+];
+
+/// Title for operations without an entry in [`BANNERS`]
+const FALLBACK_BANNER: &str = "🚀 RUNNING BARGO OPERATION";
+
+/// Columns between the banner's side borders
+const BANNER_WIDTH: usize = 36;
+
+/// Whether `print_banner` prints anything (`--no-banner`, `[ui] banners`)
+static BANNERS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turn operation banners on or off
+///
+/// Called once from `run()`; summaries and other output are unaffected.
+pub fn set_banners_enabled(enabled: bool) {
+    BANNERS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether operation banners are printed
+pub fn banners_enabled() -> bool {
+    BANNERS_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Boxed banner for `operation`, falling back to a generic title
+pub fn banner(operation: &str) -> String {
+    let title = BANNERS
+        .iter()
+        .find(|(name, _)| *name == operation)
+        .map_or(FALLBACK_BANNER, |(_, title)| title);
+    // The leading emoji is one char drawn two columns wide
+    let padding = BANNER_WIDTH.saturating_sub(title.chars().count() + 2);
+    let border = "─".repeat(BANNER_WIDTH);
+    format!("┌{border}┐\n│ {title}{}│\n└{border}┘", " ".repeat(padding))
+}
 
-    println!("{}", colorize(banner, colors::BRIGHT_BLUE));
+/// Print the banner for `operation`, unless banners are turned off
+pub fn print_banner(operation: &str) {
+    if !banners_enabled() {
+        return;
+    }
+    println!("{}", colorize(&banner(operation), colors::BRIGHT_BLUE));
 }
 
 #[cfg(test)]
@@ -126,6 +216,80 @@ mod tests {
         assert!(result.contains("test"));
     }
 
+    fn env_from(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn test_resolve_color_always_and_never_ignore_environment() {
+        let env = env_from(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]);
+        assert!(resolve_color(ColorChoice::Always, &env, false));
+        assert!(!resolve_color(ColorChoice::Never, &env, true));
+    }
+
+    #[test]
+    fn test_resolve_color_auto_follows_tty() {
+        let env = env_from(&[]);
+        assert!(resolve_color(ColorChoice::Auto, &env, true));
+        assert!(!resolve_color(ColorChoice::Auto, &env, false));
+    }
+
+    #[test]
+    fn test_resolve_color_auto_honors_no_color() {
+        let env = env_from(&[("NO_COLOR", ""), ("CLICOLOR_FORCE", "1")]);
+        assert!(!resolve_color(ColorChoice::Auto, &env, true));
+    }
+
+    #[test]
+    fn test_resolve_color_auto_honors_clicolor_force() {
+        assert!(resolve_color(
+            ColorChoice::Auto,
+            env_from(&[("CLICOLOR_FORCE", "1")]),
+            false
+        ));
+        assert!(!resolve_color(
+            ColorChoice::Auto,
+            env_from(&[("CLICOLOR_FORCE", "0")]),
+            false
+        ));
+    }
+
+    #[test]
+    fn test_resolve_color_auto_honors_clicolor_zero() {
+        assert!(!resolve_color(
+            ColorChoice::Auto,
+            env_from(&[("CLICOLOR", "0")]),
+            true
+        ));
+        assert!(resolve_color(
+            ColorChoice::Auto,
+            env_from(&[("CLICOLOR", "1")]),
+            true
+        ));
+    }
+
+    #[test]
+    fn test_banner_titles_fit_the_box() {
+        for (operation, title) in BANNERS {
+            let banner = banner(operation);
+            let lines: Vec<&str> = banner.lines().collect();
+            assert!(lines[1].contains(title), "{operation}");
+            // Every line is as wide as the border, counting the emoji twice
+            assert_eq!(lines[1].chars().count() + 1, lines[0].chars().count());
+            assert!(lines[1].ends_with(" │"), "{operation} overflows");
+        }
+        assert!(banner("cairo prove").contains("STARKNET PROOF"));
+        assert!(banner("no such operation").contains(FALLBACK_BANNER));
+    }
+
     #[test]
     fn test_print_banner() {
         // This test just ensures the function doesn't panic
@@ -0,0 +1,405 @@
+//! Provenance manifests for generated artifacts
+//!
+//! After `build`, `prove` and `gen`, bargo records what it wrote in
+//! `target/<flavour>/manifest.json`: each artifact's path, size and sha256
+//! hash, the operation and tool versions that produced it, and when. Later
+//! operations update the entries for the files they rewrite and keep the rest,
+//! so one manifest describes everything currently in the target directory.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! use bargo_core::util::manifest::{manifest_path, read_manifest, write_manifest};
+//!
+//! write_manifest(&manifest_path(Flavour::Bb), "build", &[bytecode, witness], &tools)?;
+//! if let Some(manifest) = read_manifest(&manifest_path(Flavour::Bb))? {
+//!     println!("{} artifacts recorded", manifest.artifacts.len());
+//! }
+//! ```
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::util::{BargoError, Flavour, target_dir};
+
+/// File name of the manifest kept in each target directory
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Path of the manifest for a flavour (`target/<flavour>/manifest.json`)
+pub fn manifest_path(flavour: Flavour) -> PathBuf {
+    target_dir(flavour).join(MANIFEST_FILE)
+}
+
+/// Contents of a `manifest.json`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub artifacts: Vec<ManifestEntry>,
+    /// `--oracle-hash` override the proof and vk were generated with, so
+    /// verification can pass the same hash to bb
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oracle_hash: Option<String>,
+    /// `prove --bb-profile` the proof was generated with; `None` means bb's
+    /// default flags
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bb_profile: Option<String>,
+    /// Whether the proof and vk were generated with `prove --recursive-friendly`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub recursive_friendly: bool,
+    /// `build --input` file the witness was executed with; `None` means the
+    /// package's `Prover.toml`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prover_input: Option<PathBuf>,
+    /// Circuit size measured by `build --stats`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit: Option<CircuitStats>,
+}
+
+/// Size of a compiled circuit, as reported by `build --stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitStats {
+    /// Size of the bytecode file in bytes
+    pub bytecode_size: u64,
+    /// ACIR opcodes counted by `bb gates`, if bb could count them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acir_opcodes: Option<u64>,
+    /// Gates in the proving circuit (bb's `circuit_size`), if bb could count them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gates: Option<u64>,
+}
+
+/// Provenance of a single artifact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Path as bargo wrote it, usually relative to the project root
+    pub path: PathBuf,
+    /// Size in bytes
+    pub size: u64,
+    /// Hex-encoded sha256 of the file contents
+    pub sha256: String,
+    /// Operation that produced the artifact (e.g. `build`, `evm prove`)
+    pub operation: String,
+    /// First line of `<tool> --version` for each tool involved
+    pub tools: BTreeMap<String, String>,
+    /// UTC time the artifact was recorded, in RFC 3339 format
+    pub created_at: String,
+    /// How the file is compressed (`gzip` after `prove --compress`), if at all
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<String>,
+}
+
+impl ManifestEntry {
+    /// Describe the file at `path`, hashing its current contents
+    pub fn from_file(
+        path: &Path,
+        operation: &str,
+        tools: &BTreeMap<String, String>,
+        created_at: &str,
+    ) -> Result<Self> {
+        let size = std::fs::metadata(path)
+            .wrap_err_with(|| format!("reading artifact {} for manifest", path.display()))?
+            .len();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            size,
+            sha256: sha256_file(path)?,
+            operation: operation.to_string(),
+            tools: tools.clone(),
+            created_at: created_at.to_string(),
+            compression: None,
+        })
+    }
+}
+
+impl Manifest {
+    /// Add entries, replacing any earlier entries for the same paths
+    pub fn record(&mut self, entries: Vec<ManifestEntry>) {
+        self.artifacts
+            .retain(|existing| !entries.iter().any(|entry| entry.path == existing.path));
+        self.artifacts.extend(entries);
+    }
+
+    /// Mark `compressed` artifacts as gzip-compressed
+    ///
+    /// Entries for the uncompressed files they replaced are dropped, as are
+    /// entries for compressed copies that no longer exist.
+    pub fn set_compression(&mut self, compressed: &[PathBuf]) {
+        self.artifacts.retain(|entry| {
+            let replaced = compressed
+                .iter()
+                .any(|path| path.with_extension("") == entry.path);
+            let vanished = entry.compression.is_some() && !entry.path.exists();
+            !replaced && !vanished
+        });
+        for entry in &mut self.artifacts {
+            if compressed.contains(&entry.path) {
+                entry.compression = Some(crate::util::GZIP.to_string());
+            }
+        }
+    }
+}
+
+/// Hex-encoded sha256 of a file's contents, read in chunks
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)
+        .wrap_err_with(|| format!("opening {} for hashing", path.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .wrap_err_with(|| format!("hashing {}", path.display()))?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Record `artifacts` produced by `operation` in the manifest at `manifest`
+///
+/// Entries already in the manifest for other files are kept. Artifacts that do
+/// not exist are skipped, since not every workflow writes every file.
+pub fn write_manifest(
+    manifest: &Path,
+    operation: &str,
+    artifacts: &[PathBuf],
+    tools: &BTreeMap<String, String>,
+) -> Result<()> {
+    let created_at = utc_timestamp(SystemTime::now());
+    let entries = artifacts
+        .iter()
+        .filter(|path| path.is_file())
+        .map(|path| ManifestEntry::from_file(path, operation, tools, &created_at))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut contents = read_manifest(manifest)?.unwrap_or_default();
+    contents.record(entries);
+    save_manifest(manifest, &contents)
+}
+
+/// Record the oracle hash override used for the artifacts in `manifest`
+///
+/// `None` clears an earlier override, since the artifacts were just
+/// regenerated with the backend's default hash.
+pub fn set_manifest_oracle_hash(manifest: &Path, oracle_hash: Option<&str>) -> Result<()> {
+    let mut contents = read_manifest(manifest)?.unwrap_or_default();
+    contents.oracle_hash = oracle_hash.map(str::to_string);
+    save_manifest(manifest, &contents)
+}
+
+/// Record the bb profile the proof in `manifest` was generated with
+pub fn set_manifest_bb_profile(manifest: &Path, bb_profile: Option<&str>) -> Result<()> {
+    let mut contents = read_manifest(manifest)?.unwrap_or_default();
+    contents.bb_profile = bb_profile.map(str::to_string);
+    save_manifest(manifest, &contents)
+}
+
+/// Record whether the proof and vk in `manifest` are recursion-friendly
+pub fn set_manifest_recursive_friendly(manifest: &Path, recursive_friendly: bool) -> Result<()> {
+    let mut contents = read_manifest(manifest)?.unwrap_or_default();
+    contents.recursive_friendly = recursive_friendly;
+    save_manifest(manifest, &contents)
+}
+
+/// Record the prover input file the witness in `manifest` was built from
+pub fn set_manifest_prover_input(manifest: &Path, input: Option<&Path>) -> Result<()> {
+    let mut contents = read_manifest(manifest)?.unwrap_or_default();
+    contents.prover_input = input.map(Path::to_path_buf);
+    save_manifest(manifest, &contents)
+}
+
+/// Record the circuit size of the bytecode in `manifest`
+///
+/// `None` clears stats left over from an earlier build.
+pub fn set_manifest_circuit_stats(manifest: &Path, stats: Option<&CircuitStats>) -> Result<()> {
+    let mut contents = read_manifest(manifest)?.unwrap_or_default();
+    contents.circuit = stats.copied();
+    save_manifest(manifest, &contents)
+}
+
+/// Record which artifacts in `manifest` are stored gzip-compressed
+pub fn set_manifest_compression(manifest: &Path, compressed: &[PathBuf]) -> Result<()> {
+    let mut contents = read_manifest(manifest)?.unwrap_or_default();
+    contents.set_compression(compressed);
+    save_manifest(manifest, &contents)
+}
+
+fn save_manifest(manifest: &Path, contents: &Manifest) -> Result<()> {
+    crate::util::ensure_parent_dir(manifest)?;
+    let json = serde_json::to_string_pretty(contents).wrap_err("serializing manifest")?;
+    std::fs::write(manifest, format!("{json}\n"))
+        .wrap_err_with(|| format!("writing manifest to {}", manifest.display()))
+}
+
+/// Read the manifest at `manifest`, or `None` if it does not exist
+pub fn read_manifest(manifest: &Path) -> Result<Option<Manifest>> {
+    let contents = match std::fs::read_to_string(manifest) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(e).wrap_err_with(|| format!("reading manifest {}", manifest.display()));
+        }
+    };
+
+    serde_json::from_str(&contents).map(Some).map_err(|e| {
+        BargoError::ParseFailure {
+            path: manifest.to_path_buf(),
+            message: e.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Format `time` as an RFC 3339 UTC timestamp with second precision
+pub fn utc_timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let (days, secs_of_day) = (seconds / 86_400, seconds % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    fn tools() -> BTreeMap<String, String> {
+        BTreeMap::from([(
+            "nargo".to_string(),
+            "nargo version = 1.0.0-beta.4".to_string(),
+        )])
+    }
+
+    #[test]
+    fn test_utc_timestamp_formats_known_instants() {
+        assert_eq!(utc_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            utc_timestamp(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            "2000-02-29T00:00:00Z"
+        );
+        assert_eq!(
+            utc_timestamp(UNIX_EPOCH + Duration::from_secs(1_792_152_245)),
+            "2026-10-16T12:04:05Z"
+        );
+    }
+
+    #[test]
+    fn test_manifest_hash_changes_with_artifact() {
+        let temp_dir = tempdir().unwrap();
+        let manifest = temp_dir.path().join("bb").join(MANIFEST_FILE);
+        let bytecode = temp_dir.path().join("pkg.json");
+        let witness = temp_dir.path().join("pkg.gz");
+        std::fs::write(&bytecode, r#"{"bytecode":"v1"}"#).unwrap();
+        std::fs::write(&witness, [0x1f, 0x8b]).unwrap();
+
+        write_manifest(
+            &manifest,
+            "build",
+            &[bytecode.clone(), witness.clone()],
+            &tools(),
+        )
+        .unwrap();
+        let first = read_manifest(&manifest).unwrap().unwrap();
+        assert_eq!(first.artifacts.len(), 2);
+        assert_eq!(first.artifacts[0].path, bytecode);
+        assert_eq!(first.artifacts[0].size, 17);
+        assert_eq!(first.artifacts[0].sha256.len(), 64);
+        assert_eq!(first.artifacts[0].operation, "build");
+        assert_eq!(first.artifacts[0].tools, tools());
+        assert!(first.artifacts[0].created_at.ends_with('Z'));
+
+        std::fs::write(&bytecode, r#"{"bytecode":"v2"}"#).unwrap();
+        write_manifest(
+            &manifest,
+            "rebuild",
+            std::slice::from_ref(&bytecode),
+            &tools(),
+        )
+        .unwrap();
+        let second = read_manifest(&manifest).unwrap().unwrap();
+
+        // The witness entry is kept, the bytecode entry is replaced
+        assert_eq!(second.artifacts.len(), 2);
+        assert_eq!(second.artifacts[0], first.artifacts[1]);
+        assert_eq!(second.artifacts[1].path, bytecode);
+        assert_eq!(second.artifacts[1].operation, "rebuild");
+        assert_ne!(second.artifacts[1].sha256, first.artifacts[0].sha256);
+    }
+
+    #[test]
+    fn test_oracle_hash_survives_artifact_updates() {
+        let temp_dir = tempdir().unwrap();
+        let manifest = temp_dir.path().join(MANIFEST_FILE);
+        let proof = temp_dir.path().join("proof");
+        std::fs::write(&proof, [0xab; 4]).unwrap();
+
+        write_manifest(
+            &manifest,
+            "evm prove",
+            std::slice::from_ref(&proof),
+            &tools(),
+        )
+        .unwrap();
+        set_manifest_oracle_hash(&manifest, Some("poseidon2")).unwrap();
+        write_manifest(&manifest, "evm gen", std::slice::from_ref(&proof), &tools()).unwrap();
+
+        let recorded = read_manifest(&manifest).unwrap().unwrap();
+        assert_eq!(recorded.oracle_hash.as_deref(), Some("poseidon2"));
+        assert_eq!(recorded.artifacts.len(), 1);
+
+        set_manifest_oracle_hash(&manifest, None).unwrap();
+        let json = std::fs::read_to_string(&manifest).unwrap();
+        assert!(!json.contains("oracle_hash"));
+    }
+
+    #[test]
+    fn test_write_manifest_skips_missing_artifacts() {
+        let temp_dir = tempdir().unwrap();
+        let manifest = temp_dir.path().join(MANIFEST_FILE);
+
+        write_manifest(
+            &manifest,
+            "evm prove",
+            &[temp_dir.path().join("proof")],
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert!(
+            read_manifest(&manifest)
+                .unwrap()
+                .unwrap()
+                .artifacts
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_read_manifest_missing_and_malformed() {
+        let temp_dir = tempdir().unwrap();
+        let manifest = temp_dir.path().join(MANIFEST_FILE);
+        assert!(read_manifest(&manifest).unwrap().is_none());
+
+        std::fs::write(&manifest, "{\"artifacts\": [").unwrap();
+        let error = read_manifest(&manifest).unwrap_err().to_string();
+        assert!(error.contains("Failed to parse manifest.json"));
+    }
+}
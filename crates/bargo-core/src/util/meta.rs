@@ -0,0 +1,151 @@
+//! Metadata sidecars recording where a proof came from
+//!
+//! After proving, bargo writes `<proof>.meta.json` next to the proof (for
+//! example `target/evm/proof.meta.json`) with the package name, the project's
+//! git commit, the versions of the tools that produced the proof, and when.
+//! The manifest describes a whole target directory; the sidecar is small
+//! enough to ship alongside a proof handed to an auditor. The commit is
+//! `null` for projects outside a git repository or when git is not installed.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! use bargo_core::util::meta::{ProofMeta, meta_path, write_meta};
+//!
+//! let meta = ProofMeta::new("my_circuit", commit, tools);
+//! write_meta(&meta_path(&get_proof_path(Flavour::Evm)), &meta)?;
+//! ```
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::util::{BargoError, utc_timestamp};
+
+/// Suffix appended to the proof file name to name its sidecar
+pub const META_SUFFIX: &str = ".meta.json";
+
+/// Contents of a `proof.meta.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofMeta {
+    /// Noir package the proof was generated for
+    pub package: String,
+    /// `git rev-parse HEAD` of the project, or `None` outside a git repository
+    pub commit: Option<String>,
+    /// First line of `<tool> --version`, by tool name
+    #[serde(default)]
+    pub tools: BTreeMap<String, String>,
+    /// When the proof was written, as an RFC 3339 UTC timestamp
+    pub created_at: String,
+}
+
+impl ProofMeta {
+    /// Metadata for a proof of `package` written now
+    pub fn new(
+        package: impl Into<String>,
+        commit: Option<String>,
+        tools: BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            package: package.into(),
+            commit,
+            tools,
+            created_at: utc_timestamp(SystemTime::now()),
+        }
+    }
+}
+
+/// Path of the sidecar for `proof` (`proof` becomes `proof.meta.json`)
+pub fn meta_path(proof: &Path) -> PathBuf {
+    let mut name = proof.file_name().unwrap_or_default().to_os_string();
+    name.push(META_SUFFIX);
+    proof.with_file_name(name)
+}
+
+/// Commit hash printed by `git rev-parse HEAD`, if `stdout` holds one
+pub fn parse_git_commit(stdout: &str) -> Option<String> {
+    let commit = stdout.trim();
+    let is_hash = matches!(commit.len(), 40 | 64) && commit.bytes().all(|b| b.is_ascii_hexdigit());
+    is_hash.then(|| commit.to_string())
+}
+
+/// Write `meta` to `path`, replacing an earlier sidecar
+pub fn write_meta(path: &Path, meta: &ProofMeta) -> Result<()> {
+    crate::util::ensure_parent_dir(path)?;
+    let json = serde_json::to_string_pretty(meta).wrap_err("serializing proof metadata")?;
+    std::fs::write(path, format!("{json}\n"))
+        .wrap_err_with(|| format!("writing proof metadata to {}", path.display()))
+}
+
+/// Read the sidecar at `path`, or `None` if it does not exist
+pub fn read_meta(path: &Path) -> Result<Option<ProofMeta>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(e).wrap_err_with(|| format!("reading proof metadata {}", path.display()));
+        }
+    };
+
+    serde_json::from_str(&contents).map(Some).map_err(|e| {
+        BargoError::ParseFailure {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        }
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_meta_path_sits_next_to_proof() {
+        assert_eq!(
+            meta_path(Path::new("target/evm/proof")),
+            Path::new("target/evm/proof.meta.json")
+        );
+        assert_eq!(
+            meta_path(Path::new("target/bb/proof_my_pkg")),
+            Path::new("target/bb/proof_my_pkg.meta.json")
+        );
+    }
+
+    #[test]
+    fn test_parse_git_commit_accepts_only_hashes() {
+        let sha1 = "0123456789abcdef0123456789abcdef01234567";
+        assert_eq!(
+            parse_git_commit(&format!("{sha1}\n")).as_deref(),
+            Some(sha1)
+        );
+        assert_eq!(parse_git_commit(&"ab".repeat(32)), Some("ab".repeat(32)));
+        assert_eq!(parse_git_commit(""), None);
+        assert_eq!(parse_git_commit("HEAD"), None);
+        assert_eq!(parse_git_commit(&"z".repeat(40)), None);
+    }
+
+    #[test]
+    fn test_meta_round_trips_and_rejects_garbage() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("evm/proof.meta.json");
+        assert_eq!(read_meta(&path).unwrap(), None);
+
+        let tools = BTreeMap::from([("bb".to_string(), "0.87.4".to_string())]);
+        let meta = ProofMeta::new("my_pkg", None, tools);
+        write_meta(&path, &meta).unwrap();
+        assert_eq!(read_meta(&path).unwrap(), Some(meta));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(json["commit"].is_null());
+
+        std::fs::write(&path, "not json").unwrap();
+        let error = read_meta(&path).unwrap_err();
+        assert!(error.to_string().contains("proof.meta.json"), "{error}");
+    }
+}
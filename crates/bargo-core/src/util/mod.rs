@@ -1,15 +1,37 @@
+pub mod address_book;
+pub mod cache;
+pub mod compress;
+pub mod env_file;
 pub mod error;
+pub mod exit_code;
 pub mod format;
+pub mod interrupt;
 pub mod io;
+pub mod lock;
 pub mod log;
+pub mod manifest;
+pub mod meta;
+pub mod notify;
+pub mod output;
 pub mod paths;
 pub mod summary;
 pub mod timer;
 
+pub use address_book::*;
+pub use cache::*;
+pub use compress::*;
+pub use env_file::*;
 pub use error::*;
+pub use exit_code::*;
 pub use format::*;
+pub use interrupt::*;
 pub use io::*;
+pub use lock::*;
 pub use log::*;
+pub use manifest::*;
+pub use meta::*;
+pub use notify::*;
+pub use output::*;
 
 pub use paths::*;
 
@@ -0,0 +1,198 @@
+//! Webhook notification when a command finishes
+//!
+//! With `--notify-url`, bargo POSTs a small JSON payload describing the
+//! finished command, whether it succeeded or failed, so remote proving jobs
+//! can ping CI or a chat bot. Notifying is best effort: a webhook that is
+//! down or rejects the payload only produces a warning.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! use bargo_core::util::notify::{NotifyPayload, notify};
+//!
+//! let timer = Timer::start();
+//! // ... run the command ...
+//! let payload = NotifyPayload::success("evm prove", &proof_artifacts, &timer);
+//! notify("https://hooks.example.com/bargo", &payload);
+//! ```
+
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::runner::redact_message;
+use crate::util::output::JsonArtifact;
+use crate::util::timer::Timer;
+
+/// How long to wait for the webhook before giving up
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of the command reported to the webhook and the session log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyStatus {
+    Success,
+    Failure,
+}
+
+/// JSON body POSTed to the notification URL
+#[derive(Debug, Serialize)]
+pub struct NotifyPayload {
+    pub command: String,
+    pub status: NotifyStatus,
+    pub duration_ms: u128,
+    pub artifacts: Vec<JsonArtifact>,
+    /// Error message when the command failed, with secret values masked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl NotifyPayload {
+    /// Payload for a command that succeeded and produced `artifacts`
+    pub fn success(command: &str, artifacts: &[PathBuf], timer: &Timer) -> Self {
+        Self {
+            command: command.to_string(),
+            status: NotifyStatus::Success,
+            duration_ms: timer.elapsed_ms(),
+            artifacts: artifacts
+                .iter()
+                .map(|path| JsonArtifact::from_path(path))
+                .collect(),
+            error: None,
+        }
+    }
+
+    /// Payload for a command that failed with `error`
+    pub fn failure(command: &str, error: &color_eyre::eyre::Report, timer: &Timer) -> Self {
+        Self {
+            command: command.to_string(),
+            status: NotifyStatus::Failure,
+            duration_ms: timer.elapsed_ms(),
+            artifacts: Vec::new(),
+            error: Some(redact_message(&format!("{error:#}"))),
+        }
+    }
+}
+
+/// POST `payload` to `url`, failing on transport errors and non-2xx replies
+pub fn send_notification(url: &str, payload: &NotifyPayload) -> Result<()> {
+    let body = serde_json::to_string(payload)?;
+    // Neither the body nor the URL, which may embed a token, goes to the log
+    debug!(
+        "Sending {:?} completion notification for '{}'",
+        payload.status, payload.command
+    );
+
+    ureq::post(url)
+        .timeout(NOTIFY_TIMEOUT)
+        .set("Content-Type", "application/json")
+        .send_string(&body)
+        .map_err(|e| match e {
+            ureq::Error::Status(code, response) => {
+                eyre!("{url} answered {code} {}", response.status_text())
+            }
+            ureq::Error::Transport(transport) => eyre!("{transport}"),
+        })?;
+
+    Ok(())
+}
+
+/// Send the notification, logging instead of failing if it cannot be delivered
+pub fn notify(url: &str, payload: &NotifyPayload) {
+    if let Err(e) = send_notification(url, payload) {
+        warn!("⚠️  Could not send completion notification: {e:#}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    /// Accept one request on a local port and answer it with `status`
+    ///
+    /// Returns the URL to post to and a channel receiving the request body.
+    fn mock_server(status: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            )
+            .unwrap();
+            sender.send(String::from_utf8(body).unwrap()).unwrap();
+        });
+
+        (url, receiver)
+    }
+
+    #[test]
+    fn test_payload_is_posted_as_json() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let proof = temp_dir.path().join("proof");
+        std::fs::write(&proof, b"proof bytes").unwrap();
+
+        let (url, received) = mock_server("200 OK");
+        let payload =
+            NotifyPayload::success("evm prove", std::slice::from_ref(&proof), &Timer::start());
+        send_notification(&url, &payload).unwrap();
+
+        let body: serde_json::Value = serde_json::from_str(&received.recv().unwrap()).unwrap();
+        assert_eq!(body["command"], "evm prove");
+        assert_eq!(body["status"], "success");
+        assert!(body["duration_ms"].is_u64());
+        assert_eq!(body["artifacts"][0]["path"], proof.display().to_string());
+        assert_eq!(body["artifacts"][0]["size"], 11);
+        assert!(body.get("error").is_none());
+    }
+
+    #[test]
+    fn test_failure_payload_and_rejected_notification() {
+        let (url, received) = mock_server("500 Internal Server Error");
+        let error = eyre!("bb prove failed");
+        let payload = NotifyPayload::failure("prove", &error, &Timer::start());
+
+        let result = send_notification(&url, &payload).unwrap_err().to_string();
+        assert!(result.contains("answered 500"), "{result}");
+
+        let body: serde_json::Value = serde_json::from_str(&received.recv().unwrap()).unwrap();
+        assert_eq!(body["status"], "failure");
+        assert_eq!(body["error"], "bb prove failed");
+        assert_eq!(body["artifacts"], serde_json::json!([]));
+
+        let error = eyre!("forge exited with code 1")
+            .wrap_err("Command execution failed: forge create --private-key 0xsecret");
+        let payload = NotifyPayload::failure("evm deploy", &error, &Timer::start());
+        let error = payload.error.as_deref().unwrap();
+        assert!(!error.contains("0xsecret"), "{error}");
+        assert!(error.contains("--private-key ***"), "{error}");
+
+        // Delivery problems are logged, never raised
+        notify("http://127.0.0.1:1/unreachable", &payload);
+    }
+}
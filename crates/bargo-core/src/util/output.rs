@@ -0,0 +1,424 @@
+//! Machine-readable output and progress display for bargo
+//!
+//! This module provides the JSON reports printed when bargo runs with the
+//! global `--json` flag, so CI pipelines can consume results without parsing
+//! the decorative terminal output, the [`Spinner`] shown on interactive
+//! terminals while a long external command runs, and the [`Table`] used by
+//! `info`, `status` and `doctor` to list rows with aligned columns.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! use bargo_core::util::output::emit_json;
+//! use std::path::PathBuf;
+//!
+//! let timer = Timer::start();
+//! // ... do work ...
+//! emit_json("build", &[PathBuf::from("target/bb/pkg.json")], &timer);
+//! ```
+
+use serde::Serialize;
+use std::cell::RefCell;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::util::log::{colors, colors_enabled};
+use crate::util::timer::Timer;
+
+/// JSON report for a completed operation
+#[derive(Debug, Serialize)]
+pub struct JsonReport {
+    pub operation: String,
+    pub success: bool,
+    pub elapsed_ms: u128,
+    pub artifacts: Vec<JsonArtifact>,
+    /// Command-specific fields such as `gas_estimate`, flattened into the report
+    #[serde(flatten)]
+    pub details: serde_json::Map<String, serde_json::Value>,
+}
+
+/// An artifact produced by an operation
+#[derive(Debug, Serialize)]
+pub struct JsonArtifact {
+    pub path: PathBuf,
+    /// Size in bytes, or `null` if the file does not exist (e.g. in dry-run mode)
+    pub size: Option<u64>,
+}
+
+impl JsonArtifact {
+    /// Describe the artifact at `path`, reading its size from disk
+    pub fn from_path(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            size: std::fs::metadata(path).ok().map(|metadata| metadata.len()),
+        }
+    }
+}
+
+impl JsonReport {
+    /// Build a successful report for `operation`
+    pub fn success(operation: &str, artifacts: &[PathBuf], timer: &Timer) -> Self {
+        Self {
+            operation: operation.to_string(),
+            success: true,
+            elapsed_ms: timer.elapsed_ms(),
+            artifacts: artifacts
+                .iter()
+                .map(|path| JsonArtifact::from_path(path))
+                .collect(),
+            details: serde_json::Map::new(),
+        }
+    }
+}
+
+thread_local! {
+    /// Fields recorded by the running command for its JSON report
+    static JSON_DETAILS: RefCell<serde_json::Map<String, serde_json::Value>> =
+        RefCell::new(serde_json::Map::new());
+}
+
+/// Record a command-specific field for the JSON report of the running command
+///
+/// Fields are per thread and consumed by the next [`emit_json`] call.
+pub fn record_json_detail(key: &str, value: impl Into<serde_json::Value>) {
+    JSON_DETAILS.with(|details| {
+        details.borrow_mut().insert(key.to_string(), value.into());
+    });
+}
+
+/// Print a single JSON object describing a successful operation to stdout
+pub fn emit_json(operation: &str, artifacts: &[PathBuf], timer: &Timer) {
+    let mut report = JsonReport::success(operation, artifacts, timer);
+    report.details = JSON_DETAILS.with(|details| details.take());
+    println!(
+        "{}",
+        serde_json::to_string(&report).expect("JSON report is always serializable")
+    );
+}
+
+/// Print an error as a JSON object (`{"error": "..."}`) to stdout
+pub fn emit_json_error(error: &color_eyre::eyre::Report) {
+    println!("{}", serde_json::json!({ "error": format!("{error:#}") }));
+}
+
+/// Frames cycled through by [`Spinner`]
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Delay between spinner frames
+const SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Return to column 0 and erase the current terminal line
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+/// Whether a spinner frame is currently drawn on stdout
+static SPINNER_VISIBLE: AtomicBool = AtomicBool::new(false);
+
+/// Animated progress indicator drawn on stdout while an external command runs
+///
+/// The spinner only appears when `enabled` is true, `NO_COLOR` is unset and
+/// stdout is a terminal; otherwise every method is a no-op, so logs captured
+/// in CI stay free of control sequences. The line is erased when the spinner
+/// is finished or dropped, leaving room for the final ✅ line.
+pub struct Spinner {
+    running: Option<(Arc<AtomicBool>, JoinHandle<()>)>,
+}
+
+impl Spinner {
+    /// Start drawing `message` next to an animated frame
+    pub fn start(message: &str, enabled: bool) -> Self {
+        if !enabled || !spinner_supported() {
+            return Self { running: None };
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let message = message.to_string();
+        let handle = std::thread::spawn(move || {
+            for frame in SPINNER_FRAMES.iter().cycle() {
+                if thread_stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                {
+                    let mut stdout = std::io::stdout().lock();
+                    let _ = write!(stdout, "{CLEAR_LINE}{frame} {message}");
+                    let _ = stdout.flush();
+                    SPINNER_VISIBLE.store(true, Ordering::Relaxed);
+                }
+                std::thread::sleep(SPINNER_INTERVAL);
+            }
+        });
+
+        Self {
+            running: Some((stop, handle)),
+        }
+    }
+
+    /// Stop the spinner and erase its line
+    pub fn finish(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        if let Some((stop, handle)) = self.running.take() {
+            stop.store(true, Ordering::Relaxed);
+            let _ = handle.join();
+            suspend_spinner(|| ());
+        }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Run `f` with any visible spinner line erased
+///
+/// Holds the stdout lock for the duration of `f`, so the spinner cannot
+/// redraw until `f` returns. Used when streaming tool output so that lines
+/// are not appended to a half-drawn spinner frame.
+pub fn suspend_spinner<T>(f: impl FnOnce() -> T) -> T {
+    let mut stdout = std::io::stdout().lock();
+    if SPINNER_VISIBLE.swap(false, Ordering::Relaxed) {
+        let _ = write!(stdout, "{CLEAR_LINE}");
+        let _ = stdout.flush();
+    }
+    f()
+}
+
+/// Whether the terminal can show a spinner (`NO_COLOR` unset, stdout is a TTY)
+fn spinner_supported() -> bool {
+    std::env::var_os("NO_COLOR").is_none() && atty::is(atty::Stream::Stdout)
+}
+
+/// One table cell, optionally colored when colors are enabled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    text: String,
+    color: Option<&'static str>,
+}
+
+impl Cell {
+    /// Cell drawn in `color`, or plain when colors are disabled
+    pub fn colored(text: impl Into<String>, color: &'static str) -> Self {
+        Self {
+            text: text.into(),
+            color: Some(color),
+        }
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Self { text, color: None }
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+/// Plain-text table with a header row and left-aligned columns
+///
+/// Column widths are the widest cell of each column, measured in terminal
+/// columns rather than bytes. Colors wrap the padded text, so escape codes
+/// never shift the alignment, and follow `--color` and `NO_COLOR` like the
+/// rest of bargo's output. The last column is not padded, so lines carry no
+/// trailing spaces.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+    indent: usize,
+}
+
+impl Table {
+    /// Empty table with the given column headers
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Indent every line by `indent` spaces
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Append a row; missing trailing cells are left empty
+    pub fn row<C: Into<Cell>>(&mut self, cells: impl IntoIterator<Item = C>) {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    /// Render the header and rows, colored if bargo's output is
+    pub fn render(&self) -> Vec<String> {
+        self.render_with_color(colors_enabled())
+    }
+
+    /// Print the rendered table to stdout
+    pub fn print(&self) {
+        for line in self.render() {
+            println!("{line}");
+        }
+    }
+
+    fn render_with_color(&self, colored: bool) -> Vec<String> {
+        let header: Vec<Cell> = self
+            .headers
+            .iter()
+            .map(|header| Cell::colored(header.clone(), colors::BOLD))
+            .collect();
+        let columns = self.rows.iter().map(Vec::len).max().unwrap_or_default();
+        let mut widths = vec![0; columns.max(header.len())];
+        for cells in [&header].into_iter().chain(&self.rows) {
+            for (width, cell) in widths.iter_mut().zip(cells) {
+                *width = (*width).max(display_width(&cell.text));
+            }
+        }
+
+        [&header]
+            .into_iter()
+            .chain(&self.rows)
+            .map(|cells| {
+                let mut line = " ".repeat(self.indent);
+                for (index, cell) in cells.iter().enumerate() {
+                    if index > 0 {
+                        line.push_str("  ");
+                    }
+                    let mut text = cell.text.clone();
+                    if index + 1 < cells.len() {
+                        text.push_str(&" ".repeat(widths[index] - display_width(&cell.text)));
+                    }
+                    match cell.color {
+                        Some(color) if colored => {
+                            line.push_str(&format!("{color}{text}{}", colors::RESET))
+                        }
+                        _ => line.push_str(&text),
+                    }
+                }
+                line.trim_end().to_string()
+            })
+            .collect()
+    }
+}
+
+/// Terminal columns taken by `text`
+///
+/// Emoji are drawn two columns wide, and the variation selectors and joiners
+/// that combine them take none.
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x200D | 0xFE00..=0xFE0F => 0,
+            0x2600..=0x27BF | 0x2B1B..=0x2B1C | 0x1F000.. => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_json_report_includes_artifact_sizes() {
+        let temp_dir = TempDir::new().unwrap();
+        let proof = temp_dir.path().join("proof");
+        std::fs::write(&proof, [0u8; 42]).unwrap();
+        let missing = temp_dir.path().join("vk");
+
+        let report = JsonReport::success("evm prove", &[proof.clone(), missing], &Timer::start());
+        let value = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(value["operation"], "evm prove");
+        assert_eq!(value["success"], true);
+        assert!(value["elapsed_ms"].is_u64());
+        assert_eq!(
+            value["artifacts"][0]["path"],
+            proof.to_string_lossy().as_ref()
+        );
+        assert_eq!(value["artifacts"][0]["size"], 42);
+        assert!(value["artifacts"][1]["size"].is_null());
+    }
+
+    #[test]
+    fn test_json_report_flattens_details() {
+        let mut report = JsonReport::success("evm deploy", &[], &Timer::start());
+        report
+            .details
+            .insert("gas_estimate".to_string(), 1_843_207.into());
+        let value = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(value["operation"], "evm deploy");
+        assert_eq!(value["gas_estimate"], 1_843_207);
+    }
+
+    #[test]
+    fn test_disabled_spinner_is_inactive() {
+        let spinner = Spinner::start("Generating proof", false);
+        assert!(spinner.running.is_none());
+        spinner.finish();
+
+        assert_eq!(suspend_spinner(|| 7), 7);
+    }
+
+    #[test]
+    fn test_table_aligns_mixed_width_rows() {
+        let mut table = Table::new(&["NAME", "PATH", "SIZE", "STATUS"]).with_indent(2);
+        table.row(["bytecode", "target/bb/pkg.json", "1.2 KB", "ok"]);
+        table.row(["vk", "target/evm/vk", "-", "missing"]);
+        table.row(vec![
+            Cell::from("public_inputs"),
+            "target/evm/public_inputs".into(),
+            "32 B".into(),
+            Cell::colored("ok", colors::GREEN),
+        ]);
+
+        let lines = table.render_with_color(false);
+        assert_eq!(
+            lines,
+            [
+                "  NAME           PATH                      SIZE    STATUS",
+                "  bytecode       target/bb/pkg.json        1.2 KB  ok",
+                "  vk             target/evm/vk             -       missing",
+                "  public_inputs  target/evm/public_inputs  32 B    ok",
+            ]
+        );
+
+        // Colors wrap the padded cell and leave the visible columns in place
+        let colored = table.render_with_color(true);
+        assert_eq!(
+            colored[0],
+            format!(
+                "  {bold}NAME         {reset}  {bold}PATH                    {reset}  {bold}SIZE  {reset}  {bold}STATUS{reset}",
+                bold = colors::BOLD,
+                reset = colors::RESET
+            )
+        );
+        assert_eq!(
+            colored[3],
+            format!(
+                "  public_inputs  target/evm/public_inputs  32 B    {}ok{}",
+                colors::GREEN,
+                colors::RESET
+            )
+        );
+    }
+
+    #[test]
+    fn test_display_width_counts_emoji_as_two_columns() {
+        assert_eq!(display_width("vk"), 2);
+        assert_eq!(display_width("✅ ok"), 5);
+        assert_eq!(display_width("⚠️"), 2);
+    }
+}
@@ -1,7 +1,11 @@
 use color_eyre::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::path::{Path, PathBuf};
-use tracing::{debug, warn};
+use tracing::debug;
+
+use crate::fs::Fs;
+use crate::util::BargoError;
 
 /// Backend flavour for artifact generation
 #[cfg_attr(not(feature = "cairo"), allow(dead_code))]
@@ -25,24 +29,7 @@ pub fn find_project_root(current_path: &Path) -> Result<PathBuf> {
         }
     }
 
-    Err(color_eyre::eyre::eyre!(
-        "Could not find Nargo.toml in current directory or any parent directory.\n\
-         Make sure you're running bargo from within a Noir project."
-    ))
-}
-
-/// Get the package name from Nargo.toml, with optional override
-pub fn get_package_name(pkg_override: Option<&String>) -> Result<String> {
-    if let Some(pkg_name) = pkg_override {
-        debug!("Using package name override: {}", pkg_name);
-        return Ok(pkg_name.clone());
-    }
-
-    let current_dir = std::env::current_dir()?;
-    let project_root = find_project_root(&current_dir)?;
-    let nargo_toml_path = project_root.join("Nargo.toml");
-
-    parse_package_name(&nargo_toml_path)
+    Err(BargoError::ProjectNotFound.into())
 }
 
 /// Get the package name from Nargo.toml in a specific directory, with optional override
@@ -61,8 +48,114 @@ pub fn get_package_name_in_directory(
     parse_package_name(&nargo_toml_path)
 }
 
+/// Kind of manifest described by a Nargo.toml file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NargoTomlKind {
+    /// A single package with a `[package]` section
+    Package,
+    /// A workspace with a `[workspace]` section
+    Workspace,
+}
+
+impl NargoTomlKind {
+    /// Human-readable name of the manifest kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NargoTomlKind::Package => "package",
+            NargoTomlKind::Workspace => "workspace",
+        }
+    }
+}
+
+/// Detect whether a Nargo.toml file describes a package or a workspace
+pub fn detect_nargo_toml_kind(nargo_toml_path: &Path) -> Result<NargoTomlKind> {
+    match read_toml_config(nargo_toml_path)? {
+        TomlConfig::Package { .. } => Ok(NargoTomlKind::Package),
+        TomlConfig::Workspace { .. } => Ok(NargoTomlKind::Workspace),
+    }
+}
+
 /// Parse the package name from a Nargo.toml file
 pub fn parse_package_name(nargo_toml_path: &Path) -> Result<String> {
+    match read_toml_config(nargo_toml_path)? {
+        TomlConfig::Package { package } => package.name.ok_or_else(|| {
+            color_eyre::eyre::eyre!(
+                "Missing 'name' field in [package] section of Nargo.toml at {}",
+                nargo_toml_path.display()
+            )
+        }),
+        TomlConfig::Workspace { workspace } => {
+            if let Some(default_member) = &workspace.default_member {
+                let member_toml = workspace_root(nargo_toml_path)
+                    .join(default_member)
+                    .join("Nargo.toml");
+                debug!("Using workspace default-member: {}", default_member);
+                return parse_package_name(&member_toml);
+            }
+
+            let members = list_workspace_members(nargo_toml_path)?
+                .into_iter()
+                .map(|member| member.name)
+                .collect::<Vec<_>>();
+            Err(color_eyre::eyre::eyre!(
+                "Nargo.toml at {} is a workspace, so the package to use is ambiguous.\n\
+                 Available members: {}\n\
+                 Select one with --pkg <name>, or pass --all to build or check every member.",
+                nargo_toml_path.display(),
+                members.join(", ")
+            ))
+        }
+    }
+}
+
+/// A member package of a Nargo workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkspaceMember {
+    /// Package name from the member's Nargo.toml
+    pub name: String,
+    /// Member directory, resolved against the workspace root
+    pub path: PathBuf,
+}
+
+/// List the members of a workspace Nargo.toml with their package names
+pub fn list_workspace_members(nargo_toml_path: &Path) -> Result<Vec<WorkspaceMember>> {
+    workspace_member_dirs(nargo_toml_path)?
+        .into_iter()
+        .map(|path| {
+            let name = parse_package_name(&path.join("Nargo.toml"))?;
+            Ok(WorkspaceMember { name, path })
+        })
+        .collect()
+}
+
+/// Directories of the members listed in a workspace Nargo.toml
+///
+/// Unlike [`list_workspace_members`], the members' own Nargo.toml files are
+/// not read, so a broken member does not hide the others.
+pub fn workspace_member_dirs(nargo_toml_path: &Path) -> Result<Vec<PathBuf>> {
+    let TomlConfig::Workspace { workspace } = read_toml_config(nargo_toml_path)? else {
+        return Err(color_eyre::eyre::eyre!(
+            "Nargo.toml at {} is not a workspace",
+            nargo_toml_path.display()
+        ));
+    };
+
+    let root = workspace_root(nargo_toml_path);
+    Ok(workspace
+        .members
+        .iter()
+        .map(|member| root.join(member))
+        .collect())
+}
+
+/// Directory containing a Nargo.toml file
+fn workspace_root(nargo_toml_path: &Path) -> &Path {
+    nargo_toml_path.parent().unwrap_or(Path::new("."))
+}
+
+/// Read and parse a Nargo.toml file
+fn read_toml_config(nargo_toml_path: &Path) -> Result<TomlConfig> {
     let toml_content = std::fs::read_to_string(nargo_toml_path).map_err(|e| {
         color_eyre::eyre::eyre!(
             "Failed to read Nargo.toml at {}: {}",
@@ -71,44 +164,162 @@ pub fn parse_package_name(nargo_toml_path: &Path) -> Result<String> {
         )
     })?;
 
-    let config: TomlConfig = toml::from_str(&toml_content).map_err(|e| {
-        color_eyre::eyre::eyre!(
-            "Failed to parse Nargo.toml at {}: {}",
-            nargo_toml_path.display(),
-            e
-        )
-    })?;
+    toml::from_str(&toml_content).map_err(|e| {
+        BargoError::ParseFailure {
+            path: nargo_toml_path.to_path_buf(),
+            message: e.to_string(),
+        }
+        .into()
+    })
+}
 
-    match config {
-        TomlConfig::Package { package } => package.name.ok_or_else(|| {
-            color_eyre::eyre::eyre!(
-                "Missing 'name' field in [package] section of Nargo.toml at {}",
-                nargo_toml_path.display()
-            )
-        }),
-        TomlConfig::Workspace { .. } => {
-            warn!("Found workspace Nargo.toml, using directory name as package name");
-            let dir_name = nargo_toml_path
-                .parent()
-                .and_then(|p| p.file_name())
-                .and_then(|name| name.to_str())
-                .map(|s| s.to_string())
-                .ok_or_else(|| {
-                    color_eyre::eyre::eyre!(
-                        "Could not determine package name from workspace directory"
-                    )
-                })?;
-            Ok(dir_name)
+/// Directory nargo writes compiled bytecode and witnesses to
+const NARGO_TARGET_DIR: &str = "target";
+
+/// Default base directory for bargo artifacts, relative to the project root
+pub const DEFAULT_TARGET_DIR: &str = "target";
+
+thread_local! {
+    /// Override for the artifact base directory, set from `--target-dir`
+    static TARGET_BASE: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+
+    /// Directory bargo treats as its working directory, set from `--package-dir`
+    static PROJECT_DIR: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+
+    /// How proof artifacts are named, set from `--artifact-suffix`
+    static NAMING_STRATEGY: RefCell<NamingStrategy> = const { RefCell::new(NamingStrategy::Plain) };
+}
+
+/// How the proof, verification key and public inputs files are named
+///
+/// bb always writes `proof`, `vk` and `public_inputs`; with a suffix the
+/// files are renamed after each bb run so artifacts from different packages
+/// or runs can share a target directory.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum NamingStrategy {
+    /// Fixed names: `proof`, `vk`, `public_inputs`
+    #[default]
+    Plain,
+    /// Package name suffix: `proof_<pkg>`
+    Package(String),
+    /// Timestamp suffix fixed for the whole run: `proof_<unix seconds>`
+    ///
+    /// Artifacts are written with the run's timestamp, but the path helpers
+    /// resolve to the newest existing `proof_<seconds>`, so later runs
+    /// (`verify`, `gen`, ...) find what an earlier `prove` wrote.
+    Timestamp(u64),
+}
+
+impl NamingStrategy {
+    /// File name for the artifact bb calls `base`
+    pub fn artifact_name(&self, base: &str) -> String {
+        match self {
+            NamingStrategy::Plain => base.to_string(),
+            NamingStrategy::Package(pkg) => format!("{base}_{pkg}"),
+            NamingStrategy::Timestamp(seconds) => format!("{base}_{seconds}"),
         }
     }
 }
 
+/// Set the naming strategy used by the proof, vk and public inputs helpers
+///
+/// Like `set_target_base`, this is per thread and applied from
+/// `Config::apply_paths`.
+pub fn set_naming_strategy(strategy: NamingStrategy) {
+    NAMING_STRATEGY.with(|cell| *cell.borrow_mut() = strategy);
+}
+
+/// Path of the artifact bb calls `base` in the flavour's target directory
+///
+/// With [`NamingStrategy::Timestamp`] this is the newest existing
+/// `{base}_<seconds>` file, falling back to this run's name when there is none.
+fn artifact_path(flavour: Flavour, base: &str) -> PathBuf {
+    let dir = target_dir(flavour);
+    NAMING_STRATEGY.with(|cell| match &*cell.borrow() {
+        NamingStrategy::Timestamp(seconds) => newest_timestamped(&dir, base)
+            .unwrap_or_else(|| dir.join(NamingStrategy::Timestamp(*seconds).artifact_name(base))),
+        strategy => dir.join(strategy.artifact_name(base)),
+    })
+}
+
+/// Path this run writes the artifact bb calls `base` to
+fn written_artifact_path(flavour: Flavour, base: &str) -> PathBuf {
+    target_dir(flavour).join(NAMING_STRATEGY.with(|cell| cell.borrow().artifact_name(base)))
+}
+
+/// Newest `{base}_<unix seconds>` file in `dir`, if any
+fn newest_timestamped(dir: &Path, base: &str) -> Option<PathBuf> {
+    let prefix = format!("{base}_");
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let seconds: u64 = name.strip_prefix(&prefix)?.parse().ok()?;
+            Some((seconds, entry.path()))
+        })
+        .max_by_key(|(seconds, _)| *seconds)
+        .map(|(_, path)| path)
+}
+
+/// Set the directory that relative project paths are resolved against
+///
+/// `None` restores the process working directory. Like `set_target_base`,
+/// this is per thread and applied from `Config::apply_paths`.
+pub fn set_project_dir(dir: Option<PathBuf>) {
+    PROJECT_DIR.with(|cell| *cell.borrow_mut() = dir);
+}
+
+/// Resolve a path relative to the `--package-dir` directory
+///
+/// Without `--package-dir` (or for absolute paths) the path is returned
+/// unchanged, so it keeps resolving against the process working directory.
+pub fn project_path(path: impl AsRef<Path>) -> PathBuf {
+    PROJECT_DIR.with(|cell| match cell.borrow().as_ref() {
+        Some(dir) => dir.join(path),
+        None => path.as_ref().to_path_buf(),
+    })
+}
+
+/// Render a path as a command-line argument for an external tool
+///
+/// Separators are always written as `/`, so bb, garaga and the other tools
+/// receive the same argument on every platform. Windows accepts forward
+/// slashes, while some tool argument parsers mishandle backslashes.
+pub fn tool_path_string(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if std::path::MAIN_SEPARATOR == '/' {
+        path.into_owned()
+    } else {
+        path.replace(std::path::MAIN_SEPARATOR, "/")
+    }
+}
+
+/// Set the base directory used by `target_dir` and every artifact path helper
+///
+/// `None` restores the default `target/`. The override is per thread: bargo
+/// applies it once on the main thread from `Config::target_dir` before
+/// dispatching a command.
+pub fn set_target_base(base: Option<PathBuf>) {
+    TARGET_BASE.with(|cell| *cell.borrow_mut() = base);
+}
+
+/// Get the base directory that holds the per-flavour target directories
+pub fn target_base() -> PathBuf {
+    project_path(TARGET_BASE.with(|cell| {
+        cell.borrow()
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_TARGET_DIR))
+    }))
+}
+
 /// Get the target directory path for a specific backend flavour
 pub fn target_dir(flavour: Flavour) -> PathBuf {
+    let base = target_base();
     match flavour {
-        Flavour::Bb => PathBuf::from("target/bb"),
-        Flavour::Evm => PathBuf::from("target/evm"),
-        Flavour::Starknet => PathBuf::from("target/starknet"),
+        Flavour::Bb => base.join("bb"),
+        Flavour::Evm => base.join("evm"),
+        Flavour::Starknet => base.join("starknet"),
     }
 }
 
@@ -124,83 +335,83 @@ pub fn get_witness_path(pkg_name: &str, flavour: Flavour) -> PathBuf {
 
 /// Get the proof file path for specific backend flavour
 pub fn get_proof_path(flavour: Flavour) -> PathBuf {
-    target_dir(flavour).join("proof")
+    artifact_path(flavour, "proof")
 }
 
 /// Get the verification key file path for specific backend flavour
 pub fn get_vk_path(flavour: Flavour) -> PathBuf {
-    target_dir(flavour).join("vk")
+    artifact_path(flavour, "vk")
 }
 
 /// Get the public inputs file path for specific backend flavour
 pub fn get_public_inputs_path(flavour: Flavour) -> PathBuf {
-    target_dir(flavour).join("public_inputs")
+    artifact_path(flavour, "public_inputs")
 }
 
-/// Organize build artifacts by moving nargo output to appropriate flavour directory
-pub fn organize_build_artifacts(pkg_name: &str, flavour: Flavour) -> Result<()> {
-    // Create the target directory for the flavour if it doesn't exist
-    let flavour_dir = target_dir(flavour);
-    std::fs::create_dir_all(&flavour_dir).map_err(|e| {
-        color_eyre::eyre::eyre!(
-            "Failed to create target directory {}: {}",
-            flavour_dir.display(),
-            e
-        )
-    })?;
-
-    // Move bytecode file from target/ to target/flavour/
-    let source_bytecode = PathBuf::from("target").join(format!("{pkg_name}.json"));
-    let dest_bytecode = get_bytecode_path(pkg_name, flavour);
-
-    if source_bytecode.exists() {
-        std::fs::rename(&source_bytecode, &dest_bytecode).map_err(|e| {
-            color_eyre::eyre::eyre!(
-                "Failed to move {} to {}: {}",
-                source_bytecode.display(),
-                dest_bytecode.display(),
-                e
-            )
-        })?;
-        debug!(
-            "Moved bytecode: {} -> {}",
-            source_bytecode.display(),
-            dest_bytecode.display()
-        );
+/// Rename the files bb just wrote to the flavour's target directory
+///
+/// bb ignores the naming strategy, so its plain `proof`, `vk` and
+/// `public_inputs` outputs are moved to the strategy's names. Missing files
+/// are skipped, and nothing happens with [`NamingStrategy::Plain`]. The moves
+/// go through `fs`, so a dry run only records them.
+pub fn apply_artifact_names(fs: &dyn Fs, flavour: Flavour) -> Result<()> {
+    let dir = target_dir(flavour);
+    for base in ["proof", "vk", "public_inputs"] {
+        let path = written_artifact_path(flavour, base);
+        let written = dir.join(base);
+        if written != path && written.exists() {
+            fs.rename(&written, &path).map_err(|e| {
+                color_eyre::eyre::eyre!(
+                    "Failed to move {} to {}: {}",
+                    written.display(),
+                    path.display(),
+                    e
+                )
+            })?;
+            debug!("Renamed {} -> {}", written.display(), path.display());
+        }
     }
+    Ok(())
+}
 
-    // Move witness file from target/ to target/flavour/
-    let source_witness = PathBuf::from("target").join(format!("{pkg_name}.gz"));
-    let dest_witness = get_witness_path(pkg_name, flavour);
+/// Bytecode and witness paths `nargo execute` writes before they are organized
+pub fn nargo_output_paths(pkg_name: &str) -> [PathBuf; 2] {
+    let nargo_target = project_path(NARGO_TARGET_DIR);
+    [
+        nargo_target.join(format!("{pkg_name}.json")),
+        nargo_target.join(format!("{pkg_name}.gz")),
+    ]
+}
 
-    if source_witness.exists() {
-        std::fs::rename(&source_witness, &dest_witness).map_err(|e| {
-            color_eyre::eyre::eyre!(
-                "Failed to move {} to {}: {}",
-                source_witness.display(),
-                dest_witness.display(),
-                e
-            )
-        })?;
-        debug!(
-            "Moved witness: {} -> {}",
-            source_witness.display(),
-            dest_witness.display()
-        );
-    }
+/// Organize build artifacts by moving nargo output to appropriate flavour directory
+///
+/// The moves go through `fs`, so a dry run only records them.
+pub fn organize_build_artifacts(fs: &dyn Fs, pkg_name: &str, flavour: Flavour) -> Result<()> {
+    // Keep a concurrent bargo from moving or deleting the same files
+    let _lock = if fs.is_dry_run() {
+        None
+    } else {
+        Some(crate::util::ArtifactLock::acquire()?)
+    };
+
+    let [source_bytecode, source_witness] = nargo_output_paths(pkg_name);
+    let dest_bytecode = get_bytecode_path(pkg_name, flavour);
+    let dest_witness = get_witness_path(pkg_name, flavour);
 
-    Ok(())
-}
+    // An interrupted or failed move leaves no mismatched bytecode and witness;
+    // created after the lock, so the files are removed before it is released
+    let partial = (!fs.is_dry_run()).then(|| {
+        crate::util::PartialArtifacts::track([
+            source_bytecode.clone(),
+            source_witness.clone(),
+            dest_bytecode.clone(),
+            dest_witness.clone(),
+        ])
+    });
 
-/// Organize build artifacts in a specific directory by moving nargo output to appropriate flavour directory
-pub fn organize_build_artifacts_in_directory(
-    pkg_name: &str,
-    flavour: Flavour,
-    working_dir: &Path,
-) -> Result<()> {
     // Create the target directory for the flavour if it doesn't exist
-    let flavour_dir = working_dir.join(target_dir(flavour));
-    std::fs::create_dir_all(&flavour_dir).map_err(|e| {
+    let flavour_dir = target_dir(flavour);
+    fs.create_dir_all(&flavour_dir).map_err(|e| {
         color_eyre::eyre::eyre!(
             "Failed to create target directory {}: {}",
             flavour_dir.display(),
@@ -209,11 +420,8 @@ pub fn organize_build_artifacts_in_directory(
     })?;
 
     // Move bytecode file from target/ to target/flavour/
-    let source_bytecode = working_dir.join("target").join(format!("{pkg_name}.json"));
-    let dest_bytecode = working_dir.join(get_bytecode_path(pkg_name, flavour));
-
     if source_bytecode.exists() {
-        std::fs::rename(&source_bytecode, &dest_bytecode).map_err(|e| {
+        fs.rename(&source_bytecode, &dest_bytecode).map_err(|e| {
             color_eyre::eyre::eyre!(
                 "Failed to move {} to {}: {}",
                 source_bytecode.display(),
@@ -229,11 +437,8 @@ pub fn organize_build_artifacts_in_directory(
     }
 
     // Move witness file from target/ to target/flavour/
-    let source_witness = working_dir.join("target").join(format!("{pkg_name}.gz"));
-    let dest_witness = working_dir.join(get_witness_path(pkg_name, flavour));
-
     if source_witness.exists() {
-        std::fs::rename(&source_witness, &dest_witness).map_err(|e| {
+        fs.rename(&source_witness, &dest_witness).map_err(|e| {
             color_eyre::eyre::eyre!(
                 "Failed to move {} to {}: {}",
                 source_witness.display(),
@@ -248,6 +453,9 @@ pub fn organize_build_artifacts_in_directory(
         );
     }
 
+    if let Some(partial) = partial {
+        partial.finish();
+    }
     Ok(())
 }
 
@@ -257,13 +465,8 @@ pub fn organize_build_artifacts_in_directory(
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 enum TomlConfig {
-    Package {
-        package: PackageMetadata,
-    },
-    Workspace {
-        #[allow(dead_code)]
-        workspace: WorkspaceMetadata,
-    },
+    Package { package: PackageMetadata },
+    Workspace { workspace: WorkspaceMetadata },
 }
 
 #[derive(Debug, Deserialize)]
@@ -280,9 +483,7 @@ struct PackageMetadata {
 
 #[derive(Debug, Deserialize)]
 struct WorkspaceMetadata {
-    #[allow(dead_code)]
     members: Vec<String>,
     #[serde(alias = "default-member")]
-    #[allow(dead_code)]
     default_member: Option<String>,
 }
@@ -1,8 +1,37 @@
+//! Operation summaries, and the session log written with `--summary-file`
+//!
+//! Each workflow prints an [`OperationSummary`] when it finishes. With
+//! `--summary-file`, bargo also appends one [`SessionRecord`] per invocation
+//! to a JSON-lines file, holding the operations and steps every summary of the
+//! command recorded, so a script chaining several commands can render a
+//! combined report afterwards with `bargo report`.
+
+use color_eyre::Result;
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use super::exit_code::ExitCode;
 use super::log::{colorize, colors};
+use super::notify::NotifyStatus;
+use super::timer::{Timer, format_duration};
+use crate::runner::redact_message;
+
+thread_local! {
+    /// Operations and steps added to any summary on this thread, for the session log
+    static SESSION: RefCell<(Vec<String>, Vec<StepRecord>)> = const {
+        RefCell::new((Vec::new(), Vec::new()))
+    };
+}
 
 /// Print operation summary with colored output
 pub struct OperationSummary {
     operations: Vec<String>,
+    steps: Vec<(String, Duration)>,
+    verbose: bool,
     start_time: std::time::Instant,
 }
 
@@ -10,12 +39,32 @@ impl OperationSummary {
     pub fn new() -> Self {
         Self {
             operations: Vec::new(),
+            steps: Vec::new(),
+            verbose: false,
             start_time: std::time::Instant::now(),
         }
     }
 
+    /// Show the per-step timing table when printing (set from `--verbose`)
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
     pub fn add_operation(&mut self, operation: &str) {
         self.operations.push(operation.to_string());
+        SESSION.with(|session| session.borrow_mut().0.push(operation.to_string()));
+    }
+
+    /// Record how long a workflow step took for the verbose timing breakdown
+    pub fn add_timed_operation(&mut self, name: &str, duration: Duration) {
+        self.steps.push((name.to_string(), duration));
+        SESSION.with(|session| {
+            session.borrow_mut().1.push(StepRecord {
+                name: name.to_string(),
+                duration_ms: duration.as_millis() as u64,
+            })
+        });
     }
 
     pub fn print(&self) {
@@ -24,19 +73,202 @@ impl OperationSummary {
         }
 
         let total_time = self.start_time.elapsed();
-        let time_str = if total_time.as_secs() > 0 {
-            format!("{:.1}s", total_time.as_secs_f64())
-        } else {
-            format!("{}ms", total_time.as_millis())
-        };
 
         println!("\n{}", colorize("🎉 Summary:", colors::BOLD));
         for operation in &self.operations {
             println!("   {}", colorize(&format!("• {operation}"), colors::GREEN));
         }
+        if self.verbose && !self.steps.is_empty() {
+            println!();
+            for line in self.format_steps(total_time) {
+                println!("   {}", colorize(&line, colors::GRAY));
+            }
+        }
         println!(
             "   {}",
-            colorize(&format!("Total time: {time_str}"), colors::GRAY)
+            colorize(
+                &format!("Total time: {}", format_duration(total_time)),
+                colors::GRAY
+            )
         );
     }
+
+    /// Render the recorded steps as a table of duration and share of `total`
+    pub fn format_steps(&self, total: Duration) -> Vec<String> {
+        let width = self
+            .steps
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Step".len());
+
+        let mut lines = vec![format!("{:<width$}  {:>8}  {:>6}", "Step", "Time", "%")];
+        for (name, duration) in &self.steps {
+            let percent = if total.is_zero() {
+                0.0
+            } else {
+                duration.as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+            lines.push(format!(
+                "{name:<width$}  {:>8}  {:>5.1}%",
+                format_duration(*duration),
+                percent
+            ));
+        }
+        lines
+    }
+}
+
+/// A timed workflow step in a [`SessionRecord`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepRecord {
+    pub name: String,
+    pub duration_ms: u64,
+}
+
+/// One line of a `--summary-file` session log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// UTC time the command finished, in RFC 3339 format
+    pub timestamp: String,
+    /// Command name, such as `evm prove`
+    pub command: String,
+    /// Package the command ran for, when it resolved
+    pub package: Option<String>,
+    /// Backend the command targeted, for backend-specific commands
+    pub backend: Option<String>,
+    pub status: NotifyStatus,
+    pub duration_ms: u64,
+    /// Operations the command's summaries listed
+    #[serde(default)]
+    pub operations: Vec<String>,
+    /// Timed steps the command's summaries recorded
+    #[serde(default)]
+    pub steps: Vec<StepRecord>,
+    /// Error message when the command failed, with secret values masked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Process exit code the failure maps to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+}
+
+impl SessionRecord {
+    /// Record for a finished command, taking the operations and steps its
+    /// summaries added on this thread
+    pub fn finish(
+        command: &str,
+        package: Option<String>,
+        backend: Option<String>,
+        error: Option<&color_eyre::eyre::Report>,
+        timer: &Timer,
+    ) -> Self {
+        let (operations, steps) = SESSION.with(|session| session.take());
+        Self {
+            timestamp: super::utc_timestamp(std::time::SystemTime::now()),
+            command: command.to_string(),
+            package,
+            backend,
+            status: match error {
+                Some(_) => NotifyStatus::Failure,
+                None => NotifyStatus::Success,
+            },
+            duration_ms: timer.elapsed_ms() as u64,
+            operations,
+            steps,
+            error: error.map(|error| redact_message(&format!("{error:#}"))),
+            exit_code: error.map(|error| ExitCode::for_error(error).code()),
+        }
+    }
+}
+
+/// Append `record` as one JSON line to the session log at `path`
+pub fn append_session_record(path: &Path, record: &SessionRecord) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)
+            .wrap_err_with(|| format!("creating directory {}", parent.display()))?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .wrap_err_with(|| format!("opening summary file {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(record)?)
+        .wrap_err_with(|| format!("writing summary file {}", path.display()))
+}
+
+/// Read every record of the session log at `path`, skipping blank lines
+pub fn read_session_records(path: &Path) -> Result<Vec<SessionRecord>> {
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("reading summary file {}", path.display()))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .wrap_err_with(|| format!("parsing line {} of {}", index + 1, path.display()))
+        })
+        .collect()
+}
+
+/// Render a session log as one line per command, its operations and steps,
+/// and a closing total
+pub fn format_session_report(records: &[SessionRecord]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for record in records {
+        let mark = match record.status {
+            NotifyStatus::Success => "✅",
+            NotifyStatus::Failure => "❌",
+        };
+        let target: Vec<&str> = [record.package.as_deref(), record.backend.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        let target = if target.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", target.join(", "))
+        };
+        lines.push(format!(
+            "{mark} {} {}{target} - {}",
+            record.timestamp,
+            record.command,
+            format_duration(Duration::from_millis(record.duration_ms))
+        ));
+        for operation in &record.operations {
+            lines.push(format!("   • {operation}"));
+        }
+        for step in &record.steps {
+            lines.push(format!(
+                "   {}: {}",
+                step.name,
+                format_duration(Duration::from_millis(step.duration_ms))
+            ));
+        }
+        if let Some(error) = &record.error {
+            lines.push(format!("   {error}"));
+        }
+    }
+
+    let failed = records
+        .iter()
+        .filter(|record| record.status == NotifyStatus::Failure)
+        .count();
+    let total: u64 = records.iter().map(|record| record.duration_ms).sum();
+    let noun = if records.len() == 1 {
+        "command"
+    } else {
+        "commands"
+    };
+    lines.push(format!(
+        "{} {noun}, {failed} failed, total time {}",
+        records.len(),
+        format_duration(Duration::from_millis(total))
+    ));
+    lines
 }
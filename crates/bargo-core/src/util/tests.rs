@@ -1,6 +1,7 @@
 use super::*;
+use crate::fs::{DryRunFs, FsOp, RealFs};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
 fn create_test_project(temp_dir: &TempDir, name: &str) -> PathBuf {
@@ -46,10 +47,31 @@ fn test_parse_package_name() {
     assert_eq!(name, "my_package");
 }
 
+#[test]
+fn test_detect_nargo_toml_kind() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "my_package");
+    assert_eq!(
+        detect_nargo_toml_kind(&project_dir.join("Nargo.toml")).unwrap(),
+        NargoTomlKind::Package
+    );
+
+    let workspace_toml = temp_dir.path().join("Nargo.toml");
+    fs::write(
+        &workspace_toml,
+        "[workspace]\nmembers = [\"test_project\"]\n",
+    )
+    .unwrap();
+    assert_eq!(
+        detect_nargo_toml_kind(&workspace_toml).unwrap(),
+        NargoTomlKind::Workspace
+    );
+}
+
 #[test]
 fn test_get_package_name_with_override() {
     let override_name = "override_pkg".to_string();
-    let name = get_package_name(Some(&override_name)).unwrap();
+    let name = get_package_name_in_directory(Some(&override_name), Path::new(".")).unwrap();
     assert_eq!(name, "override_pkg");
 }
 
@@ -213,7 +235,7 @@ fn test_needs_rebuild_no_target() {
     let project_dir = create_test_project(&temp_dir, "test_pkg");
 
     // Test directly with absolute path - no directory change needed!
-    let needs_rebuild = needs_rebuild_from_path("test_pkg", &project_dir).unwrap();
+    let needs_rebuild = needs_rebuild_from_path("test_pkg", &project_dir, None).unwrap();
     assert!(needs_rebuild);
 }
 
@@ -233,7 +255,7 @@ fn test_needs_rebuild_prover_toml_modified() {
     fs::write(&witness_path, "mock witness").unwrap();
 
     // Initially should not need rebuild
-    let needs_rebuild = needs_rebuild_from_path("test_pkg", &project_dir).unwrap();
+    let needs_rebuild = needs_rebuild_from_path("test_pkg", &project_dir, None).unwrap();
     assert!(
         !needs_rebuild,
         "Should not need rebuild when target files exist and are newer"
@@ -247,13 +269,99 @@ fn test_needs_rebuild_prover_toml_modified() {
     fs::write(&prover_toml, "# Circuit inputs\n").unwrap();
 
     // Now should need rebuild due to Prover.toml being newer
-    let needs_rebuild = needs_rebuild_from_path("test_pkg", &project_dir).unwrap();
+    let needs_rebuild = needs_rebuild_from_path("test_pkg", &project_dir, None).unwrap();
     assert!(
         needs_rebuild,
         "Should need rebuild when Prover.toml is newer than target files"
     );
 }
 
+fn write_build_artifacts(project_dir: &std::path::Path, pkg: &str) {
+    let target_bb_dir = project_dir.join("target/bb");
+    fs::create_dir_all(&target_bb_dir).unwrap();
+    fs::write(target_bb_dir.join(format!("{pkg}.json")), "mock bytecode").unwrap();
+    fs::write(target_bb_dir.join(format!("{pkg}.gz")), "mock witness").unwrap();
+}
+
+#[test]
+fn test_rebuild_reasons_fresh_build_is_up_to_date() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "test_pkg");
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    write_build_artifacts(&project_dir, "test_pkg");
+
+    let reasons = rebuild_reasons_from_path("test_pkg", &project_dir, None).unwrap();
+    assert!(reasons.is_empty(), "unexpected reasons: {reasons:?}");
+}
+
+#[test]
+fn test_rebuild_reasons_lists_newer_inputs() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "test_pkg");
+    write_build_artifacts(&project_dir, "test_pkg");
+    std::thread::sleep(std::time::Duration::from_millis(10));
+
+    fs::write(project_dir.join("Prover.toml"), "a = 1\n").unwrap();
+    fs::create_dir_all(project_dir.join("src/lib")).unwrap();
+    fs::write(project_dir.join("src/lib/helpers.nr"), "fn helper() {}").unwrap();
+
+    let reasons = rebuild_reasons_from_path("test_pkg", &project_dir, None).unwrap();
+    assert_eq!(
+        reasons,
+        vec![
+            "Prover.toml is newer than the build artifacts".to_string(),
+            format!(
+                "{} is newer than the build artifacts",
+                PathBuf::from("src/lib/helpers.nr").display()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_rebuild_reasons_lists_missing_artifacts() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "test_pkg");
+    write_build_artifacts(&project_dir, "test_pkg");
+    fs::remove_file(project_dir.join("target/bb/test_pkg.gz")).unwrap();
+
+    let reasons = rebuild_reasons_from_path("test_pkg", &project_dir, None).unwrap();
+    assert_eq!(reasons.len(), 1);
+    assert!(reasons[0].ends_with("test_pkg.gz is missing"));
+}
+
+#[test]
+fn test_rebuild_reasons_track_prover_input() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "test_pkg");
+    let input = project_dir.join("inputs/alt.toml");
+    fs::create_dir_all(input.parent().unwrap()).unwrap();
+    fs::write(&input, "a = 2\n").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    write_build_artifacts(&project_dir, "test_pkg");
+    set_manifest_prover_input(&project_dir.join("target/bb/manifest.json"), Some(&input)).unwrap();
+
+    let reasons = rebuild_reasons_from_path("test_pkg", &project_dir, Some(&input)).unwrap();
+    assert!(reasons.is_empty(), "unexpected reasons: {reasons:?}");
+
+    // Going back to Prover.toml needs a new witness
+    let reasons = rebuild_reasons_from_path("test_pkg", &project_dir, None).unwrap();
+    assert_eq!(
+        reasons,
+        vec![format!(
+            "witness was built from {}, not Prover.toml",
+            PathBuf::from("inputs/alt.toml").display()
+        )]
+    );
+
+    // Only the chosen input file's timestamp matters
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    fs::write(&input, "a = 3\n").unwrap();
+    let reasons = rebuild_reasons_from_path("test_pkg", &project_dir, Some(&input)).unwrap();
+    assert_eq!(reasons.len(), 1);
+    assert!(reasons[0].ends_with("alt.toml is newer than the build artifacts"));
+}
+
 #[test]
 fn test_validate_files_exist_success() {
     let temp_dir = TempDir::new().unwrap();
@@ -317,3 +425,598 @@ fn test_package_name_edge_cases() {
         assert!(bytecode_path.to_string_lossy().ends_with(".json"));
     }
 }
+
+#[test]
+fn test_enhance_error_suggests_raising_timeout() {
+    let error = color_eyre::eyre::Report::new(BargoError::Timeout {
+        tool: "bb".to_string(),
+        seconds: 120,
+    })
+    .wrap_err("Command execution failed: bb prove");
+
+    let enhanced = format!("{}", enhance_error_with_suggestions(error));
+    assert!(enhanced.contains("--timeout <seconds>"));
+}
+
+#[test]
+fn test_parse_package_name_uses_workspace_default_member() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_project(&temp_dir, "member_pkg");
+    let workspace_toml = temp_dir.path().join("Nargo.toml");
+    fs::write(
+        &workspace_toml,
+        "[workspace]\nmembers = [\"test_project\"]\ndefault-member = \"test_project\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(parse_package_name(&workspace_toml).unwrap(), "member_pkg");
+}
+
+#[test]
+fn test_path_helpers_with_custom_target_base() {
+    set_target_base(Some(PathBuf::from("build/zk")));
+
+    assert_eq!(target_base(), PathBuf::from("build/zk"));
+    assert_eq!(target_dir(Flavour::Evm), PathBuf::from("build/zk/evm"));
+    assert_eq!(
+        get_bytecode_path("test", Flavour::Bb),
+        PathBuf::from("build/zk/bb/test.json")
+    );
+    assert_eq!(
+        get_witness_path("test", Flavour::Bb),
+        PathBuf::from("build/zk/bb/test.gz")
+    );
+    assert_eq!(
+        get_proof_path(Flavour::Starknet),
+        PathBuf::from("build/zk/starknet/proof")
+    );
+    assert_eq!(get_vk_path(Flavour::Evm), PathBuf::from("build/zk/evm/vk"));
+    assert_eq!(
+        get_public_inputs_path(Flavour::Evm),
+        PathBuf::from("build/zk/evm/public_inputs")
+    );
+
+    set_target_base(None);
+    assert_eq!(target_dir(Flavour::Bb), PathBuf::from("target/bb"));
+}
+
+#[test]
+fn test_naming_strategies_generate_artifact_paths() {
+    set_naming_strategy(NamingStrategy::Package("my_circuit".to_string()));
+    assert_eq!(
+        get_proof_path(Flavour::Evm),
+        PathBuf::from("target/evm/proof_my_circuit")
+    );
+    assert_eq!(
+        get_vk_path(Flavour::Starknet),
+        PathBuf::from("target/starknet/vk_my_circuit")
+    );
+    assert_eq!(
+        get_public_inputs_path(Flavour::Evm),
+        PathBuf::from("target/evm/public_inputs_my_circuit")
+    );
+
+    set_naming_strategy(NamingStrategy::Timestamp(1760601600));
+    assert_eq!(
+        get_proof_path(Flavour::Starknet),
+        PathBuf::from("target/starknet/proof_1760601600")
+    );
+    assert_eq!(
+        get_vk_path(Flavour::Evm),
+        PathBuf::from("target/evm/vk_1760601600")
+    );
+
+    // Bytecode and witness names are already per package
+    assert_eq!(
+        get_bytecode_path("my_circuit", Flavour::Bb),
+        PathBuf::from("target/bb/my_circuit.json")
+    );
+
+    set_naming_strategy(NamingStrategy::Plain);
+    assert_eq!(
+        get_proof_path(Flavour::Evm),
+        PathBuf::from("target/evm/proof")
+    );
+}
+
+#[test]
+fn test_apply_artifact_names_renames_bb_outputs() {
+    let temp_dir = TempDir::new().unwrap();
+    set_target_base(Some(temp_dir.path().to_path_buf()));
+    let evm_dir = target_dir(Flavour::Evm);
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::write(evm_dir.join("proof"), "proof").unwrap();
+    fs::write(evm_dir.join("public_inputs"), "inputs").unwrap();
+
+    // The plain strategy leaves bb's outputs in place
+    apply_artifact_names(&RealFs, Flavour::Evm).unwrap();
+    assert!(evm_dir.join("proof").exists());
+
+    set_naming_strategy(NamingStrategy::Package("my_circuit".to_string()));
+
+    // A dry run records the moves without touching the files
+    let dry_run = DryRunFs::new();
+    apply_artifact_names(&dry_run, Flavour::Evm).unwrap();
+    assert_eq!(
+        dry_run.history(),
+        vec![
+            FsOp::Rename {
+                from: evm_dir.join("proof"),
+                to: get_proof_path(Flavour::Evm),
+            },
+            FsOp::Rename {
+                from: evm_dir.join("public_inputs"),
+                to: get_public_inputs_path(Flavour::Evm),
+            },
+        ]
+    );
+    assert!(evm_dir.join("proof").exists());
+    assert!(!get_proof_path(Flavour::Evm).exists());
+
+    apply_artifact_names(&RealFs, Flavour::Evm).unwrap();
+    assert!(!evm_dir.join("proof").exists());
+    assert_eq!(
+        fs::read_to_string(get_proof_path(Flavour::Evm)).unwrap(),
+        "proof"
+    );
+    assert!(get_public_inputs_path(Flavour::Evm).exists());
+    assert!(!get_vk_path(Flavour::Evm).exists());
+
+    set_naming_strategy(NamingStrategy::Plain);
+    set_target_base(None);
+}
+
+#[test]
+fn test_project_dir_resolves_relative_paths() {
+    set_project_dir(Some(PathBuf::from("/work/circuit")));
+    assert_eq!(
+        target_dir(Flavour::Evm),
+        PathBuf::from("/work/circuit/target/evm")
+    );
+    assert_eq!(
+        project_path("contracts/cairo"),
+        PathBuf::from("/work/circuit/contracts/cairo")
+    );
+    assert_eq!(project_path("/abs/proof"), PathBuf::from("/abs/proof"));
+
+    set_project_dir(None);
+    assert_eq!(project_path("contracts"), PathBuf::from("contracts"));
+}
+
+#[test]
+fn test_tool_path_string_uses_forward_slashes() {
+    // Joined with the platform separator, so this is `target\bb\pkg.json` on Windows
+    let path = Path::new("target").join("bb").join("pkg.json");
+    assert_eq!(tool_path_string(&path), "target/bb/pkg.json");
+    assert_eq!(
+        tool_path_string(&get_vk_path(Flavour::Starknet)),
+        "target/starknet/vk"
+    );
+    assert_eq!(tool_path_string(Path::new("proof")), "proof");
+}
+
+#[test]
+fn test_organize_build_artifacts_with_custom_target_base() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "test_pkg");
+    fs::create_dir_all(project_dir.join("target")).unwrap();
+    fs::write(project_dir.join("target/test_pkg.json"), "bytecode").unwrap();
+    fs::write(project_dir.join("target/test_pkg.gz"), "witness").unwrap();
+
+    set_project_dir(Some(project_dir.clone()));
+    set_target_base(Some(PathBuf::from("artifacts")));
+    organize_build_artifacts(&RealFs, "test_pkg", Flavour::Bb).unwrap();
+    set_target_base(None);
+    set_project_dir(None);
+
+    assert!(project_dir.join("artifacts/bb/test_pkg.json").exists());
+    assert!(project_dir.join("artifacts/bb/test_pkg.gz").exists());
+    assert!(!project_dir.join("target/test_pkg.json").exists());
+}
+
+#[test]
+fn test_organize_build_artifacts_dry_run_records_moves_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "test_pkg");
+    fs::create_dir_all(project_dir.join("target")).unwrap();
+    fs::write(project_dir.join("target/test_pkg.json"), "bytecode").unwrap();
+    fs::write(project_dir.join("target/test_pkg.gz"), "witness").unwrap();
+
+    let dry_fs = DryRunFs::new();
+    set_project_dir(Some(project_dir.clone()));
+    organize_build_artifacts(&dry_fs, "test_pkg", Flavour::Bb).unwrap();
+    set_project_dir(None);
+
+    assert!(project_dir.join("target/test_pkg.json").exists());
+    assert!(project_dir.join("target/test_pkg.gz").exists());
+    assert!(!project_dir.join("target/bb").exists());
+    assert!(!project_dir.join("target/.bargo.lock").exists());
+    assert_eq!(
+        dry_fs.history(),
+        [
+            FsOp::CreateDir(project_dir.join("target/bb")),
+            FsOp::Rename {
+                from: project_dir.join("target/test_pkg.json"),
+                to: project_dir.join("target/bb/test_pkg.json"),
+            },
+            FsOp::Rename {
+                from: project_dir.join("target/test_pkg.gz"),
+                to: project_dir.join("target/bb/test_pkg.gz"),
+            },
+        ]
+    );
+}
+
+/// Filesystem that is interrupted halfway through moving the witness
+#[derive(Debug)]
+struct InterruptedFs;
+
+impl crate::fs::Fs for InterruptedFs {
+    fn create_dir_all(&self, path: &Path) -> color_eyre::Result<()> {
+        RealFs.create_dir_all(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> color_eyre::Result<()> {
+        if from.extension().is_some_and(|ext| ext == "gz") {
+            fs::write(to, [0x1f]).unwrap();
+            return Err(color_eyre::eyre::eyre!("interrupted"));
+        }
+        RealFs.rename(from, to)
+    }
+
+    fn remove_file(&self, path: &Path) -> color_eyre::Result<()> {
+        RealFs.remove_file(path)
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> color_eyre::Result<()> {
+        RealFs.remove_dir_all(path)
+    }
+}
+
+#[test]
+fn test_organize_build_artifacts_interrupted_leaves_no_partial_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "test_pkg");
+    fs::create_dir_all(project_dir.join("target")).unwrap();
+    fs::write(project_dir.join("target/test_pkg.json"), "bytecode").unwrap();
+    fs::write(project_dir.join("target/test_pkg.gz"), "witness").unwrap();
+
+    set_project_dir(Some(project_dir.clone()));
+    let result = organize_build_artifacts(&InterruptedFs, "test_pkg", Flavour::Bb);
+    set_project_dir(None);
+
+    assert!(result.is_err());
+    // Neither the truncated witness nor the bytecode it no longer matches remain
+    assert!(!project_dir.join("target/bb/test_pkg.gz").exists());
+    assert!(!project_dir.join("target/bb/test_pkg.json").exists());
+    assert!(!project_dir.join("target/test_pkg.gz").exists());
+
+    // The lock was released with the cleanup done
+    assert!(ArtifactLock::acquire_at(&project_dir.join("target").join(LOCK_FILE)).is_ok());
+
+    // A finished phase keeps its files
+    let kept = project_dir.join("kept.json");
+    fs::write(&kept, "{}").unwrap();
+    PartialArtifacts::track([kept.clone()]).finish();
+    assert!(kept.exists());
+}
+
+fn suggestions_for(error: BargoError) -> String {
+    format!("{}", enhance_error_with_suggestions(error.into()))
+}
+
+#[test]
+fn test_missing_artifact_suggests_build_for_build_outputs() {
+    let enhanced = suggestions_for(BargoError::MissingArtifact {
+        paths: vec![PathBuf::from("target/bb/pkg.json")],
+    });
+    assert!(enhanced.contains("Required files are missing: target/bb/pkg.json"));
+    assert!(enhanced.contains("bargo build"));
+}
+
+#[test]
+fn test_missing_artifact_suggests_prove_for_proof_outputs() {
+    let enhanced = suggestions_for(BargoError::MissingArtifact {
+        paths: vec![
+            PathBuf::from("target/bb/proof"),
+            PathBuf::from("target/bb/vk"),
+        ],
+    });
+    assert!(enhanced.contains("target/bb/proof, target/bb/vk"));
+    assert!(enhanced.contains("bargo prove"));
+}
+
+#[test]
+fn test_project_not_found_suggests_nargo_new() {
+    let enhanced = suggestions_for(BargoError::ProjectNotFound);
+    assert!(enhanced.contains("Could not find Nargo.toml"));
+    assert!(enhanced.contains("nargo new"));
+}
+
+#[test]
+fn test_missing_tool_suggests_installing_that_tool() {
+    let nargo = suggestions_for(BargoError::MissingTool {
+        tool: "nargo".to_string(),
+    });
+    assert!(nargo.contains("noirup"));
+
+    let forge = suggestions_for(BargoError::MissingTool {
+        tool: "forge".to_string(),
+    });
+    assert!(forge.contains("foundryup"));
+
+    let other = suggestions_for(BargoError::MissingTool {
+        tool: "scarb".to_string(),
+    });
+    assert!(other.contains("Install scarb"));
+}
+
+#[test]
+fn test_parse_failure_suggestions_depend_on_file() {
+    let nargo_toml = suggestions_for(BargoError::ParseFailure {
+        path: PathBuf::from("proj/Nargo.toml"),
+        message: "expected `=`".to_string(),
+    });
+    assert!(nargo_toml.contains("Failed to parse Nargo.toml at proj/Nargo.toml: expected `=`"));
+    assert!(nargo_toml.contains("[package] section"));
+
+    let bytecode = suggestions_for(BargoError::ParseFailure {
+        path: PathBuf::from("target/bb/pkg.json"),
+        message: "EOF".to_string(),
+    });
+    assert!(bytecode.contains("bargo rebuild"));
+}
+
+#[test]
+fn test_command_failed_keeps_caller_context() {
+    let error = color_eyre::eyre::Report::new(BargoError::CommandFailed {
+        tool: "bb".to_string(),
+        code: Some(1),
+        stdout: String::new(),
+        stderr: "bad witness".to_string(),
+    })
+    .wrap_err("Command execution failed: bb prove");
+
+    let enhanced = format!("{}", enhance_error_with_suggestions(error));
+    assert!(enhanced.starts_with("Command execution failed: bb prove\n"));
+    assert!(enhanced.contains("Stderr: bad witness"));
+    assert!(enhanced.contains("--verbose"));
+}
+
+#[test]
+fn test_enhance_error_is_idempotent_and_keeps_variant() {
+    let once = enhance_error_with_suggestions(BargoError::ProjectNotFound.into());
+    let rendered = once.to_string();
+    let twice = enhance_error_with_suggestions(once.wrap_err("Failed to build"));
+
+    assert_eq!(twice.root_cause().to_string(), rendered);
+    assert_eq!(rendered.matches("💡 Suggestions").count(), 1);
+    assert_eq!(find_bargo_error(&twice), Some(&BargoError::ProjectNotFound));
+}
+
+#[test]
+fn test_enhance_error_leaves_unstructured_errors_alone() {
+    let error = color_eyre::eyre::eyre!("nargo: command not found");
+    let enhanced = enhance_error_with_suggestions(error);
+    assert_eq!(enhanced.to_string(), "nargo: command not found");
+    assert!(find_bargo_error(&enhanced).is_none());
+}
+
+#[test]
+fn test_stale_artifacts_lists_reasons_and_suggests_build() {
+    let enhanced = suggestions_for(BargoError::StaleArtifacts {
+        reasons: vec!["src/main.nr is newer than the build artifacts".to_string()],
+    });
+    assert!(enhanced.contains("Build artifacts are out of date"));
+    assert!(enhanced.contains("• src/main.nr is newer than the build artifacts"));
+    assert!(enhanced.contains("bargo build"));
+}
+
+#[test]
+fn test_summary_formats_step_breakdown() {
+    use std::time::Duration;
+
+    let mut summary = OperationSummary::new().verbose(true);
+    summary.add_timed_operation("VK generation", Duration::from_millis(250));
+    summary.add_timed_operation("Proof generation", Duration::from_millis(1500));
+
+    let lines = summary.format_steps(Duration::from_secs(2));
+    assert_eq!(
+        lines,
+        [
+            "Step                  Time       %",
+            "VK generation        250ms   12.5%",
+            "Proof generation      1.5s   75.0%",
+        ]
+    );
+}
+
+#[test]
+fn test_session_record_masks_secrets_in_errors() {
+    let error = color_eyre::eyre::Report::new(BargoError::CommandFailed {
+        tool: "forge".to_string(),
+        code: Some(1),
+        stdout: String::new(),
+        stderr: String::new(),
+    })
+    .wrap_err("Command execution failed: forge create --private-key 0xsecret");
+    let record = SessionRecord::finish("evm deploy", None, None, Some(&error), &Timer::start());
+
+    let error = record.error.unwrap();
+    assert!(!error.contains("0xsecret"), "{error}");
+    assert!(error.contains("--private-key ***"), "{error}");
+    assert_eq!(record.exit_code, Some(ExitCode::CommandFailed.code()));
+}
+
+#[test]
+fn test_session_records_append_and_render() {
+    use std::time::Duration;
+
+    let temp_dir = TempDir::new().unwrap();
+    let log = temp_dir.path().join("logs/session.jsonl");
+
+    let mut summary = OperationSummary::new();
+    summary.add_operation("Proof generated");
+    summary.add_timed_operation("Proof generation", Duration::from_millis(1500));
+    let mut proved = SessionRecord::finish(
+        "evm prove",
+        Some("demo".to_string()),
+        Some("evm".to_string()),
+        None,
+        &Timer::start(),
+    );
+    proved.duration_ms = 2000;
+    assert_eq!(proved.operations, ["Proof generated"]);
+    assert_eq!(proved.steps[0].duration_ms, 1500);
+    append_session_record(&log, &proved).unwrap();
+
+    // Each record only holds what was summarized since the previous one
+    let error = color_eyre::eyre::eyre!("bb verify rejected the proof");
+    let mut failed = SessionRecord::finish("evm verify", None, None, Some(&error), &Timer::start());
+    failed.duration_ms = 500;
+    assert!(failed.operations.is_empty());
+    assert_eq!(failed.exit_code, Some(1));
+    append_session_record(&log, &failed).unwrap();
+
+    let contents = fs::read_to_string(&log).unwrap();
+    assert_eq!(contents.lines().count(), 2);
+    assert!(contents.contains(r#""status":"failure""#), "{contents}");
+
+    let records = read_session_records(&log).unwrap();
+    assert_eq!(records, [proved.clone(), failed]);
+
+    let lines = format_session_report(&records);
+    assert!(lines[0].starts_with("✅ "), "{lines:?}");
+    assert!(
+        lines[0].ends_with("evm prove (demo, evm) - 2.0s"),
+        "{lines:?}"
+    );
+    assert_eq!(lines[1], "   • Proof generated");
+    assert_eq!(lines[2], "   Proof generation: 1.5s");
+    assert!(lines[3].ends_with("evm verify - 500ms"), "{lines:?}");
+    assert_eq!(lines[4], "   bb verify rejected the proof");
+    assert_eq!(lines[5], "2 commands, 1 failed, total time 2.5s");
+
+    fs::write(&log, "not json\n").unwrap();
+    let error = read_session_records(&log).unwrap_err().to_string();
+    assert!(error.contains("parsing line 1"), "{error}");
+}
+
+#[test]
+fn test_parse_package_name_without_default_member_lists_members() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_project(&temp_dir, "member_pkg");
+    let workspace_toml = temp_dir.path().join("Nargo.toml");
+    fs::write(
+        &workspace_toml,
+        "[workspace]\nmembers = [\"test_project\"]\n",
+    )
+    .unwrap();
+
+    let error = parse_package_name(&workspace_toml).unwrap_err().to_string();
+    assert!(error.contains("is a workspace"));
+    assert!(error.contains("member_pkg"));
+    assert!(error.contains("--pkg"));
+
+    let pkg = "member_pkg".to_string();
+    assert_eq!(
+        get_package_name_in_directory(Some(&pkg), temp_dir.path()).unwrap(),
+        "member_pkg"
+    );
+}
+
+/// Create `path` with a modification time `age_secs` seconds in the past
+fn write_aged(path: &Path, age_secs: u64) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "mock artifact").unwrap();
+    let time = std::time::SystemTime::now() - std::time::Duration::from_secs(age_secs);
+    fs::File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(time)
+        .unwrap();
+}
+
+#[test]
+fn test_needs_reprove_compares_proof_and_build_times() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "test_pkg");
+    set_project_dir(Some(project_dir.clone()));
+    let target = |path: &str| project_dir.join("target").join(path);
+
+    // Nothing proved yet
+    write_aged(&target("bb/test_pkg.json"), 300);
+    write_aged(&target("bb/test_pkg.gz"), 200);
+    assert!(needs_reprove("test_pkg", Flavour::Evm).unwrap());
+
+    // Proof newer than the witness; the cached vk only has to beat the bytecode
+    write_aged(&target("evm/vk"), 250);
+    write_aged(&target("evm/proof"), 100);
+    write_aged(&target("evm/public_inputs"), 100);
+    assert!(!needs_reprove("test_pkg", Flavour::Evm).unwrap());
+
+    // A compressed proof counts as present
+    fs::rename(target("evm/proof"), target("evm/proof.gz")).unwrap();
+    assert!(!needs_reprove("test_pkg", Flavour::Evm).unwrap());
+
+    // A new witness makes the proof stale
+    write_aged(&target("bb/test_pkg.gz"), 50);
+    assert!(needs_reprove("test_pkg", Flavour::Evm).unwrap());
+
+    // New bytecode makes the vk stale even when the proof is newer
+    write_aged(&target("bb/test_pkg.gz"), 200);
+    write_aged(&target("bb/test_pkg.json"), 150);
+    write_aged(&target("evm/proof.gz"), 100);
+    assert!(needs_reprove("test_pkg", Flavour::Evm).unwrap());
+
+    // Other flavours are tracked separately
+    assert!(needs_reprove("test_pkg", Flavour::Starknet).unwrap());
+
+    set_project_dir(None);
+}
+
+#[test]
+fn test_parse_age_accepts_each_unit() {
+    use std::time::Duration;
+
+    assert_eq!(parse_age("45s"), Ok(Duration::from_secs(45)));
+    assert_eq!(parse_age("30m"), Ok(Duration::from_secs(30 * 60)));
+    assert_eq!(parse_age("12h"), Ok(Duration::from_secs(12 * 3600)));
+    assert_eq!(parse_age("7d"), Ok(Duration::from_secs(7 * 86_400)));
+
+    for invalid in ["", "7", "d", "7w", "-1d", "1.5h", "7dé"] {
+        assert!(parse_age(invalid).is_err(), "{invalid} should be rejected");
+    }
+
+    assert_eq!(format_age(Duration::from_secs(7 * 86_400)), "7d");
+    assert_eq!(format_age(Duration::from_secs(90 * 60)), "90m");
+    assert_eq!(format_age(Duration::ZERO), "0s");
+}
+
+#[test]
+fn test_exit_code_follows_structured_error() {
+    let wrapped = color_eyre::eyre::Report::new(BargoError::Timeout {
+        tool: "bb".to_string(),
+        seconds: 5,
+    })
+    .wrap_err("Failed to prove");
+    assert_eq!(ExitCode::for_error(&wrapped), ExitCode::Timeout);
+
+    let enhanced = enhance_error_with_suggestions(BargoError::ProjectNotFound.into());
+    assert_eq!(ExitCode::for_error(&enhanced).code(), 7);
+
+    let stale = BargoError::StaleArtifacts { reasons: vec![] };
+    assert_eq!(ExitCode::for_bargo_error(&stale), ExitCode::ArtifactMissing);
+
+    let reported = enhance_error_with_suggestions(
+        BargoError::Reported {
+            code: ExitCode::Incomplete,
+        }
+        .into(),
+    );
+    assert_eq!(ExitCode::for_error(&reported), ExitCode::Incomplete);
+
+    let unstructured = color_eyre::eyre::eyre!("something else");
+    assert_eq!(ExitCode::for_error(&unstructured), ExitCode::Failure);
+}
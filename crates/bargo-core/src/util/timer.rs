@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// Timer for tracking operation duration
 pub struct Timer {
     start: std::time::Instant,
@@ -13,11 +15,61 @@ impl Timer {
 
     /// Get elapsed time as a formatted string
     pub fn elapsed(&self) -> String {
-        let duration = self.start.elapsed();
-        if duration.as_secs() > 0 {
-            format!("{:.1}s", duration.as_secs_f64())
-        } else {
-            format!("{}ms", duration.as_millis())
-        }
+        format_duration(self.start.elapsed())
+    }
+
+    /// Get elapsed time as a raw duration
+    pub fn duration(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Get elapsed time in whole milliseconds
+    pub fn elapsed_ms(&self) -> u128 {
+        self.start.elapsed().as_millis()
+    }
+}
+
+/// Format a duration as `1.2s`, or `340ms` when under a second
+pub fn format_duration(duration: Duration) -> String {
+    if duration.as_secs() > 0 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else {
+        format!("{}ms", duration.as_millis())
     }
 }
+
+/// Units accepted by [`parse_age`], largest first
+const AGE_UNITS: [(char, u64); 4] = [('d', 86_400), ('h', 3_600), ('m', 60), ('s', 1)];
+
+/// Parse an age such as `7d`, `12h`, `30m` or `45s`
+///
+/// Errors are plain strings so the function can be used as a clap value parser.
+pub fn parse_age(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let invalid =
+        || format!("invalid age '{text}', expected a number followed by s, m, h or d (e.g. 7d)");
+
+    let unit = text.chars().last().ok_or_else(invalid)?;
+    let (_, seconds) = AGE_UNITS
+        .iter()
+        .find(|(suffix, _)| *suffix == unit)
+        .ok_or_else(invalid)?;
+    let count: u64 = text[..text.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+
+    count
+        .checked_mul(*seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(invalid)
+}
+
+/// Format an age in the largest unit that divides it exactly (`7d`, `90m`)
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    let (suffix, seconds) = AGE_UNITS
+        .iter()
+        .find(|(_, seconds)| secs > 0 && secs.is_multiple_of(*seconds))
+        .unwrap_or(&('s', 1));
+    format!("{}{suffix}", secs / seconds)
+}
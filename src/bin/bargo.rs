@@ -1,5 +1,5 @@
-use color_eyre::Result;
+use std::process::ExitCode;
 
-fn main() -> Result<()> {
-    bargo_core::run()
+fn main() -> ExitCode {
+    bargo_core::main()
 }
@@ -240,3 +240,41 @@ fn cairo_deploy_auto_declare_vs_no_declare_output_differs() {
         "No-declare output should contain deploy step"
     );
 }
+
+#[test]
+fn cairo_deploy_dry_run_shows_declare_commands() {
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args(["--dry-run", "--pkg", "test_pkg", "cairo", "deploy"])
+        .assert()
+        .success()
+        .stdout(contains("scarb build"))
+        .stdout(contains("starkli declare"))
+        .stdout(contains("--rpc $SEPOLIA_RPC_URL"));
+}
+
+#[test]
+fn cairo_deploy_dry_run_shows_constructor_calldata() {
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args([
+            "--dry-run",
+            "--pkg",
+            "test_pkg",
+            "cairo",
+            "deploy",
+            "--class-hash",
+            "0x123456789abcdef",
+            "--no-declare",
+            "--constructor-calldata",
+            "0x1",
+            "0x2",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("starkli deploy 0x123456789abcdef 0x1 0x2"));
+}
@@ -334,18 +334,19 @@ fn test_verbose_flag() {
     assert!(normal_output.status.success());
     assert!(verbose_output.status.success());
 
-    let normal_stdout = String::from_utf8_lossy(&normal_output.stdout);
-    let verbose_stdout = String::from_utf8_lossy(&verbose_output.stdout);
+    // Log events go to stderr so stdout stays clean for --json
+    let normal_stderr = String::from_utf8_lossy(&normal_output.stderr);
+    let verbose_stderr = String::from_utf8_lossy(&verbose_output.stderr);
 
     // Verbose output should contain more information
     assert!(
-        verbose_stdout.len() >= normal_stdout.len(),
+        verbose_stderr.len() >= normal_stderr.len(),
         "Verbose output should be at least as long as normal output"
     );
 
     // Verbose should show command execution details
     assert!(
-        verbose_stdout.contains("Running:") || verbose_stdout.contains("Executing:"),
+        verbose_stderr.contains("Running:") || verbose_stderr.contains("Executing:"),
         "Verbose output should show command execution details"
     );
 }
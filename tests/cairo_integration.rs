@@ -5,78 +5,26 @@
 //! focusing on the prove, verify, and generate workflows.
 
 use assert_fs::TempDir;
-use bargo_core::config::Config;
+use bargo_core::cli::{ArtifactSuffix, ColorChoice, GaragaSystem};
+use bargo_core::commands::keystore::Keystore;
+use bargo_core::config::{
+    CalldataConfig, Config, GaragaConfig, ProveConfig, ToolPaths, VerifyConfig,
+};
+use bargo_core::fs::DryRunFs;
 use bargo_core::runner::DryRunRunner;
 use path_slash::PathExt;
 
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
-
-// Global lock to prevent concurrent directory operations across all tests
-static DIRECTORY_LOCK: Mutex<()> = Mutex::new(());
-
-/// Temporary helper functions for Cairo commands until working directory API is implemented
-fn run_cairo_prove_in_directory(
-    config: &Config,
-    project_dir: &Path,
-) -> Result<(), color_eyre::eyre::Error> {
-    // Use global lock to prevent race conditions
-    let _lock = DIRECTORY_LOCK.lock().unwrap();
-
-    // Validate project directory exists before proceeding
-    if !project_dir.exists() {
-        return Err(color_eyre::eyre::eyre!(
-            "Project directory does not exist: {}",
-            project_dir.display()
-        ));
-    }
-
-    // Get current directory before changing it
-    let original_dir = std::env::current_dir()
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to get current directory: {}", e))?;
-
-    // Change to project directory
-    std::env::set_current_dir(project_dir)
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to change to project directory: {}", e))?;
-
-    let result = bargo_core::commands::cairo::run_prove(config);
-
-    // Always restore directory, even on error
-    let _ = std::env::set_current_dir(original_dir);
-
-    result
-}
-
-fn run_cairo_gen_in_directory(
-    config: &Config,
-    project_dir: &Path,
-) -> Result<(), color_eyre::eyre::Error> {
-    // Use global lock to prevent race conditions
-    let _lock = DIRECTORY_LOCK.lock().unwrap();
-
-    // Validate project directory exists before proceeding
-    if !project_dir.exists() {
-        return Err(color_eyre::eyre::eyre!(
-            "Project directory does not exist: {}",
-            project_dir.display()
-        ));
-    }
-
-    // Get current directory before changing it
-    let original_dir = std::env::current_dir()
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to get current directory: {}", e))?;
 
-    // Change to project directory
-    std::env::set_current_dir(project_dir)
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to change to project directory: {}", e))?;
-
-    let result = bargo_core::commands::cairo::run_gen(config);
-
-    // Always restore directory, even on error
-    let _ = std::env::set_current_dir(original_dir);
-
-    result
+/// Point `config` at `project_dir` and apply it to this thread's path helpers
+fn for_package(config: &Config, project_dir: &Path) -> Config {
+    let config = Config {
+        package_dir: Some(project_dir.to_path_buf()),
+        ..config.clone()
+    };
+    config.apply_paths().unwrap();
+    config
 }
 
 /// Copy a fixture directory to a temporary location
@@ -138,15 +86,27 @@ fn test_cairo_prove_command_dry_run() {
     // Create DryRunRunner and config
     let dry_runner = std::sync::Arc::new(DryRunRunner::new());
     let config = Config {
-        verbose: false,
+        verbose: 0,
         dry_run: true,
         pkg: None,
         quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
     };
 
     // Run cairo prove command in the project directory using working directory API
-    let result = run_cairo_prove_in_directory(&config, &project_dir);
+    let result = bargo_core::commands::cairo::run_prove(&for_package(&config, &project_dir));
 
     // The command should succeed in dry run mode
     assert!(
@@ -212,14 +172,26 @@ fn test_cairo_prove_with_package_override() {
 
     let dry_runner = std::sync::Arc::new(DryRunRunner::new());
     let config = Config {
-        verbose: false,
+        verbose: 0,
         dry_run: true,
         pkg: Some("custom_package".to_string()),
         quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
     };
 
-    let result = run_cairo_prove_in_directory(&config, &project_dir);
+    let result = bargo_core::commands::cairo::run_prove(&for_package(&config, &project_dir));
 
     assert!(
         result.is_ok(),
@@ -253,14 +225,26 @@ fn test_cairo_prove_verbose_mode() {
 
     let dry_runner = std::sync::Arc::new(DryRunRunner::new());
     let config = Config {
-        verbose: true,
+        verbose: 1,
         dry_run: true,
         pkg: None,
         quiet: false,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
     };
 
-    let result = run_cairo_prove_in_directory(&config, &project_dir);
+    let result = bargo_core::commands::cairo::run_prove(&for_package(&config, &project_dir));
 
     assert!(
         result.is_ok(),
@@ -288,15 +272,27 @@ fn test_cairo_gen_command_dry_run() {
 
     let dry_runner = std::sync::Arc::new(DryRunRunner::new());
     let config = Config {
-        verbose: false,
+        verbose: 0,
         dry_run: true,
         pkg: None,
         quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
     };
 
     // Test cairo gen command using working directory API
-    let result = run_cairo_gen_in_directory(&config, &project_dir);
+    let result = bargo_core::commands::cairo::run_gen(&for_package(&config, &project_dir));
 
     // Should succeed or gracefully handle missing dependencies
     if result.is_ok() {
@@ -332,6 +328,130 @@ fn test_cairo_gen_command_dry_run() {
     }
 }
 
+#[test]
+fn test_cairo_gen_no_prove_runs_only_garaga() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = copy_fixture_to_temp("simple_circuit", &temp_dir);
+
+    let dry_runner = std::sync::Arc::new(DryRunRunner::new());
+    let config = Config {
+        verbose: 0,
+        dry_run: true,
+        pkg: None,
+        quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
+        runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
+    };
+
+    let result = bargo_core::commands::cairo::run_gen_with_config(
+        &for_package(&config, &project_dir),
+        &GaragaConfig::default(),
+        &ProveConfig::default().with_no_prove(true),
+    );
+    assert!(result.is_ok(), "gen failed: {:?}", result.err());
+
+    let history = dry_runner.history();
+    assert!(history.iter().all(|(spec, _)| spec.cmd != "bb"));
+    let garaga = history
+        .iter()
+        .find(|(spec, _)| spec.cmd == "garaga")
+        .expect("garaga gen should be recorded");
+    assert_eq!(garaga.0.args[0], "gen");
+}
+
+#[test]
+fn test_cairo_gen_moves_project_to_output_dir() {
+    use bargo_core::fs::FsOp;
+
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = copy_fixture_to_temp("simple_circuit", &temp_dir);
+
+    let dry_runner = std::sync::Arc::new(DryRunRunner::new());
+    let dry_fs = std::sync::Arc::new(DryRunFs::new());
+    let config = Config {
+        verbose: 0,
+        dry_run: true,
+        pkg: None,
+        quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
+        runner: dry_runner.clone(),
+        fs: dry_fs.clone(),
+    };
+    let config = for_package(&config, &project_dir);
+    let gen_into = |output_dir: &str, overwrite: bool| {
+        bargo_core::commands::cairo::run_gen_with_config(
+            &config,
+            &GaragaConfig::default()
+                .with_output_dir(Some(PathBuf::from(output_dir)))
+                .with_overwrite(overwrite),
+            &ProveConfig::default().with_no_prove(true),
+        )
+    };
+
+    gen_into("starknet/verifier", false).unwrap();
+    assert_eq!(
+        dry_fs.history(),
+        [
+            FsOp::CreateDir(project_dir.join("starknet")),
+            FsOp::Rename {
+                from: project_dir.join("cairo_verifier"),
+                to: project_dir.join("starknet/verifier"),
+            },
+        ]
+    );
+
+    // A directory garaga did not generate is only replaced with --force
+    let garaga_runs = || {
+        dry_runner
+            .history()
+            .iter()
+            .filter(|(spec, _)| spec.cmd == "garaga")
+            .count()
+    };
+    fs::create_dir_all(project_dir.join("existing")).unwrap();
+    fs::write(
+        project_dir.join("existing/Scarb.toml"),
+        "[package]\nname = \"my_contracts\"\n",
+    )
+    .unwrap();
+    let error = gen_into("existing", false).unwrap_err();
+    assert!(
+        error.to_string().contains("not generated by bargo"),
+        "{error}"
+    );
+    assert_eq!(garaga_runs(), 1);
+    gen_into("existing", true).unwrap();
+    assert_eq!(garaga_runs(), 2);
+
+    fs::write(
+        project_dir.join("existing/Scarb.toml"),
+        "[package]\nname = \"cairo_verifier\"\n",
+    )
+    .unwrap();
+    gen_into("existing", false).unwrap();
+}
+
 #[test]
 fn test_cairo_workflow_file_path_normalization() {
     // Test that file paths are handled correctly across platforms
@@ -342,14 +462,26 @@ fn test_cairo_workflow_file_path_normalization() {
 
     let dry_runner = std::sync::Arc::new(DryRunRunner::new());
     let config = Config {
-        verbose: false,
+        verbose: 0,
         dry_run: true,
         pkg: None,
         quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
     };
 
-    let result = run_cairo_prove_in_directory(&config, &project_dir);
+    let result = bargo_core::commands::cairo::run_prove(&for_package(&config, &project_dir));
 
     assert!(result.is_ok(), "Cairo prove failed: {:?}", result.err());
 
@@ -405,14 +537,26 @@ fn test_cairo_commands_require_build_artifacts() {
 
     let dry_runner = std::sync::Arc::new(DryRunRunner::new());
     let config = Config {
-        verbose: false,
+        verbose: 0,
         dry_run: true,
         pkg: None,
         quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
     };
 
-    let result = run_cairo_prove_in_directory(&config, &project_dir);
+    let result = bargo_core::commands::cairo::run_prove(&for_package(&config, &project_dir));
 
     // Should fail or provide helpful error about missing build artifacts
     if result.is_err() {
@@ -436,3 +580,296 @@ fn test_cairo_commands_require_build_artifacts() {
         );
     }
 }
+
+#[test]
+fn test_cairo_calldata_output_dry_run() {
+    let dry_runner = std::sync::Arc::new(DryRunRunner::new());
+    let config = Config {
+        verbose: 0,
+        dry_run: true,
+        pkg: None,
+        quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
+        runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
+    };
+    let calldata_cfg = CalldataConfig::new(Some(PathBuf::from("exports/calldata.json")));
+
+    let result = bargo_core::commands::cairo::run_calldata_with_config(
+        &config,
+        &calldata_cfg,
+        &GaragaConfig::default(),
+    );
+    assert!(result.is_ok(), "Cairo calldata failed: {:?}", result.err());
+
+    // garaga prints calldata to stdout; bargo writes it to the output path
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 1);
+    let (spec, output) = &history[0];
+    assert_eq!(spec.cmd, "garaga");
+    assert_eq!(spec.args[0], "calldata");
+    assert!(spec.args.contains(&"target/starknet/proof".to_string()));
+    assert_eq!(spec.args[1..3], ["--system", "ultra_starknet_zk_honk"]);
+    assert!(output.as_deref().unwrap().contains("calldata"));
+    assert!(!Path::new("exports/calldata.json").exists());
+}
+
+#[test]
+fn test_cairo_verify_uses_overridden_paths() {
+    let dry_runner = std::sync::Arc::new(DryRunRunner::new());
+    let config = Config {
+        verbose: 0,
+        dry_run: true,
+        pkg: Some("test_pkg".to_string()),
+        quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
+        runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
+    };
+    let verify_cfg = VerifyConfig::new(
+        Some(PathBuf::from("elsewhere/proof")),
+        None,
+        Some(PathBuf::from("elsewhere/public_inputs")),
+    );
+
+    let result = bargo_core::commands::cairo::run_verify_with_config(&config, &verify_cfg);
+    assert!(result.is_ok(), "Cairo verify failed: {:?}", result.err());
+
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 1);
+    let args = &history[0].0.args;
+    assert_eq!(args[0], "verify");
+    let value_of = |flag: &str| {
+        let index = args.iter().position(|arg| arg == flag).unwrap();
+        args[index + 1].as_str()
+    };
+    assert_eq!(value_of("-p"), "elsewhere/proof");
+    assert_eq!(value_of("-k"), "target/starknet/vk");
+    assert_eq!(value_of("-i"), "elsewhere/public_inputs");
+}
+
+#[test]
+fn test_cairo_verify_onchain_composes_sncast_call() {
+    let temp_dir = TempDir::new().unwrap();
+    let starknet_dir = temp_dir.path().join("target/starknet");
+    fs::create_dir_all(&starknet_dir).unwrap();
+    fs::write(starknet_dir.join(".bargo_contract_address"), "0x0ad0e55\n").unwrap();
+    fs::write(starknet_dir.join("calldata.json"), "3 0x1 0x2 0x3\n").unwrap();
+
+    let dry_runner = std::sync::Arc::new(DryRunRunner::new());
+    let config = Config {
+        verbose: 0,
+        dry_run: true,
+        pkg: Some("test_pkg".to_string()),
+        quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: Some("mainnet".to_string()),
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
+        runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
+    };
+
+    let result = bargo_core::commands::cairo::run_verify_onchain_with_config(
+        &for_package(&config, temp_dir.path()),
+        None,
+        &GaragaConfig::new(GaragaSystem::UltraStarknetHonk),
+    );
+    assert!(result.is_ok(), "verify-onchain failed: {:?}", result.err());
+
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 1);
+    let (spec, output) = &history[0];
+    assert_eq!(spec.cmd, "sncast");
+    assert_eq!(spec.args[0], "call");
+    let value_of = |flag: &str| {
+        let index = spec.args.iter().position(|arg| arg == flag).unwrap();
+        spec.args[index + 1].as_str()
+    };
+    assert_eq!(value_of("--contract-address"), "0x0ad0e55");
+    assert_eq!(value_of("--function"), "verify_ultra_starknet_honk_proof");
+    assert!(spec.args.ends_with(&[
+        "--calldata".to_string(),
+        "3".to_string(),
+        "0x1".to_string(),
+        "0x2".to_string(),
+        "0x3".to_string(),
+    ]));
+    assert!(spec.retryable);
+    assert!(output.as_deref().unwrap().contains("response: [0x0"));
+}
+
+#[test]
+fn test_cairo_verify_onchain_dry_run_without_artifacts() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let dry_runner = std::sync::Arc::new(DryRunRunner::new());
+    let config = Config {
+        verbose: 0,
+        dry_run: true,
+        pkg: Some("test_pkg".to_string()),
+        quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
+        runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
+    };
+
+    let result = bargo_core::commands::cairo::run_verify_onchain(
+        &for_package(&config, temp_dir.path()),
+        None,
+    );
+    assert!(result.is_ok(), "verify-onchain failed: {:?}", result.err());
+
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 1);
+    let args = &history[0].0.args;
+    assert!(args.contains(&"<contract_address>".to_string()));
+    assert!(args.contains(&"verify_ultra_starknet_zk_honk_proof".to_string()));
+    assert_eq!(args.last().unwrap(), "<calldata>");
+}
+
+#[test]
+fn test_cairo_deploy_with_keystore_composes_starkli_flags() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let dry_runner = std::sync::Arc::new(DryRunRunner::new());
+    let config = Config {
+        verbose: 0,
+        dry_run: true,
+        pkg: Some("test_pkg".to_string()),
+        quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
+        runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
+    };
+
+    let keystore_path = PathBuf::from("wallets/deployer/keystore.json");
+    let keystore = Keystore::placeholder(keystore_path.clone());
+    let result = bargo_core::commands::cairo::run_deploy(
+        &for_package(&config, temp_dir.path()),
+        "sepolia",
+        Some("0x0c1a55"),
+        &[],
+        Some(&keystore),
+    );
+    assert!(result.is_ok(), "deploy failed: {:?}", result.err());
+
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 1);
+    let spec = &history[0].0;
+    assert_eq!(spec.cmd, "starkli");
+    let index = spec
+        .args
+        .iter()
+        .position(|arg| arg == "--keystore")
+        .unwrap();
+    assert_eq!(spec.args[index + 1], keystore_path.to_string_lossy());
+
+    // Neither a private key nor a password is handed to starkli in dry-run
+    assert!(spec.env.is_empty());
+}
+
+#[test]
+fn test_cairo_deploy_appends_constructor_args_to_starkli_deploy() {
+    use bargo_core::backend::{BackendConfig, BackendKind, backend_for};
+    use bargo_core::config::CairoDeployConfig;
+
+    let temp_dir = TempDir::new().unwrap();
+
+    let dry_runner = std::sync::Arc::new(DryRunRunner::new());
+    let config = Config {
+        verbose: 0,
+        dry_run: true,
+        pkg: Some("test_pkg".to_string()),
+        quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
+        runner: dry_runner.clone(),
+        fs: std::sync::Arc::new(DryRunFs::new()),
+    };
+    let config = for_package(&config, temp_dir.path());
+
+    let deploy = |calldata: &[&str]| {
+        let mut backend = backend_for(BackendKind::Cairo);
+        let deploy_config = CairoDeployConfig::new(Some("0x0c1a55".to_string()), false, true)
+            .with_constructor_calldata(calldata.iter().map(|felt| felt.to_string()).collect());
+        backend
+            .configure(BackendConfig::CairoDeploy(deploy_config))
+            .unwrap();
+        backend.deploy(&config, Some("sepolia"))
+    };
+
+    let result = deploy(&["7", "0x2a"]);
+    assert!(result.is_ok(), "deploy failed: {:?}", result.err());
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].0.cmd, "starkli");
+    assert_eq!(history[0].0.args[..4], ["deploy", "0x0c1a55", "7", "0x2a"]);
+
+    // Values that are not felts fail before anything is declared or deployed
+    for invalid in [
+        "0xnothex",
+        "-1",
+        "0x800000000000011000000000000000000000000000000000000000000000001",
+    ] {
+        let error = deploy(&["1", invalid]).unwrap_err().to_string();
+        assert!(error.contains(&format!("'{invalid}'")), "{error}");
+    }
+    assert_eq!(dry_runner.history().len(), 1);
+}
@@ -29,6 +29,144 @@ fn check_command_dry_run() {
         .success();
 }
 
+#[test]
+fn check_dry_run_records_without_executing_nargo() {
+    use predicates::str::contains;
+
+    // Any attempt to execute nargo would fail on the missing binary
+    for extra in [&[][..], &["--deny-warnings"][..]] {
+        Command::cargo_bin("bargo")
+            .unwrap()
+            .env("BARGO_NARGO", "/nonexistent/nargo")
+            .args(["--dry-run", "--pkg", "my_pkg", "check"])
+            .args(extra)
+            .assert()
+            .success()
+            .stdout(contains("/nonexistent/nargo check --package my_pkg"));
+    }
+}
+
+#[test]
+fn no_banner_suppresses_banner_but_not_output() {
+    use predicates::prelude::*;
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"banner_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    let banner = contains("CHECKING CIRCUIT SYNTAX");
+    let bargo = || {
+        let mut command = Command::cargo_bin("bargo").unwrap();
+        command.current_dir(temp_dir.path());
+        command
+    };
+
+    bargo()
+        .args(["--dry-run", "check"])
+        .assert()
+        .success()
+        .stdout(banner.clone());
+    bargo()
+        .args(["--dry-run", "--no-banner", "check"])
+        .assert()
+        .success()
+        .stdout(banner.clone().not())
+        .stdout(contains("nargo check"));
+
+    std::fs::write(
+        temp_dir.path().join("bargo.toml"),
+        "[ui]\nbanners = false\n",
+    )
+    .unwrap();
+    bargo()
+        .args(["--dry-run", "check"])
+        .assert()
+        .success()
+        .stdout(banner.not());
+}
+
+#[test]
+fn env_file_replaces_project_dotenv() {
+    use predicates::prelude::*;
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"env_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    std::fs::write(temp_dir.path().join(".env"), "CONTRACT_ADDRESS=0xdefault\n").unwrap();
+    std::fs::write(
+        temp_dir.path().join("staging.env"),
+        "CONTRACT_ADDRESS=0xstaging\n",
+    )
+    .unwrap();
+    let bargo = || {
+        let mut command = Command::cargo_bin("bargo").unwrap();
+        command
+            .current_dir(temp_dir.path())
+            .env_remove("CONTRACT_ADDRESS");
+        command
+    };
+
+    // Doctor fails without the tools installed, but still reports the variables
+    bargo()
+        .args(["--env-file", "staging.env", "doctor", "--env"])
+        .assert()
+        .stdout(contains("CONTRACT_ADDRESS = 0xstaging"))
+        .stdout(contains("0xdefault").not());
+
+    bargo()
+        .args(["--env-file", "missing.env", "--dry-run", "check"])
+        .assert()
+        .failure()
+        .stderr(contains("Env file not found: missing.env"));
+}
+
+#[test]
+fn exit_code_distinguishes_missing_project_from_missing_tool() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+
+    // No Nargo.toml anywhere above the temp dir
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .arg("info")
+        .assert()
+        .code(7)
+        .stderr(contains("Could not find Nargo.toml"));
+
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"exit_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    let missing_nargo = || {
+        let mut command = Command::cargo_bin("bargo").unwrap();
+        command
+            .current_dir(temp_dir.path())
+            .env("BARGO_NARGO", "/nonexistent/nargo");
+        command
+    };
+    missing_nargo()
+        .arg("check")
+        .assert()
+        .code(3)
+        .stderr(contains("Tool '/nonexistent/nargo' not found in PATH"));
+    // --json reports the error on stdout but keeps the exit code
+    missing_nargo()
+        .args(["--json", "check"])
+        .assert()
+        .code(3)
+        .stdout(contains("\"error\""));
+}
+
 #[test]
 fn check_command_pkg_flag_propagated() {
     use predicates::str::contains;
@@ -125,6 +263,41 @@ fn cairo_gen_pkg_flag_propagated() {
         .success();
 }
 
+#[cfg(feature = "cairo")]
+#[test]
+fn cairo_gen_system_flag_is_passed_to_garaga() {
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args([
+            "--dry-run",
+            "--pkg",
+            "test_pkg",
+            "cairo",
+            "gen",
+            "--system",
+            "ultra_starknet_honk",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("garaga gen --system ultra_starknet_honk"));
+}
+
+#[cfg(feature = "cairo")]
+#[test]
+fn cairo_calldata_rejects_unknown_system() {
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args(["--dry-run", "cairo", "calldata", "--system", "groth16"])
+        .assert()
+        .failure()
+        .stderr(contains("ultra_starknet_zk_honk"))
+        .stderr(contains("ultra_keccak_honk"));
+}
+
 #[test]
 fn evm_gen_dry_run() {
     // Test that EVM gen command works through the new BackendTrait system
@@ -203,6 +376,48 @@ fn cairo_calldata_through_trait_system() {
         .success();
 }
 
+#[cfg(feature = "cairo")]
+#[test]
+fn cairo_calldata_output_is_reported() {
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args([
+            "--dry-run",
+            "--pkg",
+            "test_pkg",
+            "cairo",
+            "calldata",
+            "--output",
+            "exports/calldata.json",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("garaga calldata"))
+        .stdout(contains("Would write calldata to: exports/calldata.json"));
+
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .args([
+            "--dry-run",
+            "--json",
+            "--pkg",
+            "test_pkg",
+            "cairo",
+            "calldata",
+            "--output",
+            "exports/calldata.json",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let report: serde_json::Value =
+        serde_json::from_str(String::from_utf8(output.stdout).unwrap().trim()).unwrap();
+    assert_eq!(report["artifacts"][0]["path"], "exports/calldata.json");
+}
+
 // Note: cairo deploy test skipped due to workflow validation issues
 // The underlying workflow checks for Cairo contract directory before dry-run mode
 
@@ -236,6 +451,144 @@ fn evm_prove_through_trait_system() {
         .success();
 }
 
+#[test]
+fn prove_defaults_to_bb_backend() {
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args(["--dry-run", "--pkg", "test_pkg", "prove"])
+        .assert()
+        .success()
+        .stdout(contains("bb prove"))
+        .stdout(contains("-t evm"));
+}
+
+#[cfg(unix)]
+#[test]
+fn build_stats_reports_gate_count_from_bb() {
+    use predicates::str::contains;
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let root = temp_dir.path();
+    std::fs::write(
+        root.join("Nargo.toml"),
+        "[package]\nname = \"counted\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    let script = |name: &str, body: &str| {
+        let path = root.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    };
+    // Stand-ins for nargo writing its artifacts and bb reporting gates
+    let nargo = script(
+        "fake-nargo",
+        "mkdir -p target && printf bytecode > target/counted.json && printf w > target/counted.gz",
+    );
+    let bb = script(
+        "fake-bb",
+        r#"echo '{"functions": [{"acir_opcodes": 4, "circuit_size": 1234}]}'"#,
+    );
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(root)
+        .env("BARGO_NARGO", &nargo)
+        .env("BARGO_BB", &bb)
+        .args(["build", "--stats"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "📊 Circuit: 8 B bytecode, 4 ACIR opcodes, 1234 gates",
+        ));
+
+    let manifest: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(root.join("target/bb/manifest.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        manifest["circuit"],
+        serde_json::json!({ "bytecode_size": 8, "acir_opcodes": 4, "gates": 1234 })
+    );
+
+    // A bb without `gates` still gets a successful build and the bytecode size
+    let old_bb = script("old-bb", "echo 'Unknown command: gates' >&2; exit 1");
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(root)
+        .env("BARGO_NARGO", &nargo)
+        .env("BARGO_BB", &old_bb)
+        .args(["build", "--stats"])
+        .assert()
+        .success()
+        .stdout(contains("Build is up to date"))
+        .stdout(contains("8 B bytecode, gate count unavailable"));
+}
+
+#[test]
+fn prove_profile_builds_with_its_inputs_first() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"profiled\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("bargo.toml"),
+        "[inputs]\nhappy = \"inputs/happy.toml\"\n",
+    )
+    .unwrap();
+
+    let prover_name = temp_dir.path().join("inputs/happy");
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "prove", "--profile", "happy"])
+        .assert()
+        .success()
+        .stdout(contains(format!(
+            "nargo execute --prover-name {}",
+            prover_name.display()
+        )))
+        .stdout(contains("bb prove"));
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "prove", "--profile", "sad"])
+        .assert()
+        .failure()
+        .stderr(contains("Input profile 'sad' not found in bargo.toml"))
+        .stderr(contains("Available profiles: happy"));
+}
+
+#[cfg(feature = "cairo")]
+#[test]
+fn prove_dispatches_to_starknet_backend() {
+    use predicates::prelude::PredicateBooleanExt;
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args([
+            "--dry-run",
+            "--pkg",
+            "test_pkg",
+            "prove",
+            "--backend",
+            "starknet",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("--oracle_hash starknet"))
+        .stdout(contains("-t evm").not());
+}
+
 #[test]
 fn evm_verify_through_trait_system() {
     // Test that EVM verify works through the trait system
@@ -256,11 +609,201 @@ fn evm_calldata_through_trait_system() {
         .success();
 }
 
-// Note: evm deploy test skipped due to workflow validation issues
-// The underlying workflow checks for PRIVATE_KEY env var before dry-run mode
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn evm_deploy_dry_run_shows_gas_estimate() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+
+    // The workflow reads RPC_URL and PRIVATE_KEY even in dry-run mode
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("RPC_URL", "http://localhost:8545")
+        .env("PRIVATE_KEY", "0xkey")
+        .args(["--dry-run", "evm", "deploy", "--max-gas", "1"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "forge inspect contracts/evm/src/Verifier.sol:Verifier bytecode",
+        ))
+        .stdout(contains(
+            "cast estimate --rpc-url http://localhost:8545 --create",
+        ))
+        .stdout(contains("Would deploy Verifier contract"));
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn evm_deploy_forwards_constructor_args_to_forge_create() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("RPC_URL", "http://localhost:8545")
+        .env("PRIVATE_KEY", "0xkey")
+        .args([
+            "--dry-run",
+            "evm",
+            "deploy",
+            "--constructor-args",
+            "42",
+            "0xabc",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("--json --constructor-args 42 0xabc"));
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn evm_deploy_network_typo_fails_fast() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "evm", "deploy", "--network", "seploia"])
+        .assert()
+        .failure()
+        .stderr(contains("Unsupported network: seploia"))
+        .stderr(contains("Did you mean 'sepolia'?"));
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn evm_deploy_to_mainnet_requires_confirmation() {
+    use predicates::{prelude::PredicateBooleanExt, str::contains};
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+
+    // Without a terminal there is nobody to type the network name
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["evm", "deploy", "--network", "mainnet"])
+        .assert()
+        .failure()
+        .stderr(contains(
+            "Refusing to deploy the verifier contract on mainnet without confirmation",
+        ))
+        .stderr(contains("--yes"));
+
+    // --yes gets past the guard to the usual deploy checks
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["evm", "deploy", "--network", "mainnet", "--yes"])
+        .assert()
+        .failure()
+        .stderr(contains("without confirmation").not());
+
+    // Dry runs spend nothing and never ask
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("RPC_URL", "http://localhost:8545")
+        .env("PRIVATE_KEY", "0xkey")
+        .args(["--dry-run", "evm", "deploy", "--network", "mainnet"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "Would deploy Verifier contract to network: mainnet",
+        ));
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn evm_deploy_custom_network_uses_its_rpc_url() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("RPC_URL", "http://localhost:8545")
+        .env("PRIVATE_KEY", "0xkey")
+        .args([
+            "--dry-run",
+            "evm",
+            "deploy",
+            "--network",
+            "custom:https://rpc.example.org",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("cast estimate --rpc-url https://rpc.example.org"))
+        .stdout(contains(
+            "Would deploy Verifier contract to network: custom:https://rpc.example.org",
+        ));
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn evm_deploy_dry_run_with_account_uses_keystore() {
+    use predicates::{prelude::PredicateBooleanExt, str::contains};
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("deployer.json"), r#"{"crypto":{}}"#).unwrap();
+
+    // The keystore replaces PRIVATE_KEY, and no password is asked for in dry-run
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("RPC_URL", "http://localhost:8545")
+        .env_remove("PRIVATE_KEY")
+        .args(["--dry-run", "evm", "deploy", "--account", "deployer.json"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "forge create contracts/evm/src/Verifier.sol:Verifier --rpc-url http://localhost:8545 --keystore",
+        ))
+        .stdout(contains("deployer.json --json"))
+        .stdout(contains("--private-key").not());
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn evm_deploy_dry_run_does_not_echo_private_key() {
+    use predicates::{prelude::PredicateBooleanExt, str::contains};
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("RPC_URL", "http://localhost:8545")
+        .env("PRIVATE_KEY", "0xsecretkey")
+        .args(["--dry-run", "evm", "deploy"])
+        .assert()
+        .success()
+        .stdout(contains("--private-key $PRIVATE_KEY"))
+        .stdout(contains("0xsecretkey").not());
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn evm_verify_onchain_dry_run_shows_cast_call() {
+    use predicates::str::contains;
 
-// Note: evm verify-onchain test skipped due to workflow validation issues
-// The underlying workflow checks for RPC_URL env var before dry-run mode
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "--pkg", "test_pkg", "evm", "verify-onchain"])
+        .assert()
+        .success()
+        .stdout(contains("cast call"))
+        .stdout(contains("verify(bytes,bytes32[])"));
+}
 
 #[test]
 fn all_trait_workflows_preserve_pkg_flag() {
@@ -294,3 +837,1051 @@ fn all_trait_workflows_preserve_pkg_flag() {
         .assert()
         .success();
 }
+
+#[test]
+fn timeout_flag_is_accepted() {
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args([
+            "--dry-run",
+            "--timeout",
+            "120",
+            "--pkg",
+            "test_pkg",
+            "evm",
+            "prove",
+        ])
+        .assert()
+        .success();
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn retries_flag_is_accepted() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "--retries", "3", "evm", "verify-onchain"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn json_flag_emits_single_report() {
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .args(["--dry-run", "--json", "--pkg", "test_pkg", "evm", "prove"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let report: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(report["operation"], "evm prove");
+    assert_eq!(report["success"], true);
+    assert!(report["elapsed_ms"].is_u64());
+    assert_eq!(report["artifacts"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn json_flag_reports_errors() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--json", "evm", "prove"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let report: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert!(report["error"].as_str().unwrap().contains("Nargo.toml"));
+}
+
+#[test]
+fn prove_rejects_unknown_scheme() {
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args([
+            "--dry-run",
+            "--pkg",
+            "test_pkg",
+            "evm",
+            "prove",
+            "--scheme",
+            "plonk",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("ultra_honk"))
+        .stderr(contains("ultra_keccak_honk"));
+}
+
+#[test]
+fn info_json_reports_project_metadata() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"info_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "--json", "info"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let info: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(info["package_name"], "info_pkg");
+    assert_eq!(info["nargo_toml"], "package");
+    assert_eq!(info["targets"][0]["flavour"], "bb");
+    assert!(info["tools"].is_array());
+}
+
+#[test]
+fn bench_dry_run_shows_prove_commands() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"bench_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "bench", "--iterations", "3"])
+        .assert()
+        .success()
+        .stdout(contains("Would prove 3 time(s) with the EVM backend"))
+        .stdout(contains("bb prove -b target/bb/bench_pkg.json"));
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "bench", "--iterations", "0"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn addresses_lists_recorded_deployments() {
+    use predicates::{prelude::PredicateBooleanExt, str::contains};
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"deployed_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    std::fs::create_dir_all(&evm_dir).unwrap();
+    std::fs::write(
+        evm_dir.join("addresses.json"),
+        r#"{
+  "mainnet": [{"address": "0xmain", "timestamp": "2026-10-01T00:00:00Z", "package": "deployed_pkg"}],
+  "sepolia": [
+    {"address": "0xold", "timestamp": "2026-10-02T00:00:00Z", "package": "deployed_pkg"},
+    {"address": "0xnew", "timestamp": "2026-10-03T00:00:00Z", "package": "deployed_pkg"}
+  ]
+}"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["addresses", "--network", "sepolia"])
+        .assert()
+        .success()
+        .stdout(contains("0xold (deployed_pkg)\n"))
+        .stdout(contains("0xnew (deployed_pkg) (latest)"))
+        .stdout(contains("0xmain").not());
+}
+
+#[test]
+fn convert_round_trips_proof_between_bytes_and_fields() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    std::fs::create_dir_all(&evm_dir).unwrap();
+    let mut proof = vec![0u8; 64];
+    proof[31] = 7;
+    std::fs::write(evm_dir.join("proof"), &proof).unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--json", "convert", "--to", "fields"])
+        .assert()
+        .success();
+    let fields: Vec<String> =
+        serde_json::from_str(&std::fs::read_to_string(evm_dir.join("proof_fields.json")).unwrap())
+            .unwrap();
+    assert_eq!(fields.len(), 2);
+    assert!(fields[0].ends_with("07"), "{fields:?}");
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["convert", "--to", "bytes", "-o", "copy/proof"])
+        .assert()
+        .success();
+    assert_eq!(
+        std::fs::read(temp_dir.path().join("copy/proof")).unwrap(),
+        proof
+    );
+}
+
+#[test]
+fn summary_file_records_each_command_for_report() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"session_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    std::fs::create_dir_all(&evm_dir).unwrap();
+    std::fs::write(evm_dir.join("proof"), [0u8; 32]).unwrap();
+
+    for (args, succeeds) in [
+        (&["convert", "--to", "fields"][..], true),
+        (
+            &["convert", "--to", "bytes", "-i", "missing.json"][..],
+            false,
+        ),
+    ] {
+        let assert = Command::cargo_bin("bargo")
+            .unwrap()
+            .current_dir(temp_dir.path())
+            .args(["--summary-file", "session.jsonl"])
+            .args(args)
+            .assert();
+        if succeeds {
+            assert.success();
+        } else {
+            assert.failure();
+        }
+    }
+
+    let log = std::fs::read_to_string(temp_dir.path().join("session.jsonl")).unwrap();
+    assert_eq!(log.lines().count(), 2, "{log}");
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["report", "session.jsonl"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("convert (session_pkg)"))
+        .stdout(predicates::str::contains("missing.json"))
+        .stdout(predicates::str::contains("2 commands, 1 failed"));
+
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--json", "report", "session.jsonl"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let records: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(records[0]["status"], "success");
+    assert_eq!(records[0]["package"], "session_pkg");
+    assert_eq!(records[1]["status"], "failure");
+
+    // Reports are not recorded in the session they summarize
+    let log = std::fs::read_to_string(temp_dir.path().join("session.jsonl")).unwrap();
+    assert_eq!(log.lines().count(), 2, "{log}");
+}
+
+#[test]
+fn package_dir_resolves_project_outside_cwd() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("circuit");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(
+        project_dir.join("Nargo.toml"),
+        "[package]\nname = \"remote_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--package-dir", "circuit", "--json", "info"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let info: serde_json::Value =
+        serde_json::from_str(String::from_utf8(output.stdout).unwrap().trim()).unwrap();
+    assert_eq!(info["package_name"], "remote_pkg");
+
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--package-dir", "circuit", "--json", "build", "--check"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let report: serde_json::Value =
+        serde_json::from_str(String::from_utf8(output.stdout).unwrap().trim()).unwrap();
+    assert_eq!(report["reasons"][0], "target/bb/remote_pkg.json is missing");
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn working_dir_loads_env_from_the_project() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("circuit");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(
+        project_dir.join("Nargo.toml"),
+        "[package]\nname = \"remote_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    std::fs::write(project_dir.join(".env"), "CONTRACT_ADDRESS=0xfromproject\n").unwrap();
+    // A .env where bargo is started must not shadow the project's
+    std::fs::write(temp_dir.path().join(".env"), "CONTRACT_ADDRESS=0xfromcwd\n").unwrap();
+
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env_remove("CONTRACT_ADDRESS")
+        .args(["--working-dir", "circuit", "doctor", "--env", "--json"])
+        .output()
+        .unwrap();
+    let report: serde_json::Value =
+        serde_json::from_str(String::from_utf8(output.stdout).unwrap().trim()).unwrap();
+
+    let variable = report["env"]["variables"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|variable| variable["name"] == "CONTRACT_ADDRESS")
+        .unwrap();
+    assert_eq!(variable["value"], "0xfromproject");
+    assert_eq!(report["env"]["files"][0]["name"], ".env");
+    assert_eq!(report["env"]["files"][0]["present"], true);
+}
+
+#[test]
+fn doctor_json_lists_tool_statuses() {
+    // Exit status depends on which tools are installed, so only the shape is checked
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .args(["doctor", "--json"])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let report: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    // Exit code 2 (only optional tools missing) still counts as ok
+    assert_eq!(report["ok"], output.status.code() != Some(1));
+
+    let tools = report["tools"].as_array().unwrap();
+    assert_eq!(tools[0]["name"], "nargo");
+    assert_eq!(tools[1]["name"], "bb");
+    for tool in tools {
+        let status = tool["status"].as_str().unwrap();
+        assert!(["ok", "warn", "error"].contains(&status));
+    }
+}
+
+#[cfg(all(unix, any(feature = "cairo", feature = "evm-foundry")))]
+#[test]
+fn doctor_exit_code_tells_required_from_optional_gaps() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // A PATH holding only stand-in tools that print a version
+    let bin = assert_fs::TempDir::new().unwrap();
+    let install = |name: &str, version: &str| {
+        let path = bin.path().join(name);
+        std::fs::write(&path, format!("#!/bin/sh\necho '{version}'\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    };
+    let doctor = || {
+        let output = Command::cargo_bin("bargo")
+            .unwrap()
+            .current_dir(bin.path())
+            .env("PATH", bin.path())
+            .env_remove("BARGO_NARGO")
+            .env_remove("BARGO_BB")
+            .env_remove("BARGO_GARAGA")
+            .env_remove("BARGO_FORGE")
+            .args(["--summary-file", "session.jsonl", "doctor", "--json"])
+            .output()
+            .unwrap();
+        let report: serde_json::Value =
+            serde_json::from_str(String::from_utf8(output.stdout).unwrap().trim()).unwrap();
+        (output.status.code(), report["ok"].clone())
+    };
+
+    assert_eq!(doctor(), (Some(1), serde_json::json!(false)));
+
+    install("nargo", "nargo version = 1.0.0-beta.4");
+    install("bb", "0.84.0");
+    assert_eq!(doctor(), (Some(2), serde_json::json!(true)));
+
+    install("garaga", "garaga 0.18.1");
+    install("forge", "forge Version: 1.0.0");
+    install("cast", "cast Version: 1.0.0");
+    assert_eq!(doctor(), (Some(0), serde_json::json!(true)));
+
+    // Non-zero outcomes still reach the session record written after dispatch
+    let log = std::fs::read_to_string(bin.path().join("session.jsonl")).unwrap();
+    assert_eq!(log.lines().count(), 3, "{log}");
+}
+
+#[test]
+fn doctor_fix_dry_run_lists_install_commands() {
+    use predicates::{prelude::PredicateBooleanExt, str::contains};
+
+    // An empty PATH makes every tool missing; dry runs never prompt or install
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .env("PATH", "")
+        .env_remove("BARGO_NARGO")
+        .env_remove("BARGO_BB")
+        .args(["--dry-run", "doctor", "--fix"])
+        .assert()
+        .failure()
+        .stdout(contains("Install commands for missing tools"))
+        .stdout(contains("nargo: curl -L"))
+        .stdout(contains("noirup"))
+        .stdout(contains("bb: curl -L"))
+        .stdout(contains("bbup"))
+        .stdout(contains("Would run").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn doctor_write_lock_dry_run_prints_lock_without_writing() {
+    use predicates::str::contains;
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let nargo = temp_dir.path().join("nargo");
+    std::fs::write(&nargo, "#!/bin/sh\necho 'nargo version = 1.0.0-beta.4'\n").unwrap();
+    std::fs::set_permissions(&nargo, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("PATH", temp_dir.path())
+        .env_remove("BARGO_NARGO")
+        .env_remove("BARGO_BB")
+        .env_remove("BARGO_GARAGA")
+        .args(["--dry-run", "doctor", "--write-lock"])
+        .assert()
+        .success()
+        .stdout(contains("Would write"))
+        .stdout(contains("bargo-tools.lock"))
+        .stdout(contains("nargo = \""));
+
+    assert!(!temp_dir.path().join("bargo-tools.lock").exists());
+}
+
+#[test]
+fn target_dir_flag_relocates_artifacts() {
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args([
+            "--dry-run",
+            "--pkg",
+            "my_pkg",
+            "--target-dir",
+            "build/zk",
+            "evm",
+            "prove",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("-b build/zk/bb/my_pkg.json"))
+        .stdout(contains("-o build/zk/evm"));
+}
+
+#[test]
+fn target_dir_flag_scopes_clean() {
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args(["--dry-run", "--target-dir", "build/zk", "clean"])
+        .assert()
+        .success()
+        .stdout(contains("Would run: rm -rf build/zk/"));
+}
+
+#[test]
+fn clean_dry_run_lists_artifacts_with_sizes() {
+    use predicates::prelude::PredicateBooleanExt;
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let bb_dir = temp_dir.path().join("target/bb");
+    let starknet_dir = temp_dir.path().join("target/starknet");
+    std::fs::create_dir_all(&bb_dir).unwrap();
+    std::fs::create_dir_all(&starknet_dir).unwrap();
+    std::fs::write(bb_dir.join("pkg.json"), [0u8; 2048]).unwrap();
+    std::fs::write(starknet_dir.join("proof"), [0u8; 100]).unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "clean"])
+        .assert()
+        .success()
+        .stdout(contains("target/bb/ (2.0 KB)"))
+        .stdout(contains("target/bb/pkg.json (2.0 KB)"))
+        .stdout(contains("target/starknet/proof (100 B)"))
+        .stdout(contains("Would reclaim 2.1 KB (2 files)"));
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--dry-run", "clean", "--backend", "bb"])
+        .assert()
+        .success()
+        .stdout(contains("target/bb/pkg.json (2.0 KB)"))
+        .stdout(contains("proof").not())
+        .stdout(contains("Would reclaim 2.0 KB (1 file)"));
+
+    // Nothing was actually removed
+    assert!(bb_dir.join("pkg.json").exists());
+}
+
+#[test]
+fn clean_cache_removes_only_cache_files() {
+    use predicates::prelude::PredicateBooleanExt;
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let bb_dir = temp_dir.path().join("target/bb");
+    let evm_dir = temp_dir.path().join("target/evm");
+    std::fs::create_dir_all(&bb_dir).unwrap();
+    std::fs::create_dir_all(&evm_dir).unwrap();
+    std::fs::write(bb_dir.join("pkg.json"), "{}").unwrap();
+    std::fs::write(bb_dir.join("manifest.json"), "{}").unwrap();
+    for file in ["proof", "vk", "public_inputs", "vk.hash", "manifest.json"] {
+        std::fs::write(evm_dir.join(file), file).unwrap();
+    }
+
+    let bargo = |args: &[&str]| {
+        let mut command = Command::cargo_bin("bargo").unwrap();
+        command.current_dir(temp_dir.path()).args(args);
+        command
+    };
+
+    // Dry run lists the cache files without touching them
+    bargo(&["--dry-run", "clean", "--cache"])
+        .assert()
+        .success()
+        .stdout(contains("Would remove: target/bb/manifest.json"))
+        .stdout(contains("Would remove: target/evm/vk.hash"))
+        .stdout(contains("Would remove: target/evm/vk ").not());
+    assert!(evm_dir.join("vk.hash").exists());
+
+    bargo(&["clean", "--cache"]).assert().success();
+    assert!(!bb_dir.join("manifest.json").exists());
+    assert!(!evm_dir.join("vk.hash").exists());
+    assert!(!evm_dir.join("manifest.json").exists());
+    for file in ["proof", "vk", "public_inputs"] {
+        assert!(evm_dir.join(file).exists(), "{file} should be kept");
+    }
+    assert!(bb_dir.join("pkg.json").exists());
+
+    // --all also drops the cached vk, but never proofs
+    bargo(&["clean", "--cache", "--all"]).assert().success();
+    assert!(!evm_dir.join("vk").exists());
+    assert!(evm_dir.join("proof").exists());
+    assert!(evm_dir.join("public_inputs").exists());
+
+    bargo(&["clean", "--all"]).assert().failure();
+}
+
+#[test]
+fn clean_older_than_keeps_recent_artifacts() {
+    use predicates::prelude::PredicateBooleanExt;
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let write_aged = |path: &str, days: u64| {
+        let path = temp_dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, [0u8; 1024]).unwrap();
+        let time = std::time::SystemTime::now() - std::time::Duration::from_secs(days * 86_400);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+        path
+    };
+    let old_bytecode = write_aged("target/bb/pkg.json", 10);
+    let new_witness = write_aged("target/bb/pkg.gz", 1);
+    let old_proof = write_aged("target/starknet/proof", 30);
+
+    let bargo = |args: &[&str]| {
+        let mut command = Command::cargo_bin("bargo").unwrap();
+        command.current_dir(temp_dir.path()).args(args);
+        command
+    };
+
+    // Dry run lists only the stale candidates
+    bargo(&["--dry-run", "clean", "--older-than", "7d"])
+        .assert()
+        .success()
+        .stdout(contains("Would remove: target/bb/pkg.json (1.0 KB)"))
+        .stdout(contains("Would remove: target/starknet/proof (1.0 KB)"))
+        .stdout(contains("pkg.gz").not())
+        .stdout(contains("Would reclaim 2.0 KB (2 files)"));
+    assert!(old_bytecode.exists());
+
+    // Scoped to one backend
+    bargo(&["clean", "--backend", "bb", "--older-than", "7d"])
+        .assert()
+        .success()
+        .stdout(contains("Removed 1 file older than 7d, reclaimed 1.0 KB"));
+    assert!(!old_bytecode.exists());
+    assert!(new_witness.exists());
+    assert!(old_proof.exists());
+
+    bargo(&["clean", "--older-than", "2w"])
+        .assert()
+        .failure()
+        .stderr(contains("invalid age '2w'"));
+    bargo(&["clean", "--cache", "--older-than", "7d"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn doctor_env_never_prints_private_key() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("PRIVATE_KEY", "0xsupersecretkey")
+        .env("RPC_URL", "https://rpc.example.com/v2/secret-api-key")
+        .env_remove("STARKNET_ACCOUNT")
+        .args(["doctor", "--env", "--json"])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("supersecretkey"));
+    assert!(!stdout.contains("secret-api-key"));
+
+    let report: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(report["env"]["files"][0]["name"], ".env");
+    assert_eq!(report["env"]["files"][0]["present"], false);
+
+    let variables = report["env"]["variables"].as_array().unwrap();
+    let find = |name: &str| {
+        variables
+            .iter()
+            .find(|variable| variable["name"] == name)
+            .unwrap()
+    };
+    #[cfg(feature = "evm-foundry")]
+    {
+        assert_eq!(find("PRIVATE_KEY")["set"], true);
+        assert!(find("PRIVATE_KEY")["value"].is_null());
+        assert_eq!(find("RPC_URL")["value"], "https://rpc.example.com/…");
+    }
+    #[cfg(feature = "cairo")]
+    {
+        assert_eq!(find("STARKNET_ACCOUNT")["set"], false);
+        assert_eq!(find("SEPOLIA_RPC_URL")["used_by"][0], "cairo declare");
+    }
+}
+
+/// Install an executable `nargo` shell script into `dir` and return a PATH with it first
+#[cfg(unix)]
+fn fake_nargo_path(dir: &std::path::Path, script: &str) -> std::ffi::OsString {
+    use std::os::unix::fs::PermissionsExt;
+
+    let bin = dir.join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    let nargo = bin.join("nargo");
+    std::fs::write(&nargo, format!("#!/bin/sh\n{script}\n")).unwrap();
+    std::fs::set_permissions(&nargo, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let mut paths = vec![bin];
+    paths.extend(std::env::split_paths(
+        &std::env::var_os("PATH").unwrap_or_default(),
+    ));
+    std::env::join_paths(paths).unwrap()
+}
+
+#[cfg(unix)]
+#[test]
+fn quiet_flag_hides_tool_stdout() {
+    use predicates::prelude::PredicateBooleanExt;
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let path = fake_nargo_path(temp_dir.path(), "echo 'Compiling quiet_pkg'");
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("PATH", &path)
+        .args(["--pkg", "quiet_pkg", "check"])
+        .assert()
+        .success()
+        .stdout(contains("Compiling quiet_pkg"));
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("PATH", &path)
+        .args(["--quiet", "--pkg", "quiet_pkg", "check"])
+        .assert()
+        .success()
+        .stdout(contains("Compiling quiet_pkg").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn quiet_flag_reports_tool_stdout_on_failure() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let path = fake_nargo_path(temp_dir.path(), "echo 'error: unknown field'; exit 1");
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("PATH", &path)
+        .args(["--quiet", "--pkg", "quiet_pkg", "check"])
+        .assert()
+        .failure()
+        .stderr(contains("error: unknown field"));
+}
+
+#[test]
+fn color_flag_controls_ansi_output() {
+    use predicates::prelude::PredicateBooleanExt;
+    use predicates::str::contains;
+
+    // stdout is a pipe here, so `auto` would not color
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .env_remove("NO_COLOR")
+        .args(["--color", "always", "--dry-run", "check"])
+        .assert()
+        .success()
+        .stdout(contains("\x1b["));
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .env("CLICOLOR_FORCE", "1")
+        .args(["--color", "never", "--dry-run", "check"])
+        .assert()
+        .success()
+        .stdout(contains("\x1b[").not());
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .env_remove("NO_COLOR")
+        .env("CLICOLOR_FORCE", "1")
+        .args(["--dry-run", "check"])
+        .assert()
+        .success()
+        .stdout(contains("\x1b["));
+}
+
+#[test]
+fn build_check_reports_staleness_without_running_nargo() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"check_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+
+    // Missing artifacts: human-readable failure lists them
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["build", "--check"])
+        .assert()
+        .failure()
+        .stderr(contains("Build artifacts are out of date"))
+        .stderr(contains("check_pkg.json is missing"));
+
+    // Fresh artifacts pass
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    let bb_dir = temp_dir.path().join("target/bb");
+    std::fs::create_dir_all(&bb_dir).unwrap();
+    std::fs::write(bb_dir.join("check_pkg.json"), "{}").unwrap();
+    std::fs::write(bb_dir.join("check_pkg.gz"), [0x1f, 0x8b]).unwrap();
+
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args(["--json", "build", "--check"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value =
+        serde_json::from_str(String::from_utf8(output.stdout).unwrap().trim()).unwrap();
+    assert_eq!(report["up_to_date"], true);
+    assert_eq!(report["reasons"], serde_json::json!([]));
+
+    // A newer input makes the check fail
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    std::fs::write(temp_dir.path().join("Prover.toml"), "a = 1\n").unwrap();
+
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args([
+            "--json",
+            "--summary-file",
+            "session.jsonl",
+            "build",
+            "--check",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(4));
+    assert!(output.stderr.is_empty());
+    let report: serde_json::Value =
+        serde_json::from_str(String::from_utf8(output.stdout).unwrap().trim()).unwrap();
+    assert_eq!(report["up_to_date"], false);
+    assert_eq!(
+        report["reasons"][0],
+        "Prover.toml is newer than the build artifacts"
+    );
+
+    // The stale result still goes through the normal shutdown path
+    let log = std::fs::read_to_string(temp_dir.path().join("session.jsonl")).unwrap();
+    let record: serde_json::Value = serde_json::from_str(log.trim()).unwrap();
+    assert_eq!(record["status"], "failure");
+}
+
+#[test]
+fn tool_binary_overrides_change_the_command() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"pinned_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        temp_dir.path().join("bargo.toml"),
+        "[tools]\nnargo = \"/opt/noir/nargo\"\nbb = \"/opt/bb/from-config/bb\"\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env_remove("BARGO_NARGO")
+        .args(["--dry-run", "build"])
+        .assert()
+        .success()
+        .stdout(contains("Would run: /opt/noir/nargo execute"));
+
+    // The environment variable takes precedence over bargo.toml
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .env("BARGO_BB", "/opt/bb/from-env/bb")
+        .args(["--dry-run", "evm", "prove"])
+        .assert()
+        .success()
+        .stdout(contains("Would run: /opt/bb/from-env/bb prove"));
+}
+
+#[test]
+fn json_stdout_stays_parseable_when_a_warning_fires() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"warn_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+
+    // Nothing listens on port 1, so delivering the notification warns
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .args([
+            "--json",
+            "--notify-url",
+            "http://127.0.0.1:1/x",
+            "config",
+            "list",
+        ])
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Could not send completion notification"),
+        "{stderr}"
+    );
+    serde_json::from_slice::<serde_json::Value>(&output.stdout)
+        .unwrap_or_else(|e| panic!("{e}: {}", String::from_utf8_lossy(&output.stdout)));
+}
+
+#[test]
+fn console_log_follows_color_choice() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"color_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    let warning = |color: &str, no_color: bool| {
+        let mut command = Command::cargo_bin("bargo").unwrap();
+        command
+            .current_dir(temp_dir.path())
+            .env_remove("NO_COLOR")
+            .env_remove("CLICOLOR_FORCE")
+            .args(["--color", color, "--notify-url", "http://127.0.0.1:1/x"])
+            .args(["config", "list"]);
+        if no_color {
+            command.env("NO_COLOR", "1");
+        }
+        String::from_utf8(command.output().unwrap().stderr).unwrap()
+    };
+
+    let always = warning("always", false);
+    assert!(always.contains("Could not send completion notification"));
+    assert!(always.contains('\x1b'), "{always:?}");
+    assert!(!warning("never", false).contains('\x1b'));
+    assert!(!warning("auto", true).contains('\x1b'));
+}
+
+#[test]
+fn log_file_records_tool_invocations() {
+    use predicates::{prelude::PredicateBooleanExt, str::contains};
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let log_file = temp_dir.path().join("bargo.log");
+    let run = |extra: &[&str]| {
+        Command::cargo_bin("bargo")
+            .unwrap()
+            .args(["--dry-run", "--pkg", "log_pkg", "--log-file"])
+            .arg(&log_file)
+            .args(extra)
+            .arg("build")
+            .assert()
+            .success()
+            // The invocation is logged at debug level, below console verbosity
+            .stderr(contains("exec:").not());
+    };
+    let occurrences = || {
+        std::fs::read_to_string(&log_file)
+            .unwrap()
+            .matches("exec: nargo execute --package log_pkg")
+            .count()
+    };
+
+    run(&[]);
+    assert_eq!(occurrences(), 1);
+
+    run(&["--log-append"]);
+    assert_eq!(occurrences(), 2);
+
+    // Without --log-append the previous run's log is discarded
+    run(&[]);
+    assert_eq!(occurrences(), 1);
+}
+
+#[test]
+fn record_then_replay_session() {
+    use predicates::str::contains;
+
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let recording = temp_dir.path().join("session.json");
+    let bargo = |flag: &str, command: &str| {
+        let mut cmd = Command::cargo_bin("bargo").unwrap();
+        cmd.args(["--dry-run", "--pkg", "replay_pkg", flag])
+            .arg(&recording)
+            .arg(command);
+        cmd
+    };
+
+    bargo("--record", "build").assert().success();
+    assert!(
+        std::fs::read_to_string(&recording)
+            .unwrap()
+            .contains("replay_pkg")
+    );
+
+    bargo("--replay", "build").assert().success();
+
+    // A different command diverges from the recording
+    bargo("--replay", "check")
+        .assert()
+        .failure()
+        .stderr(contains("Unexpected command #1 during replay"));
+}
+
+#[test]
+fn doctor_require_passes_when_listed_tools_are_present() {
+    // bargo itself stands in for nargo; bb and the rest are absent but optional
+    let output = Command::cargo_bin("bargo")
+        .unwrap()
+        .env("PATH", "")
+        .env("BARGO_NARGO", assert_cmd::cargo::cargo_bin("bargo"))
+        .env_remove("BARGO_BB")
+        .args(["doctor", "--json", "--require", "nargo"])
+        .output()
+        .unwrap();
+
+    // Only optional tools are missing
+    assert_eq!(output.status.code(), Some(2));
+    let report: serde_json::Value =
+        serde_json::from_str(String::from_utf8(output.stdout).unwrap().trim()).unwrap();
+    assert_eq!(report["ok"], true);
+    assert_eq!(report["missing"], serde_json::json!([]));
+    assert_eq!(report["tools"][1]["name"], "bb");
+    assert_eq!(report["tools"][1]["required"], false);
+}
+
+#[test]
+fn doctor_require_names_missing_tools() {
+    use predicates::str::contains;
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .env("PATH", "")
+        .env("BARGO_NARGO", assert_cmd::cargo::cargo_bin("bargo"))
+        .env_remove("BARGO_BB")
+        .args(["doctor", "--require", "nargo,bb"])
+        .assert()
+        .failure()
+        .stdout(contains("Missing required tools: bb"));
+
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .args(["doctor", "--require", "nargo,solc"])
+        .assert()
+        .failure()
+        .stderr(contains("Unknown tool 'solc' in --require"));
+}
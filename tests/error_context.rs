@@ -4,8 +4,10 @@
 //! include rich context information and proper error chains.
 
 use bargo_core::{
-    config::Config,
-    runner::{CmdSpec, DryRunRunner, Runner},
+    cli::{ArtifactSuffix, ColorChoice},
+    config::{Config, ToolPaths},
+    fs::{DryRunFs, RealFs},
+    runner::{CapturedOutput, CmdSpec, DryRunRunner, Runner},
 };
 use color_eyre::Result;
 use std::sync::{Arc, Mutex};
@@ -50,7 +52,7 @@ impl Runner for FailingDryRunRunner {
         self.inner.run(spec)
     }
 
-    fn run_capture(&self, spec: &CmdSpec) -> Result<String> {
+    fn run_capture(&self, spec: &CmdSpec) -> Result<CapturedOutput> {
         if self.should_fail(spec) {
             return Err(color_eyre::eyre::eyre!(
                 "Command '{}' failed with exit code 1\nStdout: \nStderr: Tool not found or execution failed",
@@ -68,11 +70,23 @@ fn test_missing_project_error_context() {
     color_eyre::install().ok();
 
     let config = Config {
-        verbose: true,
+        verbose: 1,
         dry_run: false,
         pkg: None,
         quiet: false,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: Arc::new(DryRunRunner::new()),
+        fs: Arc::new(RealFs),
     };
 
     // Try to run a command that will fail due to missing Nargo.toml
@@ -106,11 +120,23 @@ fn test_missing_artifacts_error_context() {
     color_eyre::install().ok();
 
     let config = Config {
-        verbose: true,
+        verbose: 1,
         dry_run: false,
         pkg: Some("test_pkg".to_string()),
         quiet: false,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: Arc::new(DryRunRunner::new()),
+        fs: Arc::new(RealFs),
     };
 
     // Try to run Cairo prove which will fail due to missing artifacts
@@ -147,12 +173,24 @@ fn test_file_operation_error_context() {
     failing_runner.fail_on_tool("rm"); // Won't actually be called, but simulates file operation failure
 
     let config = Config {
-        verbose: true,
+        verbose: 1,
         // Avoid deleting the workspace target/ during CI; dry-run is enough here.
         dry_run: true,
         pkg: Some("nonexistent_package".to_string()),
         quiet: false,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: Arc::new(failing_runner),
+        fs: Arc::new(DryRunFs::new()),
     };
 
     // This test verifies that file operations have proper error context
@@ -175,11 +213,23 @@ fn test_missing_proof_artifacts_error_context() {
     color_eyre::install().ok();
 
     let config = Config {
-        verbose: true,
+        verbose: 1,
         dry_run: false,
         pkg: Some("test_pkg".to_string()),
         quiet: false,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: Arc::new(DryRunRunner::new()),
+        fs: Arc::new(RealFs),
     };
 
     // Try to run Cairo calldata which will fail due to missing proof artifacts
@@ -214,11 +264,23 @@ fn test_missing_verifier_contract_error_context() {
     color_eyre::install().ok();
 
     let config = Config {
-        verbose: true,
+        verbose: 1,
         dry_run: false,
         pkg: Some("test_pkg".to_string()),
         quiet: false,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: Arc::new(DryRunRunner::new()),
+        fs: Arc::new(RealFs),
     };
 
     // Try to run EVM deploy which will fail due to missing verifier contract
@@ -255,11 +317,23 @@ fn test_tool_execution_error_chain() {
     failing_runner.fail_on_tool("nonexistent_tool");
 
     let config = Config {
-        verbose: true,
+        verbose: 1,
         dry_run: false,
         pkg: Some("test_pkg".to_string()),
         quiet: false,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: Arc::new(failing_runner),
+        fs: Arc::new(RealFs),
     };
 
     // Try to run a command that uses the runner directly
@@ -294,11 +368,23 @@ fn test_workflow_error_propagation() {
     failing_runner.fail_on_tool("nargo");
 
     let config = Config {
-        verbose: false,
+        verbose: 0,
         dry_run: false,
         pkg: None,
         quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: Arc::new(failing_runner),
+        fs: Arc::new(RealFs),
     };
 
     // Test that check command properly propagates errors
@@ -329,11 +415,23 @@ fn test_actual_tool_execution_error_chain() {
     failing_runner.fail_on_tool("bb");
 
     let config = Config {
-        verbose: true,
+        verbose: 1,
         dry_run: false,
         pkg: Some("test_pkg".to_string()),
         quiet: false,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
         runner: Arc::new(failing_runner),
+        fs: Arc::new(RealFs),
     };
 
     // Use the common::run_tool function to test the full error chain
@@ -366,3 +464,64 @@ fn test_actual_tool_execution_error_chain() {
         error_string
     );
 }
+
+/// Test that captured tool executions carry the same command context as `run_tool`
+#[test]
+fn test_captured_tool_execution_error_chain() {
+    color_eyre::install().ok();
+
+    let failing_runner = FailingDryRunRunner::new();
+    failing_runner.fail_on_tool("garaga");
+    failing_runner.fail_on_tool("forge");
+
+    let config = Config {
+        verbose: 1,
+        dry_run: false,
+        pkg: Some("test_pkg".to_string()),
+        quiet: false,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
+        runner: Arc::new(failing_runner),
+        fs: Arc::new(RealFs),
+    };
+
+    let result = bargo_core::commands::common::run_tool_capture(
+        &config,
+        "garaga",
+        &["calldata", "--system", "ultra_starknet_zk_honk"],
+    );
+    let error = result.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Command execution failed: garaga calldata --system ultra_starknet_zk_honk"
+    );
+    assert!(
+        format!("{:?}", error).contains("failed with exit code"),
+        "Error should keep the runner's failure as its cause: {:?}",
+        error
+    );
+
+    // Network commands keep secrets passed through the environment out of the context
+    let result = bargo_core::commands::common::run_tool_capture_with_retry(
+        &config,
+        "forge",
+        &["create", "Verifier"],
+        &[("PRIVATE_KEY", "0xsecret")],
+    );
+    let error_string = format!("{:?}", result.unwrap_err());
+    assert!(
+        error_string.contains("Command execution failed: forge create Verifier"),
+        "Error should contain the command line: {}",
+        error_string
+    );
+    assert!(!error_string.contains("0xsecret"));
+}
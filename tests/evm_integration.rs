@@ -0,0 +1,1382 @@
+//! Integration tests for bargo evm commands
+//!
+//! These tests use DryRunRunner to verify the commands composed by EVM workflows
+//! without running external tools or touching a network.
+
+use assert_fs::TempDir;
+use bargo_core::BargoError;
+use bargo_core::cli::{
+    ArtifactSuffix, Backend, BbProfile, BbScheme, ColorChoice, OracleHash, ProofOutputFormat,
+};
+use bargo_core::config::{
+    CalldataConfig, Config, FoundryConfig, ProveConfig, ToolPaths, VerifyConfig,
+};
+use bargo_core::fs::{DryRunFs, RealFs};
+use bargo_core::runner::{
+    CapturedOutput, CmdSpec, DryRunRunner, Recording, RecordingRunner, ReplayRunner, Runner,
+};
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Point `config` at `project_dir` and apply it to this thread's path helpers
+fn for_package(config: &Config, project_dir: &Path) -> Config {
+    let config = Config {
+        package_dir: Some(project_dir.to_path_buf()),
+        ..config.clone()
+    };
+    config.apply_paths().unwrap();
+    config
+}
+
+fn dry_run_config(runner: Arc<DryRunRunner>) -> Config {
+    Config {
+        verbose: 0,
+        dry_run: true,
+        pkg: Some("test_pkg".to_string()),
+        quiet: true,
+        timeout: None,
+        retries: 0,
+        target_dir: None,
+        package_dir: None,
+        evm_network: None,
+        cairo_network: None,
+        json: false,
+        color: ColorChoice::Auto,
+        tools: ToolPaths::default(),
+        artifact_suffix: ArtifactSuffix::None,
+        keep_going: false,
+        runner,
+        fs: Arc::new(DryRunFs::new()),
+    }
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn test_evm_verify_onchain_composes_cast_call() {
+    let temp_dir = TempDir::new().unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::write(
+        evm_dir.join(".bargo_contract_address"),
+        "0x742d35Cc6634C0532925a3b8D400d1b0fB000000\n",
+    )
+    .unwrap();
+    fs::write(
+        evm_dir.join("calldata.json"),
+        r#"{"proof": "0xdeadbeef", "public_inputs": ["0x01", "0x02"]}"#,
+    )
+    .unwrap();
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+
+    let result =
+        bargo_core::commands::evm::run_verify_onchain(&for_package(&config, temp_dir.path()));
+    assert!(result.is_ok(), "verify-onchain failed: {:?}", result.err());
+
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 1);
+
+    let (spec, output) = &history[0];
+    assert_eq!(spec.cmd, "cast");
+    assert_eq!(spec.args[0], "call");
+    assert_eq!(spec.args[1], "0x742d35Cc6634C0532925a3b8D400d1b0fB000000");
+    assert_eq!(spec.args[2], "verify(bytes,bytes32[])");
+    assert_eq!(spec.args[3], "0xdeadbeef");
+    assert_eq!(spec.args[4], "[0x01,0x02]");
+    assert_eq!(spec.args[5], "--rpc-url");
+    assert!(spec.retryable);
+    assert_eq!(output.as_deref(), Some("true"));
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn test_evm_verify_onchain_uses_latest_address_for_network() {
+    let temp_dir = TempDir::new().unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::write(
+        evm_dir.join("addresses.json"),
+        r#"{
+  "mainnet": [
+    {"address": "0x0000000000000000000000000000000000000001", "timestamp": "2026-10-01T00:00:00Z", "package": "test_pkg"},
+    {"address": "0x0000000000000000000000000000000000000002", "timestamp": "2026-10-02T00:00:00Z", "package": "test_pkg"}
+  ],
+  "sepolia": [
+    {"address": "0x0000000000000000000000000000000000000003", "timestamp": "2026-10-03T00:00:00Z", "package": "test_pkg"}
+  ]
+}"#,
+    )
+    .unwrap();
+    // Written before the address book existed; the book takes precedence
+    fs::write(
+        evm_dir.join(".bargo_contract_address"),
+        "0x742d35Cc6634C0532925a3b8D400d1b0fB000000\n",
+    )
+    .unwrap();
+    fs::write(
+        evm_dir.join("calldata.json"),
+        r#"{"proof": "0xdeadbeef", "public_inputs": ["0x01"]}"#,
+    )
+    .unwrap();
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = Config {
+        evm_network: Some("mainnet".to_string()),
+        ..dry_run_config(dry_runner.clone())
+    };
+
+    let result =
+        bargo_core::commands::evm::run_verify_onchain(&for_package(&config, temp_dir.path()));
+    assert!(result.is_ok(), "verify-onchain failed: {:?}", result.err());
+
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 1);
+    assert_eq!(
+        history[0].0.args[1],
+        "0x0000000000000000000000000000000000000002"
+    );
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn test_evm_verify_onchain_record_then_replay() {
+    let temp_dir = TempDir::new().unwrap();
+    let recording_path = temp_dir.path().join("session.json");
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let recorder = RecordingRunner::new(dry_runner.clone(), &recording_path).unwrap();
+    let config = for_package(
+        &Config {
+            runner: Arc::new(recorder),
+            ..dry_run_config(dry_runner.clone())
+        },
+        temp_dir.path(),
+    );
+    bargo_core::commands::evm::run_verify_onchain(&config).unwrap();
+
+    // Replaying answers the same cast call without running anything
+    let replay = Arc::new(ReplayRunner::from_file(&recording_path).unwrap());
+    let config = Config {
+        runner: replay.clone(),
+        ..config
+    };
+    bargo_core::commands::evm::run_verify_onchain(&config).unwrap();
+    assert_eq!(replay.remaining(), 0);
+    assert_eq!(dry_runner.history().len(), 1);
+
+    // The recorded output, not the dry-run fake, decides the result
+    let mut recording = Recording::load(&recording_path).unwrap();
+    recording.commands[0].output.as_mut().unwrap().stdout = "execution reverted".to_string();
+    let config = Config {
+        runner: Arc::new(ReplayRunner::new(recording)),
+        ..config
+    };
+    assert!(bargo_core::commands::evm::run_verify_onchain(&config).is_err());
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn test_evm_verify_onchain_dry_run_without_artifacts() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+
+    let result =
+        bargo_core::commands::evm::run_verify_onchain(&for_package(&config, temp_dir.path()));
+    assert!(result.is_ok(), "verify-onchain failed: {:?}", result.err());
+
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].0.cmd, "cast");
+    assert!(history[0].0.args.contains(&"<proof>".to_string()));
+}
+
+#[test]
+fn test_evm_prove_default_scheme_omits_flag() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+
+    bargo_core::commands::evm::run_prove(&config).unwrap();
+
+    let history = dry_runner.history();
+    let prove = history
+        .iter()
+        .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "prove")
+        .expect("bb prove should be recorded");
+    assert!(!prove.0.args.contains(&"--scheme".to_string()));
+}
+
+#[test]
+fn test_evm_gen_no_prove_skips_bb_prove() {
+    let temp_dir = TempDir::new().unwrap();
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = for_package(&dry_run_config(dry_runner.clone()), temp_dir.path());
+
+    bargo_core::commands::evm::run_gen_with_config(
+        &config,
+        &ProveConfig::default().with_no_prove(true),
+        &FoundryConfig::default(),
+    )
+    .unwrap();
+
+    let history = dry_runner.history();
+    let bb_commands: Vec<&str> = history
+        .iter()
+        .filter(|(spec, _)| spec.cmd == "bb")
+        .map(|(spec, _)| spec.args[0].as_str())
+        .collect();
+    assert_eq!(bb_commands, ["write_solidity_verifier"]);
+}
+
+#[test]
+fn test_evm_gen_no_prove_requires_vk() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = for_package(
+        &dry_run_config(Arc::new(DryRunRunner::new())),
+        temp_dir.path(),
+    );
+    config.dry_run = false;
+
+    let error = bargo_core::commands::evm::run_gen_with_config(
+        &config,
+        &ProveConfig::default().with_no_prove(true),
+        &FoundryConfig::default(),
+    )
+    .unwrap_err();
+    let message = format!("{error:?}");
+    assert!(message.contains("target/evm/vk"), "{message}");
+    assert!(!message.contains("test_pkg.gz"), "{message}");
+}
+
+#[test]
+fn test_evm_gen_writes_verifier_to_output_dir() {
+    let temp_dir = TempDir::new().unwrap();
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = for_package(&dry_run_config(dry_runner.clone()), temp_dir.path());
+    let gen_into = |output_dir: &str, overwrite: bool| {
+        bargo_core::commands::evm::run_gen_with_config(
+            &config,
+            &ProveConfig::default().with_no_prove(true),
+            &FoundryConfig::default()
+                .with_output_dir(Some(output_dir.into()))
+                .with_overwrite(overwrite),
+        )
+    };
+
+    gen_into("chains/evm", false).unwrap();
+    let history = dry_runner.history();
+    let verifier = history
+        .iter()
+        .find(|(spec, _)| spec.cmd == "bb")
+        .expect("bb write_solidity_verifier should be recorded");
+    let output = &verifier.0.args[verifier.0.args.iter().position(|a| a == "-o").unwrap() + 1];
+    assert_eq!(
+        Path::new(output),
+        temp_dir.path().join("chains/evm/src/Verifier.sol")
+    );
+    #[cfg(feature = "evm-foundry")]
+    assert!(
+        history
+            .iter()
+            .any(|(spec, _)| spec.cmd == "forge" && spec.args == ["init", "--force", "chains/evm"])
+    );
+
+    // A directory without a generated Verifier.sol is only replaced with --force
+    dry_runner.clear_history();
+    fs::create_dir_all(temp_dir.path().join("contracts_src")).unwrap();
+    fs::write(
+        temp_dir.path().join("contracts_src/Token.sol"),
+        "contract Token {}",
+    )
+    .unwrap();
+    let error = gen_into("contracts_src", false).unwrap_err();
+    assert!(
+        error.to_string().contains("not generated by bargo"),
+        "{error}"
+    );
+    assert!(dry_runner.history().is_empty());
+    gen_into("contracts_src", true).unwrap();
+}
+
+#[test]
+fn test_evm_gen_rejects_malformed_solc_version() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+
+    let error = bargo_core::commands::evm::run_gen_with_config(
+        &config,
+        &ProveConfig::default(),
+        &FoundryConfig::new(Some("0.8".to_string()), None),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("Invalid solc version '0.8'"));
+    assert!(dry_runner.history().is_empty());
+}
+
+#[test]
+fn test_evm_prove_appends_scheme() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+    let prove_cfg = ProveConfig::new(Some(BbScheme::UltraKeccakHonk));
+
+    bargo_core::commands::evm::run_prove_with_config(&config, &prove_cfg).unwrap();
+
+    let history = dry_runner.history();
+    let prove = history
+        .iter()
+        .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "prove")
+        .expect("bb prove should be recorded");
+    let args = &prove.0.args;
+    let scheme_index = args.iter().position(|arg| arg == "--scheme").unwrap();
+    assert_eq!(args[scheme_index + 1], "ultra_keccak_honk");
+}
+
+#[test]
+fn test_evm_prove_recursive_passes_recursion_flags() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+    let prove_cfg = ProveConfig::default().with_recursive(true);
+
+    bargo_core::commands::evm::run_prove_with_config(&config, &prove_cfg).unwrap();
+
+    let history = dry_runner.history();
+    for subcommand in ["write_vk", "prove"] {
+        let (spec, _) = history
+            .iter()
+            .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == subcommand)
+            .unwrap_or_else(|| panic!("bb {subcommand} should be recorded"));
+        let args = spec.args.join(" ");
+        assert!(
+            args.ends_with("--recursive --output_format bytes_and_fields"),
+            "bb {subcommand} args: {args}"
+        );
+    }
+}
+
+#[test]
+fn test_evm_prove_recursive_friendly_passes_honk_recursion() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+    let prove_cfg = ProveConfig::default().with_recursive_friendly(true);
+
+    bargo_core::commands::evm::run_prove_with_config(&config, &prove_cfg).unwrap();
+
+    let history = dry_runner.history();
+    for subcommand in ["write_vk", "prove"] {
+        let (spec, _) = history
+            .iter()
+            .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == subcommand)
+            .unwrap_or_else(|| panic!("bb {subcommand} should be recorded"));
+        let args = spec.args.join(" ");
+        assert!(args.contains("--recursive"), "bb {subcommand} args: {args}");
+        assert!(
+            args.contains("--honk_recursion 1"),
+            "bb {subcommand} args: {args}"
+        );
+    }
+}
+
+#[test]
+fn test_evm_prove_recursive_friendly_bb_argv_has_no_duplicate_flags() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+    let prove_cfg = ProveConfig::default()
+        .with_recursive_friendly(true)
+        .with_bb_profile(Some(BbProfile::LowMemory))
+        .with_extra_args(vec!["--output_format".to_string(), "fields".to_string()]);
+
+    bargo_core::commands::evm::run_prove_with_config(&config, &prove_cfg).unwrap();
+
+    let history = dry_runner.history();
+    let bb_args = |subcommand: &str| {
+        history
+            .iter()
+            .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == subcommand)
+            .unwrap_or_else(|| panic!("bb {subcommand} should be recorded"))
+            .0
+            .args
+            .join(" ")
+    };
+    let prove = bb_args("prove");
+    assert!(
+        prove.ends_with(
+            "-t evm --recursive --honk_recursion 1 --slow_low_memory --output_format fields"
+        ),
+        "bb prove args: {prove}"
+    );
+    let write_vk = bb_args("write_vk");
+    assert!(
+        write_vk
+            .ends_with("-t evm --recursive --honk_recursion 1 --output_format bytes_and_fields"),
+        "bb write_vk args: {write_vk}"
+    );
+}
+
+#[cfg(feature = "evm-foundry")]
+#[test]
+fn test_evm_gen_keeps_recorded_recursive_friendly_vk() {
+    let temp_dir = TempDir::new().unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::write(
+        evm_dir.join("manifest.json"),
+        r#"{"artifacts":[],"recursive_friendly":true}"#,
+    )
+    .unwrap();
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = for_package(&dry_run_config(dry_runner.clone()), temp_dir.path());
+
+    bargo_core::commands::evm::run_gen_with_config(
+        &config,
+        &ProveConfig::default(),
+        &FoundryConfig::default(),
+    )
+    .unwrap();
+
+    // The verifier is written from a vk made with the same recursion flags
+    let history = dry_runner.history();
+    for subcommand in ["write_vk", "prove"] {
+        let (spec, _) = history
+            .iter()
+            .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == subcommand)
+            .unwrap_or_else(|| panic!("bb {subcommand} should be recorded"));
+        let args = spec.args.join(" ");
+        assert!(
+            args.contains("--honk_recursion 1"),
+            "bb {subcommand} args: {args}"
+        );
+    }
+}
+
+#[test]
+fn test_evm_prove_output_format_maps_to_bb_values() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+
+    let prove_args = |prove_cfg: ProveConfig| {
+        dry_runner.clear_history();
+        bargo_core::commands::evm::run_prove_with_config(&config, &prove_cfg).unwrap();
+        dry_runner
+            .history()
+            .into_iter()
+            .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "prove")
+            .expect("bb prove should be recorded")
+            .0
+            .args
+            .join(" ")
+    };
+
+    // Without --output-format bb picks its own default
+    assert!(!prove_args(ProveConfig::default()).contains("--output_format"));
+
+    for (format, value) in [
+        (ProofOutputFormat::Bytes, "bytes"),
+        (ProofOutputFormat::Fields, "fields"),
+        (ProofOutputFormat::Both, "bytes_and_fields"),
+    ] {
+        let args = prove_args(ProveConfig::default().with_output_format(Some(format)));
+        assert!(
+            args.ends_with(&format!("--output_format {value}")),
+            "{format:?}: {args}"
+        );
+    }
+
+    // An explicit format replaces the recursive default
+    let args = prove_args(
+        ProveConfig::default()
+            .with_recursive(true)
+            .with_output_format(Some(ProofOutputFormat::Fields)),
+    );
+    assert!(
+        args.ends_with("--recursive --output_format fields"),
+        "{args}"
+    );
+}
+
+#[test]
+fn test_evm_prove_bb_profile_selects_bb_flags() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+
+    let prove_args = |prove_cfg: ProveConfig| {
+        dry_runner.clear_history();
+        bargo_core::commands::evm::run_prove_with_config(&config, &prove_cfg).unwrap();
+        dry_runner
+            .history()
+            .into_iter()
+            .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "prove")
+            .expect("bb prove should be recorded")
+            .0
+            .args
+            .join(" ")
+    };
+
+    let default_args = prove_args(ProveConfig::default());
+    assert_eq!(
+        prove_args(ProveConfig::default().with_bb_profile(Some(BbProfile::Release))),
+        default_args
+    );
+
+    let args = prove_args(ProveConfig::default().with_bb_profile(Some(BbProfile::Debug)));
+    assert!(args.ends_with("--verbose --debug_logging"), "{args}");
+
+    // Passthrough arguments still come last
+    let args = prove_args(
+        ProveConfig::default()
+            .with_bb_profile(Some(BbProfile::LowMemory))
+            .with_extra_args(vec!["-v".to_string()]),
+    );
+    assert!(args.ends_with("--slow_low_memory -v"), "{args}");
+}
+
+#[test]
+fn test_evm_prove_passthrough_args_come_last() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+    let prove_cfg = ProveConfig::default()
+        .with_recursive(true)
+        .with_extra_args(vec!["--slow_low_memory".to_string(), "-v".to_string()]);
+
+    bargo_core::commands::evm::run_prove_with_config(&config, &prove_cfg).unwrap();
+
+    let history = dry_runner.history();
+    let (prove, _) = history
+        .iter()
+        .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "prove")
+        .expect("bb prove should be recorded");
+    let args = prove.args.join(" ");
+    assert!(
+        args.ends_with("--recursive --output_format bytes_and_fields --slow_low_memory -v"),
+        "bb prove args: {args}"
+    );
+
+    // The vk is not affected, so the cached key stays valid
+    let (write_vk, _) = history
+        .iter()
+        .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "write_vk")
+        .expect("bb write_vk should be recorded");
+    assert!(!write_vk.args.contains(&"--slow_low_memory".to_string()));
+
+    // A passthrough --output_format replaces the recursive default
+    dry_runner.clear_history();
+    let prove_cfg = ProveConfig::default()
+        .with_recursive(true)
+        .with_extra_args(vec!["--output_format".to_string(), "fields".to_string()]);
+    bargo_core::commands::evm::run_prove_with_config(&config, &prove_cfg).unwrap();
+    let history = dry_runner.history();
+    let (prove, _) = history
+        .iter()
+        .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "prove")
+        .expect("bb prove should be recorded");
+    let args = prove.args.join(" ");
+    assert_eq!(args.matches("--output_format").count(), 1, "{args}");
+    assert!(
+        args.ends_with("--recursive --output_format fields"),
+        "{args}"
+    );
+}
+
+#[test]
+fn test_evm_gen_passthrough_args_reach_write_solidity_verifier() {
+    let temp_dir = TempDir::new().unwrap();
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = for_package(&dry_run_config(dry_runner.clone()), temp_dir.path());
+
+    bargo_core::commands::evm::run_gen_with_config(
+        &config,
+        &ProveConfig::default().with_no_prove(true),
+        &FoundryConfig::default().with_extra_args(vec!["--optimized".to_string()]),
+    )
+    .unwrap();
+
+    let history = dry_runner.history();
+    let (spec, _) = history
+        .iter()
+        .find(|(spec, _)| spec.cmd == "bb")
+        .expect("bb write_solidity_verifier should be recorded");
+    assert_eq!(spec.args[0], "write_solidity_verifier");
+    assert_eq!(spec.args.last().unwrap(), "--optimized");
+}
+
+#[test]
+fn test_prove_recursive_rejects_unsupported_scheme() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+    let prove_cfg = ProveConfig::new(Some(BbScheme::UltraKeccakHonk)).with_recursive(true);
+
+    let error = bargo_core::commands::prove::run(&config, Backend::Bb, &prove_cfg, false)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("--recursive is not supported with --scheme ultra_keccak_honk"));
+    assert!(dry_runner.history().is_empty());
+}
+
+#[test]
+fn test_prove_custom_witness_is_passed_to_bb() {
+    let temp_dir = TempDir::new().unwrap();
+    let witness = temp_dir.path().join("alternate.gz");
+    fs::write(&witness, [0x1f, 0x8b]).unwrap();
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+    let prove_cfg = ProveConfig::default().with_witness(Some(witness.clone()));
+
+    bargo_core::commands::prove::run(&config, Backend::Bb, &prove_cfg, false).unwrap();
+
+    let history = dry_runner.history();
+    let (spec, _) = history
+        .iter()
+        .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "prove")
+        .expect("bb prove should be recorded");
+    let flag = spec.args.iter().position(|arg| arg == "-w").unwrap();
+    assert_eq!(spec.args[flag + 1], witness.to_str().unwrap());
+}
+
+#[test]
+fn test_prove_missing_witness_fails_before_running_bb() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+    let prove_cfg = ProveConfig::default().with_witness(Some("missing/alternate.gz".into()));
+
+    let error = bargo_core::commands::prove::run(&config, Backend::Bb, &prove_cfg, false)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("missing/alternate.gz"), "error: {error}");
+    assert!(dry_runner.history().is_empty());
+}
+
+#[test]
+fn test_evm_prove_oracle_hash_overrides_target() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+    let prove_cfg = ProveConfig::default().with_oracle_hash(Some(OracleHash::Poseidon2));
+
+    bargo_core::commands::evm::run_prove_with_config(&config, &prove_cfg).unwrap();
+
+    let history = dry_runner.history();
+    for subcommand in ["write_vk", "prove"] {
+        let (spec, _) = history
+            .iter()
+            .find(|(spec, _)| spec.cmd == "bb" && spec.args[0] == subcommand)
+            .unwrap_or_else(|| panic!("bb {subcommand} should be recorded"));
+        let args = spec.args.join(" ");
+        assert!(
+            args.contains("--oracle_hash poseidon2"),
+            "bb {subcommand} args: {args}"
+        );
+        assert!(!args.contains("-t evm"), "bb {subcommand} args: {args}");
+    }
+}
+
+#[test]
+fn test_evm_verify_uses_recorded_oracle_hash() {
+    let temp_dir = TempDir::new().unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::write(
+        evm_dir.join("manifest.json"),
+        r#"{"artifacts":[],"oracle_hash":"poseidon2"}"#,
+    )
+    .unwrap();
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = for_package(&dry_run_config(dry_runner.clone()), temp_dir.path());
+
+    bargo_core::commands::evm::run_verify_with_config(&config, &VerifyConfig::default()).unwrap();
+
+    let history = dry_runner.history();
+    let args = history[0].0.args.join(" ");
+    assert!(
+        args.ends_with("--oracle_hash poseidon2"),
+        "bb verify args: {args}"
+    );
+}
+
+#[test]
+fn test_evm_calldata_writes_to_output_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::write(evm_dir.join("proof"), [0xab; 4]).unwrap();
+    fs::write(evm_dir.join("public_inputs"), [0x01; 32]).unwrap();
+
+    let mut config = dry_run_config(Arc::new(DryRunRunner::new()));
+    config.dry_run = false;
+    let calldata_cfg = CalldataConfig::new(Some("exports/nested/calldata.json".into()));
+
+    let result = bargo_core::commands::evm::run_calldata_with_config(
+        &for_package(&config, temp_dir.path()),
+        &calldata_cfg,
+    );
+    assert!(result.is_ok(), "calldata failed: {:?}", result.err());
+
+    assert!(!evm_dir.join("calldata.json").exists());
+    let calldata: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(temp_dir.path().join("exports/nested/calldata.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(calldata["proof"], "0xabababab");
+    assert_eq!(calldata["public_inputs"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_evm_calldata_encodes_call_from_verifier_abi() {
+    let temp_dir = TempDir::new().unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    let src_dir = temp_dir.path().join("contracts/evm/src");
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::create_dir_all(&src_dir).unwrap();
+    fs::write(evm_dir.join("proof"), [0xab; 4]).unwrap();
+    fs::write(evm_dir.join("public_inputs"), [0x01; 32]).unwrap();
+    fs::write(
+        src_dir.join("Verifier.sol"),
+        "interface IVerifier {\n    function verify(bytes calldata _proof, bytes32[] calldata _publicInputs) external view returns (bool);\n}\n",
+    )
+    .unwrap();
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let mut config = dry_run_config(dry_runner.clone());
+    config.dry_run = false;
+
+    bargo_core::commands::evm::run_calldata(&for_package(&config, temp_dir.path())).unwrap();
+
+    let history = dry_runner.history();
+    let (cast, _) = history
+        .iter()
+        .find(|(spec, _)| spec.cmd == "cast")
+        .expect("cast calldata should be recorded");
+    assert_eq!(
+        cast.args,
+        [
+            "calldata",
+            "verify(bytes,bytes32[])",
+            "0xabababab",
+            &format!("[0x{}]", "01".repeat(32)),
+        ]
+    );
+
+    let calldata: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(evm_dir.join("calldata.json")).unwrap()).unwrap();
+    assert_eq!(calldata["signature"], "verify(bytes,bytes32[])");
+    assert!(
+        calldata["calldata"]
+            .as_str()
+            .unwrap()
+            .starts_with("0xea50d0e4")
+    );
+    assert_eq!(calldata["proof"], "0xabababab");
+
+    let abi: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(evm_dir.join("abi.json")).unwrap()).unwrap();
+    assert_eq!(abi[0]["name"], "verify");
+    assert_eq!(abi[0]["inputs"][0]["type"], "bytes");
+}
+
+#[test]
+fn test_evm_prove_reuses_cached_vk() {
+    let temp_dir = TempDir::new().unwrap();
+    let bb_dir = temp_dir.path().join("target/bb");
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&bb_dir).unwrap();
+    fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"test_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    fs::write(bb_dir.join("test_pkg.json"), r#"{"bytecode":"v1"}"#).unwrap();
+    fs::write(bb_dir.join("test_pkg.gz"), [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let mut config = dry_run_config(dry_runner.clone());
+    config.dry_run = false;
+
+    let write_vk_count = |prove_cfg: ProveConfig| {
+        dry_runner.clear_history();
+        bargo_core::commands::evm::run_prove_with_config(
+            &for_package(&config, temp_dir.path()),
+            &prove_cfg,
+        )
+        .unwrap();
+        dry_runner
+            .history()
+            .iter()
+            .filter(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "write_vk")
+            .count()
+    };
+
+    // Cache miss: nothing generated yet
+    assert_eq!(write_vk_count(ProveConfig::default()), 1);
+    assert!(evm_dir.join("vk.hash").exists());
+
+    // The runner doesn't write files, so stand in for bb's vk output
+    fs::write(evm_dir.join("vk"), [0u8; 8]).unwrap();
+
+    // Cache hit: bytecode unchanged
+    assert_eq!(write_vk_count(ProveConfig::default()), 0);
+
+    // --force bypasses the cache
+    assert_eq!(write_vk_count(ProveConfig::default().with_force(true)), 1);
+
+    // Cache miss: bytecode changed
+    fs::write(bb_dir.join("test_pkg.json"), r#"{"bytecode":"v2"}"#).unwrap();
+    assert_eq!(write_vk_count(ProveConfig::default()), 1);
+    assert_eq!(write_vk_count(ProveConfig::default()), 0);
+}
+
+#[test]
+fn test_evm_verify_uses_overridden_paths() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+    let verify_cfg = VerifyConfig::new(
+        Some("elsewhere/proof".into()),
+        Some("elsewhere/vk".into()),
+        Some("elsewhere/public_inputs".into()),
+    );
+
+    bargo_core::commands::evm::run_verify_with_config(&config, &verify_cfg).unwrap();
+
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 1);
+    let args = &history[0].0.args;
+    assert_eq!(
+        args,
+        &[
+            "verify",
+            "-p",
+            "elsewhere/proof",
+            "-k",
+            "elsewhere/vk",
+            "-i",
+            "elsewhere/public_inputs",
+            "-t",
+            "evm"
+        ]
+    );
+}
+
+#[test]
+fn test_evm_verify_adapts_to_missing_public_inputs_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"test_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    fs::write(evm_dir.join("proof"), [0u8; 64]).unwrap();
+    fs::write(evm_dir.join("vk"), [0u8; 32]).unwrap();
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let mut config = dry_run_config(dry_runner.clone());
+    config.dry_run = false;
+    let config = for_package(&config, temp_dir.path());
+    let verify_cfg = VerifyConfig::default().with_force(true);
+    let public_inputs_arg = |index: usize| {
+        let args = &dry_runner.history()[index].0.args;
+        args.iter()
+            .position(|arg| arg == "-i")
+            .map(|i| args[i + 1].clone())
+    };
+
+    // Only the field encoding was written: the binary file is re-created
+    fs::write(
+        evm_dir.join("public_inputs_fields.json"),
+        r#"["0x01", "0x02"]"#,
+    )
+    .unwrap();
+    bargo_core::commands::evm::run_verify_with_config(&config, &verify_cfg).unwrap();
+    assert!(
+        public_inputs_arg(0)
+            .unwrap()
+            .ends_with("target/evm/public_inputs")
+    );
+    let public_inputs = fs::read(evm_dir.join("public_inputs")).unwrap();
+    assert_eq!(public_inputs.len(), 64);
+    assert_eq!((public_inputs[31], public_inputs[63]), (1, 2));
+
+    // Public inputs embedded in the proof: bb gets no -i flag
+    fs::remove_file(evm_dir.join("public_inputs")).unwrap();
+    fs::remove_file(evm_dir.join("public_inputs_fields.json")).unwrap();
+    bargo_core::commands::evm::run_verify_with_config(&config, &verify_cfg).unwrap();
+    assert_eq!(public_inputs_arg(1), None);
+
+    // An explicit --public-inputs path must still exist
+    let missing = VerifyConfig::new(None, None, Some(temp_dir.path().join("missing")));
+    let error = bargo_core::commands::evm::run_verify_with_config(&config, &missing).unwrap_err();
+    assert!(format!("{error:#}").contains("missing"), "{error:#}");
+}
+
+/// Runner that executes nothing and keeps the proof bytes `bb verify` was given
+#[test]
+fn test_evm_prove_skips_when_proof_is_newer_than_build() {
+    let temp_dir = TempDir::new().unwrap();
+    let bb_dir = temp_dir.path().join("target/bb");
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"test_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+
+    fs::create_dir_all(&bb_dir).unwrap();
+    fs::write(bb_dir.join("test_pkg.json"), r#"{"bytecode":"v1"}"#).unwrap();
+    fs::write(bb_dir.join("test_pkg.gz"), [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+
+    // Give every artifact an explicit age so the comparison is deterministic
+    let set_age = |path: &Path, age_secs: u64| {
+        let time = std::time::SystemTime::now() - std::time::Duration::from_secs(age_secs);
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    };
+    set_age(&bb_dir.join("test_pkg.json"), 300);
+    set_age(&bb_dir.join("test_pkg.gz"), 200);
+    for artifact in ["proof", "public_inputs", "vk"] {
+        fs::write(evm_dir.join(artifact), [0u8; 8]).unwrap();
+        set_age(&evm_dir.join(artifact), 100);
+    }
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let mut config = dry_run_config(dry_runner.clone());
+    config.dry_run = false;
+    let config = for_package(&config, temp_dir.path());
+
+    let bb_runs = |prove_cfg: ProveConfig| {
+        dry_runner.clear_history();
+        bargo_core::commands::evm::run_prove_with_config(&config, &prove_cfg).unwrap();
+        dry_runner
+            .history()
+            .iter()
+            .filter(|(spec, _)| spec.cmd == "bb")
+            .count()
+    };
+
+    // Fresh proof: nothing to do
+    assert_eq!(bb_runs(ProveConfig::default()), 0);
+
+    // --force, or options bargo cannot compare, always prove
+    assert!(bb_runs(ProveConfig::default().with_force(true)) > 0);
+    assert!(bb_runs(ProveConfig::default().with_recursive(true)) > 0);
+    assert_eq!(bb_runs(ProveConfig::default()), 0);
+
+    // Stale proof: the witness was rebuilt after proving
+    set_age(&bb_dir.join("test_pkg.gz"), 50);
+    assert!(bb_runs(ProveConfig::default()) > 0);
+
+    // A different oracle hash than the manifest records
+    set_age(&bb_dir.join("test_pkg.gz"), 200);
+    assert_eq!(bb_runs(ProveConfig::default()), 0);
+    let poseidon2 = ProveConfig::default().with_oracle_hash(Some(OracleHash::Poseidon2));
+    assert!(bb_runs(poseidon2.clone()) > 0);
+    assert_eq!(bb_runs(poseidon2), 0);
+}
+
+/// Runner that executes nothing and answers `git rev-parse HEAD` with `commit`
+///
+/// `None` fails the git call the way a directory outside a repository does.
+#[derive(Debug)]
+struct GitStub(Option<&'static str>);
+
+impl Runner for GitStub {
+    fn run(&self, _spec: &CmdSpec) -> color_eyre::Result<()> {
+        Ok(())
+    }
+
+    fn run_capture(&self, spec: &CmdSpec) -> color_eyre::Result<CapturedOutput> {
+        let stdout = match (spec.cmd.as_str(), self.0) {
+            ("git", Some(commit)) => format!("{commit}\n"),
+            ("git", None) => {
+                return Err(BargoError::CommandFailed {
+                    tool: "git".to_string(),
+                    code: Some(128),
+                    stdout: String::new(),
+                    stderr: "fatal: not a git repository".to_string(),
+                }
+                .into());
+            }
+            (tool, _) => format!("{tool} 1.2.3\n"),
+        };
+        Ok(CapturedOutput {
+            stdout,
+            ..CapturedOutput::default()
+        })
+    }
+}
+
+#[test]
+fn test_evm_prove_writes_proof_metadata_with_and_without_git() {
+    let temp_dir = TempDir::new().unwrap();
+    let bb_dir = temp_dir.path().join("target/bb");
+    fs::create_dir_all(&bb_dir).unwrap();
+    fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"test_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    fs::write(bb_dir.join("test_pkg.json"), r#"{"bytecode":"v1"}"#).unwrap();
+    fs::write(bb_dir.join("test_pkg.gz"), [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+    let meta_path = temp_dir.path().join("target/evm/proof.meta.json");
+
+    let prove_with_git = |commit: Option<&'static str>| {
+        let config = for_package(
+            &Config {
+                dry_run: false,
+                runner: Arc::new(GitStub(commit)),
+                ..dry_run_config(Arc::new(DryRunRunner::new()))
+            },
+            temp_dir.path(),
+        );
+        bargo_core::commands::evm::run_prove_with_config(
+            &config,
+            &ProveConfig::default().with_force(true),
+        )
+        .unwrap();
+        let meta: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&meta_path).unwrap()).unwrap();
+        meta
+    };
+
+    let commit = "0123456789abcdef0123456789abcdef01234567";
+    let meta = prove_with_git(Some(commit));
+    assert_eq!(meta["package"], "test_pkg");
+    assert_eq!(meta["commit"], commit);
+    assert_eq!(meta["tools"]["bb"], "bb 1.2.3");
+    assert_eq!(meta["tools"]["nargo"], "nargo 1.2.3");
+    assert!(meta["created_at"].as_str().unwrap().ends_with('Z'));
+
+    let meta = prove_with_git(None);
+    assert_eq!(meta["package"], "test_pkg");
+    assert!(meta["commit"].is_null());
+}
+
+#[derive(Debug, Default)]
+struct ProofCapture(std::sync::Mutex<Option<Vec<u8>>>);
+
+impl Runner for ProofCapture {
+    fn run(&self, spec: &CmdSpec) -> color_eyre::Result<()> {
+        if spec.cmd == "bb" && spec.args[0] == "verify" {
+            let flag = spec.args.iter().position(|arg| arg == "-p").unwrap();
+            *self.0.lock().unwrap() = Some(fs::read(&spec.args[flag + 1])?);
+        }
+        Ok(())
+    }
+
+    fn run_capture(&self, spec: &CmdSpec) -> color_eyre::Result<CapturedOutput> {
+        self.run(spec).map(|_| CapturedOutput::default())
+    }
+}
+
+#[test]
+fn test_evm_prove_compress_then_verify_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let bb_dir = temp_dir.path().join("target/bb");
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&bb_dir).unwrap();
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"test_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    fs::write(bb_dir.join("test_pkg.json"), r#"{"bytecode":"v1"}"#).unwrap();
+    fs::write(bb_dir.join("test_pkg.gz"), [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+
+    // The runner doesn't write files, so stand in for bb's prove output
+    let proof: Vec<u8> = (0..8192u32).map(|i| (i % 32) as u8).collect();
+    fs::write(evm_dir.join("proof"), &proof).unwrap();
+    fs::write(evm_dir.join("public_inputs"), [0u8; 1024]).unwrap();
+    fs::write(evm_dir.join("vk"), [0u8; 8]).unwrap();
+
+    let runner = Arc::new(ProofCapture::default());
+    let config = for_package(
+        &Config {
+            dry_run: false,
+            runner: runner.clone(),
+            ..dry_run_config(Arc::new(DryRunRunner::new()))
+        },
+        temp_dir.path(),
+    );
+
+    bargo_core::commands::evm::run_prove_with_config(
+        &config,
+        &ProveConfig::default().with_compress(true),
+    )
+    .unwrap();
+
+    assert!(!evm_dir.join("proof").exists());
+    assert!(!evm_dir.join("public_inputs").exists());
+    let compressed_size = fs::metadata(evm_dir.join("proof.gz")).unwrap().len();
+    assert!(compressed_size < proof.len() as u64, "{compressed_size}");
+    assert!(
+        fs::metadata(evm_dir.join("public_inputs.gz"))
+            .unwrap()
+            .len()
+            < 1024
+    );
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(evm_dir.join("manifest.json")).unwrap()).unwrap();
+    let artifacts = manifest["artifacts"].as_array().unwrap();
+    let entry = |name: &str| {
+        artifacts
+            .iter()
+            .find(|entry| entry["path"].as_str().unwrap().ends_with(name))
+    };
+    assert_eq!(entry("proof.gz").unwrap()["compression"], "gzip");
+    assert_eq!(entry("public_inputs.gz").unwrap()["compression"], "gzip");
+    assert!(entry("/proof").is_none());
+    assert!(entry("vk").unwrap().get("compression").is_none());
+
+    // Verify finds proof.gz and hands bb the original bytes
+    bargo_core::commands::evm::run_verify_with_config(&config, &VerifyConfig::default()).unwrap();
+    assert_eq!(runner.0.lock().unwrap().as_deref(), Some(proof.as_slice()));
+}
+
+#[test]
+fn test_evm_timestamp_suffix_verifies_proof_from_earlier_run() {
+    let temp_dir = TempDir::new().unwrap();
+    let bb_dir = temp_dir.path().join("target/bb");
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&bb_dir).unwrap();
+    fs::create_dir_all(&evm_dir).unwrap();
+    fs::write(
+        temp_dir.path().join("Nargo.toml"),
+        "[package]\nname = \"test_pkg\"\ntype = \"bin\"\n",
+    )
+    .unwrap();
+    fs::write(bb_dir.join("test_pkg.json"), r#"{"bytecode":"v1"}"#).unwrap();
+    fs::write(bb_dir.join("test_pkg.gz"), [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+
+    // Artifacts left by a run long ago
+    fs::write(evm_dir.join("proof_1000"), [1u8; 64]).unwrap();
+    fs::write(evm_dir.join("vk_1000"), [1u8; 8]).unwrap();
+    fs::write(evm_dir.join("public_inputs_1000"), [1u8; 32]).unwrap();
+
+    let runner = Arc::new(ProofCapture::default());
+    let timestamp_config = || {
+        for_package(
+            &Config {
+                dry_run: false,
+                artifact_suffix: ArtifactSuffix::Timestamp,
+                runner: runner.clone(),
+                fs: Arc::new(RealFs),
+                ..dry_run_config(Arc::new(DryRunRunner::new()))
+            },
+            temp_dir.path(),
+        )
+    };
+    let verify = || {
+        bargo_core::commands::evm::run_verify_with_config(
+            &timestamp_config(),
+            &VerifyConfig::default().with_force(true),
+        )
+        .unwrap();
+        runner.0.lock().unwrap().take()
+    };
+
+    // A fresh run picks up the earlier run's proof
+    assert_eq!(verify(), Some(vec![1u8; 64]));
+
+    // The runner doesn't write files, so stand in for bb's prove output
+    fs::write(evm_dir.join("proof"), [2u8; 64]).unwrap();
+    fs::write(evm_dir.join("vk"), [2u8; 8]).unwrap();
+    fs::write(evm_dir.join("public_inputs"), [2u8; 32]).unwrap();
+    bargo_core::commands::evm::run_prove_with_config(
+        &timestamp_config(),
+        &ProveConfig::default().with_force(true),
+    )
+    .unwrap();
+    assert!(!evm_dir.join("proof").exists());
+
+    // Verifying with another Config finds the newest proof
+    assert_eq!(verify(), Some(vec![2u8; 64]));
+}
+
+/// Runner that executes nothing and answers `bb verify` with a fixed verdict
+#[derive(Debug)]
+enum VerdictRunner {
+    /// bb exits zero with this output
+    Accept(&'static str),
+    /// bb exits non-zero, as it does for an invalid proof
+    Reject,
+}
+
+impl Runner for VerdictRunner {
+    fn run(&self, spec: &CmdSpec) -> color_eyre::Result<()> {
+        self.run_capture(spec).map(|_| ())
+    }
+
+    fn run_capture(&self, spec: &CmdSpec) -> color_eyre::Result<CapturedOutput> {
+        match self {
+            Self::Accept(stderr) => Ok(CapturedOutput {
+                stderr: stderr.to_string(),
+                ..CapturedOutput::default()
+            }),
+            Self::Reject => Err(BargoError::CommandFailed {
+                tool: spec.cmd.clone(),
+                code: Some(1),
+                stdout: String::new(),
+                stderr: "Proof verification failed".to_string(),
+            }
+            .into()),
+        }
+    }
+}
+
+/// Verify the files in `temp_dir` with a real (non dry-run) config and `runner`
+fn verify_with_verdict(
+    temp_dir: &TempDir,
+    runner: VerdictRunner,
+    expect_valid: bool,
+) -> color_eyre::Result<()> {
+    for name in ["proof", "vk", "public_inputs"] {
+        fs::write(temp_dir.path().join(name), [0u8; 4]).unwrap();
+    }
+    let config = for_package(
+        &Config {
+            dry_run: false,
+            runner: Arc::new(runner),
+            ..dry_run_config(Arc::new(DryRunRunner::new()))
+        },
+        temp_dir.path(),
+    );
+    // Every call verifies the same bytes, so bypass the verification cache
+    let verify_cfg = VerifyConfig::new(
+        Some(temp_dir.path().join("proof")),
+        Some(temp_dir.path().join("vk")),
+        Some(temp_dir.path().join("public_inputs")),
+    )
+    .with_expect(expect_valid)
+    .with_force(true);
+
+    bargo_core::commands::evm::run_verify_with_config(&config, &verify_cfg)
+}
+
+#[test]
+fn test_evm_verify_expect_false_passes_when_bb_rejects() {
+    let temp_dir = TempDir::new().unwrap();
+    verify_with_verdict(&temp_dir, VerdictRunner::Reject, false).unwrap();
+
+    // Some bb versions report the verdict without a failing exit code
+    verify_with_verdict(&temp_dir, VerdictRunner::Accept("verified: 0"), false).unwrap();
+
+    // Without --expect false a rejected proof is still an error
+    assert!(verify_with_verdict(&temp_dir, VerdictRunner::Reject, true).is_err());
+}
+
+#[test]
+fn test_evm_verify_expect_false_fails_when_bb_accepts() {
+    let temp_dir = TempDir::new().unwrap();
+    let error = verify_with_verdict(&temp_dir, VerdictRunner::Accept(""), false)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("Expected the EVM proof to fail verification, but bb accepted"),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_evm_verify_reuses_cached_outcome_until_inputs_change() {
+    let temp_dir = TempDir::new().unwrap();
+    let evm_dir = temp_dir.path().join("target/evm");
+    fs::create_dir_all(&evm_dir).unwrap();
+    for name in ["proof", "vk", "public_inputs"] {
+        fs::write(evm_dir.join(name), name).unwrap();
+    }
+
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let mut config = dry_run_config(dry_runner.clone());
+    config.dry_run = false;
+    let config = for_package(&config, temp_dir.path());
+
+    let bb_verify_count = |verify_cfg: VerifyConfig| {
+        dry_runner.clear_history();
+        bargo_core::commands::evm::run_verify_with_config(&config, &verify_cfg).unwrap();
+        dry_runner.history().len()
+    };
+
+    // First run verifies and records the outcome
+    assert_eq!(bb_verify_count(VerifyConfig::default()), 1);
+    let cache: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(evm_dir.join("verify_cache.json")).unwrap())
+            .unwrap();
+    assert_eq!(cache["accepted"], true);
+    assert!(cache["proof_hash"].is_string());
+    assert!(cache["vk_hash"].is_string());
+
+    // Unchanged inputs are a cache hit
+    assert_eq!(bb_verify_count(VerifyConfig::default()), 0);
+
+    // --force re-runs bb anyway
+    assert_eq!(bb_verify_count(VerifyConfig::default().with_force(true)), 1);
+
+    // A new proof is a miss, then cached again
+    fs::write(evm_dir.join("proof"), "new proof").unwrap();
+    assert_eq!(bb_verify_count(VerifyConfig::default()), 1);
+    assert_eq!(bb_verify_count(VerifyConfig::default()), 0);
+
+    // A cached acceptance does not satisfy --expect false
+    assert!(
+        bargo_core::commands::evm::run_verify_with_config(
+            &config,
+            &VerifyConfig::default().with_expect(false)
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_evm_verify_watch_reverifies_on_change() {
+    let dry_runner = Arc::new(DryRunRunner::new());
+    let config = dry_run_config(dry_runner.clone());
+
+    // One simulated change to the proof, then the watcher goes away
+    let (changes_tx, changes) = std::sync::mpsc::channel();
+    changes_tx.send(()).unwrap();
+    drop(changes_tx);
+
+    bargo_core::commands::watch::run_cycles(&config, "verify", &changes, |cfg| {
+        bargo_core::commands::evm::run_verify(cfg)
+    });
+
+    let history = dry_runner.history();
+    assert_eq!(history.len(), 2);
+    assert!(
+        history
+            .iter()
+            .all(|(spec, _)| spec.cmd == "bb" && spec.args[0] == "verify")
+    );
+}
+
+#[test]
+fn test_evm_verify_requires_overridden_files() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("proof"), [0u8; 4]).unwrap();
+
+    let mut config = dry_run_config(Arc::new(DryRunRunner::new()));
+    config.dry_run = false;
+    let verify_cfg = VerifyConfig::new(
+        Some(temp_dir.path().join("proof")),
+        Some(temp_dir.path().join("vk")),
+        Some(temp_dir.path().join("public_inputs")),
+    );
+
+    let error = bargo_core::commands::evm::run_verify_with_config(&config, &verify_cfg)
+        .unwrap_err()
+        .to_string();
+    let missing = |name: &str| error.contains(&temp_dir.path().join(name).display().to_string());
+    assert!(missing("vk"));
+    assert!(missing("public_inputs"));
+    assert!(!missing("proof"));
+}
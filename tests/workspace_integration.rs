@@ -0,0 +1,154 @@
+//! Integration tests for Nargo workspaces with several member packages
+//!
+//! The fixture is only read, never built, so tests run against it in place.
+
+use assert_cmd::Command;
+use bargo_core::config::list_workspace_members;
+use predicates::str::contains;
+use std::path::PathBuf;
+
+fn workspace_fixture() -> PathBuf {
+    fixture("workspace_project")
+}
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+#[test]
+fn test_list_workspace_members() {
+    let root = workspace_fixture();
+    let members = list_workspace_members(&root.join("Nargo.toml")).unwrap();
+
+    let names: Vec<_> = members.iter().map(|member| member.name.as_str()).collect();
+    assert_eq!(names, ["circuit_a", "circuit_b"]);
+    assert_eq!(members[0].path, root.join("circuit_a"));
+    assert_eq!(members[1].path, root.join("circuit_b"));
+}
+
+#[test]
+fn test_list_workspace_members_rejects_package_manifest() {
+    let manifest = workspace_fixture().join("circuit_a").join("Nargo.toml");
+    let error = list_workspace_members(&manifest).unwrap_err();
+    assert!(error.to_string().contains("not a workspace"));
+}
+
+#[test]
+fn test_build_all_runs_every_member() {
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(workspace_fixture())
+        .args(["--dry-run", "build", "--all"])
+        .assert()
+        .success()
+        .stdout(contains("nargo execute --package circuit_a"))
+        .stdout(contains("nargo execute --package circuit_b"));
+}
+
+#[test]
+fn test_check_all_runs_every_member() {
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(workspace_fixture())
+        .args(["--dry-run", "check", "--all"])
+        .assert()
+        .success()
+        .stdout(contains("nargo check --package circuit_a"))
+        .stdout(contains("nargo check --package circuit_b"));
+}
+
+#[test]
+fn test_check_workspace_reports_each_member() {
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(workspace_fixture())
+        .args(["--dry-run", "check", "--workspace"])
+        .assert()
+        .success()
+        .stdout(contains("nargo check --package circuit_a"))
+        .stdout(contains("nargo check --package circuit_b"))
+        .stdout(contains("circuit_a  circuit_a  passed"))
+        .stdout(contains("circuit_b  circuit_b  passed"));
+}
+
+#[test]
+fn test_check_workspace_reports_member_without_nargo_toml() {
+    let temp_dir = assert_fs::TempDir::new().unwrap();
+    let root = temp_dir.path();
+    std::fs::write(
+        root.join("Nargo.toml"),
+        "[workspace]\nmembers = [\"circuit_a\", \"broken\"]\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("circuit_a")).unwrap();
+    std::fs::copy(
+        workspace_fixture().join("circuit_a").join("Nargo.toml"),
+        root.join("circuit_a").join("Nargo.toml"),
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("broken")).unwrap();
+
+    // The valid member is still checked, and the broken one is reported
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(root)
+        .args(["--dry-run", "check", "--workspace"])
+        .assert()
+        .failure()
+        .stdout(contains("nargo check --package circuit_a"))
+        .stdout(contains("circuit_a  circuit_a  passed"))
+        .stdout(contains("broken     broken     invalid"))
+        .stderr(contains(
+            "nargo check failed for 1 of 2 workspace members: broken",
+        ));
+}
+
+#[test]
+fn test_build_without_pkg_lists_members() {
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(workspace_fixture())
+        .arg("build")
+        .assert()
+        .failure()
+        .stderr(contains("is a workspace"))
+        .stderr(contains("circuit_a, circuit_b"))
+        .stderr(contains("--pkg"));
+}
+
+#[test]
+fn test_build_with_pkg_in_workspace() {
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(workspace_fixture())
+        .args(["--dry-run", "--pkg", "circuit_b", "build"])
+        .assert()
+        .success()
+        .stdout(contains("nargo execute --package circuit_b"));
+}
+
+#[test]
+fn test_default_member_resolves_package() {
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(fixture("workspace_default_member"))
+        .arg("info")
+        .assert()
+        .success()
+        .stdout(contains("Package: circuit_b"))
+        .stdout(contains("target/bb/circuit_b.json"));
+}
+
+#[test]
+fn test_pkg_overrides_default_member() {
+    Command::cargo_bin("bargo")
+        .unwrap()
+        .current_dir(fixture("workspace_default_member"))
+        .args(["--pkg", "circuit_a", "info"])
+        .assert()
+        .success()
+        .stdout(contains("Package: circuit_a"));
+}